@@ -0,0 +1,14 @@
+//! Arbitrary bytes through the GTS ID validators: none may panic, and no
+//! string may be both a valid schema ID and a valid instance ID.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let input = String::from_utf8_lossy(data);
+    let schema = gts_id::validate_schema_id(&input);
+    let instance = gts_id::validate_instance_id(&input);
+    assert!(schema.is_err() || instance.is_err(), "{input:?}");
+    let _ = gts_id::validate_ref_pattern(&input);
+});
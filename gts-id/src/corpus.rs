@@ -0,0 +1,154 @@
+//! GTS ID validation corpus, shared by the `gts-id`, `gts-validator` and
+//! `gts-macros` test suites so they cannot drift. Enabled by the
+//! `test-corpus` feature; not a stable API.
+
+/// The shared corpus: `(input, schema, instance, ref pattern)`, where each
+/// expectation is `None` for valid or `Some(substring of the cause)`. The
+/// substring pins the diagnostic, so it must not be empty.
+/// Add new cases here rather than to consumer crates.
+#[allow(clippy::type_complexity)]
+pub const ID_CORPUS: &[(&str, Option<&str>, Option<&str>, Option<&str>)] = &[
+    // Schema IDs
+    (
+        "gts.x.core.events.type.v1~",
+        None,
+        Some("must not end with '~'"),
+        None,
+    ),
+    (
+        "gts.x.core.events.type.v1.2~",
+        None,
+        Some("must not end with '~'"),
+        None,
+    ),
+    (
+        "gts.x.core.events.type.v1~x.core.audit.event.v1~",
+        None,
+        Some("must not end with '~'"),
+        None,
+    ),
+    (
+        "  gts.x.core.events.type.v1~  ",
+        None,
+        Some("must not end with '~'"),
+        None,
+    ),
+    // Instance IDs
+    (
+        "gts.x.core.events.type.v1~x.commerce.orders.created.v1.0",
+        Some("must end with '~'"),
+        None,
+        None,
+    ),
+    (
+        "gts.x.core.events.type.v1~x.app._.custom.v1",
+        Some("must end with '~'"),
+        None,
+        None,
+    ),
+    // Instance segments, checked by their own grammar
+    (
+        "gts.x.core.events.type.v1~order.created#2024",
+        Some("Too few tokens"),
+        Some("InstanceSegmentInvalidChar"),
+        Some("Too few tokens"),
+    ),
+    (
+        "gts.x.core.events.type.v1~x.core.events.Order.v1",
+        Some("lowercase"),
+        Some("InstanceSegmentInvalidChar"),
+        Some("lowercase"),
+    ),
+    (
+        "gts.x.core.events.type.v1~x.core.events.caf\u{e9}.v1",
+        Some("Invalid type token"),
+        Some("InstanceSegmentInvalidChar"),
+        Some("Invalid type token"),
+    ),
+    (
+        "gts.x.core.events.type.v1~x.core.events.order",
+        Some("Too few tokens (got 4"),
+        Some("InstanceSegmentTokenCount"),
+        Some("Too few tokens (got 4"),
+    ),
+    (
+        "gts.x.core.events.type.v1~x.core.events.order.v01",
+        Some("Major version must be an integer"),
+        Some("InstanceSegmentInvalidVersion"),
+        Some("Major version must be an integer"),
+    ),
+    (
+        "gts.x.core.events.type.v1~7a1d2f34-5678-49ab-9012-abcdef123456",
+        Some("must end with '~'"),
+        None,
+        None,
+    ),
+    // Reference patterns
+    (
+        "gts.x.*",
+        Some("Too few tokens"),
+        Some("Too few tokens"),
+        None,
+    ),
+    (
+        "gts.x.core.events.type.v1~*",
+        Some("Too few tokens"),
+        Some("InstanceSegmentInvalidChar"),
+        None,
+    ),
+    // Invalid everywhere
+    (
+        "gts.x.core.events.type.v1",
+        Some("Single-segment instance IDs are prohibited"),
+        Some("Single-segment instance IDs are prohibited"),
+        Some("Single-segment instance IDs are prohibited"),
+    ),
+    (
+        "gts.X.core.events.type.v1~",
+        Some("lowercase"),
+        Some("lowercase"),
+        Some("lowercase"),
+    ),
+    (
+        "gts.my-vendor.core.events.type.v1~",
+        Some("must not contain '-'"),
+        Some("must not contain '-'"),
+        Some("must not contain '-'"),
+    ),
+    (
+        "gts.1x.core.events.type.v1~",
+        Some("Invalid vendor token '1x'"),
+        Some("Invalid vendor token '1x'"),
+        Some("Invalid vendor token '1x'"),
+    ),
+    (
+        "gts.x.core.events.type.v1~x.1core.audit.event.v1",
+        Some("Invalid package token '1core'"),
+        Some("InstanceSegmentInvalidName"),
+        Some("Invalid package token '1core'"),
+    ),
+    (
+        "gts.x.core.events.type~",
+        Some("Too few tokens (got 4"),
+        Some("Too few tokens (got 4"),
+        Some("Too few tokens (got 4"),
+    ),
+    (
+        "x.core.events.type.v1~",
+        Some("must start with 'gts.'"),
+        Some("must start with 'gts.'"),
+        Some("must start with 'gts.'"),
+    ),
+    (
+        "gts.x.*.events.*",
+        Some("Too few tokens"),
+        Some("Too few tokens"),
+        Some("allowed only once"),
+    ),
+    (
+        "",
+        Some("must start with 'gts.'"),
+        Some("must start with 'gts.'"),
+        Some("must start with 'gts.'"),
+    ),
+];
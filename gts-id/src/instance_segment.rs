@@ -0,0 +1,232 @@
+//! The instance-segment grammar.
+//!
+//! An instance segment is the part of a chained instance ID after its last
+//! `~`, e.g. `x.commerce.orders.created.v1.0` in
+//! `gts.x.core.events.type.v1~x.commerce.orders.created.v1.0`. It has the
+//! shape of a type segment without the `~` marker,
+//! `vendor.package.namespace.type.vMAJOR[.MINOR]`, with dots as token
+//! separators and only `[a-z0-9_.]` allowed. It is checked on its own so a
+//! malformed instance gets a diagnostic naming what is wrong with it.
+//! Anonymous instances (a UUID after the last `~`) have no instance segment.
+
+use std::fmt;
+
+use crate::parse::{is_valid_segment_token, parse_u32_exact};
+
+/// Why an instance segment is malformed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InstanceSegmentError {
+    /// A character outside `[a-z0-9_.]`, at a byte offset within the segment.
+    InvalidChar { ch: char, offset: usize },
+    /// The segment is longer than the bytes the ID length limit leaves it.
+    TooLong { len: usize, max: usize },
+    /// Not 5 or 6 dot-separated tokens.
+    TokenCount { found: usize },
+    /// A vendor, package, namespace or type token that is empty or does not
+    /// start with `[a-z_]`.
+    InvalidName { name: &'static str, token: String },
+    /// The fifth token is not a `vMAJOR` version.
+    MissingVersion { token: String },
+    /// A major or minor version that is not an integer without leading
+    /// zeros.
+    InvalidVersion { token: String },
+}
+
+impl InstanceSegmentError {
+    /// Stable name of the diagnostic, e.g. `InstanceSegmentInvalidChar`.
+    #[must_use]
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidChar { .. } => "InstanceSegmentInvalidChar",
+            Self::TooLong { .. } => "InstanceSegmentTooLong",
+            Self::TokenCount { .. } => "InstanceSegmentTokenCount",
+            Self::InvalidName { .. } => "InstanceSegmentInvalidName",
+            Self::MissingVersion { .. } => "InstanceSegmentMissingVersion",
+            Self::InvalidVersion { .. } => "InstanceSegmentInvalidVersion",
+        }
+    }
+}
+
+impl fmt::Display for InstanceSegmentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidChar { ch, offset } => write!(
+                f,
+                "invalid character '{ch}' at offset {offset}; instance segments use only [a-z0-9_.]"
+            ),
+            Self::TooLong { len, max } => {
+                write!(f, "too long ({len} bytes, max {max} after the type chain)")
+            }
+            Self::TokenCount { found } => write!(
+                f,
+                "expected vendor.package.namespace.type.vMAJOR[.MINOR], got {found} token(s)"
+            ),
+            Self::InvalidName { name, token } => write!(
+                f,
+                "invalid {name} token '{token}'; must start with [a-z_] and contain only [a-z0-9_]"
+            ),
+            Self::MissingVersion { token } => {
+                write!(
+                    f,
+                    "expected a 'vMAJOR' version after the type, got '{token}'"
+                )
+            }
+            Self::InvalidVersion { token } => write!(
+                f,
+                "version '{token}' must be an integer without leading zeros"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InstanceSegmentError {}
+
+/// Validates `segment` against the instance-segment grammar, allowing at
+/// most `max_len` bytes.
+///
+/// # Errors
+/// Returns the first [`InstanceSegmentError`] found, checking characters,
+/// then length, then the token structure.
+pub fn validate_instance_segment(
+    segment: &str,
+    max_len: usize,
+) -> Result<(), InstanceSegmentError> {
+    let allowed = |c: char| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '.';
+    if let Some((offset, ch)) = segment.char_indices().find(|&(_, c)| !allowed(c)) {
+        return Err(InstanceSegmentError::InvalidChar { ch, offset });
+    }
+    if segment.len() > max_len {
+        return Err(InstanceSegmentError::TooLong {
+            len: segment.len(),
+            max: max_len,
+        });
+    }
+
+    let tokens: Vec<&str> = segment.split('.').collect();
+    if !(5..=6).contains(&tokens.len()) {
+        return Err(InstanceSegmentError::TokenCount {
+            found: tokens.len(),
+        });
+    }
+    for (name, token) in ["vendor", "package", "namespace", "type"]
+        .into_iter()
+        .zip(&tokens)
+    {
+        if !is_valid_segment_token(token) {
+            return Err(InstanceSegmentError::InvalidName {
+                name,
+                token: (*token).to_owned(),
+            });
+        }
+    }
+    let Some(major) = tokens[4].strip_prefix('v') else {
+        return Err(InstanceSegmentError::MissingVersion {
+            token: tokens[4].to_owned(),
+        });
+    };
+    for version in std::iter::once(major).chain(tokens.get(5).copied()) {
+        if parse_u32_exact(version).is_none() {
+            return Err(InstanceSegmentError::InvalidVersion {
+                token: version.to_owned(),
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GTS_ID_MAX_LENGTH;
+
+    #[test]
+    fn test_instance_segment_cases() {
+        let long = format!("x.core.events.{}.v1", "a".repeat(100));
+        let cases: &[(&str, Option<&str>)] = &[
+            ("x.commerce.orders.created.v1", None),
+            ("x.commerce.orders.created.v1.0", None),
+            ("x.app._.custom.v1", None),
+            ("_x.a1.b_2.c.v10.20", None),
+            ("order.created#2024", Some("InstanceSegmentInvalidChar")),
+            ("x.core.events.Order.v1", Some("InstanceSegmentInvalidChar")),
+            (
+                "x.core.events.order-created.v1",
+                Some("InstanceSegmentInvalidChar"),
+            ),
+            (
+                "x.core.events.caf\u{e9}.v1",
+                Some("InstanceSegmentInvalidChar"),
+            ),
+            (
+                "x.core.events.\u{445}.v1",
+                Some("InstanceSegmentInvalidChar"),
+            ),
+            ("x.core.events.type.v1 ", Some("InstanceSegmentInvalidChar")),
+            ("order.created", Some("InstanceSegmentTokenCount")),
+            ("x.core.events.type", Some("InstanceSegmentTokenCount")),
+            (
+                "x.core.events.type.v1.0.1",
+                Some("InstanceSegmentTokenCount"),
+            ),
+            ("x.1core.events.type.v1", Some("InstanceSegmentInvalidName")),
+            ("x..events.type.v1", Some("InstanceSegmentInvalidName")),
+            (
+                "x.core.events.type.extra.v1",
+                Some("InstanceSegmentMissingVersion"),
+            ),
+            (
+                "x.core.events.type.1",
+                Some("InstanceSegmentMissingVersion"),
+            ),
+            (
+                "x.core.events.type.v",
+                Some("InstanceSegmentInvalidVersion"),
+            ),
+            (
+                "x.core.events.type.v01",
+                Some("InstanceSegmentInvalidVersion"),
+            ),
+            (
+                "x.core.events.type.v1.x",
+                Some("InstanceSegmentInvalidVersion"),
+            ),
+            (&long, None),
+        ];
+        for &(segment, expected) in cases {
+            let result = validate_instance_segment(segment, GTS_ID_MAX_LENGTH);
+            assert_eq!(
+                result.as_ref().err().map(InstanceSegmentError::code),
+                expected,
+                "{segment:?}: {result:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_instance_segment_details() {
+        assert_eq!(
+            validate_instance_segment("order.created#2024", 100),
+            Err(InstanceSegmentError::InvalidChar {
+                ch: '#',
+                offset: 13
+            })
+        );
+        assert_eq!(
+            validate_instance_segment("x.core.\u{e9}v.type.v1", 100),
+            Err(InstanceSegmentError::InvalidChar {
+                ch: '\u{e9}',
+                offset: 7
+            })
+        );
+        let segment = "x.core.events.type.v1";
+        assert_eq!(validate_instance_segment(segment, segment.len()), Ok(()));
+        assert_eq!(
+            validate_instance_segment(segment, segment.len() - 1),
+            Err(InstanceSegmentError::TooLong {
+                len: segment.len(),
+                max: segment.len() - 1
+            })
+        );
+    }
+}
@@ -4,16 +4,25 @@
 //! and validation, used by both the `gts` runtime library and the `gts-macros`
 //! proc-macro crate.
 
+#[cfg(feature = "test-corpus")]
+#[doc(hidden)]
+pub mod corpus;
 mod error;
 mod gts_id;
 mod gts_id_pattern;
 mod gts_id_segment;
+mod instance_segment;
 pub(crate) mod parse;
 pub(crate) mod prefix;
+#[cfg(feature = "proptest")]
+pub mod strategies;
+mod validate;
 
 pub use error::{GtsIdError, GtsIdSegmentError};
 pub use gts_id::GtsId;
 pub use gts_id_pattern::GtsIdPattern;
 pub use gts_id_segment::{GtsIdPatternSegment, GtsIdSegment, GtsIdSegmentParts, GtsUuidTail};
-pub use parse::GTS_ID_MAX_LENGTH;
+pub use instance_segment::{InstanceSegmentError, validate_instance_segment};
+pub use parse::{GTS_ID_MAX_LENGTH, is_valid_segment_token, parse_u32_exact};
 pub use prefix::{DEFAULT_GTS_ID_PREFIX, GTS_ID_PREFIX, GTS_ID_PREFIX_ENV};
+pub use validate::{validate_instance_id, validate_ref_pattern, validate_schema_id};
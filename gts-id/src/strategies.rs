@@ -0,0 +1,73 @@
+//! [`proptest`] strategies generating GTS identifiers from the grammar.
+//!
+//! Every generated ID uses the configured [`GTS_ID_PREFIX`] and is valid, so
+//! property tests here and in consumer crates (e.g. the `gts-validator`
+//! scanners) can assert that valid IDs are always accepted or discovered.
+//! Tests derive invalid inputs by mutating them.
+//!
+//! Enabled by the `proptest` feature.
+
+use proptest::prelude::*;
+
+use crate::GTS_ID_PREFIX;
+
+/// A segment token: `[a-z_][a-z0-9_]*`, at most 8 characters.
+pub fn token() -> impl Strategy<Value = String> {
+    "[a-z_][a-z0-9_]{0,7}"
+}
+
+/// A version: `vMAJOR` or `vMAJOR.MINOR`, without leading zeros.
+pub fn version() -> impl Strategy<Value = String> {
+    (0u32..1000, proptest::option::of(0u32..1000)).prop_map(|(major, minor)| match minor {
+        Some(minor) => format!("v{major}.{minor}"),
+        None => format!("v{major}"),
+    })
+}
+
+/// A segment without its trailing `~`:
+/// `vendor.package.namespace.type.vMAJOR[.MINOR]`.
+pub fn segment() -> impl Strategy<Value = String> {
+    (token(), token(), token(), token(), version()).prop_map(
+        |(vendor, package, namespace, type_name, version)| {
+            format!("{vendor}.{package}.{namespace}.{type_name}.{version}")
+        },
+    )
+}
+
+/// A UUID in its canonical lowercase form, as used for anonymous instances.
+pub fn uuid() -> impl Strategy<Value = String> {
+    "[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}"
+}
+
+/// A schema (type) ID: one to four chained segments, ending with `~`.
+pub fn schema_id() -> impl Strategy<Value = String> {
+    proptest::collection::vec(segment(), 1..=4).prop_map(|segments| {
+        let mut id = GTS_ID_PREFIX.to_owned();
+        for segment in segments {
+            id.push_str(&segment);
+            id.push('~');
+        }
+        id
+    })
+}
+
+/// An instance ID: a schema ID followed by a named instance segment or a
+/// UUID tail.
+pub fn instance_id() -> impl Strategy<Value = String> {
+    (schema_id(), prop_oneof![segment(), uuid()]).prop_map(|(schema, tail)| schema + &tail)
+}
+
+/// A schema or instance ID.
+pub fn id() -> impl Strategy<Value = String> {
+    prop_oneof![schema_id(), instance_id()]
+}
+
+/// A reference pattern: a schema ID, or a run of leading tokens of one,
+/// followed by a single trailing `*`.
+pub fn wildcard_pattern() -> impl Strategy<Value = String> {
+    prop_oneof![
+        schema_id().prop_map(|id| id + "*"),
+        proptest::collection::vec(token(), 1..=4)
+            .prop_map(|tokens| format!("{GTS_ID_PREFIX}{}.*", tokens.join("."))),
+    ]
+}
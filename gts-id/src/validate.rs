@@ -0,0 +1,174 @@
+//! Kind-specific validation of GTS identifier strings.
+//!
+//! These are the checks behind "is this string a valid GTS schema ID /
+//! instance ID / reference pattern, and why not". Every consumer — the typed
+//! wrappers in `gts`, `x-gts-ref` validation and `gts-validator` — calls
+//! them, so the answer cannot diverge between tools. Failures are reported as
+//! a structured [`GtsIdError`] (cause plus the offending segment, if any).
+
+use crate::parse::is_uuid;
+use crate::{GTS_ID_MAX_LENGTH, GtsId, GtsIdError, GtsIdPattern, validate_instance_segment};
+
+/// Validates a concrete GTS schema (type) ID, e.g. `gts.x.core.events.type.v1~`.
+///
+/// # Errors
+/// Returns [`GtsIdError`] if `id` is not a valid GTS identifier, or if it is
+/// an instance ID (no trailing `~`).
+pub fn validate_schema_id(id: &str) -> Result<(), GtsIdError> {
+    if GtsId::try_new(id)?.is_type() {
+        Ok(())
+    } else {
+        Err(GtsIdError::new(id, "GTS type IDs must end with '~'"))
+    }
+}
+
+/// Validates a concrete GTS instance ID, e.g.
+/// `gts.x.core.events.type.v1~x.commerce.orders.created.v1.0`.
+///
+/// The type chain is checked first, then the instance segment after the last
+/// `~` against its own grammar (see [`validate_instance_segment`]); the
+/// error cause then starts with the diagnostic's code, e.g.
+/// `InstanceSegmentInvalidChar`.
+///
+/// # Errors
+/// Returns [`GtsIdError`] if `id` is not a valid GTS identifier, or if it is
+/// a schema ID (trailing `~`).
+pub fn validate_instance_id(id: &str) -> Result<(), GtsIdError> {
+    let trimmed = id.trim();
+    if let Some(tilde) = trimmed.rfind('~') {
+        let (chain, segment) = (&trimmed[..=tilde], &trimmed[tilde + 1..]);
+        if !segment.is_empty() && !is_uuid(segment) {
+            let chain_id = GtsId::try_new(chain).map_err(|err| GtsIdError {
+                input: id.to_owned(),
+                ..err
+            })?;
+            validate_instance_segment(segment, GTS_ID_MAX_LENGTH.saturating_sub(chain.len()))
+                .map_err(|err| {
+                    GtsIdError::new(id, format!("{}: {err}", err.code())).with_segment(
+                        chain_id.segments().len() + 1,
+                        chain.len(),
+                        segment,
+                    )
+                })?;
+        }
+    }
+    if GtsId::try_new(id)?.is_type() {
+        Err(GtsIdError::new(
+            id,
+            "GTS instance IDs must not end with '~' (a trailing '~' denotes a type id)",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Validates a GTS reference pattern as used in `x-gts-ref`: a concrete
+/// schema or instance ID, or one ending in a single trailing `*` wildcard
+/// (e.g. `gts.x.core.*`).
+///
+/// # Errors
+/// Returns [`GtsIdError`] if `pattern` is not a valid GTS identifier pattern.
+pub fn validate_ref_pattern(pattern: &str) -> Result<(), GtsIdError> {
+    GtsIdPattern::try_new(pattern).map(drop)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::corpus::ID_CORPUS;
+
+    fn check(
+        kind: &str,
+        validate: fn(&str) -> Result<(), GtsIdError>,
+        input: &str,
+        expected: Option<&str>,
+    ) {
+        match (validate(input), expected) {
+            (Ok(()), None) => {}
+            (Err(err), Some(cause)) => {
+                assert!(
+                    err.cause.contains(cause),
+                    "{kind} {input:?}: cause {:?} does not contain {cause:?}",
+                    err.cause
+                );
+            }
+            (result, expected) => {
+                panic!("{kind} {input:?}: got {result:?}, expected error {expected:?}")
+            }
+        }
+    }
+
+    #[test]
+    fn test_corpus() {
+        for &(input, schema, instance, pattern) in ID_CORPUS {
+            check("schema", validate_schema_id, input, schema);
+            check("instance", validate_instance_id, input, instance);
+            check("ref pattern", validate_ref_pattern, input, pattern);
+        }
+    }
+
+    #[test]
+    fn test_corpus_pins_every_cause() {
+        for &(input, schema, instance, pattern) in ID_CORPUS {
+            for cause in [schema, instance, pattern].into_iter().flatten() {
+                assert!(
+                    !cause.is_empty(),
+                    "{input:?}: an empty cause matches any error; pin its diagnostic"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_diagnostic_locates_segment() {
+        let err = validate_schema_id("gts.x.core.events.type.v1~x.1core.audit.event.v1~")
+            .expect_err("package starts with a digit");
+        let segment = err.segment.expect("segment-level error");
+        assert_eq!(segment.num, 2);
+        assert_eq!(segment.segment, "x.1core.audit.event.v1~");
+    }
+
+    #[test]
+    fn test_instance_segment_diagnostic_locates_segment() {
+        let id = "gts.x.core.events.type.v1~x.core.audit.event.v1~order.created#2024";
+        let err = validate_instance_id(id).expect_err("'#' in the instance segment");
+        assert_eq!(err.input, id);
+        assert!(
+            err.cause.starts_with("InstanceSegmentInvalidChar: "),
+            "{}",
+            err.cause
+        );
+        let segment = err.segment.expect("segment-level error");
+        assert_eq!(segment.num, 3);
+        assert_eq!(segment.offset, 48);
+        assert_eq!(segment.segment, "order.created#2024");
+
+        // Errors in the type chain are reported as before.
+        let err = validate_instance_id("gts.x.1core.events.type.v1~order.created#2024")
+            .expect_err("package starts with a digit");
+        assert_eq!(err.segment.expect("segment-level error").num, 1);
+    }
+
+    #[test]
+    fn test_instance_segment_max_length() {
+        let chain = "gts.x.core.events.type.v1~";
+        let prefix = "x.core.events.";
+        let fill = |len: usize| {
+            let name = "a".repeat(len - chain.len() - prefix.len() - ".v1".len());
+            format!("{chain}{prefix}{name}.v1")
+        };
+        assert_eq!(validate_instance_id(&fill(GTS_ID_MAX_LENGTH)), Ok(()));
+
+        let err = validate_instance_id(&fill(GTS_ID_MAX_LENGTH + 1)).expect_err("too long");
+        assert!(
+            err.cause.starts_with("InstanceSegmentTooLong: "),
+            "{}",
+            err.cause
+        );
+        assert!(err.cause.contains(&format!(
+            "max {} after the type chain",
+            GTS_ID_MAX_LENGTH - chain.len()
+        )));
+    }
+}
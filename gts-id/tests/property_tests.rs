@@ -0,0 +1,135 @@
+//! Property tests for the GTS ID parser: IDs generated from the grammar must
+//! be accepted and round-trip, targeted mutations of them must be rejected
+//! with the right kind of diagnostic, and no input may panic.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use gts_id::strategies::{self, id, instance_id, schema_id, wildcard_pattern};
+use gts_id::{
+    GTS_ID_PREFIX, GtsId, GtsIdPattern, GtsIdSegment, validate_instance_id, validate_ref_pattern,
+    validate_schema_id,
+};
+use proptest::prelude::*;
+use proptest::sample::Index;
+
+/// Uppercase one letter after the prefix.
+fn uppercase_one(id: &str, index: Index) -> String {
+    let letters: Vec<usize> = id
+        .char_indices()
+        .skip(GTS_ID_PREFIX.len())
+        .filter(|(_, c)| c.is_ascii_lowercase())
+        .map(|(i, _)| i)
+        .collect();
+    let at = letters[index.index(letters.len())];
+    let mut mutated = id.to_owned();
+    mutated.replace_range(at..=at, &id[at..=at].to_ascii_uppercase());
+    mutated
+}
+
+/// Drop one of the four name tokens of one named segment.
+fn drop_token(id: &str, segment: Index, token: Index) -> String {
+    let remainder = &id[GTS_ID_PREFIX.len()..];
+    let mut parts: Vec<String> = remainder.split('~').map(str::to_owned).collect();
+    // Only named segments have tokens to drop; skip the empty part after a
+    // trailing `~` and a UUID tail.
+    let named: Vec<usize> = (0..parts.len())
+        .filter(|&i| parts[i].split('.').count() >= 5)
+        .collect();
+    let target = named[segment.index(named.len())];
+    let mut tokens: Vec<&str> = parts[target].split('.').collect();
+    tokens.remove(token.index(4));
+    parts[target] = tokens.join(".");
+    format!("{GTS_ID_PREFIX}{}", parts.join("~"))
+}
+
+/// Double one `~`.
+fn duplicate_tilde(id: &str, index: Index) -> String {
+    let tildes: Vec<usize> = id.match_indices('~').map(|(i, _)| i).collect();
+    let at = tildes[index.index(tildes.len())];
+    format!("{}~{}", &id[..at], &id[at..])
+}
+
+proptest! {
+    #[test]
+    fn generated_schema_ids_are_accepted(id in schema_id()) {
+        prop_assert!(validate_schema_id(&id).is_ok(), "{:?}", validate_schema_id(&id));
+        prop_assert!(validate_instance_id(&id).is_err());
+        prop_assert!(validate_ref_pattern(&id).is_ok());
+    }
+
+    #[test]
+    fn generated_instance_ids_are_accepted(id in instance_id()) {
+        prop_assert!(validate_instance_id(&id).is_ok(), "{:?}", validate_instance_id(&id));
+        prop_assert!(validate_schema_id(&id).is_err());
+        prop_assert!(validate_ref_pattern(&id).is_ok());
+    }
+
+    #[test]
+    fn generated_ids_round_trip(id in id()) {
+        let parsed = GtsId::try_new(&id).unwrap();
+        prop_assert_eq!(parsed.id(), id.as_str());
+        // Segments keep their `~`, so they concatenate back to the ID.
+        let raw: String = parsed.segments().iter().map(GtsIdSegment::raw).collect();
+        prop_assert!(parsed.matches_pattern(&parsed.to_pattern()));
+        prop_assert_eq!(GtsId::try_new(parsed.id()).unwrap(), parsed);
+        prop_assert_eq!(format!("{GTS_ID_PREFIX}{raw}"), id);
+    }
+
+    #[test]
+    fn generated_patterns_are_accepted(pattern in wildcard_pattern()) {
+        prop_assert!(GtsIdPattern::try_new(&pattern).is_ok(), "{:?}", GtsIdPattern::try_new(&pattern));
+        prop_assert!(validate_schema_id(&pattern).is_err());
+    }
+
+    #[test]
+    fn uppercase_is_an_identifier_level_error(id in id(), index in any::<Index>()) {
+        let err = GtsId::try_new(&uppercase_one(&id, index)).unwrap_err();
+        prop_assert!(err.segment.is_none(), "{}", err);
+        prop_assert!(err.cause.contains("must be lowercase"), "{}", err);
+    }
+
+    #[test]
+    fn dropped_token_is_a_segment_error(
+        id in id(),
+        segment in any::<Index>(),
+        token in any::<Index>(),
+    ) {
+        let mutated = drop_token(&id, segment, token);
+        let err = GtsId::try_new(&mutated).unwrap_err();
+        prop_assert!(err.segment.is_some(), "{}: {}", mutated, err);
+    }
+
+    #[test]
+    fn duplicated_tilde_is_an_empty_segment_error(id in id(), index in any::<Index>()) {
+        let mutated = duplicate_tilde(&id, index);
+        let err = GtsId::try_new(&mutated).unwrap_err();
+        prop_assert!(err.segment.is_none(), "{}: {}", mutated, err);
+        prop_assert!(err.cause.contains("empty segment"), "{}: {}", mutated, err);
+    }
+
+    #[test]
+    fn arbitrary_input_never_panics(input in "\\PC*") {
+        let _ = GtsId::try_new(&input);
+        let _ = GtsIdPattern::try_new(&input);
+        let schema = validate_schema_id(&input);
+        let instance = validate_instance_id(&input);
+        prop_assert!(schema.is_err() || instance.is_err());
+    }
+
+    #[test]
+    fn prefixed_noise_never_panics(rest in "[a-z0-9_.~*-]{0,64}") {
+        let input = format!("{GTS_ID_PREFIX}{rest}");
+        let _ = GtsId::try_new(&input);
+        let _ = GtsIdPattern::try_new(&input);
+    }
+}
+
+#[test]
+fn strategies_use_the_configured_prefix() {
+    use proptest::strategy::ValueTree;
+    use proptest::test_runner::TestRunner;
+
+    let mut runner = TestRunner::deterministic();
+    let id = strategies::id().new_tree(&mut runner).unwrap().current();
+    assert!(id.starts_with(GTS_ID_PREFIX), "{id}");
+}
@@ -1,9 +1,11 @@
 use gts::gts::GtsTypeId;
 use std::fmt::Write;
 use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use gts::gts_schema_for;
+use gts::manifest::{SchemaManifest, SchemaManifestEntry};
 use serde::{Deserialize, Serialize};
 
 const SEPARATOR: &str =
@@ -104,7 +106,7 @@ mod test_structs {
     }
 }
 
-/// GTS Macros CLI - Demo tool for GTS schema introspection and inheritance
+/// GTS Macros CLI - schema codegen and introspection for the GTS inheritance demo chain
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
@@ -113,20 +115,280 @@ struct Args {
     /// Instances are saved as `{instance_id}.json`.
     #[arg(long, value_name = "DIR")]
     dump: Option<PathBuf>,
+
+    /// Print the schema index: every registered type's ID, description,
+    /// base ID and schema, as a JSON array
+    #[arg(long)]
+    list_schemas: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
 }
 
-fn main() -> anyhow::Result<()> {
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Print the demo output (default when no subcommand is given)
+    Demo,
+    /// Write every registered type's schema to `<schema_id>.schema.json`
+    Generate {
+        /// Output directory
+        #[arg(long, value_name = "DIR", default_value = "schemas")]
+        out_dir: PathBuf,
+        /// Also write the well-known instances as `<instance_id>.json`
+        #[arg(long)]
+        instances: bool,
+        /// Compare against the existing files instead of writing; exit
+        /// non-zero if any file is missing or differs
+        #[arg(long)]
+        check: bool,
+        /// Run gts-validator over the output directory afterwards; exit
+        /// non-zero and print its report if any identifier is invalid
+        #[arg(long)]
+        validate: bool,
+    },
+    /// Write a manifest listing every schema's ID, path, base ID,
+    /// description and content hash
+    Manifest {
+        /// Manifest file to write
+        #[arg(long, value_name = "FILE", default_value = "schemas/index.json")]
+        out: PathBuf,
+        /// Index the schemas in this directory instead of the registered types
+        #[arg(long, value_name = "DIR")]
+        dir: Option<PathBuf>,
+        /// Compare against the manifest on disk instead of writing; exit
+        /// non-zero if it is missing or differs
+        #[arg(long)]
+        check: bool,
+    },
+    /// Check that a schema directory is self-contained: every gts:// $ref
+    /// resolves within it, no $id is defined twice, the $ref graph is
+    /// acyclic and all schemas declare the same $schema
+    Verify {
+        /// Schema directory to check
+        #[arg(value_name = "DIR", default_value = "schemas")]
+        dir: PathBuf,
+    },
+}
+
+fn main() -> anyhow::Result<ExitCode> {
     let args = Args::parse();
 
     if let Some(dir) = args.dump {
         dump_to_directory(&dir)?;
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if args.list_schemas {
+        println!("{}", gts::generate_schema_index_json());
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    match args.command.unwrap_or(Command::Demo) {
+        Command::Demo => {
+            run_demo()?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Generate {
+            out_dir,
+            instances,
+            check,
+            validate,
+        } => run_generate(&out_dir, instances, check, validate),
+        Command::Manifest { out, dir, check } => run_manifest(&out, dir.as_deref(), check),
+        Command::Verify { dir } => run_verify(&dir),
+    }
+}
+
+fn run_generate(
+    out_dir: &Path,
+    instances: bool,
+    check: bool,
+    validate: bool,
+) -> anyhow::Result<ExitCode> {
+    let files = generated_files(instances)?;
+    if check {
+        let drift = check_files(out_dir, &files)?;
+        for name in &drift {
+            eprintln!("Out of date: {}", out_dir.join(name).display());
+        }
+        if !drift.is_empty() {
+            eprintln!(
+                "{} file(s) out of date; run `gts-macros-cli generate` to update",
+                drift.len()
+            );
+            return Ok(ExitCode::FAILURE);
+        }
+        println!("All {} file(s) up to date", files.len());
+    } else {
+        write_files(out_dir, &files)?;
+    }
+    if validate && !validate_output(out_dir)? {
+        return Ok(ExitCode::FAILURE);
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn run_manifest(out: &Path, dir: Option<&Path>, check: bool) -> anyhow::Result<ExitCode> {
+    let manifest = match dir {
+        Some(dir) => SchemaManifest::from_dir(dir)?,
+        None => registry_manifest()?,
+    };
+    let rendered = manifest.to_json_pretty()?;
+    if check {
+        if !is_up_to_date(out, &rendered)? {
+            eprintln!(
+                "Out of date: {}; run `gts-macros-cli manifest` to update",
+                out.display()
+            );
+            return Ok(ExitCode::FAILURE);
+        }
+        println!("Manifest up to date: {}", out.display());
     } else {
-        run_demo()?;
+        if let Some(parent) = out.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        write_if_changed(out, &rendered)?;
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn run_verify(dir: &Path) -> anyhow::Result<ExitCode> {
+    let report = gts::GtsStore::verify_bundle(dir)?;
+    if !report.is_self_contained() {
+        for finding in &report.findings {
+            eprintln!("{finding}");
+        }
+        eprintln!(
+            "{} finding(s) in {}; the bundle is not self-contained",
+            report.findings.len(),
+            dir.display()
+        );
+        return Ok(ExitCode::FAILURE);
+    }
+    println!("Verified {} schema(s) in {}", report.schemas, dir.display());
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Every type in the link-time registry (`gts::schema_index`), as
+/// `(type_id, pretty schema)` sorted by type ID.
+fn registered_schemas() -> anyhow::Result<Vec<(&'static str, String)>> {
+    gts::schema_index()
+        .into_iter()
+        .map(|entry| {
+            let schema: serde_json::Value = serde_json::from_str(&(entry.schema_json)())?;
+            Ok((
+                entry.schema_id,
+                gts::canonical_schema_to_string_pretty(&schema),
+            ))
+        })
+        .collect()
+}
+
+/// The files `generate` produces, as `(file name, contents)` sorted by name.
+///
+/// Schemas are written in canonical form
+/// (`gts::canonical_schema_to_string_pretty`) and every file ends with a
+/// newline, so the output is byte-for-byte stable across runs and field
+/// reorderings.
+fn generated_files(instances: bool) -> anyhow::Result<Vec<(String, String)>> {
+    let mut files: Vec<(String, String)> = registered_schemas()?
+        .into_iter()
+        .map(|(type_id, schema)| (format!("{type_id}.schema.json"), schema + "\n"))
+        .collect();
+    if instances {
+        let event = create_sample_event()?;
+        files.push((
+            format!("{}.json", event.id),
+            serde_json::to_string_pretty(&event)? + "\n",
+        ));
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Write `files` into `dir`, leaving files whose contents already match untouched.
+fn write_files(dir: &Path, files: &[(String, String)]) -> anyhow::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    for (name, contents) in files {
+        write_if_changed(&dir.join(name), contents)?;
     }
+    Ok(())
+}
 
+fn write_if_changed(path: &Path, contents: &str) -> anyhow::Result<()> {
+    if is_up_to_date(path, contents)? {
+        println!("Unchanged: {}", path.display());
+    } else {
+        std::fs::write(path, contents)?;
+        println!("Wrote: {}", path.display());
+    }
     Ok(())
 }
 
+/// Whether `path` exists with exactly `contents`.
+fn is_up_to_date(path: &Path, contents: &str) -> anyhow::Result<bool> {
+    match std::fs::read_to_string(path) {
+        Ok(existing) => Ok(existing == contents),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Manifest of the registered types, with the paths and hashes `generate` writes.
+fn registry_manifest() -> anyhow::Result<SchemaManifest> {
+    let entries = generated_files(false)?
+        .into_iter()
+        .filter_map(|(name, contents)| SchemaManifestEntry::from_schema_file(name, &contents))
+        .collect();
+    Ok(SchemaManifest::new(entries))
+}
+
+/// Run `gts_validator` over `dir` with key scanning on and the vendors of the
+/// registered types as the allowed vendors. Prints the human report to stderr
+/// and returns `false` when validation fails.
+fn validate_output(dir: &Path) -> anyhow::Result<bool> {
+    let mut vendors = std::collections::BTreeSet::new();
+    for (type_id, _) in registered_schemas()? {
+        let id = gts::GtsId::try_new(type_id)?;
+        if let Some(first) = id.segments().first() {
+            vendors.insert(first.vendor().to_owned());
+        }
+    }
+
+    let mut fs_config = gts_validator::FsSourceConfig::default();
+    fs_config.paths = vec![dir.to_path_buf()];
+    let mut validation_config = gts_validator::ValidationConfig::default();
+    validation_config.scan_keys = true;
+    validation_config.vendor_policy = if vendors.len() == 1 {
+        gts_validator::VendorPolicy::MustMatch(vendors.into_iter().collect())
+    } else {
+        gts_validator::VendorPolicy::AllowList(vendors.into_iter().collect())
+    };
+
+    let report = gts_validator::validate_fs(&fs_config, &validation_config)?;
+    if report.ok {
+        println!(
+            "Validated {} file(s) in {}",
+            report.scanned_files,
+            dir.display()
+        );
+    } else {
+        gts_validator::output::write_human(&report, &mut std::io::stderr())?;
+    }
+    Ok(report.ok)
+}
+
+/// Names of the `files` that are missing from `dir` or differ from it.
+fn check_files(dir: &Path, files: &[(String, String)]) -> anyhow::Result<Vec<String>> {
+    let mut drift = Vec::new();
+    for (name, contents) in files {
+        if !is_up_to_date(&dir.join(name), contents)? {
+            drift.push(name.clone());
+        }
+    }
+    Ok(drift)
+}
+
 /// Helper function to save a schema to a file
 fn save_schema(
     dir: &std::path::Path,
@@ -134,7 +396,10 @@ fn save_schema(
     schema_id: &str,
 ) -> anyhow::Result<()> {
     let schema_path = dir.join(format!("{schema_id}.schema.json"));
-    std::fs::write(&schema_path, serde_json::to_string_pretty(schema)? + "\n")?;
+    std::fs::write(
+        &schema_path,
+        gts::canonical_schema_to_string_pretty(schema) + "\n",
+    )?;
     println!("Saved schema: {}", schema_path.display());
     Ok(())
 }
@@ -516,6 +781,35 @@ mod tests {
         assert!(schema4_id.contains("v1~"));
     }
 
+    #[test]
+    fn test_schema_index_lists_registered_types() {
+        use gts::GtsSchema;
+
+        let index: serde_json::Value =
+            serde_json::from_str(&gts::generate_schema_index_json()).unwrap();
+        let indexed: Vec<&str> = index
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|entry| entry["schema_id"].as_str().unwrap())
+            .collect();
+        let registered: Vec<&str> = registered_schemas()
+            .unwrap()
+            .into_iter()
+            .map(|(type_id, _)| type_id)
+            .collect();
+        assert_eq!(indexed, registered);
+        assert_eq!(
+            registered,
+            [
+                test_structs::BaseEventV1::<()>::TYPE_ID,
+                test_structs::AuditPayloadV1::<()>::TYPE_ID,
+                test_structs::PlaceOrderDataV1::<()>::TYPE_ID,
+                test_structs::PlaceOrderDataPayloadV1::TYPE_ID,
+            ]
+        );
+    }
+
     #[test]
     fn test_schema_serialization() {
         // Test that we can generate schemas
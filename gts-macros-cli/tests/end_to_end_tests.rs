@@ -0,0 +1,100 @@
+//! End-to-end contract between the workspace crates: the schemas and instance
+//! `gts-macros-cli generate` emits for the `BaseEventV1` chain must pass
+//! `gts-validator`, load into a `GtsStore` and validate the instance there.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use std::path::Path;
+use std::process::Command;
+
+use gts::{GtsFileReader, GtsStore};
+use serde_json::{Value, json};
+
+const INSTANCE_FILE: &str = "d1b475cf-8155-45c3-ab75-b245bd38116b.json";
+
+fn cli_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_gts-macros-cli")
+}
+
+/// Generate the full chain (4 schemas and the sample instance) into `dir`.
+fn generate(dir: &Path) {
+    let output = Command::new(cli_bin())
+        .arg("generate")
+        .arg("--out-dir")
+        .arg(dir)
+        .arg("--instances")
+        .output()
+        .expect("failed to run gts-macros-cli");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+fn load_store(dir: &Path) -> GtsStore {
+    let reader = GtsFileReader::new(&[dir.to_string_lossy().into_owned()], None);
+    GtsStore::with_reader(Box::new(reader))
+}
+
+#[test]
+fn generated_chain_passes_validator_and_store() {
+    let tmp = tempfile::tempdir().unwrap();
+    generate(tmp.path());
+
+    let mut fs_config = gts_validator::FsSourceConfig::default();
+    fs_config.paths = vec![tmp.path().to_path_buf()];
+    let mut validation_config = gts_validator::ValidationConfig::default();
+    validation_config.scan_keys = true;
+    validation_config.vendor_policy = gts_validator::VendorPolicy::MustMatch("x".to_owned());
+    let report = gts_validator::validate_fs(&fs_config, &validation_config).unwrap();
+    assert_eq!(report.scanned_files, 5, "{report:?}");
+    assert!(report.validation_errors.is_empty(), "{report:?}");
+    assert!(report.ok);
+
+    let instance: Value =
+        serde_json::from_str(&std::fs::read_to_string(tmp.path().join(INSTANCE_FILE)).unwrap())
+            .unwrap();
+    let type_id = instance["type"].as_str().unwrap().to_owned();
+    let mut store = load_store(tmp.path());
+    for schema_id in [
+        "gts.x.core.events.type.v1~",
+        "gts.x.core.events.type.v1~x.core.audit.event.v1~",
+        "gts.x.core.events.type.v1~x.core.audit.event.v1~x.marketplace.orders.purchase.v1~",
+        type_id.as_str(),
+    ] {
+        store
+            .validate_schema(schema_id)
+            .unwrap_or_else(|e| panic!("{schema_id}: {e}"));
+    }
+    store.validate_payload(&type_id, &instance).unwrap();
+    assert!(
+        store
+            .payload_violations(&type_id, &instance)
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn corrupted_instance_fails_inside_payload() {
+    let tmp = tempfile::tempdir().unwrap();
+    generate(tmp.path());
+
+    let mut instance: Value =
+        serde_json::from_str(&std::fs::read_to_string(tmp.path().join(INSTANCE_FILE)).unwrap())
+            .unwrap();
+    let type_id = instance["type"].as_str().unwrap().to_owned();
+    instance["payload"]["data"]["product_id"] = json!(17);
+
+    let mut store = load_store(tmp.path());
+    assert!(store.validate_payload(&type_id, &instance).is_err());
+    let violations = store.payload_violations(&type_id, &instance).unwrap();
+    assert!(!violations.is_empty());
+    assert!(
+        violations
+            .iter()
+            .all(|v| v.instance_path == "/payload/data/product_id"),
+        "{violations:?}"
+    );
+}
@@ -0,0 +1,199 @@
+//! Integration tests for `gts-macros-cli generate`.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::process::{Command, Output};
+use std::time::SystemTime;
+
+fn cli_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_gts-macros-cli")
+}
+
+fn generate(dir: &Path, extra: &[&str]) -> Output {
+    Command::new(cli_bin())
+        .arg("generate")
+        .arg("--out-dir")
+        .arg(dir)
+        .args(extra)
+        .output()
+        .expect("failed to run gts-macros-cli")
+}
+
+fn snapshot(dir: &Path) -> BTreeMap<String, (String, SystemTime)> {
+    std::fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| {
+            let entry = entry.unwrap();
+            let name = entry.file_name().into_string().unwrap();
+            let contents = std::fs::read_to_string(entry.path()).unwrap();
+            let modified = entry.metadata().unwrap().modified().unwrap();
+            (name, (contents, modified))
+        })
+        .collect()
+}
+
+#[test]
+fn generate_second_run_is_noop() {
+    let tmp = tempfile::tempdir().unwrap();
+
+    let first = generate(tmp.path(), &["--instances"]);
+    assert!(first.status.success());
+    let before = snapshot(tmp.path());
+    assert_eq!(before.len(), 5, "4 schemas + 1 instance: {before:?}");
+    assert!(
+        before
+            .iter()
+            .filter(|(name, _)| name.ends_with(".schema.json"))
+            .all(|(_, (contents, _))| contents.ends_with("}\n"))
+    );
+
+    let second = generate(tmp.path(), &["--instances"]);
+    assert!(second.status.success());
+    assert_eq!(snapshot(tmp.path()), before);
+    let stdout = String::from_utf8_lossy(&second.stdout);
+    assert!(!stdout.contains("Wrote:"), "{stdout}");
+    assert_eq!(stdout.matches("Unchanged:").count(), 5);
+}
+
+#[test]
+fn generate_check_passes_when_up_to_date() {
+    let tmp = tempfile::tempdir().unwrap();
+    assert!(generate(tmp.path(), &[]).status.success());
+
+    let check = generate(tmp.path(), &["--check"]);
+    assert!(
+        check.status.success(),
+        "{}",
+        String::from_utf8_lossy(&check.stderr)
+    );
+}
+
+/// `generate` covers exactly the schemas `--list-schemas` reports.
+#[test]
+fn generate_covers_every_registered_schema() {
+    let list = Command::new(cli_bin())
+        .arg("--list-schemas")
+        .output()
+        .expect("failed to run gts-macros-cli");
+    assert!(list.status.success());
+    let index: serde_json::Value = serde_json::from_slice(&list.stdout).unwrap();
+    let mut expected: Vec<String> = index
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| format!("{}.schema.json", entry["schema_id"].as_str().unwrap()))
+        .collect();
+    expected.sort();
+
+    let tmp = tempfile::tempdir().unwrap();
+    let output = generate(tmp.path(), &["--validate"]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let written: Vec<String> = snapshot(tmp.path()).into_keys().collect();
+    assert_eq!(written, expected);
+
+    let check = generate(tmp.path(), &["--check", "--validate"]);
+    assert!(check.status.success());
+    let stdout = String::from_utf8_lossy(&check.stdout);
+    assert!(
+        stdout.contains(&format!("All {} file(s) up to date", expected.len())),
+        "{stdout}"
+    );
+}
+
+/// The committed fixtures under `src/schemas/` are golden files: regenerating
+/// them must reproduce them byte for byte.
+#[test]
+fn committed_schemas_match_generated_output() {
+    let committed = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/schemas");
+    let check = generate(&committed, &["--check"]);
+    assert!(
+        check.status.success(),
+        "{}\nrun `cargo run -p gts-macros-cli -- generate --out-dir gts-macros-cli/src/schemas`",
+        String::from_utf8_lossy(&check.stderr)
+    );
+}
+
+#[test]
+fn generate_check_fails_on_drift_without_writing() {
+    let tmp = tempfile::tempdir().unwrap();
+    assert!(generate(tmp.path(), &[]).status.success());
+
+    let drifted = std::fs::read_dir(tmp.path())
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    std::fs::write(&drifted, "{}\n").unwrap();
+
+    let check = generate(tmp.path(), &["--check", "--instances"]);
+    assert!(!check.status.success());
+    let stderr = String::from_utf8_lossy(&check.stderr);
+    assert!(
+        stderr.contains(drifted.file_name().unwrap().to_str().unwrap()),
+        "{stderr}"
+    );
+    assert!(stderr.contains("2 file(s) out of date"), "{stderr}");
+    assert_eq!(std::fs::read_to_string(&drifted).unwrap(), "{}\n");
+    assert_eq!(std::fs::read_dir(tmp.path()).unwrap().count(), 4);
+}
+
+/// The generator's output must always pass gts-validator.
+#[test]
+fn generate_output_passes_validator() {
+    let tmp = tempfile::tempdir().unwrap();
+
+    let output = generate(tmp.path(), &["--instances", "--validate"]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Validated 5 file(s)"));
+}
+
+/// The generated chain must pass the store's schema validation, including
+/// the `x-gts-generic-field` extension contract.
+#[test]
+fn generate_output_passes_store_validation() {
+    let tmp = tempfile::tempdir().unwrap();
+    assert!(generate(tmp.path(), &[]).status.success());
+
+    let mut store = gts::GtsStore::new();
+    let mut type_ids = Vec::new();
+    for name in snapshot(tmp.path()).into_keys() {
+        let type_id = name.strip_suffix(".schema.json").unwrap().to_owned();
+        let content = std::fs::read_to_string(tmp.path().join(&name)).unwrap();
+        let schema: serde_json::Value = serde_json::from_str(&content).unwrap();
+        store.register_schema(&type_id, &schema).unwrap();
+        type_ids.push(type_id);
+    }
+    assert_eq!(type_ids.len(), 4);
+    for type_id in &type_ids {
+        if let Err(e) = store.validate_schema(type_id) {
+            panic!("{type_id}: {e}");
+        }
+    }
+}
+
+#[test]
+fn generate_validate_fails_on_invalid_ids() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(
+        tmp.path().join("extra.json"),
+        "{ \"$ref\": \"gts://gts.y.core.events.type.v1~\" }\n",
+    )
+    .unwrap();
+
+    let output = generate(tmp.path(), &["--validate"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("VALIDATION ERRORS"), "{stderr}");
+    assert!(stderr.contains("Vendor mismatch"), "{stderr}");
+}
@@ -0,0 +1,100 @@
+//! Integration tests for `gts-macros-cli manifest`.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use gts::manifest::SchemaManifest;
+
+fn cli_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_gts-macros-cli")
+}
+
+/// The committed `BaseEventV1` chain fixtures.
+fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("src/schemas")
+}
+
+fn manifest(out: &Path, extra: &[&str]) -> Output {
+    Command::new(cli_bin())
+        .arg("manifest")
+        .arg("--out")
+        .arg(out)
+        .args(extra)
+        .output()
+        .expect("failed to run gts-macros-cli")
+}
+
+fn read_manifest(path: &Path) -> SchemaManifest {
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn manifest_lists_chain_sorted_by_id() {
+    let tmp = tempfile::tempdir().unwrap();
+    let out = tmp.path().join("index.json");
+    assert!(manifest(&out, &[]).status.success());
+
+    let parsed = read_manifest(&out);
+    assert_eq!(parsed.schemas.len(), 4);
+    let ids: Vec<&str> = parsed.schemas.iter().map(|e| e.id.as_str()).collect();
+    let mut sorted = ids.clone();
+    sorted.sort_unstable();
+    assert_eq!(ids, sorted);
+
+    assert_eq!(parsed.schemas[0].id, "gts.x.core.events.type.v1~");
+    assert_eq!(parsed.schemas[0].base_id, None);
+    for pair in parsed.schemas.windows(2) {
+        assert_eq!(pair[1].base_id.as_deref(), Some(pair[0].id.as_str()));
+    }
+    for entry in &parsed.schemas {
+        assert_eq!(entry.path, format!("{}.schema.json", entry.id));
+        assert!(entry.hash.starts_with("sha256:"), "{}", entry.hash);
+        assert!(entry.description.is_some());
+    }
+}
+
+#[test]
+fn manifest_from_fixtures_matches_registry() {
+    let tmp = tempfile::tempdir().unwrap();
+    let from_registry = tmp.path().join("registry.json");
+    let from_dir = tmp.path().join("dir.json");
+    assert!(manifest(&from_registry, &[]).status.success());
+    let fixtures = fixtures_dir();
+    let output = manifest(&from_dir, &["--dir", fixtures.to_str().unwrap()]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    assert_eq!(
+        std::fs::read_to_string(&from_registry).unwrap(),
+        std::fs::read_to_string(&from_dir).unwrap()
+    );
+}
+
+#[test]
+fn manifest_check_detects_drift() {
+    let tmp = tempfile::tempdir().unwrap();
+    let out = tmp.path().join("index.json");
+
+    assert!(!manifest(&out, &["--check"]).status.success());
+    assert!(!out.exists());
+
+    assert!(manifest(&out, &[]).status.success());
+    let second = manifest(&out, &[]);
+    assert!(String::from_utf8_lossy(&second.stdout).contains("Unchanged:"));
+    assert!(manifest(&out, &["--check"]).status.success());
+
+    let mut edited = read_manifest(&out);
+    edited.schemas[0].hash = "sha256:0".to_owned();
+    let edited = serde_json::to_string_pretty(&edited).unwrap() + "\n";
+    std::fs::write(&out, &edited).unwrap();
+
+    let check = manifest(&out, &["--check"]);
+    assert!(!check.status.success());
+    assert!(String::from_utf8_lossy(&check.stderr).contains("Out of date"));
+    assert_eq!(std::fs::read_to_string(&out).unwrap(), edited);
+}
@@ -0,0 +1,76 @@
+//! Integration tests for `gts-macros-cli verify`.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use serde_json::json;
+
+fn cli_bin() -> &'static str {
+    env!("CARGO_BIN_EXE_gts-macros-cli")
+}
+
+/// The committed `BaseEventV1` chain fixtures.
+fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("src/schemas")
+}
+
+fn verify(dir: &Path) -> Output {
+    Command::new(cli_bin())
+        .arg("verify")
+        .arg(dir)
+        .output()
+        .expect("failed to run gts-macros-cli")
+}
+
+#[test]
+fn verify_accepts_fixtures() {
+    let output = verify(&fixtures_dir());
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Verified 4 schema(s)"));
+}
+
+#[test]
+fn verify_reports_broken_bundle() {
+    let tmp = tempfile::tempdir().unwrap();
+    let write = |name: &str, id: &str, draft: &str, reference: &str| {
+        let schema = json!({
+            "$schema": draft,
+            "$id": format!("gts://{id}"),
+            "type": "object",
+            "properties": {"next": {"$ref": format!("gts://{reference}")}}
+        });
+        std::fs::write(tmp.path().join(name), schema.to_string()).unwrap();
+    };
+    let draft_07 = "http://json-schema.org/draft-07/schema#";
+    write("a.json", "gts.x.b.c.a.v1~", draft_07, "gts.x.b.c.b.v1~");
+    write("b.json", "gts.x.b.c.b.v1~", draft_07, "gts.x.b.c.a.v1~");
+    write("c.json", "gts.x.b.c.c.v1~", draft_07, "gts.x.b.c.gone.v1~");
+    write(
+        "d.json",
+        "gts.x.b.c.c.v1~",
+        "https://json-schema.org/draft/2020-12/schema",
+        "gts.x.b.c.a.v1~",
+    );
+
+    let output = verify(tmp.path());
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    for expected in [
+        "c.json: $ref at '/properties/next/$ref' to 'gts.x.b.c.gone.v1~' does not resolve",
+        "'gts.x.b.c.c.v1~' is defined by both",
+        "$ref cycle: gts.x.b.c.a.v1~ -> gts.x.b.c.b.v1~ -> gts.x.b.c.a.v1~",
+        "d.json: $schema 'https://json-schema.org/draft/2020-12/schema' differs",
+        "4 finding(s)",
+    ] {
+        assert!(
+            stderr.contains(expected),
+            "missing {expected:?} in:\n{stderr}"
+        );
+    }
+}
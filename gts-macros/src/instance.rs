@@ -44,12 +44,14 @@ use syn::{
 };
 
 /// Validate an `instance_id` literal against the full GTS spec via the
-/// shared `gts-id` validator. Catches malformed segments (missing
+/// shared `gts-id` validator — the same check gts-validator applies to
+/// instance IDs at scan time. Catches malformed segments (missing
 /// `<vendor>.<package>.<namespace>.<type>.v<N>` shape, bad version
-/// suffix, etc.) at compile time with span pointing at the literal.
+/// suffix, a trailing `~`, etc.) at compile time with span pointing at the
+/// literal.
 fn validate_instance_id_format(instance_id: &LitStr) -> syn::Result<()> {
     let raw = instance_id.value();
-    if let Err(e) = gts_id::GtsId::try_new(&raw) {
+    if let Err(e) = gts_id::validate_instance_id(&raw) {
         let msg = format!("Invalid GTS instance ID: {e}");
         return Err(syn::Error::new_spanned(instance_id, msg));
     }
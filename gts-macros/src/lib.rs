@@ -213,6 +213,40 @@ fn get_serde_rename(field: &syn::Field) -> Option<String> {
     None
 }
 
+/// How a field is filled in when it is missing from the input, per its
+/// `#[serde(default)]` / `#[serde(default = "path")]` attribute.
+enum SerdeDefault {
+    /// `#[serde(default)]` - use `Default::default()`.
+    Trait,
+    /// `#[serde(default = "path")]` - call the named function.
+    Path(syn::ExprPath),
+}
+
+/// Extract the serde `default` setting from field attributes
+fn get_serde_default(field: &syn::Field) -> Option<SerdeDefault> {
+    let mut found = None;
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("serde")) {
+        // Other serde keys (rename, with, ...) are skipped; parse errors are
+        // left for serde's own derive to report.
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("default") {
+                found = Some(if meta.input.peek(Token![=]) {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    SerdeDefault::Path(lit.parse()?)
+                } else {
+                    SerdeDefault::Trait
+                });
+            } else if meta.input.peek(Token![=]) {
+                let _: syn::Expr = meta.value()?.parse()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                meta.parse_nested_meta(|_| Ok(()))?;
+            }
+            Ok(())
+        });
+    }
+    found
+}
+
 /// Check if a field has a serde rename matching any of the given names
 fn has_matching_serde_rename(field: &syn::Field, names: &[&str]) -> bool {
     get_serde_rename(field).is_some_and(|rename| names.contains(&rename.as_str()))
@@ -258,6 +292,26 @@ fn validate_base_struct_fields(
     validate_field_types(input, fields)
 }
 
+/// Find the GTS Type field (one of `TYPE_FIELD_NAMES` or a matching serde
+/// rename, typed `GtsTypeId`) and return it with its serialized name.
+fn find_type_field(
+    fields: &syn::punctuated::Punctuated<syn::Field, syn::token::Comma>,
+) -> Option<(&syn::Field, String)> {
+    use syn::ext::IdentExt;
+    fields
+        .iter()
+        .find(|field| {
+            (field_name_matches(field, TYPE_FIELD_NAMES)
+                || has_matching_serde_rename(field, SERDE_TYPE_RENAMES))
+                && is_type_gts_schema_id(&field.ty)
+        })
+        .and_then(|field| {
+            let ident = field.ident.as_ref()?;
+            let name = get_serde_rename(field).unwrap_or_else(|| ident.unraw().to_string());
+            Some((field, name))
+        })
+}
+
 /// Validate that field types are correct for ID and GTS Type fields
 fn validate_field_types(
     input: &syn::DeriveInput,
@@ -508,9 +562,11 @@ fn add_missing_derives(input: &mut syn::DeriveInput, base: &BaseAttr) {
         let derives_str = derives_to_add.join(", ");
         let derives_tokens: proc_macro2::TokenStream =
             derives_str.parse().expect("Failed to parse derive tokens");
+        // Insert first so container-level `#[serde(...)]` / `#[schemars(...)]`
+        // helper attributes come after the derive that introduces them.
         input
             .attrs
-            .push(syn::parse_quote!(#[derive(#derives_tokens)]));
+            .insert(0, syn::parse_quote!(#[derive(#derives_tokens)]));
     }
 }
 
@@ -600,6 +656,279 @@ fn add_gts_serde_attrs(input: &mut syn::DeriveInput, base: &BaseAttr) {
     }
 }
 
+/// Add the serde hooks for `inject_type_field = true` to the base struct's GTS
+/// Type field and return the field's serialized name.
+///
+/// The hooks are the hidden `__gts_serialize_type_field` /
+/// `__gts_deserialize_type_field` associated functions emitted alongside the
+/// struct, addressed with a turbofish so they resolve inside serde's generated
+/// wrapper types.
+fn inject_type_field_attrs(
+    input: &syn::DeriveInput,
+    modified_input: &mut syn::DeriveInput,
+    base: &BaseAttr,
+) -> Result<String, syn::Error> {
+    if !matches!(base, BaseAttr::IsBase) {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "struct_to_gts_schema: `inject_type_field` is only valid on base structs \
+             (base = true); derived types inherit it from their base",
+        ));
+    }
+    let field_name = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => {
+                find_type_field(&fields.named).map(|(f, name)| (f.ident.clone(), name))
+            }
+            _ => None,
+        },
+        _ => None,
+    };
+    let Some((Some(field_ident), serialized_name)) = field_name else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            format!(
+                "struct_to_gts_schema: `inject_type_field` requires a GTS Type field (one of: {}) \
+                 of type GtsTypeId",
+                TYPE_FIELD_NAMES.join(", ")
+            ),
+        ));
+    };
+
+    let struct_name = &input.ident;
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let turbofish = ty_generics.as_turbofish();
+    let serialize_with = quote!(#struct_name #turbofish :: __gts_serialize_type_field).to_string();
+    let deserialize_with =
+        quote!(#struct_name #turbofish :: __gts_deserialize_type_field).to_string();
+
+    if let Data::Struct(data) = &mut modified_input.data
+        && let Fields::Named(fields) = &mut data.fields
+        && let Some(field) = fields
+            .named
+            .iter_mut()
+            .find(|f| f.ident.as_ref() == Some(&field_ident))
+    {
+        field.attrs.push(syn::parse_quote!(
+            #[serde(serialize_with = #serialize_with, deserialize_with = #deserialize_with)]
+        ));
+    }
+    Ok(serialized_name)
+}
+
+/// Replace `#[gts_schema(...)]` field attributes with the equivalent
+/// `#[schemars(...)]` overrides:
+///
+/// - `enum_values = "a,b,c"` makes the property's schema
+///   `{"type": "string", "enum": ["a", "b", "c"]}` in every generated schema.
+///   This is for enum-typed fields whose serde renames (e.g.
+///   `#[serde(rename_all = "camelCase")]`) the property schema should spell
+///   out explicitly. An `Option<_>` field also accepts `null`.
+/// - `min_length`, `max_length` and `pattern` add `minLength`, `maxLength`
+///   and `pattern` to a string property; `minimum` and `maximum` add
+///   `minimum` and `maximum` to a numeric one.
+fn apply_gts_schema_field_attrs(input: &mut syn::DeriveInput) -> Result<(), syn::Error> {
+    let Data::Struct(data) = &mut input.data else {
+        return Ok(());
+    };
+    for field in &mut data.fields {
+        let mut enum_values: Option<Vec<String>> = None;
+        let mut constraints = FieldConstraints::default();
+        let mut error: Option<syn::Error> = None;
+        field.attrs.retain(|attr| {
+            if !attr.path().is_ident("gts_schema") {
+                return true;
+            }
+            let parsed = attr.parse_nested_meta(|meta| {
+                if constraints.parse(&meta)? {
+                    return Ok(());
+                }
+                if !meta.path.is_ident("enum_values") {
+                    return Err(meta.error(
+                        "struct_to_gts_schema: unknown gts_schema field attribute; \
+                         expected `enum_values`, `min_length`, `max_length`, `pattern`, \
+                         `minimum` or `maximum`",
+                    ));
+                }
+                let lit: LitStr = meta.value()?.parse()?;
+                let values: Vec<String> = lit
+                    .value()
+                    .split(',')
+                    .map(|value| value.trim().to_owned())
+                    .collect();
+                if values.iter().any(String::is_empty) {
+                    return Err(syn::Error::new_spanned(
+                        &lit,
+                        "struct_to_gts_schema: `enum_values` must be a comma-separated list \
+                         of non-empty values",
+                    ));
+                }
+                enum_values = Some(values);
+                Ok(())
+            });
+            if let Err(err) = parsed {
+                error = Some(err);
+            }
+            false
+        });
+        if let Some(err) = error {
+            return Err(err);
+        }
+        constraints.check()?;
+        if !constraints.keywords.is_empty() {
+            let keywords = constraints
+                .keywords
+                .iter()
+                .map(|(keyword, value)| quote!(#keyword = #value));
+            field.attrs.push(syn::parse_quote!(
+                #[schemars(extend(#(#keywords),*))]
+            ));
+        }
+        let Some(values) = enum_values else {
+            continue;
+        };
+        let (with, null) = if is_option_type(&field.ty) {
+            ("Option<String>", Some(quote!(null)))
+        } else {
+            ("String", None)
+        };
+        let enum_tokens = quote!([#(#values,)* #null]);
+        field.attrs.push(syn::parse_quote!(
+            #[schemars(with = #with, extend("enum" = #enum_tokens))]
+        ));
+    }
+    Ok(())
+}
+
+/// The JSON Schema validation keywords of one field's
+/// `#[gts_schema(...)]` attributes, in the order given.
+#[derive(Default)]
+struct FieldConstraints {
+    keywords: Vec<(&'static str, proc_macro2::TokenStream)>,
+    /// `(min_length, max_length)` and `(minimum, maximum)` as given, to
+    /// check that each range is not empty.
+    lengths: (Option<(u64, proc_macro2::Span)>, Option<u64>),
+    range: (Option<(f64, proc_macro2::Span)>, Option<f64>),
+}
+
+impl FieldConstraints {
+    /// Parse `meta` if it is one of the constraint keys; `Ok(false)` if it
+    /// is some other key.
+    fn parse(&mut self, meta: &syn::meta::ParseNestedMeta<'_>) -> syn::Result<bool> {
+        let keyword = if meta.path.is_ident("min_length") {
+            "minLength"
+        } else if meta.path.is_ident("max_length") {
+            "maxLength"
+        } else if meta.path.is_ident("pattern") {
+            "pattern"
+        } else if meta.path.is_ident("minimum") {
+            "minimum"
+        } else if meta.path.is_ident("maximum") {
+            "maximum"
+        } else {
+            return Ok(false);
+        };
+        if self
+            .keywords
+            .iter()
+            .any(|(existing, _)| *existing == keyword)
+        {
+            return Err(meta.error(format!(
+                "struct_to_gts_schema: duplicate gts_schema `{}`",
+                meta.path
+                    .get_ident()
+                    .map(ToString::to_string)
+                    .unwrap_or_default()
+            )));
+        }
+        let value = meta.value()?;
+        let tokens = match keyword {
+            "minLength" | "maxLength" => {
+                let lit: syn::LitInt = value.parse().map_err(|err| {
+                    syn::Error::new(
+                        err.span(),
+                        "struct_to_gts_schema: `min_length` and `max_length` must be \
+                         non-negative integers",
+                    )
+                })?;
+                let length: u64 = lit.base10_parse()?;
+                if keyword == "minLength" {
+                    self.lengths.0 = Some((length, lit.span()));
+                } else {
+                    self.lengths.1 = Some(length);
+                }
+                let length = proc_macro2::Literal::u64_unsuffixed(length);
+                quote!(#length)
+            }
+            "pattern" => {
+                let lit: LitStr = value.parse()?;
+                quote!(#lit)
+            }
+            _ => {
+                let (number, tokens) = parse_number(value)?;
+                if keyword == "minimum" {
+                    self.range.0 = Some((number, syn::spanned::Spanned::span(&meta.path)));
+                } else {
+                    self.range.1 = Some(number);
+                }
+                tokens
+            }
+        };
+        self.keywords.push((keyword, tokens));
+        Ok(true)
+    }
+
+    /// Reject ranges no value can satisfy.
+    fn check(&self) -> syn::Result<()> {
+        if let (Some((min, span)), Some(max)) = self.lengths
+            && min > max
+        {
+            return Err(syn::Error::new(
+                span,
+                "struct_to_gts_schema: `min_length` must not exceed `max_length`",
+            ));
+        }
+        if let (Some((min, span)), Some(max)) = self.range
+            && min > max
+        {
+            return Err(syn::Error::new(
+                span,
+                "struct_to_gts_schema: `minimum` must not exceed `maximum`",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Parse an integer or float literal, optionally negated, into its value and
+/// the tokens that emit it as a JSON number.
+fn parse_number(input: ParseStream<'_>) -> syn::Result<(f64, proc_macro2::TokenStream)> {
+    let negative = input.parse::<Option<Token![-]>>()?.is_some();
+    let lit: syn::Lit = input.parse()?;
+    let value: f64 = match &lit {
+        syn::Lit::Int(int) => int.base10_parse()?,
+        syn::Lit::Float(float) => float.base10_parse()?,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &lit,
+                "struct_to_gts_schema: `minimum` and `maximum` must be numbers",
+            ));
+        }
+    };
+    if negative {
+        Ok((-value, quote!(-#lit)))
+    } else {
+        Ok((value, quote!(#lit)))
+    }
+}
+
+/// Whether `ty` is spelled `Option<_>` (with or without a path prefix).
+fn is_option_type(ty: &syn::Type) -> bool {
+    matches!(ty, syn::Type::Path(path)
+        if path.qself.is_none()
+            && path.path.segments.last().is_some_and(|seg| seg.ident == "Option"))
+}
+
 /// Build a custom where clause with additional trait bounds on generic params
 fn build_where_clause(
     generics: &syn::Generics,
@@ -675,6 +1004,16 @@ struct GtsSchemaArgs {
     gts_abstract: bool,
     /// `gts_final = true` -> `x-gts-final: true`.
     gts_final: bool,
+    /// `inject_type_field = true` -> the base struct's GTS Type field always
+    /// serializes as the instance's type ID and rejects mismatches on input.
+    inject_type_field: bool,
+    /// `check_unique = true` -> register the type ID for
+    /// `gts::assert_unique_type_ids`.
+    check_unique: bool,
+    /// `draft = "07" | "2020-12"` -> JSON Schema dialect of the generated
+    /// schema; `None` falls back to the crate's `GTS_SCHEMA_DRAFT`, then
+    /// draft-07.
+    draft: Option<LitStr>,
 }
 
 impl Parse for GtsSchemaArgs {
@@ -690,6 +1029,9 @@ impl Parse for GtsSchemaArgs {
         let mut traits: Option<syn::Expr> = None;
         let mut gts_abstract = false;
         let mut gts_final = false;
+        let mut inject_type_field = false;
+        let mut check_unique = false;
+        let mut draft: Option<LitStr> = None;
 
         while !input.is_empty() {
             let key: syn::Ident = input.parse()?;
@@ -712,17 +1054,8 @@ impl Parse for GtsSchemaArgs {
                     // `gts_id!("...")` marker form.
                     let value = id_arg::parse_gts_id_arg(input)?;
                     let id = value.value();
-                    // Schema-specific check: must end with ~
-                    if !id.ends_with('~') {
-                        return Err(syn::Error::new_spanned(
-                            value,
-                            format!(
-                                "struct_to_gts_schema: Invalid GTS type ID: must end with '~' (type marker), got '{id}'"
-                            ),
-                        ));
-                    }
-                    // General GTS ID validation via shared crate
-                    if let Err(e) = gts_id::GtsId::try_new(&id) {
+                    // Same check gts-validator applies to type IDs at scan time
+                    if let Err(e) = gts_id::validate_schema_id(&id) {
                         return Err(syn::Error::new_spanned(
                             value,
                             format!("struct_to_gts_schema: Invalid GTS type ID: {e}"),
@@ -790,10 +1123,24 @@ impl Parse for GtsSchemaArgs {
                     let lit: syn::LitBool = input.parse()?;
                     gts_final = lit.value;
                 }
+                "inject_type_field" => {
+                    let lit: syn::LitBool = input.parse()?;
+                    inject_type_field = lit.value;
+                }
+                "check_unique" => {
+                    let lit: syn::LitBool = input.parse()?;
+                    check_unique = lit.value;
+                }
+                "draft" => {
+                    let value: LitStr = input.parse()?;
+                    schema_draft_variant(&value.value())
+                        .ok_or_else(|| syn::Error::new_spanned(&value, SCHEMA_DRAFT_HELP))?;
+                    draft = Some(value);
+                }
                 _ => {
                     return Err(syn::Error::new_spanned(
                         key,
-                        "Unknown attribute. Expected: dir_path, type_id (or deprecated `schema_id`), description, properties, base, traits_schema, traits, gts_abstract, or gts_final",
+                        "Unknown attribute. Expected: dir_path, type_id (or deprecated `schema_id`), description, properties, base, traits_schema, traits, gts_abstract, gts_final, inject_type_field, check_unique, or draft",
                     ));
                 }
             }
@@ -821,10 +1168,43 @@ impl Parse for GtsSchemaArgs {
             traits,
             gts_abstract,
             gts_final,
+            inject_type_field,
+            check_unique,
+            draft,
         })
     }
 }
 
+const SCHEMA_DRAFT_HELP: &str =
+    "struct_to_gts_schema: unsupported `draft`, expected \"07\" or \"2020-12\"";
+
+/// The `gts::SchemaDraft` variant for a `draft = "..."` name.
+fn schema_draft_variant(name: &str) -> Option<proc_macro2::TokenStream> {
+    match name {
+        "07" => Some(quote! { ::gts::SchemaDraft::Draft07 }),
+        "2020-12" => Some(quote! { ::gts::SchemaDraft::Draft2020_12 }),
+        _ => None,
+    }
+}
+
+/// The dialect selected for a type, as an `Option<gts::SchemaDraft>`
+/// expression: its own `draft = "..."`, else the `GTS_SCHEMA_DRAFT`
+/// environment variable of the crate being compiled. The variable is read by
+/// `option_env!` in the generated code, so rustc tracks it per crate and
+/// rebuilds when it changes. `None` when neither is set, leaving the
+/// generated schema untouched (draft-07 `$schema` with schemars' keywords)
+/// as before dialect selection existed.
+fn resolve_schema_draft(draft: Option<&LitStr>) -> syn::Result<proc_macro2::TokenStream> {
+    match draft {
+        Some(lit) => schema_draft_variant(&lit.value())
+            .map(|variant| quote! { Some(#variant) })
+            .ok_or_else(|| syn::Error::new_spanned(lit, SCHEMA_DRAFT_HELP)),
+        None => Ok(quote! {
+            ::gts::schema_draft::draft_from_env(option_env!("GTS_SCHEMA_DRAFT"))
+        }),
+    }
+}
+
 /// Classify the expression form of `traits_schema = ...` into a
 /// [`TraitsSchemaSpec`]: `inline(T)`, a bare type path `T`, or reject anything
 /// else (the `true`/`false` literal forms are handled before this is called).
@@ -868,7 +1248,7 @@ fn classify_traits_schema_expr(expr: syn::Expr) -> syn::Result<TraitsSchemaSpec>
 /// The macro validates your annotations at compile time, catching errors early:
 /// - ✅ All required attributes exist (`dir_path`, `type_id`, `description`, `properties`)
 /// - ✅ Every property in `properties` exists as a field in the struct
-/// - ✅ Only structs with named fields are supported (no tuple/unit structs or enums)
+/// - ✅ Only structs with named fields (or unit structs) and base-type enums are supported (no tuple structs)
 /// - ✅ Single generic parameter maximum (prevents inheritance ambiguity)
 /// - ✅ Valid GTS ID format enforcement
 /// - ✅ Zero runtime allocation for generated constants
@@ -917,6 +1297,33 @@ fn classify_traits_schema_expr(expr: syn::Expr) -> syn::Result<TraitsSchemaSpec>
 /// * `base` - Explicit base/parent struct declaration (required):
 ///   - `base = true`: Marks this struct as a base type (must have single-segment `type_id`)
 ///   - `base = ParentStruct`: Parent struct name (macro automatically uses `ParentStruct<()>`)
+/// * `inject_type_field` - Optional, base structs only. With `inject_type_field = true` the
+///   GTS Type field (e.g. `#[serde(rename = "type")] event_type: GtsTypeId`) always serializes
+///   as the instance's type ID (the innermost type of `Base<Child>`), deserialization rejects
+///   any other value, and the generated schemas pin the property with `const`
+/// * `draft` - Optional JSON Schema dialect of the generated schema: `"07"` or `"2020-12"`.
+///   Sets `$schema` and the dialect's keywords (`definitions`/`$defs`, `items`/`prefixItems`).
+///   The crate-wide default can be set with the `GTS_SCHEMA_DRAFT` environment variable at
+///   build time, read per crate with `option_env!` so changing it triggers a rebuild; with
+///   neither, the schema declares draft-07 and keeps schemars' keywords
+/// * `check_unique` - Optional. With `check_unique = true` the type ID is registered at link
+///   time, and `gts::assert_unique_type_ids()` (typically called from a `#[test]`) panics
+///   naming both types if two registered types share a type ID
+///
+/// Every annotated type is also registered in the schema index: `gts::schema_index()` lists
+/// the type ID, description, base type ID and schema of each, and
+/// `gts::generate_schema_index_json()` renders them as a JSON array.
+///
+/// # Field Attributes
+///
+/// * `#[gts_schema(enum_values = "pending,active,completed")]` - Describe the field as a string
+///   restricted to these values (`"enum": [...]`), e.g. for an enum-typed field whose variants
+///   serde renames. The values are comma-separated and trimmed; on an `Option<_>` field `null`
+///   is allowed as well. The values are not checked against the enum's serde representation
+/// * `#[gts_schema(min_length = 1, max_length = 256, pattern = "^[a-z]+$")]` - Add
+///   `minLength`, `maxLength` and `pattern` to a string field's property schema
+/// * `#[gts_schema(minimum = 0, maximum = 100)]` - Add `minimum` and `maximum` to a numeric
+///   field's property schema. Integers, floats and negative values are emitted as JSON numbers
 ///
 /// # Memory Efficiency
 ///
@@ -949,15 +1356,130 @@ fn classify_traits_schema_expr(expr: syn::Expr) -> syn::Result<TraitsSchemaSpec>
 /// assert_eq!(instance_id.as_ref(), "gts.x.core.events.topic.v1~vendor.marketplace.orders.order_created.v1");
 /// ```
 #[proc_macro_attribute]
-#[allow(
-    clippy::too_many_lines,
-    clippy::missing_panics_doc,
-    clippy::cognitive_complexity
-)]
+#[allow(clippy::missing_panics_doc)]
 pub fn struct_to_gts_schema(attr: TokenStream, item: TokenStream) -> TokenStream {
     let args = parse_macro_input!(attr as GtsSchemaArgs);
     let input = parse_macro_input!(item as DeriveInput);
+    expand_gts_schema(&args, &input, ExpandMode::Attribute)
+}
 
+/// Derive-style entry point equivalent to [`macro@struct_to_gts_schema`].
+///
+/// Takes the same arguments through a `#[gts(...)]` helper attribute and
+/// generates the same impls, so both forms produce byte-identical schemas:
+///
+/// ```ignore
+/// #[derive(Serialize, Deserialize, JsonSchema, GtsSchemaDerive)]
+/// #[gts(
+///     dir_path = "schemas",
+///     type_id = "gts.x.core.events.topic.v1~",
+///     description = "Event broker topics",
+///     properties = "id,name",
+///     base = true,
+/// )]
+/// pub struct TopicV1 {
+///     pub id: GtsInstanceId,
+///     pub name: String,
+/// }
+/// ```
+///
+/// A derive cannot rewrite the struct it is attached to, so the derives the
+/// attribute form adds implicitly must be written out: base structs need
+/// `Serialize`, `Deserialize` and `JsonSchema`; child structs need
+/// `JsonSchema` only. Base structs that the attribute form rewrites (generic
+/// base structs and unit base structs) are rejected; use the attribute form
+/// for those.
+#[proc_macro_derive(GtsSchemaDerive, attributes(gts))]
+#[allow(clippy::missing_panics_doc)]
+pub fn derive_gts_schema(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+    let Some(attr) = input.attrs.iter().find(|a| a.path().is_ident("gts")) else {
+        return syn::Error::new_spanned(
+            &input.ident,
+            "GtsSchemaDerive: missing `#[gts(...)]` attribute (same arguments as \
+             `#[struct_to_gts_schema(...)]`)",
+        )
+        .to_compile_error()
+        .into();
+    };
+    let args = match attr.parse_args::<GtsSchemaArgs>() {
+        Ok(args) => args,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    expand_gts_schema(&args, &input, ExpandMode::Derive)
+}
+
+/// Enums are restricted to standalone base types: no parent, no generics, no
+/// properties list, and none of the struct-only field options.
+fn validate_enum_input(
+    input: &DeriveInput,
+    args: &GtsSchemaArgs,
+    property_names: &[String],
+) -> Result<(), syn::Error> {
+    let reject = |msg: &str| Err(syn::Error::new_spanned(&input.ident, msg));
+    if matches!(args.base, BaseAttr::Parent(_)) {
+        return reject(
+            "struct_to_gts_schema: enums must be base types (base = true); \
+             deriving an enum from a parent type is not supported",
+        );
+    }
+    if input.generics.type_params().next().is_some() {
+        return reject("struct_to_gts_schema: generic enums are not supported");
+    }
+    if !property_names.is_empty() {
+        return reject(
+            "struct_to_gts_schema: enums have no properties list; use properties = \"\" \
+             (variants are taken from the enum itself)",
+        );
+    }
+    if args.inject_type_field {
+        return reject("struct_to_gts_schema: `inject_type_field` is not supported on enums");
+    }
+    Ok(())
+}
+
+/// Which entry point drives [`expand_gts_schema`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ExpandMode {
+    /// `#[struct_to_gts_schema(...)]`: the struct is re-emitted with the
+    /// derives and serde attributes the generated impls rely on.
+    Attribute,
+    /// `#[derive(GtsSchemaDerive)]`: only the impls are emitted; the struct
+    /// must already carry the derives.
+    Derive,
+}
+
+/// Reject inputs the derive form cannot express, since it has no way to
+/// rewrite the struct the way the attribute form does.
+fn validate_derive_input(input: &DeriveInput, base: &BaseAttr) -> Result<(), syn::Error> {
+    const HINT: &str = "use `#[struct_to_gts_schema(...)]` instead";
+    if matches!(base, BaseAttr::IsBase) {
+        if input.generics.type_params().next().is_some() {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                format!(
+                    "GtsSchemaDerive: generic base structs need serde attributes on the generic \
+                     field that a derive cannot add; {HINT}"
+                ),
+            ));
+        }
+        if matches!(&input.data, Data::Struct(s) if matches!(s.fields, Fields::Unit)) {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                format!(
+                    "GtsSchemaDerive: unit base structs need custom serde impls that conflict \
+                     with derived ones; {HINT}"
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Shared implementation of [`macro@struct_to_gts_schema`] and
+/// [`macro@GtsSchemaDerive`].
+#[allow(clippy::too_many_lines, clippy::cognitive_complexity)]
+fn expand_gts_schema(args: &GtsSchemaArgs, input: &DeriveInput, mode: ExpandMode) -> TokenStream {
     // Semantic check: `x-gts-abstract: true` + `x-gts-final: true` is invalid
     // (mutual exclusion). The registry also rejects it; this fails faster.
     if args.gts_abstract && args.gts_final {
@@ -971,6 +1493,21 @@ pub fn struct_to_gts_schema(attr: TokenStream, item: TokenStream) -> TokenStream
         .into();
     }
 
+    if mode == ExpandMode::Derive
+        && let Err(err) = validate_derive_input(input, &args.base)
+    {
+        return err.to_compile_error().into();
+    }
+    if mode == ExpandMode::Derive && args.inject_type_field {
+        return syn::Error::new_spanned(
+            &input.ident,
+            "GtsSchemaDerive: `inject_type_field` needs serde attributes on the type field \
+             that a derive cannot add; use `#[struct_to_gts_schema(...)]` instead",
+        )
+        .to_compile_error()
+        .into();
+    }
+
     // Prohibit multiple type generic parameters (GTS notation assumes nested segments)
     let generic_count = input.generics.type_params().count();
     if generic_count > 1 {
@@ -1023,15 +1560,22 @@ pub fn struct_to_gts_schema(attr: TokenStream, item: TokenStream) -> TokenStream
                 .into();
             }
         },
-        _ => {
+        Data::Enum(_) => {
+            if let Err(err) = validate_enum_input(input, args, &property_names) {
+                return err.to_compile_error().into();
+            }
+            None // Enums have no properties to validate
+        }
+        Data::Union(_) => {
             return syn::Error::new_spanned(
                 &input.ident,
-                "struct_to_gts_schema: Only structs are supported",
+                "struct_to_gts_schema: Only structs and enums are supported",
             )
             .to_compile_error()
             .into();
         }
     };
+    let is_enum = matches!(&input.data, Data::Enum(_));
 
     // Validate that all requested properties exist (only for structs with fields)
     if let Some(fields) = struct_fields {
@@ -1054,7 +1598,7 @@ pub fn struct_to_gts_schema(attr: TokenStream, item: TokenStream) -> TokenStream
         }
 
         // Validate base struct field requirements
-        if let Err(err) = validate_base_struct_fields(&input, fields, &args) {
+        if let Err(err) = validate_base_struct_fields(input, fields, args) {
             return err.to_compile_error().into();
         }
     }
@@ -1088,6 +1632,11 @@ pub fn struct_to_gts_schema(attr: TokenStream, item: TokenStream) -> TokenStream
         remove_derives(&mut modified_input, &["Serialize", "Deserialize"]);
     }
 
+    // `#[gts_schema(...)]` field overrides
+    if let Err(err) = apply_gts_schema_field_attrs(&mut modified_input) {
+        return err.to_compile_error().into();
+    }
+
     // Automatically add required derives: Serialize, Deserialize, JsonSchema
     // For nested structs, only JsonSchema is added (no direct serialization)
     add_missing_derives(&mut modified_input, &args.base);
@@ -1095,8 +1644,19 @@ pub fn struct_to_gts_schema(attr: TokenStream, item: TokenStream) -> TokenStream
     // For base structs with generic fields, add serde attributes for GtsSerialize/GtsDeserialize
     add_gts_serde_attrs(&mut modified_input, &args.base);
 
+    // `inject_type_field = true`: route the base's GTS Type field through
+    // generated serde hooks that pin it to the instance's type ID.
+    let injected_type_field = if args.inject_type_field {
+        match inject_type_field_attrs(input, &mut modified_input, &args.base) {
+            Ok(name) => Some(name),
+            Err(err) => return err.to_compile_error().into(),
+        }
+    } else {
+        None
+    };
+
     // Validate base attribute consistency with schema_id segments
-    if let Err(err) = validate_base_segments(&input, &args.base, &args.type_id) {
+    if let Err(err) = validate_base_segments(input, &args.base, &args.type_id) {
         return err.to_compile_error().into();
     }
     let expected_parent_type_id = extract_parent_type_id(&args.type_id);
@@ -1393,10 +1953,138 @@ pub fn struct_to_gts_schema(attr: TokenStream, item: TokenStream) -> TokenStream
         }
     };
 
+    // Advertises where derived types may plug in: the serialized name of this
+    // type's generic field, as `x-gts-generic-field` at the document top level
+    // (see `gts::schema_generic`). Omitted for types without a generic field.
+    let inject_generic_field = quote! {
+        if let Some(generic_field) = <Self as ::gts::GtsSchema>::GENERIC_FIELD {
+            schema[::gts::schema_generic::X_GTS_GENERIC_FIELD] = serde_json::json!(generic_field);
+        }
+    };
+
+    // Rewrites the finished document into the selected JSON Schema dialect:
+    // `$schema`, plus keywords schemars spells the 2020-12 way (`$defs`,
+    // `prefixItems`) when the target is draft-07.
+    let selected_draft = match resolve_schema_draft(args.draft.as_ref()) {
+        Ok(draft) => draft,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let apply_draft = quote! {
+        let schema = match const { #selected_draft } {
+            Some(draft) => ::gts::convert_schema_draft(&schema, draft),
+            None => schema,
+        };
+    };
+
+    // --- inject_type_field ---------------------------------------------------
+    // `TYPE_FIELD` const: a base names its own injected field, a derived type
+    // inherits its parent's so every level can pin it in its schema.
+    let type_field_value = match (&args.base, &injected_type_field) {
+        (BaseAttr::IsBase, Some(name)) => quote! { Some(#name) },
+        (BaseAttr::IsBase, None) => quote! { None },
+        (BaseAttr::Parent(parent_ident), _) => {
+            quote! { <#parent_ident<()> as ::gts::GtsSchema>::TYPE_FIELD }
+        }
+    };
+    // Pins the injected field to this type's ID with `const`. A derived type
+    // adds it to its `allOf` overlay; a base only pins it when it has no
+    // generic field, since otherwise its schema is shared with descendants
+    // whose instances carry their own type IDs.
+    let pin_type_field = match &args.base {
+        BaseAttr::Parent(_) => quote! {
+            if let Some(type_field) = <Self as ::gts::GtsSchema>::TYPE_FIELD {
+                if let Some(overlay) = schema
+                    .pointer_mut("/allOf/1/properties")
+                    .and_then(|v| v.as_object_mut())
+                {
+                    overlay.insert(type_field.to_owned(), serde_json::json!({ "const": type_id }));
+                }
+            }
+        },
+        BaseAttr::IsBase if injected_type_field.is_some() && !has_generic => quote! {
+            if let Some(type_field) = <Self as ::gts::GtsSchema>::TYPE_FIELD {
+                if let Some(prop) = schema
+                    .get_mut("properties")
+                    .and_then(|p| p.get_mut(type_field))
+                    .and_then(|v| v.as_object_mut())
+                {
+                    prop.insert("const".to_owned(), serde_json::json!(type_id));
+                }
+            }
+        },
+        BaseAttr::IsBase => quote! {},
+    };
+    // Hidden serde hooks referenced from the field's `serialize_with` /
+    // `deserialize_with`. The expected ID is the innermost type's, so
+    // `Base<Child>` instances carry the child's ID.
+    let type_field_impl = if injected_type_field.is_some() {
+        let injected_type_id_body = if let Some(generic_param) = input.generics.type_params().next()
+        {
+            let generic_ident = &generic_param.ident;
+            quote! {
+                let inner = <#generic_ident as ::gts::GtsSchema>::innermost_type_id();
+                if inner.is_empty() { #type_id_lit_derived } else { inner }
+            }
+        } else {
+            quote! { #type_id_lit_derived }
+        };
+        quote! {
+            #[doc(hidden)]
+            impl #impl_generics #struct_name #ty_generics #where_clause {
+                #[doc(hidden)]
+                fn __gts_injected_type_id() -> &'static str {
+                    #injected_type_id_body
+                }
+
+                #[doc(hidden)]
+                fn __gts_serialize_type_field<__S>(
+                    _value: &::gts::gts::GtsTypeId,
+                    serializer: __S,
+                ) -> Result<__S::Ok, __S::Error>
+                where
+                    __S: serde::Serializer,
+                {
+                    serializer.serialize_str(Self::__gts_injected_type_id())
+                }
+
+                #[doc(hidden)]
+                fn __gts_deserialize_type_field<'de, __D>(
+                    deserializer: __D,
+                ) -> Result<::gts::gts::GtsTypeId, __D::Error>
+                where
+                    __D: serde::Deserializer<'de>,
+                {
+                    let value = <::gts::gts::GtsTypeId as serde::Deserialize>::deserialize(deserializer)?;
+                    let expected = Self::__gts_injected_type_id();
+                    if value.as_ref() != expected {
+                        return Err(<__D::Error as serde::de::Error>::custom(format!(
+                            "type field mismatch: expected `{}`, got `{}`",
+                            expected,
+                            value.as_ref()
+                        )));
+                    }
+                    Ok(value)
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
     // Bool literals for the `GTS_FINAL` / `GTS_ABSTRACT` associated consts.
     let gts_final_value = args.gts_final;
     let gts_abstract_value = args.gts_abstract;
 
+    // Leaf-segment version literals for `gts_schema_version` /
+    // `gts_schema_minor_version` (the type ID was validated above).
+    let leaf_version = extract_type_id_version(&args.type_id);
+    let ver_major_value = leaf_version.as_ref().map_or(0, |v| v.major);
+    let ver_minor_value = if let Some(minor) = leaf_version.and_then(|v| v.minor) {
+        quote! { Some(#minor) }
+    } else {
+        quote! { None }
+    };
+
     // --- chain-aggregated trait-schema state (`TRAIT_SCHEMA`) + guard ---------
     // This type's own `x-gts-traits-schema` kind, mapped to a `TraitSchemaState`.
     let own_trait_state = match &args.traits_schema {
@@ -1412,6 +2100,17 @@ pub fn struct_to_gts_schema(attr: TokenStream, item: TokenStream) -> TokenStream
             quote! { <#parent_ident<()> as ::gts::GtsSchema>::TRAIT_SCHEMA }
         }
     };
+    // Ancestor schemas, outermost first, for `gts_schema_for_bundled!`.
+    let ancestor_schemas_impl = match &args.base {
+        BaseAttr::IsBase => quote! {},
+        BaseAttr::Parent(parent_ident) => quote! {
+            fn gts_ancestor_schemas() -> Vec<serde_json::Value> {
+                let mut schemas = <#parent_ident<()> as ::gts::GtsSchema>::gts_ancestor_schemas();
+                schemas.push(<#parent_ident<()> as ::gts::GtsSchema>::gts_schema_with_refs());
+                schemas
+            }
+        },
+    };
     // Compile-time guard: `traits` values need a usable trait shape in the chain.
     // Emitted only when this type carries `traits`; mirrors the registry's OP#13
     // precondition (`Absent` -> no schema, `Prohibited` -> `false` in chain).
@@ -1463,7 +2162,46 @@ pub fn struct_to_gts_schema(attr: TokenStream, item: TokenStream) -> TokenStream
         quote! {}
     };
 
-    let gts_schema_impl = if has_generic {
+    let gts_schema_impl = if is_enum {
+        quote! {
+            fn gts_schema() -> serde_json::Value {
+                Self::gts_schema_with_refs()
+            }
+            fn innermost_type_id() -> &'static str {
+                Self::TYPE_ID
+            }
+            fn innermost_schema() -> serde_json::Value {
+                let root_schema = schemars::schema_for!(Self);
+                serde_json::to_value(&root_schema).expect("schemars")
+            }
+            fn gts_schema_with_refs_allof() -> serde_json::Value {
+                let type_id = Self::TYPE_ID;
+
+                // schemars maps serde's enum representation (external,
+                // internal `tag`, adjacent `tag` + `content`, untagged,
+                // `rename_all`) to `enum` / `oneOf`, with the discriminator
+                // const-ed per variant, so the schema tracks serialization.
+                let root_schema = schemars::schema_for!(Self);
+                let body = serde_json::to_value(&root_schema).expect("schemars");
+
+                let mut schema = serde_json::json!({
+                    "$id": format!("gts://{}", type_id),
+                    "$schema": ::gts::JSON_SCHEMA_DRAFT_07,
+                    "description": #description
+                });
+                if let (Some(obj), serde_json::Value::Object(body)) = (schema.as_object_mut(), body) {
+                    for (key, value) in body {
+                        if !matches!(key.as_str(), "$schema" | "title" | "description") {
+                            obj.insert(key, value);
+                        }
+                    }
+                }
+                #inject_root_traits
+                #apply_draft
+                schema
+            }
+        }
+    } else if has_generic {
         let generic_param = input.generics.type_params().next().unwrap();
         let generic_ident = &generic_param.ident;
         let generic_field_for_path = generic_field_name.as_deref().unwrap_or_default();
@@ -1580,6 +2318,11 @@ pub fn struct_to_gts_schema(attr: TokenStream, item: TokenStream) -> TokenStream
                     }
                 }
 
+                // Any other `#/$defs/...` ref (an enum such as an adjacently
+                // tagged `#[serde(tag, content)]` one, or a plain nested
+                // struct) points into the `$defs` dropped above: inline it.
+                let properties = ::gts::inline_local_refs(&properties, &schema_val);
+
                 // If no parent (base type), return simple schema without allOf
                 // Base types have additionalProperties: false at root level
                 // Generic fields are just {"type": "object"} (will be extended by children)
@@ -1595,7 +2338,10 @@ pub fn struct_to_gts_schema(attr: TokenStream, item: TokenStream) -> TokenStream
                     if !required.as_array().map(|a| a.is_empty()).unwrap_or(true) {
                         schema["required"] = required;
                     }
+                    #pin_type_field
                     #inject_root_traits
+                    #inject_generic_field
+                    #apply_draft
                     return schema;
                 }
 
@@ -1647,7 +2393,10 @@ pub fn struct_to_gts_schema(attr: TokenStream, item: TokenStream) -> TokenStream
                 });
                 // Trait/modifier keywords go at the document top level, never in
                 // the allOf overlay.
+                #pin_type_field
                 #inject_root_traits
+                #inject_generic_field
+                #apply_draft
                 schema
             }
         }
@@ -1702,6 +2451,11 @@ pub fn struct_to_gts_schema(attr: TokenStream, item: TokenStream) -> TokenStream
                     }
                 }
 
+                // Any other `#/$defs/...` ref (an enum such as an adjacently
+                // tagged `#[serde(tag, content)]` one, or a plain nested
+                // struct) points into the `$defs` dropped above: inline it.
+                let properties = ::gts::inline_local_refs(&properties, &schema_val);
+
                 // If no parent (base type), return simple schema without allOf
                 // Non-generic base types have additionalProperties: false at root level
                 if parent_type_id.is_empty() {
@@ -1716,7 +2470,9 @@ pub fn struct_to_gts_schema(attr: TokenStream, item: TokenStream) -> TokenStream
                     if !required.as_array().map(|a| a.is_empty()).unwrap_or(true) {
                         schema["required"] = required;
                     }
+                    #pin_type_field
                     #inject_root_traits
+                    #apply_draft
                     return schema;
                 }
 
@@ -1745,7 +2501,9 @@ pub fn struct_to_gts_schema(attr: TokenStream, item: TokenStream) -> TokenStream
                 });
                 // Trait/modifier keywords go at the document top level, never in
                 // the allOf overlay.
+                #pin_type_field
                 #inject_root_traits
+                #apply_draft
                 schema
             }
         }
@@ -1875,6 +2633,34 @@ pub fn struct_to_gts_schema(attr: TokenStream, item: TokenStream) -> TokenStream
             })
             .unwrap_or_default();
 
+        // How each field is filled in when missing: `#[serde(default)]` fields
+        // fall back to their default (matching their absence from `required`
+        // in the schema), every other field is a `missing_field` error.
+        let field_fallbacks: Vec<_> = struct_fields
+            .map(|fields| {
+                fields
+                    .iter()
+                    .filter(|field| field.ident.is_some())
+                    .map(|field| match get_serde_default(field) {
+                        Some(SerdeDefault::Trait) => {
+                            quote! { ::core::default::Default::default() }
+                        }
+                        Some(SerdeDefault::Path(path)) => quote! { #path() },
+                        None => {
+                            let name = get_serde_rename(field).unwrap_or_else(|| {
+                                field
+                                    .ident
+                                    .as_ref()
+                                    .map(ToString::to_string)
+                                    .unwrap_or_default()
+                            });
+                            quote! { return Err(serde::de::Error::missing_field(#name)) }
+                        }
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
         let num_fields = fields_for_serialize.len();
         let field_serialize_calls: Vec<_> = fields_for_serialize
             .iter()
@@ -2038,8 +2824,10 @@ pub fn struct_to_gts_schema(attr: TokenStream, item: TokenStream) -> TokenStream
                                 }
                             }
 
-                            #(let #field_idents = #field_idents
-                                .ok_or_else(|| serde::de::Error::missing_field(#field_names))?;)*
+                            #(let #field_idents = match #field_idents {
+                                Some(value) => value,
+                                None => #field_fallbacks,
+                            };)*
 
                             Ok(#struct_name {
                                 #(#field_idents,)*
@@ -2149,11 +2937,57 @@ pub fn struct_to_gts_schema(attr: TokenStream, item: TokenStream) -> TokenStream
         }
     };
 
+    // The derive form leaves the user's struct untouched.
+    let item_tokens = match mode {
+        ExpandMode::Attribute => quote! { #modified_input },
+        ExpandMode::Derive => quote! {},
+    };
+
+    // `check_unique = true`: register the type ID for the uniqueness check
+    let unique_registration = if args.check_unique {
+        quote! {
+            ::gts::type_registry::inventory::submit! {
+                ::gts::GtsTypeRegistration {
+                    type_id: #type_id_lit_derived,
+                    rust_type: concat!(module_path!(), "::", stringify!(#struct_name)),
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Every type registers in the schema index (`gts::schema_index`); a
+    // generic type is indexed as `Type<()>`, the same as it is used as a base.
+    let index_args = generics.params.iter().map(|param| {
+        if matches!(param, syn::GenericParam::Lifetime(_)) {
+            quote! { 'static }
+        } else {
+            quote! { () }
+        }
+    });
+    let index_type = if generics.params.is_empty() {
+        quote! { #struct_name }
+    } else {
+        quote! { #struct_name<#(#index_args),*> }
+    };
+    let index_registration = quote! {
+        ::gts::type_registry::inventory::submit! {
+            ::gts::SchemaIndexEntry {
+                schema_id: #type_id_lit_derived,
+                description: #description,
+                base_schema_id: #base_schema_id_option,
+                schema_json: <#index_type>::gts_schema_with_refs_as_string,
+                schema: ::gts::schema_dyn::ErasedSchema::<#index_type>::DYN,
+            }
+        }
+    };
+
     let expanded = quote! {
         // Compile-time deprecation warning when `schema_id` alias was used
         #deprecation_warning
 
-        #modified_input
+        #item_tokens
 
         // Compile-time assertion for base struct matching (if specified)
         #base_assertion
@@ -2170,6 +3004,15 @@ pub fn struct_to_gts_schema(attr: TokenStream, item: TokenStream) -> TokenStream
         // Custom serialization for unit structs to serialize as {} instead of null
         #custom_serialize_impl
 
+        // Serde hooks for `inject_type_field = true`
+        #type_field_impl
+
+        // Registration for `check_unique = true`
+        #unique_registration
+
+        // Registration in the schema index
+        #index_registration
+
         // GtsSerialize/GtsDeserialize impls for nested structs
         #gts_serialize_impl
         #no_direct_serde_impl
@@ -2238,6 +3081,10 @@ pub fn struct_to_gts_schema(attr: TokenStream, item: TokenStream) -> TokenStream
         }
 
         // Implement GtsSchema trait for runtime schema composition
+        // Evaluated even if no schema is ever generated, so an unsupported
+        // `GTS_SCHEMA_DRAFT` always fails the build.
+        const _: Option<::gts::SchemaDraft> = #selected_draft;
+
         impl #impl_generics ::gts::GtsSchema for #struct_name #ty_generics #gts_schema_where_clause {
             const TYPE_ID: &'static str = #type_id_lit;
             const GENERIC_FIELD: Option<&'static str> = #generic_field_option;
@@ -2245,6 +3092,7 @@ pub fn struct_to_gts_schema(attr: TokenStream, item: TokenStream) -> TokenStream
             // on the parent's / target's finality / abstractness.
             const GTS_FINAL: bool = #gts_final_value;
             const GTS_ABSTRACT: bool = #gts_abstract_value;
+            const TYPE_FIELD: Option<&'static str> = #type_field_value;
             // Chain-aggregated trait-schema state (own layer `allOf`-composed with
             // the parent's), powering the traits-need-a-schema compile-time guard.
             const TRAIT_SCHEMA: ::gts::TraitSchemaState =
@@ -2254,6 +3102,14 @@ pub fn struct_to_gts_schema(attr: TokenStream, item: TokenStream) -> TokenStream
                 Self::gts_schema_with_refs_allof()
             }
 
+            fn gts_schema_version() -> u32 {
+                #ver_major_value
+            }
+
+            fn gts_schema_minor_version() -> Option<u32> {
+                #ver_minor_value
+            }
+
             // This type's own `x-gts-traits-schema` / `x-gts-traits` (the same
             // values injected at the document top level by gts_schema_with_refs),
             // exposed as typed accessors so callers need not re-parse the schema.
@@ -2266,25 +3122,29 @@ pub fn struct_to_gts_schema(attr: TokenStream, item: TokenStream) -> TokenStream
                 #traits_value_tokens
             }
 
+            #ancestor_schemas_impl
+
             #gts_schema_impl
         }
 
         // Public API methods for schema serialization
         impl #impl_generics #struct_name #ty_generics #gts_schema_where_clause {
-            /// Get the JSON Schema with `allOf` + `$ref` for inheritance as a JSON string.
+            /// Get the JSON Schema with `allOf` + `$ref` for inheritance as a JSON string,
+            /// in canonical form (see `gts::canonical_schema_to_string`).
             #[allow(dead_code)]
             #[must_use]
             pub fn gts_schema_with_refs_as_string() -> String {
                 use ::gts::GtsSchema;
-                serde_json::to_string(&Self::gts_schema_with_refs_allof()).expect("Failed to serialize schema")
+                ::gts::canonical_schema_to_string(&Self::gts_schema_with_refs_allof())
             }
 
-            /// Get the JSON Schema with `allOf` + `$ref` for inheritance as a pretty-printed JSON string.
+            /// Get the JSON Schema with `allOf` + `$ref` for inheritance as a pretty-printed JSON
+            /// string, in canonical form (see `gts::canonical_schema_to_string_pretty`).
             #[allow(dead_code)]
             #[must_use]
             pub fn gts_schema_with_refs_as_string_pretty() -> String {
                 use ::gts::GtsSchema;
-                serde_json::to_string_pretty(&Self::gts_schema_with_refs_allof()).expect("Failed to serialize schema")
+                ::gts::canonical_schema_to_string_pretty(&Self::gts_schema_with_refs_allof())
             }
         }
 
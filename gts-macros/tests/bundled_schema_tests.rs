@@ -0,0 +1,207 @@
+//! Test: `gts_schema_for_bundled!` inlines the `$id` chain into `$defs`, and
+//! `gts_schema_inline()` replaces it outright; both accept exactly the
+//! instances the `$ref` form accepts with a resolver.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use gts::gts::GtsTypeId;
+use gts::{GtsSchema, gts_schema_for, gts_schema_for_bundled};
+use gts_macros::{gts_id, struct_to_gts_schema};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+/* ============================================================
+Same chain as the macros CLI: base event -> audit -> order
+============================================================ */
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = true,
+    type_id = gts_id!("x.core.events.type.v1~"),
+    description = "Base event type definition",
+    properties = "event_type,id,tenant_id,sequence_id,payload"
+)]
+#[derive(Debug)]
+pub struct BaseEventV1<P> {
+    #[serde(rename = "type")]
+    pub event_type: GtsTypeId,
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub sequence_id: u64,
+    pub payload: P,
+}
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = BaseEventV1,
+    type_id = gts_id!("x.core.events.type.v1~x.core.audit.event.v1~"),
+    description = "Audit event with user context",
+    properties = "user_agent,user_id,ip_address,data"
+)]
+#[derive(Debug)]
+pub struct AuditPayloadV1<D> {
+    pub user_agent: String,
+    pub user_id: Uuid,
+    pub ip_address: String,
+    pub data: D,
+}
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = AuditPayloadV1,
+    type_id = gts_id!("x.core.events.type.v1~x.core.audit.event.v1~x.marketplace.orders.purchase.v1~"),
+    description = "Order placement audit event",
+    properties = "order_id,product_id"
+)]
+#[derive(Debug)]
+pub struct PlaceOrderDataV1 {
+    pub order_id: Uuid,
+    pub product_id: Uuid,
+}
+
+const BASE_ID: &str = gts_id!("x.core.events.type.v1~");
+const AUDIT_ID: &str = gts_id!("x.core.events.type.v1~x.core.audit.event.v1~");
+
+fn instance(data: &Value) -> Value {
+    json!({
+        "type": PlaceOrderDataV1::TYPE_ID,
+        "id": Uuid::nil(),
+        "tenant_id": Uuid::nil(),
+        "sequence_id": 1,
+        "payload": {
+            "user_agent": "curl",
+            "user_id": Uuid::nil(),
+            "ip_address": "127.0.0.1",
+            "data": data
+        }
+    })
+}
+
+fn instance_set() -> Vec<(Value, bool)> {
+    let order = json!({ "order_id": Uuid::nil(), "product_id": Uuid::nil() });
+    let mut missing_payload = instance(&order);
+    missing_payload.as_object_mut().unwrap().remove("payload");
+    let mut bad_sequence = instance(&order);
+    bad_sequence["sequence_id"] = json!("one");
+    vec![
+        (instance(&order), true),
+        (instance(&json!({ "order_id": Uuid::nil() })), false),
+        (
+            instance(&json!({ "order_id": Uuid::nil(), "product_id": 7 })),
+            false,
+        ),
+        (missing_payload, false),
+        (bad_sequence, false),
+    ]
+}
+
+fn resolver_validator(schema: &Value) -> jsonschema::Validator {
+    let mut options = jsonschema::options();
+    for ancestor in PlaceOrderDataV1::gts_ancestor_schemas() {
+        let uri = ancestor["$id"].as_str().unwrap().to_owned();
+        options = options.with_resource(uri, jsonschema::Resource::from_contents(ancestor));
+    }
+    options.build(schema).unwrap()
+}
+
+#[test]
+fn test_bundled_schema_inlines_chain() {
+    let bundled = gts_schema_for_bundled!(PlaceOrderDataV1);
+    let defs = bundled["$defs"].as_object().unwrap();
+    let keys: Vec<&String> = defs.keys().collect();
+    assert_eq!(keys, [BASE_ID, AUDIT_ID]);
+    assert_eq!(
+        bundled["allOf"][0]["$ref"],
+        json!(format!("#/$defs/{}", AUDIT_ID.replace('~', "~0")))
+    );
+    assert_eq!(
+        defs[AUDIT_ID]["allOf"][0]["$ref"],
+        json!(format!("#/$defs/{}", BASE_ID.replace('~', "~0")))
+    );
+    assert!(
+        !bundled
+            .to_string()
+            .contains(&format!("\"gts://{BASE_ID}\""))
+    );
+}
+
+#[test]
+fn test_bundled_schema_keeps_generic_field_annotations() {
+    use gts::schema_generic::X_GTS_GENERIC_FIELD;
+
+    assert_eq!(
+        gts_schema_for!(BaseEventV1<()>)[X_GTS_GENERIC_FIELD],
+        json!("payload")
+    );
+    let bundled = gts_schema_for_bundled!(PlaceOrderDataV1);
+    assert!(bundled.get(X_GTS_GENERIC_FIELD).is_none());
+    assert_eq!(
+        bundled["$defs"][BASE_ID][X_GTS_GENERIC_FIELD],
+        json!("payload")
+    );
+    assert_eq!(
+        bundled["$defs"][AUDIT_ID][X_GTS_GENERIC_FIELD],
+        json!("data")
+    );
+}
+
+#[test]
+fn test_bundled_schema_matches_ref_form() {
+    let with_refs = gts_schema_for!(PlaceOrderDataV1);
+    let bundled = gts_schema_for_bundled!(PlaceOrderDataV1);
+    let resolved = resolver_validator(&with_refs);
+    let offline = jsonschema::validator_for(&bundled).unwrap();
+
+    for (value, expected) in instance_set() {
+        assert_eq!(resolved.is_valid(&value), expected, "ref form: {value}");
+        assert_eq!(offline.is_valid(&value), expected, "bundled form: {value}");
+    }
+}
+
+#[test]
+fn test_inline_schema_has_no_refs() {
+    let inline = PlaceOrderDataV1::gts_schema_inline();
+    assert_eq!(
+        inline["$id"],
+        json!(format!("gts://{}", PlaceOrderDataV1::TYPE_ID))
+    );
+    let text = inline.to_string();
+    assert!(!text.contains("\"$ref\""), "{text}");
+    assert!(inline.get("$defs").is_none());
+}
+
+#[test]
+fn test_inline_schema_matches_ref_form() {
+    let with_refs = PlaceOrderDataV1::gts_schema_with_refs();
+    let inline = PlaceOrderDataV1::gts_schema_inline();
+    let resolved = resolver_validator(&with_refs);
+    let offline = jsonschema::validator_for(&inline).unwrap();
+
+    for (value, expected) in instance_set() {
+        assert_eq!(resolved.is_valid(&value), expected, "ref form: {value}");
+        assert_eq!(offline.is_valid(&value), expected, "inline form: {value}");
+    }
+}
+
+#[test]
+fn test_base_type_inline_is_unchanged() {
+    assert_eq!(
+        BaseEventV1::<()>::gts_schema_inline(),
+        BaseEventV1::<()>::gts_schema_with_refs()
+    );
+}
+
+#[test]
+fn test_bundled_schema_is_deterministic() {
+    assert_eq!(
+        gts_schema_for_bundled!(PlaceOrderDataV1).to_string(),
+        gts_schema_for_bundled!(PlaceOrderDataV1).to_string()
+    );
+}
+
+#[test]
+fn test_base_type_bundle_has_no_defs() {
+    let bundled = gts_schema_for_bundled!(BaseEventV1<()>);
+    assert!(bundled.get("$defs").is_none());
+    assert_eq!(bundled, BaseEventV1::<()>::gts_schema_with_refs());
+}
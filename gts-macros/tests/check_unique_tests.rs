@@ -0,0 +1,98 @@
+//! Test: `check_unique = true` registers type IDs so duplicates across the
+//! crate are reported, naming every type that shares the ID.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use gts::GtsInstanceId;
+use gts_macros::struct_to_gts_schema;
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = true,
+    type_id = "gts.x.test.unique.order.v1~",
+    description = "Order",
+    properties = "id",
+    check_unique = true
+)]
+#[derive(Debug)]
+pub struct UniqueOrderV1 {
+    pub id: GtsInstanceId,
+}
+
+mod billing {
+    use gts::GtsInstanceId;
+    use gts_macros::struct_to_gts_schema;
+
+    /// Accidentally reuses `UniqueOrderV1`'s type ID.
+    #[struct_to_gts_schema(
+        dir_path = "schemas",
+        base = true,
+        type_id = "gts.x.test.unique.order.v1~",
+        description = "Invoice",
+        properties = "id",
+        check_unique = true
+    )]
+    #[derive(Debug)]
+    pub struct InvoiceV1 {
+        pub id: GtsInstanceId,
+    }
+}
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = true,
+    type_id = "gts.x.test.unique.customer.v1~",
+    description = "Customer",
+    properties = "id",
+    check_unique = true
+)]
+#[derive(Debug)]
+pub struct UniqueCustomerV1 {
+    pub id: GtsInstanceId,
+}
+
+/// Not opted in, so never reported even though it shares an ID.
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = true,
+    type_id = "gts.x.test.unique.customer.v1~",
+    description = "Customer copy",
+    properties = "id"
+)]
+#[derive(Debug)]
+pub struct UncheckedCustomerV1 {
+    pub id: GtsInstanceId,
+}
+
+#[test]
+fn registers_opted_in_types() {
+    let registered = gts::registered_type_ids();
+    assert_eq!(registered.len(), 3, "{registered:?}");
+    assert_eq!(registered[0].type_id, "gts.x.test.unique.customer.v1~");
+    assert_eq!(
+        registered[0].rust_type,
+        "check_unique_tests::UniqueCustomerV1"
+    );
+}
+
+#[test]
+fn reports_duplicates_naming_both_types() {
+    let duplicates = gts::duplicate_type_ids();
+    assert_eq!(duplicates.len(), 1, "{duplicates:?}");
+    assert_eq!(duplicates[0].type_id, "gts.x.test.unique.order.v1~");
+    assert_eq!(
+        duplicates[0].rust_types,
+        [
+            "check_unique_tests::UniqueOrderV1",
+            "check_unique_tests::billing::InvoiceV1"
+        ]
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "'gts.x.test.unique.order.v1~' is used by check_unique_tests::UniqueOrderV1 and check_unique_tests::billing::InvoiceV1"
+)]
+fn assert_unique_type_ids_panics_on_duplicates() {
+    gts::assert_unique_type_ids();
+}
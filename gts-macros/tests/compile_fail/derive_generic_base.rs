@@ -0,0 +1,21 @@
+//! Test: GtsSchemaDerive rejects generic base structs (the derive cannot add
+//! the serde attributes the generic field needs)
+
+use gts_macros::GtsSchemaDerive;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, JsonSchema, GtsSchemaDerive)]
+#[gts(
+    dir_path = "schemas",
+    type_id = "gts.x.core.events.type.v1~",
+    description = "Base event",
+    properties = "id,payload",
+    base = true
+)]
+pub struct BaseEventV1<P> {
+    pub id: gts::GtsInstanceId,
+    pub payload: P,
+}
+
+fn main() {}
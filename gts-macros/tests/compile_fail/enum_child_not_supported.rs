@@ -0,0 +1,29 @@
+//! Test: Enums must be base types (deriving from a parent is not supported)
+
+use gts_macros::struct_to_gts_schema;
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = true,
+    type_id = "gts.x.core.events.type.v1~",
+    description = "Base event",
+    properties = "id,payload"
+)]
+pub struct BaseEventV1<P> {
+    pub id: gts::GtsInstanceId,
+    pub payload: P,
+}
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = BaseEventV1,
+    type_id = "gts.x.core.events.type.v1~x.app.entities.status.v1~",
+    description = "Status enum",
+    properties = ""
+)]
+pub enum StatusV1 {
+    Active,
+    Inactive,
+}
+
+fn main() {}
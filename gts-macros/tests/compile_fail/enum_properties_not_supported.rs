@@ -1,4 +1,4 @@
-//! Test: Enums are not supported
+//! Test: Enums take their variants from the enum itself, not a properties list
 
 use gts_macros::struct_to_gts_schema;
 
@@ -0,0 +1,24 @@
+//! Test: enum_values must not contain empty values
+
+use gts_macros::struct_to_gts_schema;
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub enum Status {
+    Pending,
+    Active,
+}
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = true,
+    type_id = "gts.x.core.tasks.task.v1~",
+    description = "Task",
+    properties = "id,status"
+)]
+pub struct TaskV1 {
+    pub id: gts::GtsInstanceId,
+    #[gts_schema(enum_values = "Pending,,Active")]
+    pub status: Status,
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+//! Test: min_length must not exceed max_length
+
+use gts_macros::struct_to_gts_schema;
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = true,
+    type_id = "gts.x.core.tasks.task.v1~",
+    description = "Task",
+    properties = "id,name"
+)]
+pub struct TaskV1 {
+    pub id: gts::GtsInstanceId,
+    #[gts_schema(min_length = 10, max_length = 5)]
+    pub name: String,
+}
+
+fn main() {}
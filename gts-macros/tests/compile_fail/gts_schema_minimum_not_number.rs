@@ -0,0 +1,18 @@
+//! Test: minimum must be a number literal
+
+use gts_macros::struct_to_gts_schema;
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = true,
+    type_id = "gts.x.core.tasks.task.v1~",
+    description = "Task",
+    properties = "id,priority"
+)]
+pub struct TaskV1 {
+    pub id: gts::GtsInstanceId,
+    #[gts_schema(minimum = "0")]
+    pub priority: u32,
+}
+
+fn main() {}
@@ -0,0 +1,32 @@
+//! Test: inject_type_field is only valid on base structs
+
+use gts_macros::struct_to_gts_schema;
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = true,
+    type_id = "gts.x.core.events.type.v1~",
+    description = "Base event",
+    properties = "event_type,id,payload",
+    inject_type_field = true
+)]
+pub struct BaseEventV1<P> {
+    #[serde(rename = "type")]
+    pub event_type: gts::gts::GtsTypeId,
+    pub id: uuid::Uuid,
+    pub payload: P,
+}
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = BaseEventV1,
+    type_id = "gts.x.core.events.type.v1~x.core.audit.event.v1~",
+    description = "Audit event",
+    properties = "user_id",
+    inject_type_field = true
+)]
+pub struct AuditEventV1 {
+    pub user_id: String,
+}
+
+fn main() {}
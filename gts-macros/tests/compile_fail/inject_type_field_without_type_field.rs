@@ -0,0 +1,18 @@
+//! Test: inject_type_field requires a GTS Type field of type GtsTypeId
+
+use gts_macros::struct_to_gts_schema;
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = true,
+    type_id = "gts.x.core.events.topic.v1~",
+    description = "Topic",
+    properties = "id,name",
+    inject_type_field = true
+)]
+pub struct TopicV1 {
+    pub id: gts::GtsInstanceId,
+    pub name: String,
+}
+
+fn main() {}
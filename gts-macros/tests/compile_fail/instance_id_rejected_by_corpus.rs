@@ -0,0 +1,27 @@
+//! Test: an instance ID that gts-validator rejects at scan time is rejected
+//! at compile time too. The ID comes from the shared GTS ID corpus
+//! (`gts_id::corpus`): the second segment's package token
+//! starts with a digit.
+
+use gts::GtsInstanceId;
+use gts_macros::{gts_instance, struct_to_gts_schema};
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = true,
+    type_id = "gts.x.core.events.type.v1~",
+    description = "Test permission type for compile_fail/instance_id_rejected_by_corpus",
+    properties = "id,action"
+)]
+#[derive(Debug)]
+pub struct PermV1 {
+    pub id: GtsInstanceId,
+    pub action: String,
+}
+
+fn main() {
+    let _ = gts_instance!(PermV1 {
+        id: "gts.x.core.events.type.v1~x.1core.audit.event.v1",
+        action: "read".to_owned(),
+    });
+}
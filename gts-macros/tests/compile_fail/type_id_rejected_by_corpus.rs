@@ -0,0 +1,18 @@
+//! Test: a type ID that gts-validator rejects at scan time is rejected at
+//! compile time too. The ID comes from the shared GTS ID corpus
+//! (`gts_id::corpus`): the vendor token must be lowercase.
+
+use gts_macros::struct_to_gts_schema;
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = true,
+    type_id = "gts.X.core.events.type.v1~",
+    description = "Event type with an uppercase vendor",
+    properties = "id"
+)]
+pub struct EventV1 {
+    pub id: gts::GtsInstanceId,
+}
+
+fn main() {}
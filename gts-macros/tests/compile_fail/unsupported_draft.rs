@@ -0,0 +1,17 @@
+//! Test: draft must be "07" or "2020-12"
+
+use gts_macros::struct_to_gts_schema;
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = true,
+    type_id = "gts.x.core.events.topic.v1~",
+    description = "Event topic",
+    properties = "name",
+    draft = "2019-09"
+)]
+pub struct TopicV1 {
+    pub name: String,
+}
+
+fn main() {}
@@ -3,8 +3,49 @@
 //! These tests verify that the macro produces appropriate compile errors
 //! for invalid inputs.
 
+#![allow(clippy::unwrap_used)]
+
+use gts_id::corpus::ID_CORPUS;
+
 #[test]
 fn compile_fail_tests() {
     let t = trybuild::TestCases::new();
     t.compile_fail("tests/compile_fail/*.rs");
 }
+
+/// The `*_rejected_by_corpus` cases must use IDs the shared corpus rejects,
+/// so they cannot drift from what `gts-validator` reports at scan time.
+#[test]
+fn corpus_cases_use_ids_from_corpus() {
+    let mut cases = 0;
+    for entry in std::fs::read_dir("tests/compile_fail").unwrap() {
+        let path = entry.unwrap().path();
+        let name = path.file_name().unwrap().to_str().unwrap();
+        if !name.ends_with("_rejected_by_corpus.rs") {
+            continue;
+        }
+        cases += 1;
+        let source = std::fs::read_to_string(&path).unwrap();
+        // String literals are the odd pieces between quotes.
+        let rejected: Vec<&str> = source
+            .split('"')
+            .skip(1)
+            .step_by(2)
+            .filter(|literal| literal.starts_with("gts."))
+            .filter(|literal| {
+                gts_id::validate_schema_id(literal).is_err()
+                    && gts_id::validate_instance_id(literal).is_err()
+            })
+            .collect();
+        assert!(!rejected.is_empty(), "{name}: no rejected GTS ID");
+        for id in rejected {
+            assert!(
+                ID_CORPUS.iter().any(|&(input, schema, instance, pattern)| {
+                    input == id && [schema, instance, pattern].iter().any(Option::is_some)
+                }),
+                "{name}: {id:?} is not a rejected entry of gts_id::corpus::ID_CORPUS"
+            );
+        }
+    }
+    assert_eq!(cases, 2);
+}
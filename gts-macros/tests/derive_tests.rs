@@ -0,0 +1,141 @@
+//! Tests for the `#[derive(GtsSchemaDerive)]` entry point.
+//!
+//! The same struct shapes are annotated with both the attribute form and the
+//! derive form; the generated schemas must be byte-identical.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use gts::{GtsInstanceId, GtsSchema};
+use gts_macros::{GtsSchemaDerive, struct_to_gts_schema};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+// --- Base type, both forms ---------------------------------------------------
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    type_id = "gts.x.test.derive.topic.v1~",
+    description = "Derive parity topic",
+    properties = "id,name,retention_days",
+    base = true
+)]
+#[derive(Debug)]
+pub struct AttrTopicV1 {
+    pub id: GtsInstanceId,
+    pub name: String,
+    pub retention_days: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, GtsSchemaDerive)]
+#[gts(
+    dir_path = "schemas",
+    type_id = "gts.x.test.derive.topic.v1~",
+    description = "Derive parity topic",
+    properties = "id,name,retention_days",
+    base = true
+)]
+pub struct DeriveTopicV1 {
+    pub id: GtsInstanceId,
+    pub name: String,
+    pub retention_days: Option<u32>,
+}
+
+// --- Child type, both forms (parent declared with the attribute form) -------
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    type_id = "gts.x.test.derive.event.v1~",
+    description = "Derive parity base event",
+    properties = "id,payload",
+    base = true
+)]
+#[derive(Debug)]
+pub struct DeriveParentEventV1<P> {
+    pub id: GtsInstanceId,
+    pub payload: P,
+}
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    type_id = "gts.x.test.derive.event.v1~x.test.derive.audit.v1~",
+    description = "Derive parity audit payload",
+    properties = "user_id,action",
+    base = DeriveParentEventV1
+)]
+#[derive(Debug)]
+pub struct AttrAuditV1 {
+    pub user_id: String,
+    pub action: String,
+}
+
+#[derive(Debug, JsonSchema, GtsSchemaDerive)]
+#[gts(
+    dir_path = "schemas",
+    type_id = "gts.x.test.derive.event.v1~x.test.derive.audit.v1~",
+    description = "Derive parity audit payload",
+    properties = "user_id,action",
+    base = DeriveParentEventV1
+)]
+pub struct DeriveAuditV1 {
+    pub user_id: String,
+    pub action: String,
+}
+
+#[test]
+fn test_base_schema_is_byte_identical() {
+    assert_eq!(
+        AttrTopicV1::gts_schema_with_refs_as_string(),
+        DeriveTopicV1::gts_schema_with_refs_as_string()
+    );
+    assert_eq!(
+        AttrTopicV1::gts_schema_with_refs_as_string_pretty(),
+        DeriveTopicV1::gts_schema_with_refs_as_string_pretty()
+    );
+}
+
+#[test]
+fn test_child_schema_is_byte_identical() {
+    assert_eq!(
+        AttrAuditV1::gts_schema_with_refs_as_string(),
+        DeriveAuditV1::gts_schema_with_refs_as_string()
+    );
+}
+
+#[test]
+fn test_derive_generates_same_trait_consts() {
+    assert_eq!(
+        <AttrTopicV1 as GtsSchema>::TYPE_ID,
+        <DeriveTopicV1 as GtsSchema>::TYPE_ID
+    );
+    assert_eq!(
+        <AttrAuditV1 as GtsSchema>::TYPE_ID,
+        <DeriveAuditV1 as GtsSchema>::TYPE_ID
+    );
+    assert_eq!(
+        AttrAuditV1::gts_base_type_id(),
+        DeriveAuditV1::gts_base_type_id()
+    );
+    assert_eq!(
+        DeriveTopicV1::gts_make_instance_id("x.test._.orders.v1").as_ref(),
+        "gts.x.test.derive.topic.v1~x.test._.orders.v1"
+    );
+}
+
+#[test]
+fn test_derive_instance_json_matches() {
+    let id = DeriveTopicV1::gts_make_instance_id("x.test._.orders.v1");
+    let attr = AttrTopicV1 {
+        id: id.clone(),
+        name: "orders".to_owned(),
+        retention_days: Some(7),
+    };
+    let derived = DeriveTopicV1 {
+        id,
+        name: "orders".to_owned(),
+        retention_days: Some(7),
+    };
+    assert_eq!(
+        serde_json::to_string(&attr).unwrap(),
+        serde_json::to_string(&derived).unwrap()
+    );
+}
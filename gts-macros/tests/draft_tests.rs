@@ -0,0 +1,104 @@
+//! Test: `draft = "..."` selects the JSON Schema dialect of generated schemas,
+//! and `gts_schema_for!(T, draft = "...")` converts on demand.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use gts::{
+    GtsInstanceId, GtsSchema, JSON_SCHEMA_DRAFT_07, JSON_SCHEMA_DRAFT_2020_12, gts_schema_for,
+};
+use gts_macros::struct_to_gts_schema;
+use serde_json::json;
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = true,
+    type_id = "gts.x.test.draft.legacy.v1~",
+    description = "Draft-07 host",
+    properties = "id,point",
+    draft = "07"
+)]
+#[derive(Debug)]
+pub struct LegacyPointV1 {
+    pub id: GtsInstanceId,
+    pub point: (i32, String),
+}
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = true,
+    type_id = "gts.x.test.draft.modern.v1~",
+    description = "Draft 2020-12 host",
+    properties = "id,point",
+    draft = "2020-12"
+)]
+#[derive(Debug)]
+pub struct ModernPointV1 {
+    pub id: GtsInstanceId,
+    pub point: (i32, String),
+}
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = true,
+    type_id = "gts.x.test.draft.default.v1~",
+    description = "Host without a draft",
+    properties = "id,point"
+)]
+#[derive(Debug)]
+pub struct DefaultPointV1 {
+    pub id: GtsInstanceId,
+    pub point: (i32, String),
+}
+
+fn instance(point: &serde_json::Value) -> serde_json::Value {
+    json!({ "id": "gts.x.test.draft.legacy.v1~x.test._.p.v1", "point": point })
+}
+
+#[test]
+fn test_draft_07_uses_items_array() {
+    let schema = LegacyPointV1::gts_schema_with_refs();
+    assert_eq!(schema["$schema"], json!(JSON_SCHEMA_DRAFT_07));
+    let point = &schema["properties"]["point"];
+    assert!(point["items"].is_array(), "{point}");
+    assert!(point.get("prefixItems").is_none());
+}
+
+#[test]
+fn test_draft_2020_12_uses_prefix_items() {
+    let schema = ModernPointV1::gts_schema_with_refs();
+    assert_eq!(schema["$schema"], json!(JSON_SCHEMA_DRAFT_2020_12));
+    let point = &schema["properties"]["point"];
+    assert!(point["prefixItems"].is_array(), "{point}");
+    assert!(point.get("items").is_none());
+}
+
+#[test]
+fn test_both_drafts_validate_tuples_under_their_dialect() {
+    for schema in [
+        LegacyPointV1::gts_schema_with_refs(),
+        ModernPointV1::gts_schema_with_refs(),
+    ] {
+        let validator = jsonschema::validator_for(&schema).unwrap();
+        assert!(validator.is_valid(&instance(&json!([1, "a"]))), "{schema}");
+        assert!(!validator.is_valid(&instance(&json!(["a", 1]))), "{schema}");
+    }
+}
+
+#[test]
+fn test_default_output_is_unchanged() {
+    let schema = DefaultPointV1::gts_schema_with_refs();
+    assert_eq!(schema["$schema"], json!(JSON_SCHEMA_DRAFT_07));
+    assert!(schema["properties"]["point"]["prefixItems"].is_array());
+}
+
+#[test]
+fn test_gts_schema_for_accepts_draft() {
+    let converted = gts_schema_for!(ModernPointV1, draft = "07");
+    let legacy = LegacyPointV1::gts_schema_with_refs();
+    assert_eq!(converted["$schema"], json!(JSON_SCHEMA_DRAFT_07));
+    assert_eq!(converted["properties"], legacy["properties"]);
+
+    let converted = gts_schema_for!(DefaultPointV1, draft = "2020-12");
+    assert_eq!(converted["$schema"], json!(JSON_SCHEMA_DRAFT_2020_12));
+    assert!(converted["properties"]["point"]["prefixItems"].is_array());
+}
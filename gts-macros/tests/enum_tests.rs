@@ -0,0 +1,238 @@
+//! Test: `#[struct_to_gts_schema]` on enums.
+//!
+//! Every variant is serialized with serde and validated against the generated
+//! schema, so the schema is pinned to the actual wire format of each serde
+//! enum representation.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use gts::GtsSchema;
+use gts_macros::struct_to_gts_schema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = true,
+    type_id = "gts.x.test.enums.status.v1~",
+    description = "Order status",
+    properties = ""
+)]
+#[derive(Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderStatusV1 {
+    Pending,
+    InTransit,
+    Delivered,
+}
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = true,
+    type_id = "gts.x.test.enums.external.v1~",
+    description = "Externally tagged shape",
+    properties = ""
+)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExternalShapeV1 {
+    Empty,
+    Circle { radius: f64 },
+    Label(String),
+}
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = true,
+    type_id = "gts.x.test.enums.internal.v1~",
+    description = "Internally tagged command",
+    properties = ""
+)]
+#[derive(Debug, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum InternalCommandV1 {
+    Start { id: String },
+    Stop { id: String, force: bool },
+    Reset,
+}
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = true,
+    type_id = "gts.x.test.enums.adjacent.v1~",
+    description = "Adjacently tagged message",
+    properties = ""
+)]
+#[derive(Debug, Clone, PartialEq)]
+#[serde(tag = "type", content = "data")]
+pub enum AdjacentMessageV1 {
+    Text(String),
+    Ping,
+    Move { x: i32, y: i32 },
+}
+
+/// An adjacently tagged enum used as a struct property, not as a GTS type.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(tag = "type", content = "data")]
+pub enum Notice {
+    Text(String),
+    Ping,
+    Move { x: i32, y: i32 },
+}
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = true,
+    type_id = "gts.x.test.enums.notice_holder.v1~",
+    description = "Struct with an adjacently tagged property",
+    properties = "id,notice"
+)]
+#[derive(Debug, Clone)]
+pub struct NoticeHolderV1 {
+    pub id: gts::GtsInstanceId,
+    pub notice: Notice,
+}
+
+fn assert_round_trips<T: Serialize + GtsSchema>(values: &[T]) {
+    let schema = T::gts_schema_with_refs();
+    let validator = jsonschema::validator_for(&schema).unwrap();
+    for value in values {
+        let instance = serde_json::to_value(value).unwrap();
+        let errors: Vec<String> = validator
+            .iter_errors(&instance)
+            .map(|e| e.to_string())
+            .collect();
+        assert!(
+            errors.is_empty(),
+            "{instance} should validate against {schema}: {errors:?}"
+        );
+    }
+}
+
+fn assert_rejects<T: GtsSchema>(instance: &Value) {
+    let schema = T::gts_schema_with_refs();
+    let validator = jsonschema::validator_for(&schema).unwrap();
+    assert!(
+        !validator.is_valid(instance),
+        "{instance} should not validate against {schema}"
+    );
+}
+
+#[test]
+fn test_unit_enum_schema_is_string_enum() {
+    let schema = OrderStatusV1::gts_schema_with_refs();
+    assert_eq!(schema["$id"], json!("gts://gts.x.test.enums.status.v1~"));
+    assert_eq!(schema["description"], json!("Order status"));
+    assert_eq!(schema["type"], json!("string"));
+    assert_eq!(
+        schema["enum"],
+        json!(["pending", "in_transit", "delivered"])
+    );
+    assert!(schema.get("title").is_none());
+}
+
+#[test]
+fn test_unit_enum_round_trip() {
+    assert_round_trips(&[
+        OrderStatusV1::Pending,
+        OrderStatusV1::InTransit,
+        OrderStatusV1::Delivered,
+    ]);
+    assert_rejects::<OrderStatusV1>(&json!("InTransit"));
+
+    let back: OrderStatusV1 = serde_json::from_value(json!("in_transit")).unwrap();
+    assert_eq!(back, OrderStatusV1::InTransit);
+}
+
+#[test]
+fn test_external_tagging_round_trip() {
+    assert_round_trips(&[
+        ExternalShapeV1::Empty,
+        ExternalShapeV1::Circle { radius: 1.5 },
+        ExternalShapeV1::Label("a".to_owned()),
+    ]);
+    assert_rejects::<ExternalShapeV1>(&json!({ "Circle": { "radius": "big" } }));
+    assert_rejects::<ExternalShapeV1>(&json!({ "Square": { "side": 1 } }));
+}
+
+#[test]
+fn test_internal_tagging_round_trip() {
+    assert_round_trips(&[
+        InternalCommandV1::Start { id: "a".to_owned() },
+        InternalCommandV1::Stop {
+            id: "a".to_owned(),
+            force: true,
+        },
+        InternalCommandV1::Reset,
+    ]);
+    assert_rejects::<InternalCommandV1>(&json!({ "kind": "pause", "id": "a" }));
+    assert_rejects::<InternalCommandV1>(&json!({ "kind": "stop", "id": "a" }));
+
+    let schema = InternalCommandV1::gts_schema_with_refs();
+    let tags: Vec<&Value> = schema["oneOf"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| &v["properties"]["kind"]["const"])
+        .collect();
+    assert_eq!(tags, [&json!("start"), &json!("stop"), &json!("reset")]);
+}
+
+#[test]
+fn test_adjacent_tagging_round_trip() {
+    assert_round_trips(&[
+        AdjacentMessageV1::Text("hi".to_owned()),
+        AdjacentMessageV1::Ping,
+        AdjacentMessageV1::Move { x: 1, y: -2 },
+    ]);
+    assert_rejects::<AdjacentMessageV1>(&json!({ "type": "Text", "data": 5 }));
+}
+
+#[test]
+fn test_adjacent_tagging_struct_property() {
+    let holder = |notice| NoticeHolderV1 {
+        id: gts::GtsInstanceId::new("gts.x.test.enums.notice_holder.v1~", "x.test._.n.v1"),
+        notice,
+    };
+    assert_round_trips(&[
+        holder(Notice::Text("hi".to_owned())),
+        holder(Notice::Ping),
+        holder(Notice::Move { x: 1, y: -2 }),
+    ]);
+
+    let schema = NoticeHolderV1::gts_schema_with_refs();
+    let variants = schema["properties"]["notice"]["oneOf"].as_array().unwrap();
+    let tags: Vec<&Value> = variants
+        .iter()
+        .map(|v| &v["properties"]["type"]["const"])
+        .collect();
+    assert_eq!(tags, [&json!("Text"), &json!("Ping"), &json!("Move")]);
+    assert_eq!(variants[2]["required"], json!(["type", "data"]));
+    assert_eq!(variants[2]["properties"]["data"]["type"], json!("object"));
+
+    let id = "gts.x.test.enums.notice_holder.v1~x.test._.n.v1";
+    assert_rejects::<NoticeHolderV1>(&json!({
+        "id": id,
+        "notice": { "type": "Text", "data": 5 }
+    }));
+    assert_rejects::<NoticeHolderV1>(&json!({
+        "id": id,
+        "notice": { "type": "Move" }
+    }));
+    assert_rejects::<NoticeHolderV1>(&json!({
+        "id": id,
+        "notice": { "type": "Jump", "data": {} }
+    }));
+}
+
+#[test]
+fn test_enum_accessors() {
+    assert_eq!(
+        OrderStatusV1::gts_type_id().as_ref(),
+        "gts.x.test.enums.status.v1~"
+    );
+    assert!(OrderStatusV1::gts_base_type_id().is_none());
+    assert_eq!(
+        OrderStatusV1::InTransit.gts_instance_json(),
+        json!("in_transit")
+    );
+}
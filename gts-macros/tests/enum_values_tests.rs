@@ -0,0 +1,90 @@
+//! Test: `#[gts_schema(enum_values = "...")]` on enum-typed struct fields
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use gts::GtsSchema;
+use gts::gts::GtsTypeId;
+use gts_macros::{gts_id, struct_to_gts_schema};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// A plain string enum with renamed variants. It deliberately does not derive
+/// `JsonSchema`: the field override replaces its schema entirely.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OrderStatus {
+    Pending,
+    Active,
+    Completed,
+    OnHold,
+}
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = true,
+    type_id = gts_id!("x.shop.orders.order.v1~"),
+    description = "Order with enum-typed status fields",
+    properties = "order_type,status,previous_status"
+)]
+#[derive(Debug)]
+pub struct OrderV1 {
+    #[serde(rename = "type")]
+    pub order_type: GtsTypeId,
+    #[gts_schema(enum_values = "pending, active, completed, onHold")]
+    pub status: OrderStatus,
+    #[gts_schema(enum_values = "pending,active,completed,onHold")]
+    pub previous_status: Option<OrderStatus>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_enum_values_property_schema() {
+        let schema = OrderV1::gts_schema_with_refs();
+        assert_eq!(
+            schema["properties"]["status"],
+            json!({
+                "type": "string",
+                "enum": ["pending", "active", "completed", "onHold"]
+            })
+        );
+    }
+
+    #[test]
+    fn test_enum_values_option_allows_null() {
+        let schema = OrderV1::gts_schema_with_refs();
+        let previous = &schema["properties"]["previous_status"];
+        assert_eq!(
+            previous["enum"],
+            json!(["pending", "active", "completed", "onHold", null])
+        );
+        let required = schema["required"].as_array().unwrap();
+        assert!(required.contains(&json!("status")));
+        assert!(!required.contains(&json!("previous_status")));
+    }
+
+    #[test]
+    fn test_enum_values_match_serde_representation() {
+        let order = OrderV1 {
+            order_type: GtsTypeId::new(gts_id!("x.shop.orders.order.v1~")),
+            status: OrderStatus::OnHold,
+            previous_status: None,
+        };
+        let value = serde_json::to_value(&order).unwrap();
+        assert_eq!(value["status"], "onHold");
+
+        let schema = OrderV1::gts_schema_with_refs();
+        let validator = jsonschema::validator_for(&schema).expect("valid schema");
+        assert!(validator.is_valid(&value));
+        assert!(!validator.is_valid(&json!({
+            "type": gts_id!("x.shop.orders.order.v1~"),
+            "status": "OnHold",
+            "previous_status": null
+        })));
+
+        let back: OrderV1 = serde_json::from_value(value).unwrap();
+        assert_eq!(back.status, OrderStatus::OnHold);
+    }
+}
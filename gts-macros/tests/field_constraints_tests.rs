@@ -0,0 +1,84 @@
+//! Test: `#[gts_schema(...)]` string length, pattern and numeric range constraints
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use gts::GtsSchema;
+use gts::gts::GtsTypeId;
+use gts_macros::struct_to_gts_schema;
+use serde_json::json;
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = true,
+    type_id = gts_id!("x.shop.products.product.v1~"),
+    description = "Product with constrained fields",
+    properties = "product_type,sku,title,discount,rating,offset"
+)]
+#[derive(Debug)]
+pub struct ProductV1 {
+    #[serde(rename = "type")]
+    pub product_type: GtsTypeId,
+    #[gts_schema(min_length = 1, max_length = 256, pattern = "^[a-z]+$")]
+    pub sku: String,
+    #[gts_schema(max_length = 80)]
+    pub title: Option<String>,
+    #[gts_schema(minimum = 0, maximum = 100)]
+    pub discount: u32,
+    #[gts_schema(minimum = 0.5, maximum = 5.0)]
+    pub rating: f64,
+    #[gts_schema(minimum = -10)]
+    pub offset: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_string_constraints() {
+        let schema = ProductV1::gts_schema_with_refs();
+        let sku = &schema["properties"]["sku"];
+        assert_eq!(sku["type"], "string");
+        assert_eq!(sku["minLength"], json!(1));
+        assert_eq!(sku["maxLength"], json!(256));
+        assert_eq!(sku["pattern"], json!("^[a-z]+$"));
+        assert_eq!(schema["properties"]["title"]["maxLength"], json!(80));
+    }
+
+    #[test]
+    fn test_numeric_constraints_are_numbers() {
+        let schema = ProductV1::gts_schema_with_refs();
+        let discount = &schema["properties"]["discount"];
+        assert_eq!(discount["minimum"], json!(0));
+        assert_eq!(discount["maximum"], json!(100));
+        assert!(discount["maximum"].is_u64());
+
+        let rating = &schema["properties"]["rating"];
+        assert_eq!(rating["minimum"], json!(0.5));
+        assert_eq!(rating["maximum"], json!(5.0));
+        assert!(rating["minimum"].is_f64());
+
+        assert_eq!(schema["properties"]["offset"]["minimum"], json!(-10));
+        assert!(schema["properties"]["offset"].get("maximum").is_none());
+    }
+
+    #[test]
+    fn test_constraints_validate_instances() {
+        let schema = ProductV1::gts_schema_with_refs();
+        let validator = jsonschema::validator_for(&schema).expect("valid schema");
+        let product = |sku: &str, discount: u32| {
+            json!({
+                "type": "gts.x.shop.products.product.v1~",
+                "sku": sku,
+                "title": null,
+                "discount": discount,
+                "rating": 4.5,
+                "offset": 0
+            })
+        };
+        assert!(validator.is_valid(&product("abc", 10)));
+        assert!(!validator.is_valid(&product("", 10)));
+        assert!(!validator.is_valid(&product("ABC", 10)));
+        assert!(!validator.is_valid(&product("abc", 101)));
+    }
+}
@@ -0,0 +1,27 @@
+// Golden case: the same struct emitted with `draft = "2020-12"`; compare with
+// `draft_07`. Tuple fields (`items` vs `prefixItems`) are covered in
+// `draft_tests.rs` instead: the registry meta-validates with draft 2020-12.
+
+use gts::{GtsInstanceId, GtsSchema};
+use gts_macros::struct_to_gts_schema;
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = true,
+    type_id = gts_id!("x.test.golden.draft.v1~"),
+    description = "Draft selection host",
+    properties = "id,tags",
+    draft = "2020-12",
+)]
+#[derive(Debug)]
+pub struct DraftHostV1 {
+    pub id: GtsInstanceId,
+    pub tags: Vec<String>,
+}
+
+pub fn schemas() -> Vec<(String, serde_json::Value)> {
+    vec![(
+        DraftHostV1::TYPE_ID.to_owned(),
+        DraftHostV1::gts_schema_with_refs(),
+    )]
+}
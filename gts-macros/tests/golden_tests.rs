@@ -143,6 +143,8 @@ golden_cases!(
     traits_schema_narrowing,
     traits_referenced_chain,
     traits_struct_literal,
+    draft_07,
+    draft_2020_12,
 );
 
 /// Compare each generated `(type_id, schema)` against
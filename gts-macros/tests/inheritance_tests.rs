@@ -645,6 +645,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_gts_schema_version_uses_leaf_segment() {
+        assert_eq!(BaseEventV1::<()>::gts_schema_version(), 1);
+        assert_eq!(BaseEventV1::<()>::gts_schema_minor_version(), None);
+        assert_eq!(PlaceOrderDataV1::gts_schema_version(), 1);
+        assert_eq!(PlaceOrderDataV1::gts_schema_minor_version(), None);
+    }
+
     #[test]
     fn test_explicit_base_attribute_schema_generation() {
         // TopicV1 is marked with base = true
@@ -2884,4 +2892,31 @@ mod tests {
             "inner_data.properties should have 'content_value'"
         );
     }
+
+    #[test]
+    fn test_generic_field_annotation() {
+        use gts::schema_generic::X_GTS_GENERIC_FIELD;
+
+        // Emitted at the top level with the serialized field name
+        assert_eq!(
+            BaseEventV1::<()>::gts_schema_with_refs()[X_GTS_GENERIC_FIELD],
+            serde_json::json!("payload")
+        );
+        assert_eq!(
+            ContainerV1::<()>::gts_schema_with_refs()[X_GTS_GENERIC_FIELD],
+            serde_json::json!("inner_data")
+        );
+        let audit = AuditPayloadV1::<()>::gts_schema_with_refs();
+        assert_eq!(audit[X_GTS_GENERIC_FIELD], serde_json::json!("data"));
+        assert!(audit["allOf"][1].get(X_GTS_GENERIC_FIELD).is_none());
+
+        // Omitted for types without a generic field
+        for schema in [
+            PlaceOrderDataV1::gts_schema_with_refs(),
+            ContentV1::gts_schema_with_refs(),
+            OrderTopicConfigV1::gts_schema_with_refs(),
+        ] {
+            assert!(schema.get(X_GTS_GENERIC_FIELD).is_none(), "{schema}");
+        }
+    }
 }
@@ -0,0 +1,153 @@
+//! Test: `inject_type_field = true` pins the base's GTS Type field to the
+//! instance's type ID on serialization and rejects mismatches on input.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use gts::GtsSchema;
+use gts::gts::GtsTypeId;
+use gts_macros::struct_to_gts_schema;
+use serde_json::json;
+use uuid::Uuid;
+
+/* ============================================================
+Generic base with a derived payload
+============================================================ */
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = true,
+    type_id = "gts.x.test.inject.event.v1~",
+    description = "Base event with an injected type field",
+    properties = "event_type,id,payload",
+    inject_type_field = true
+)]
+#[derive(Debug)]
+pub struct InjectEventV1<P> {
+    #[serde(rename = "type")]
+    pub event_type: GtsTypeId,
+    pub id: Uuid,
+    pub payload: P,
+}
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = InjectEventV1,
+    type_id = "gts.x.test.inject.event.v1~x.test.inject.created.v1~",
+    description = "Created event payload",
+    properties = "name"
+)]
+#[derive(Debug)]
+pub struct InjectCreatedV1 {
+    pub name: String,
+}
+
+/* ============================================================
+Non-generic base
+============================================================ */
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = true,
+    type_id = "gts.x.test.inject.topic.v1~",
+    description = "Topic with an injected type field",
+    properties = "gts_type,name",
+    inject_type_field = true
+)]
+#[derive(Debug)]
+pub struct InjectTopicV1 {
+    pub gts_type: GtsTypeId,
+    pub name: String,
+}
+
+const CREATED_ID: &str = "gts.x.test.inject.event.v1~x.test.inject.created.v1~";
+
+fn created_event(event_type: &str) -> InjectEventV1<InjectCreatedV1> {
+    InjectEventV1 {
+        event_type: GtsTypeId::new(event_type),
+        id: Uuid::nil(),
+        payload: InjectCreatedV1 {
+            name: "order".to_owned(),
+        },
+    }
+}
+
+#[test]
+fn test_serialize_corrects_wrong_type_value() {
+    let event = created_event("gts.x.test.inject.event.v1~");
+    let value = serde_json::to_value(&event).unwrap();
+    assert_eq!(value["type"], json!(CREATED_ID));
+}
+
+#[test]
+fn test_serialize_base_with_unit_payload_uses_base_id() {
+    let event = InjectEventV1 {
+        event_type: GtsTypeId::new(CREATED_ID),
+        id: Uuid::nil(),
+        payload: (),
+    };
+    let value = serde_json::to_value(&event).unwrap();
+    assert_eq!(value["type"], json!("gts.x.test.inject.event.v1~"));
+}
+
+#[test]
+fn test_deserialize_round_trip() {
+    let value = serde_json::to_value(created_event(CREATED_ID)).unwrap();
+    let back: InjectEventV1<InjectCreatedV1> = serde_json::from_value(value).unwrap();
+    assert_eq!(back.event_type.as_ref(), CREATED_ID);
+    assert_eq!(back.payload.name, "order");
+}
+
+#[test]
+fn test_deserialize_rejects_wrong_type_value() {
+    let value = json!({
+        "type": "gts.x.test.inject.event.v1~",
+        "id": Uuid::nil(),
+        "payload": { "name": "order" }
+    });
+    let err = serde_json::from_value::<InjectEventV1<InjectCreatedV1>>(value).unwrap_err();
+    assert!(
+        err.to_string().contains(&format!(
+            "type field mismatch: expected `{CREATED_ID}`, got `gts.x.test.inject.event.v1~`"
+        )),
+        "{err}"
+    );
+}
+
+#[test]
+fn test_non_generic_base_pins_own_id() {
+    let topic = InjectTopicV1 {
+        gts_type: GtsTypeId::new("gts.x.test.inject.other.v1~"),
+        name: "orders".to_owned(),
+    };
+    let value = serde_json::to_value(&topic).unwrap();
+    assert_eq!(value["gts_type"], json!("gts.x.test.inject.topic.v1~"));
+
+    let bad = json!({ "gts_type": "gts.x.test.inject.other.v1~", "name": "orders" });
+    assert!(serde_json::from_value::<InjectTopicV1>(bad).is_err());
+}
+
+#[test]
+fn test_type_field_const_is_inherited() {
+    assert_eq!(<InjectEventV1<()> as GtsSchema>::TYPE_FIELD, Some("type"));
+    assert_eq!(<InjectCreatedV1 as GtsSchema>::TYPE_FIELD, Some("type"));
+    assert_eq!(<InjectTopicV1 as GtsSchema>::TYPE_FIELD, Some("gts_type"));
+}
+
+#[test]
+fn test_schema_marks_type_field_const() {
+    let topic = InjectTopicV1::gts_schema_with_refs();
+    assert_eq!(
+        topic["properties"]["gts_type"]["const"],
+        json!("gts.x.test.inject.topic.v1~")
+    );
+
+    // The generic base's schema is shared with descendants, so it stays open.
+    let base = InjectEventV1::<()>::gts_schema_with_refs();
+    assert!(base["properties"]["type"].get("const").is_none());
+
+    let child = InjectCreatedV1::gts_schema_with_refs();
+    assert_eq!(
+        child["allOf"][1]["properties"]["type"],
+        json!({ "const": CREATED_ID })
+    );
+}
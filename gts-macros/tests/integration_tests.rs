@@ -189,6 +189,26 @@ fn test_gts_instance_id_multi_segment() {
     assert_eq!(id, gts_id!("x.core.events.topic.v1~x.core.idp.contacts.v1"));
 }
 
+#[test]
+fn test_gts_instance_id_from_parts_and_unique() {
+    let id =
+        EventTopicV1::gts_make_instance_id_from_parts("x", "commerce", "orders", "orders", "v1.0")
+            .unwrap();
+    assert_eq!(
+        id,
+        EventTopicV1::gts_make_instance_id("x.commerce.orders.orders.v1.0")
+    );
+
+    let err = EventTopicV1::gts_make_instance_id_from_parts("x", "commerce", "", "orders", "v1")
+        .unwrap_err();
+    assert!(err.cause.contains("namespace"), "{err}");
+
+    let unique = EventTopicV1::gts_make_unique_instance_id("x.commerce.orders").unwrap();
+    let parsed = GtsInstanceId::try_new(&unique).unwrap();
+    assert_eq!(parsed, unique);
+    assert!(unique.starts_with(EventTopicV1::TYPE_ID));
+}
+
 #[test]
 fn test_gts_instance_id_with_wildcard_segment() {
     // Test with segment containing wildcard "_"
@@ -1153,6 +1173,32 @@ fn test_version_extraction_underscore_format() {
     );
 }
 
+#[derive(Debug, Clone)]
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = true,
+    type_id = gts_id!("x.test.versioned.wide.v10.5~"),
+    description = "Test struct with a two-digit major version",
+    properties = "id"
+)]
+pub struct WideMinorV10_5 {
+    pub id: GtsInstanceId,
+}
+
+#[test]
+fn test_gts_schema_version_accessors() {
+    assert_eq!(MinorVersionV1_0::gts_schema_version(), 1);
+    assert_eq!(MinorVersionV1_0::gts_schema_minor_version(), Some(0));
+    assert_eq!(ComplexMinorV2_5::gts_schema_version(), 2);
+    assert_eq!(ComplexMinorV2_5::gts_schema_minor_version(), Some(5));
+    assert_eq!(WideMinorV10_5::gts_schema_version(), 10);
+    assert_eq!(WideMinorV10_5::gts_schema_minor_version(), Some(5));
+
+    // Placeholders have no version.
+    assert_eq!(<() as GtsSchema>::gts_schema_version(), 0);
+    assert_eq!(<() as GtsSchema>::gts_schema_minor_version(), None);
+}
+
 #[derive(Debug, Clone)]
 #[struct_to_gts_schema(
     dir_path = "schemas",
@@ -56,4 +56,24 @@ mod tests {
         println!("Regular: {regular}");
         println!("Pretty: {pretty}");
     }
+
+    #[test]
+    fn test_gts_schema_hash_ignores_formatting() {
+        use gts::GtsSchema;
+        use gts::manifest::SchemaManifestEntry;
+
+        let hash = TestPrettyStructV1::gts_schema_hash();
+        assert!(hash.starts_with("sha256:"), "{hash}");
+
+        // Both renderings describe the same schema, so the manifest hashes
+        // them alike, and equal to the type's own hash.
+        for rendered in [
+            TestPrettyStructV1::gts_schema_with_refs_as_string(),
+            TestPrettyStructV1::gts_schema_with_refs_as_string_pretty(),
+        ] {
+            let entry = SchemaManifestEntry::from_schema_file("pretty.schema.json", &rendered)
+                .expect("schema has a gts:// $id");
+            assert_eq!(entry.hash, hash);
+        }
+    }
 }
@@ -0,0 +1,96 @@
+//! Test: generated types of different Rust types behind `dyn gts::GtsSchemaDyn`
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use gts::{GtsInstanceId, GtsSchema, GtsSchemaDyn};
+use gts_macros::struct_to_gts_schema;
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = true,
+    type_id = "gts.x.test.dyn.event.v1~",
+    description = "Erasable base event",
+    properties = "id,payload"
+)]
+#[derive(Debug)]
+pub struct DynEventV1<P> {
+    pub id: GtsInstanceId,
+    pub payload: P,
+}
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = true,
+    type_id = "gts.x.test.dyn.topic.v1~",
+    description = "Erasable topic",
+    properties = "id,name"
+)]
+#[derive(Debug)]
+pub struct DynTopicV1 {
+    pub id: GtsInstanceId,
+    pub name: String,
+}
+
+fn topic() -> DynTopicV1 {
+    DynTopicV1 {
+        id: GtsInstanceId::new("gts.x.test.dyn.topic.v1~", "x.test.orders.v1"),
+        name: "orders".to_owned(),
+    }
+}
+
+#[test]
+fn collects_ids_of_mixed_types() {
+    let types: Vec<Box<dyn GtsSchemaDyn>> =
+        vec![gts::erase::<DynEventV1<()>>(), gts::erase::<DynTopicV1>()];
+    let ids: Vec<&str> = types.iter().map(|schema| schema.schema_id()).collect();
+    assert_eq!(
+        ids,
+        ["gts.x.test.dyn.event.v1~", "gts.x.test.dyn.topic.v1~"]
+    );
+    assert_eq!(
+        types[1].schema_with_refs(),
+        DynTopicV1::gts_schema_with_refs()
+    );
+    assert!(
+        types
+            .iter()
+            .all(|schema| schema.instance_json().unwrap().is_none())
+    );
+}
+
+#[test]
+fn values_serialize_as_instances() {
+    let event = DynEventV1 {
+        id: GtsInstanceId::new("gts.x.test.dyn.event.v1~", "x.test.created.v1"),
+        payload: (),
+    };
+    let values: Vec<Box<dyn GtsSchemaDyn>> = vec![Box::new(event), Box::new(topic())];
+    let ids: Vec<&str> = values.iter().map(|value| value.schema_id()).collect();
+    assert_eq!(
+        ids,
+        ["gts.x.test.dyn.event.v1~", "gts.x.test.dyn.topic.v1~"]
+    );
+    let instance = values[1].instance_json().unwrap().unwrap();
+    assert_eq!(instance["name"], "orders");
+    assert_eq!(instance, serde_json::to_value(topic()).unwrap());
+}
+
+#[test]
+fn registry_hands_out_dyn_schemas() {
+    let schema = gts::registered_schema(DynTopicV1::TYPE_ID).unwrap();
+    assert_eq!(schema.schema_id(), DynTopicV1::TYPE_ID);
+    assert_eq!(
+        schema.schema_with_refs(),
+        DynTopicV1::gts_schema_with_refs()
+    );
+    assert!(gts::registered_schema("gts.x.test.dyn.missing.v1~").is_none());
+
+    let ids: Vec<&str> = gts::schema_index()
+        .iter()
+        .map(|entry| entry.schema.schema_id())
+        .collect();
+    assert_eq!(
+        ids,
+        ["gts.x.test.dyn.event.v1~", "gts.x.test.dyn.topic.v1~"]
+    );
+}
@@ -0,0 +1,132 @@
+//! Test: every `#[struct_to_gts_schema]` type is listed by `gts::schema_index()`
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use gts::{GtsInstanceId, GtsSchema};
+use gts_macros::{GtsSchemaDerive, struct_to_gts_schema};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = true,
+    type_id = "gts.x.test.index.event.v1~",
+    description = "Indexed base event",
+    properties = "id,payload"
+)]
+#[derive(Debug)]
+pub struct IndexEventV1<P> {
+    pub id: GtsInstanceId,
+    pub payload: P,
+}
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = IndexEventV1,
+    type_id = "gts.x.test.index.event.v1~x.test.index.audit.v1~",
+    description = "Indexed audit event",
+    properties = "actor,data"
+)]
+#[derive(Debug)]
+pub struct IndexAuditV1<D> {
+    pub actor: String,
+    pub data: D,
+}
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = IndexAuditV1,
+    type_id = "gts.x.test.index.event.v1~x.test.index.audit.v1~x.test.index.login.v1~",
+    description = "Indexed login audit event",
+    properties = "ip"
+)]
+#[derive(Debug)]
+pub struct IndexLoginV1 {
+    pub ip: String,
+}
+
+mod nested {
+    use super::{Deserialize, GtsInstanceId, GtsSchemaDerive, JsonSchema, Serialize};
+
+    #[derive(Debug, Serialize, Deserialize, JsonSchema, GtsSchemaDerive)]
+    #[gts(
+        dir_path = "schemas",
+        base = true,
+        type_id = "gts.x.test.index.topic.v1~",
+        description = "Indexed topic (derive form)",
+        properties = "id"
+    )]
+    pub struct IndexTopicV1 {
+        pub id: GtsInstanceId,
+    }
+}
+
+#[test]
+fn lists_every_annotated_type_sorted_by_id() {
+    let index = gts::schema_index();
+    let ids: Vec<(&str, &str, Option<&str>)> = index
+        .iter()
+        .map(|e| (e.schema_id, e.description, e.base_schema_id))
+        .collect();
+    assert_eq!(
+        ids,
+        [
+            ("gts.x.test.index.event.v1~", "Indexed base event", None),
+            (
+                "gts.x.test.index.event.v1~x.test.index.audit.v1~",
+                "Indexed audit event",
+                Some("gts.x.test.index.event.v1~")
+            ),
+            (
+                "gts.x.test.index.event.v1~x.test.index.audit.v1~x.test.index.login.v1~",
+                "Indexed login audit event",
+                Some("gts.x.test.index.event.v1~x.test.index.audit.v1~")
+            ),
+            (
+                "gts.x.test.index.topic.v1~",
+                "Indexed topic (derive form)",
+                None
+            ),
+        ]
+    );
+}
+
+#[test]
+fn entries_build_the_type_schema() {
+    let index = gts::schema_index();
+    let login = index
+        .iter()
+        .find(|e| e.schema_id == IndexLoginV1::TYPE_ID)
+        .unwrap();
+    assert_eq!(
+        (login.schema_json)(),
+        IndexLoginV1::gts_schema_with_refs_as_string()
+    );
+    let event = index
+        .iter()
+        .find(|e| e.schema_id == IndexEventV1::<()>::TYPE_ID)
+        .unwrap();
+    assert_eq!(
+        (event.schema_json)(),
+        IndexEventV1::<()>::gts_schema_with_refs_as_string()
+    );
+}
+
+#[test]
+fn generates_index_json_array() {
+    let index: Value = serde_json::from_str(&gts::generate_schema_index_json()).unwrap();
+    let entries = index.as_array().unwrap();
+    assert_eq!(entries.len(), 4);
+    assert_eq!(entries[0]["schema_id"], "gts.x.test.index.event.v1~");
+    assert_eq!(entries[0]["base_schema_id"], Value::Null);
+    assert_eq!(
+        entries[0]["schema"]["$id"],
+        "gts://gts.x.test.index.event.v1~"
+    );
+    assert_eq!(
+        entries[2]["schema"]["$id"],
+        "gts://gts.x.test.index.event.v1~x.test.index.audit.v1~x.test.index.login.v1~"
+    );
+    assert_eq!(entries[3]["description"], "Indexed topic (derive form)");
+}
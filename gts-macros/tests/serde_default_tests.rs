@@ -0,0 +1,104 @@
+//! Test: `#[serde(default)]` fields are optional in generated schemas
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use gts::{GtsInstanceId, GtsSchema};
+use gts_macros::struct_to_gts_schema;
+use serde_json::json;
+
+fn default_limit() -> u32 {
+    10
+}
+
+fn default_channel() -> String {
+    "email".to_owned()
+}
+
+/* ============================================================
+Base struct mixing defaulted and required fields
+============================================================ */
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = true,
+    type_id = "gts.x.test.defaults.config.v1~",
+    description = "Config with serde defaults",
+    properties = "id,name,limit,tags,payload"
+)]
+#[derive(Debug)]
+pub struct DefaultsConfigV1<P> {
+    pub id: GtsInstanceId,
+    pub name: String,
+    #[serde(default = "default_limit")]
+    pub limit: u32,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub payload: P,
+}
+
+/* ============================================================
+Child struct mixing defaulted and required fields
+============================================================ */
+
+#[struct_to_gts_schema(
+    dir_path = "schemas",
+    base = DefaultsConfigV1,
+    type_id = "gts.x.test.defaults.config.v1~x.test.defaults.notify.v1~",
+    description = "Notification settings with serde defaults",
+    properties = "recipient,channel,retries"
+)]
+#[derive(Debug)]
+pub struct DefaultsNotifyV1 {
+    pub recipient: String,
+    #[serde(default = "default_channel")]
+    pub channel: String,
+    #[serde(default)]
+    pub retries: u8,
+}
+
+#[test]
+fn test_base_defaulted_fields_not_required() {
+    let schema = DefaultsConfigV1::<()>::gts_schema_with_refs();
+    assert_eq!(schema["required"], json!(["id", "name", "payload"]));
+    assert_eq!(schema["properties"]["limit"]["default"], json!(10));
+    assert_eq!(schema["properties"]["tags"]["default"], json!([]));
+    assert!(schema["properties"]["name"].get("default").is_none());
+}
+
+#[test]
+fn test_child_defaulted_fields_not_required() {
+    let schema = DefaultsNotifyV1::gts_schema_with_refs();
+    let overlay = &schema["allOf"][1]["properties"]["payload"];
+    assert_eq!(overlay["required"], json!(["recipient"]));
+    assert_eq!(overlay["properties"]["channel"]["default"], json!("email"));
+    assert_eq!(overlay["properties"]["retries"]["default"], json!(0));
+}
+
+#[test]
+fn test_missing_defaulted_fields_deserialize() {
+    let value = json!({
+        "id": "gts.x.test.defaults.config.v1~x.test.defaults.notify.v1~x.test._.cfg.v1",
+        "name": "alerts",
+        "payload": { "recipient": "ops@example.com" }
+    });
+    let config: DefaultsConfigV1<DefaultsNotifyV1> = serde_json::from_value(value).unwrap();
+    assert_eq!(config.limit, 10);
+    assert!(config.tags.is_empty());
+    assert_eq!(config.payload.recipient, "ops@example.com");
+    assert_eq!(config.payload.channel, "email");
+    assert_eq!(config.payload.retries, 0);
+}
+
+#[test]
+fn test_missing_required_child_field_still_fails() {
+    let value = json!({
+        "id": "gts.x.test.defaults.config.v1~x.test.defaults.notify.v1~x.test._.cfg.v1",
+        "name": "alerts",
+        "payload": { "channel": "sms" }
+    });
+    let err = serde_json::from_value::<DefaultsConfigV1<DefaultsNotifyV1>>(value).unwrap_err();
+    assert!(
+        err.to_string().contains("missing field `recipient`"),
+        "{err}"
+    );
+}
@@ -0,0 +1,43 @@
+//! JSON report output: pretty-printed (`write_json`) against compact
+//! (`write_json_compact`), on a report of 10,000 validation errors.
+//!
+//! ```sh
+//! cargo bench -p gts-validator --bench output
+//! ```
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use gts_validator::bench::ContentScanner;
+use gts_validator::output::{write_json, write_json_compact};
+use gts_validator::{ValidationConfig, VendorPolicy};
+
+/// Validation errors in the measured report.
+const REPORT_ERRORS: usize = 10_000;
+
+fn write_json_benches(c: &mut Criterion) {
+    let mut config = ValidationConfig::default();
+    config.vendor_policy = VendorPolicy::MustMatch("x".to_owned());
+    let report = ContentScanner::new(&config)
+        .unwrap()
+        .error_report(REPORT_ERRORS);
+
+    let mut group = c.benchmark_group("write_json");
+    let mut buffer = Vec::new();
+    group.bench_function("pretty", |b| {
+        b.iter(|| {
+            buffer.clear();
+            write_json(&report, &mut buffer).unwrap();
+        });
+    });
+    group.bench_function("compact", |b| {
+        b.iter(|| {
+            buffer.clear();
+            write_json_compact(&report, &mut buffer).unwrap();
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, write_json_benches);
+criterion_main!(benches);
@@ -0,0 +1,96 @@
+//! Scanner throughput over the synthetic corpus of `gts_validator::bench`:
+//! end to end through `validate_fs`, and per format on content in memory.
+//!
+//! ```sh
+//! cargo bench -p gts-validator
+//! ```
+//!
+//! The corpus is generated into a temporary directory for each run. Set
+//! `GTS_BENCH_CORPUS` to a directory written by `gts-validator bench-corpus`
+//! to reuse one instead.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use std::path::PathBuf;
+
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use gts_validator::bench::{
+    ContentScanner, CorpusSize, deep_yaml, generate_corpus, large_json, markdown_file,
+};
+use gts_validator::{FsSourceConfig, ValidationConfig, VendorPolicy, validate_fs};
+use tempfile::TempDir;
+
+fn validation_config() -> ValidationConfig {
+    let mut config = ValidationConfig::default();
+    config.vendor_policy = VendorPolicy::MustMatch("x".to_owned());
+    config
+}
+
+/// The corpus directory, and the temporary directory holding it when it was
+/// generated for this run.
+fn corpus() -> (PathBuf, Option<TempDir>) {
+    if let Some(dir) = std::env::var_os("GTS_BENCH_CORPUS") {
+        return (PathBuf::from(dir), None);
+    }
+    let tmp = TempDir::new().expect("temp dir");
+    generate_corpus(tmp.path(), &CorpusSize::FULL).expect("generate corpus");
+    (tmp.path().to_path_buf(), Some(tmp))
+}
+
+fn validate_fs_benches(c: &mut Criterion) {
+    let (dir, _tmp) = corpus();
+    let config = validation_config();
+    let mut group = c.benchmark_group("validate_fs");
+    group.sample_size(10);
+    for (name, path) in [
+        ("markdown_10k_files", dir.join("markdown")),
+        ("json_50mb", dir.join("large.json")),
+        ("yaml_deep", dir.join("deep.yaml")),
+    ] {
+        let mut fs_config = FsSourceConfig::default();
+        fs_config.paths = vec![path];
+        // The JSON document is over the default size limit, and would be skipped.
+        fs_config.max_file_size = 2 * CorpusSize::FULL.json_bytes as u64;
+        group.bench_function(name, |b| {
+            b.iter(|| validate_fs(&fs_config, &config).unwrap());
+        });
+    }
+    group.finish();
+}
+
+fn scan_content_benches(c: &mut Criterion) {
+    let mut config = validation_config();
+    config.skip_tokens = (0..20).map(|i| format!("**Token{i}**")).collect();
+    let scanner = ContentScanner::new(&config).unwrap();
+    let markdown = markdown_file(0);
+    let json = large_json(CorpusSize::FULL.json_bytes);
+    let yaml = deep_yaml(CorpusSize::FULL.yaml_depth);
+    let bytes = |content: &str| Throughput::Bytes(content.len() as u64);
+
+    let mut group = c.benchmark_group("scan_content");
+    group.throughput(bytes(&markdown));
+    group.bench_function("scan_markdown_content", |b| {
+        b.iter(|| scanner.scan_markdown_content(&markdown));
+    });
+    // What `validate_fs` saves by compiling the config once per run.
+    group.bench_function("scan_markdown_content_compiling_config", |b| {
+        b.iter(|| {
+            ContentScanner::new(&config)
+                .unwrap()
+                .scan_markdown_content(&markdown)
+        });
+    });
+    group.throughput(bytes(&yaml));
+    group.bench_function("scan_yaml_content", |b| {
+        b.iter(|| scanner.scan_yaml_content(&yaml).unwrap());
+    });
+    group.sample_size(10);
+    group.throughput(bytes(&json));
+    group.bench_function("scan_json_content", |b| {
+        b.iter(|| scanner.scan_json_content(&json).unwrap());
+    });
+    group.finish();
+}
+
+criterion_group!(benches, validate_fs_benches, scan_content_benches);
+criterion_main!(benches);
@@ -0,0 +1,342 @@
+//! CI annotations: validation errors in the formats CI systems pick up from
+//! build logs or report artifacts.
+
+use serde_json::json;
+
+use crate::error::ValidationError;
+use crate::report::ValidationReport;
+
+/// The CI system an annotation is formatted for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AnnotationFormat {
+    /// GitHub Actions workflow command (`::error file=…::message`).
+    GithubActions,
+    /// GitLab Code Quality report entry (JSON).
+    Gitlab,
+    /// `TeamCity` service message (`##teamcity[buildProblem …]`).
+    TeamCity,
+    /// Azure Pipelines logging command (`##vso[task.logissue …]message`).
+    AzureDevOps,
+}
+
+impl ValidationError {
+    /// Format the error as a CI annotation.
+    ///
+    /// Line and column are included only when known (markdown files).
+    /// [`AnnotationFormat::Gitlab`] yields one Code Quality issue object; a
+    /// Code Quality report is an array of them (see
+    /// [`ValidationReport::to_annotations`]).
+    #[must_use]
+    pub fn to_annotation(&self, format: AnnotationFormat) -> String {
+        match format {
+            AnnotationFormat::GithubActions => self.github_annotation(),
+            AnnotationFormat::Gitlab => self.gitlab_issue().to_string(),
+            AnnotationFormat::TeamCity => self.teamcity_annotation(),
+            AnnotationFormat::AzureDevOps => self.azure_annotation(),
+        }
+    }
+
+    /// The annotation text: the error, the raw value and, for JSON/YAML,
+    /// where it was found.
+    fn annotation_message(&self) -> String {
+        if self.json_path.is_empty() {
+            format!("{} [{}]", self.error, self.raw_value)
+        } else {
+            format!(
+                "{} [{}] (at {})",
+                self.error, self.raw_value, self.json_path
+            )
+        }
+    }
+
+    fn github_annotation(&self) -> String {
+        let mut properties = vec![format!(
+            "file={}",
+            escape_github_property(&self.file.display().to_string())
+        )];
+        if self.line > 0 {
+            properties.push(format!("line={}", self.line));
+        }
+        if self.column > 0 {
+            properties.push(format!("col={}", self.column));
+        }
+        properties.push(format!("title={}", escape_github_property(self.rule_id)));
+        format!(
+            "::error {}::{}",
+            properties.join(","),
+            escape_github_message(&self.annotation_message())
+        )
+    }
+
+    fn gitlab_issue(&self) -> serde_json::Value {
+        let path = self.file.display().to_string();
+        let fingerprint = fnv1a_hex(&[
+            &path,
+            &self.line.to_string(),
+            &self.json_path,
+            &self.raw_value,
+            self.rule_id,
+        ]);
+        json!({
+            "description": self.annotation_message(),
+            "check_name": self.rule_id,
+            "fingerprint": fingerprint,
+            "severity": "major",
+            "location": {
+                "path": path,
+                "lines": { "begin": self.line.max(1) }
+            }
+        })
+    }
+
+    fn teamcity_annotation(&self) -> String {
+        let location = if self.line > 0 {
+            format!("{}:{}", self.file.display(), self.line)
+        } else {
+            self.file.display().to_string()
+        };
+        format!(
+            "##teamcity[buildProblem description='{}']",
+            escape_teamcity(&format!(
+                "{location}: {} {}",
+                self.rule_id,
+                self.annotation_message()
+            ))
+        )
+    }
+
+    fn azure_annotation(&self) -> String {
+        let mut properties = vec![
+            "type=error".to_owned(),
+            format!(
+                "sourcepath={}",
+                escape_azure_property(&self.file.display().to_string())
+            ),
+        ];
+        if self.line > 0 {
+            properties.push(format!("linenumber={}", self.line));
+        }
+        if self.column > 0 {
+            properties.push(format!("columnnumber={}", self.column));
+        }
+        properties.push(format!("code={}", escape_azure_property(self.rule_id)));
+        format!(
+            "##vso[task.logissue {}]{}",
+            properties.join(";"),
+            escape_azure_message(&self.annotation_message())
+        )
+    }
+}
+
+impl ValidationReport {
+    /// Format every validation error as a CI annotation.
+    ///
+    /// Line-based formats yield one annotation per line. For
+    /// [`AnnotationFormat::Gitlab`] the result is a complete Code Quality
+    /// report: a JSON array of issues. Scan errors and omitted validation
+    /// errors (see [`ValidationReport::truncate_errors`]) are not included.
+    #[must_use]
+    pub fn to_annotations(&self, format: AnnotationFormat) -> String {
+        if format == AnnotationFormat::Gitlab {
+            let issues: Vec<serde_json::Value> = self
+                .validation_errors
+                .iter()
+                .map(ValidationError::gitlab_issue)
+                .collect();
+            return serde_json::Value::Array(issues).to_string();
+        }
+        self.validation_errors
+            .iter()
+            .map(|err| err.to_annotation(format) + "\n")
+            .collect()
+    }
+}
+
+fn escape_github_message(s: &str) -> String {
+    s.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_github_property(s: &str) -> String {
+    escape_github_message(s)
+        .replace(':', "%3A")
+        .replace(',', "%2C")
+}
+
+fn escape_teamcity(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '|' => escaped.push_str("||"),
+            '\'' => escaped.push_str("|'"),
+            '\n' => escaped.push_str("|n"),
+            '\r' => escaped.push_str("|r"),
+            '[' => escaped.push_str("|["),
+            ']' => escaped.push_str("|]"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn escape_azure_message(s: &str) -> String {
+    s.replace('%', "%AZP25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_azure_property(s: &str) -> String {
+    escape_azure_message(s)
+        .replace(';', "%3B")
+        .replace(']', "%5D")
+}
+
+/// 64-bit FNV-1a over `parts` (separated by NUL), as hex. Stable across
+/// runs and toolchains, unlike `std`'s hasher, so GitLab can match issues
+/// between pipelines.
+fn fnv1a_hex(parts: &[&str]) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for (i, part) in parts.iter().enumerate() {
+        let separator: &[u8] = if i == 0 { &[] } else { &[0] };
+        for &byte in separator.iter().chain(part.as_bytes()) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    format!("{hash:016x}")
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn markdown_error() -> ValidationError {
+        ValidationError {
+            file: PathBuf::from("docs/a,b.md"),
+            line: 42,
+            column: 10,
+            json_path: String::new(),
+            raw_value: "gts.x.core.events.type.v1".to_owned(),
+            normalized_id: "gts.x.core.events.type.v1".to_owned(),
+            rule_id: crate::rules::INVALID_ID,
+            error: "Invalid GTS ID: 50% [bad]\nsecond line".to_owned(),
+            context: String::new(),
+            section: None,
+            document_kind: None,
+            fix: None,
+            help: None,
+            original_file: None,
+        }
+    }
+
+    fn json_error() -> ValidationError {
+        ValidationError {
+            file: PathBuf::from("schemas/event.json"),
+            line: 0,
+            column: 0,
+            json_path: "$.properties.type.x-gts-ref".to_owned(),
+            raw_value: "gts.y.core.events.type.v1~".to_owned(),
+            normalized_id: "gts.y.core.events.type.v1~".to_owned(),
+            rule_id: crate::rules::VENDOR_MISMATCH,
+            error: "Vendor mismatch: expected 'x', found 'y'".to_owned(),
+            context: String::new(),
+            section: None,
+            document_kind: None,
+            fix: None,
+            help: None,
+            original_file: None,
+        }
+    }
+
+    #[test]
+    fn test_github_actions() {
+        assert_eq!(
+            markdown_error().to_annotation(AnnotationFormat::GithubActions),
+            "::error file=docs/a%2Cb.md,line=42,col=10,title=GTS003::\
+             Invalid GTS ID: 50%25 [bad]%0Asecond line [gts.x.core.events.type.v1]"
+        );
+        assert_eq!(
+            json_error().to_annotation(AnnotationFormat::GithubActions),
+            "::error file=schemas/event.json,title=GTS001::\
+             Vendor mismatch: expected 'x', found 'y' [gts.y.core.events.type.v1~] \
+             (at $.properties.type.x-gts-ref)"
+        );
+    }
+
+    #[test]
+    fn test_gitlab() {
+        let issue: serde_json::Value =
+            serde_json::from_str(&json_error().to_annotation(AnnotationFormat::Gitlab)).unwrap();
+        assert_eq!(issue["check_name"], "GTS001");
+        assert_eq!(issue["severity"], "major");
+        assert_eq!(issue["location"]["path"], "schemas/event.json");
+        assert_eq!(issue["location"]["lines"]["begin"], 1);
+        assert_eq!(issue["fingerprint"].as_str().unwrap().len(), 16);
+        assert!(
+            issue["description"]
+                .as_str()
+                .unwrap()
+                .ends_with("(at $.properties.type.x-gts-ref)")
+        );
+
+        let other: serde_json::Value =
+            serde_json::from_str(&markdown_error().to_annotation(AnnotationFormat::Gitlab))
+                .unwrap();
+        assert_eq!(other["location"]["lines"]["begin"], 42);
+        assert_ne!(other["fingerprint"], issue["fingerprint"]);
+    }
+
+    #[test]
+    fn test_teamcity() {
+        assert_eq!(
+            markdown_error().to_annotation(AnnotationFormat::TeamCity),
+            "##teamcity[buildProblem description='docs/a,b.md:42: GTS003 \
+             Invalid GTS ID: 50% |[bad|]|nsecond line |[gts.x.core.events.type.v1|]']"
+        );
+        assert_eq!(
+            json_error().to_annotation(AnnotationFormat::TeamCity),
+            "##teamcity[buildProblem description='schemas/event.json: GTS001 \
+             Vendor mismatch: expected |'x|', found |'y|' |[gts.y.core.events.type.v1~|] \
+             (at $.properties.type.x-gts-ref)']"
+        );
+    }
+
+    #[test]
+    fn test_azure_devops() {
+        assert_eq!(
+            markdown_error().to_annotation(AnnotationFormat::AzureDevOps),
+            "##vso[task.logissue type=error;sourcepath=docs/a,b.md;linenumber=42;\
+             columnnumber=10;code=GTS003]\
+             Invalid GTS ID: 50%AZP25 [bad]%0Asecond line [gts.x.core.events.type.v1]"
+        );
+        assert_eq!(
+            json_error().to_annotation(AnnotationFormat::AzureDevOps),
+            "##vso[task.logissue type=error;sourcepath=schemas/event.json;code=GTS001]\
+             Vendor mismatch: expected 'x', found 'y' [gts.y.core.events.type.v1~] \
+             (at $.properties.type.x-gts-ref)"
+        );
+    }
+
+    #[test]
+    fn test_report_to_annotations() {
+        let report = ValidationReport {
+            validation_errors: vec![markdown_error(), json_error()],
+            ..ValidationReport::default()
+        };
+        let lines = report.to_annotations(AnnotationFormat::GithubActions);
+        assert_eq!(lines.lines().count(), 2);
+        assert!(lines.ends_with('\n'));
+
+        let gitlab: serde_json::Value =
+            serde_json::from_str(&report.to_annotations(AnnotationFormat::Gitlab)).unwrap();
+        assert_eq!(gitlab.as_array().unwrap().len(), 2);
+
+        let empty = ValidationReport::default();
+        assert_eq!(empty.to_annotations(AnnotationFormat::TeamCity), "");
+        assert_eq!(empty.to_annotations(AnnotationFormat::Gitlab), "[]");
+    }
+}
@@ -0,0 +1,235 @@
+//! Synthetic corpora and scanner entry points for measuring throughput.
+//!
+//! The corpora are generated from their size alone, without randomness, so
+//! the same [`CorpusSize`] always yields the same files and benchmark numbers
+//! stay comparable between runs and machines. [`generate_corpus`] writes:
+//!
+//! - `markdown/NN/NNNNN.md`: small markdown files, 1,000 per directory;
+//! - `large.json`: one JSON document of schemas and instances;
+//! - `deep.yaml`: one YAML document nested `yaml_depth` levels deep.
+//!
+//! About one identifier in ten is malformed or of a foreign vendor, so the
+//! error paths are measured too. Used by the `benches/` suite, its smoke
+//! test and `gts-validator bench-corpus`; not a stable API.
+
+use std::fmt::Write as _;
+use std::io;
+use std::path::Path;
+
+use crate::config::{CompiledValidationConfig, ValidationConfig};
+use crate::error::ValidationError;
+use crate::format;
+use crate::report::ValidationReport;
+
+/// Markdown files per directory of the corpus.
+const FILES_PER_DIR: usize = 1000;
+
+/// How large a generated corpus is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CorpusSize {
+    /// Number of small markdown files.
+    pub markdown_files: usize,
+    /// Approximate size of the JSON document in bytes.
+    pub json_bytes: usize,
+    /// Nesting depth of the YAML document.
+    pub yaml_depth: usize,
+}
+
+impl CorpusSize {
+    /// The benchmark corpus: 10,000 markdown files, a 50 MB JSON document
+    /// and a YAML document 100 levels deep.
+    pub const FULL: Self = Self {
+        markdown_files: 10_000,
+        json_bytes: 50 * 1024 * 1024,
+        yaml_depth: 100,
+    };
+
+    /// A corpus small enough for a test run in a debug build.
+    pub const SMOKE: Self = Self {
+        markdown_files: 500,
+        json_bytes: 1024 * 1024,
+        yaml_depth: 50,
+    };
+}
+
+/// Write the corpus of `size` into `dir`, creating it if needed.
+///
+/// # Errors
+///
+/// Returns an error if a directory or file cannot be written.
+pub fn generate_corpus(dir: &Path, size: &CorpusSize) -> io::Result<()> {
+    let shards = (0..size.markdown_files).step_by(FILES_PER_DIR).enumerate();
+    for (shard_index, first) in shards {
+        let shard = dir.join("markdown").join(format!("{shard_index:02}"));
+        std::fs::create_dir_all(&shard)?;
+        for index in first..(first + FILES_PER_DIR).min(size.markdown_files) {
+            std::fs::write(shard.join(format!("{index:05}.md")), markdown_file(index))?;
+        }
+    }
+    std::fs::create_dir_all(dir)?;
+    std::fs::write(dir.join("large.json"), large_json(size.json_bytes))?;
+    std::fs::write(dir.join("deep.yaml"), deep_yaml(size.yaml_depth))?;
+    Ok(())
+}
+
+/// The GTS type ID numbered `n`: well-formed with vendor `x`, except for
+/// every tenth one, which alternates between a malformed ID and a foreign
+/// vendor.
+fn type_id(n: usize) -> String {
+    match (n % 10, n % 20) {
+        (9, 9) => format!("gts.x.bench.events.Type{n}.v1~"),
+        (9, _) => format!("gts.other.bench.events.type{n}.v1~"),
+        _ => format!("gts.x.bench.events.type{n}.v1~"),
+    }
+}
+
+/// The `index`th markdown file of the corpus: a heading, prose, a table and
+/// a fenced JSON block, with five identifiers.
+#[must_use]
+pub fn markdown_file(index: usize) -> String {
+    let id = |k: usize| type_id(index * 5 + k);
+    format!(
+        "# Event {index}\n\n\
+         The `{}` event is emitted when an order is placed. Consumers derive \
+         from `{}` and reference `{}`.\n\n\
+         | Type | Purpose |\n|------|---------|\n| `{}` | audit |\n\n\
+         ```json\n{{\"$id\": \"gts://{}\"}}\n```\n",
+        id(0),
+        id(1),
+        id(2),
+        id(3),
+        id(4)
+    )
+}
+
+/// A JSON array of schema and instance objects of about `bytes` bytes.
+#[must_use]
+pub fn large_json(bytes: usize) -> String {
+    let mut json = String::with_capacity(bytes + 512);
+    json.push('[');
+    let mut n = 0;
+    while json.len() < bytes {
+        if n > 0 {
+            json.push(',');
+        }
+        let _ = write!(
+            json,
+            "{{\"$id\":\"gts://{}\",\"type\":\"object\",\"properties\":{{\"kind\":\
+             {{\"x-gts-ref\":\"{}\"}},\"note\":{{\"description\":\"entry {n}\"}}}},\
+             \"examples\":[{{\"id\":\"{}x.bench.items.item{n}.v1\",\"name\":\"item {n}\"}}]}}",
+            type_id(n),
+            type_id(n + 1),
+            type_id(n + 2)
+        );
+        n += 1;
+    }
+    json.push(']');
+    json
+}
+
+/// A YAML mapping nested `depth` levels deep, each level holding a few
+/// identifiers next to the next level.
+#[must_use]
+pub fn deep_yaml(depth: usize) -> String {
+    let mut yaml = String::new();
+    for level in 0..depth {
+        let indent = "  ".repeat(level);
+        let _ = write!(
+            yaml,
+            "{indent}type: {}\n{indent}refs:\n{indent}  - {}\n{indent}  - {}\n{indent}child:\n",
+            type_id(level * 3),
+            type_id(level * 3 + 1),
+            type_id(level * 3 + 2)
+        );
+    }
+    let _ = writeln!(yaml, "{}leaf: true", "  ".repeat(depth));
+    yaml
+}
+
+/// The per-format scanners over content in memory, with a config compiled
+/// once, as `validate_fs` runs them for each file.
+pub struct ContentScanner {
+    compiled: CompiledValidationConfig,
+}
+
+impl ContentScanner {
+    /// # Errors
+    ///
+    /// Returns an error if a setting of `config` is invalid.
+    pub fn new(config: &ValidationConfig) -> anyhow::Result<Self> {
+        Ok(Self {
+            compiled: CompiledValidationConfig::new(config)?,
+        })
+    }
+
+    /// Scan markdown `content`.
+    #[must_use]
+    pub fn scan_markdown_content(&self, content: &str) -> Vec<ValidationError> {
+        format::markdown::scan_markdown(
+            content,
+            Path::new("bench.md"),
+            &self.compiled,
+            &mut Vec::new(),
+            &mut (),
+        )
+    }
+
+    /// A failed report of `errors` validation errors, as scanning the
+    /// markdown files of the corpus finds them, for measuring output.
+    #[must_use]
+    pub fn error_report(&self, errors: usize) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        let mut index = 0;
+        while report.validation_errors.len() < errors {
+            report
+                .validation_errors
+                .extend(self.scan_markdown_content(&markdown_file(index)));
+            report.scanned_files += 1;
+            index += 1;
+        }
+        report.validation_errors.truncate(errors);
+        report.discovered_files = report.scanned_files;
+        report
+            .scanned_by_format
+            .insert("markdown".to_owned(), report.scanned_files);
+        report
+    }
+
+    /// Scan JSON `content`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `content` is not valid JSON.
+    pub fn scan_json_content(&self, content: &str) -> anyhow::Result<Vec<ValidationError>> {
+        format::json::scan_json_content(
+            content,
+            Path::new("bench.json"),
+            &self.compiled.vendor_check,
+            &self.compiled.json_options,
+            &mut Vec::new(),
+            &mut (),
+        )
+        .map_err(|error| anyhow::anyhow!(error.message))
+    }
+
+    /// Scan YAML `content`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `content` is not valid YAML.
+    pub fn scan_yaml_content(&self, content: &str) -> anyhow::Result<Vec<ValidationError>> {
+        let (errors, scan_errors) = format::yaml::scan_yaml_content(
+            content,
+            Path::new("bench.yaml"),
+            &self.compiled.vendor_check,
+            &self.compiled.json_options,
+            &mut Vec::new(),
+            &mut (),
+        );
+        match scan_errors.into_iter().next() {
+            Some(error) => Err(anyhow::anyhow!(error.message)),
+            None => Ok(errors),
+        }
+    }
+}
@@ -4,10 +4,27 @@
 //! (how content is discovered). This ensures the core API does not leak
 //! filesystem concerns.
 
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::rc::Rc;
+
+use anyhow::Context;
+use glob::Pattern;
+use serde::{Deserialize, Serialize};
+
+use crate::effective::invalid_setting;
+use crate::format::document::{DocumentKind, DocumentKindOverrides, normalize_dialect};
+use crate::format::json::{EmbeddedScan, JsonScanOptions, OpenApiScanMode};
+use crate::format::markdown::SkipTokens;
+use crate::outcome::IdLimits;
+use crate::schemas::SchemaStore;
+use crate::stats::StatsSegment;
+use crate::vendor::VendorCheck;
 
 /// Vendor matching policy for GTS ID validation.
-#[derive(Debug, Clone, Default)]
+///
+/// Evaluate a single ID with [`VendorPolicy::evaluate`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum VendorPolicy {
     /// Accept any vendor (no vendor enforcement).
@@ -17,6 +34,23 @@ pub enum VendorPolicy {
     MustMatch(String),
     /// All GTS IDs must match one of the listed vendors (example vendors are always tolerated).
     AllowList(Vec<String>),
+    /// GTS IDs must not use any of the listed vendors (including example vendors).
+    Deny(Vec<String>),
+}
+
+/// Which segments of a chained GTS ID a [`VendorPolicy`] applies to.
+///
+/// In `gts.x.core.events.type.v1~acme.billing.invoice.created.v1~` the first
+/// segment's vendor is `x` and the last segment's is `acme`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum VendorScope {
+    /// Only the first segment, which owns the base type.
+    FirstSegment,
+    /// Every segment.
+    AllSegments,
+    /// Only the last segment: the most derived type, or the instance.
+    LastSegment,
 }
 
 /// Controls how GTS identifier candidates are discovered in markdown files.
@@ -31,25 +65,301 @@ pub enum DiscoveryMode {
     Heuristic,
 }
 
+/// Which candidates in markdown prose are validated, for documentation that
+/// writes GTS identifiers as inline code. Fenced code blocks are code and
+/// are scanned the same under every profile.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MarkdownProfile {
+    /// Every candidate (default).
+    #[default]
+    Default,
+    /// Only candidates inside inline code spans, e.g.
+    /// `` `gts.x.core.events.type.v1~` ``; others are skipped.
+    CodeSpansOnly,
+    /// Like `CodeSpansOnly`, and every candidate outside a code span is
+    /// reported as a `BareIdentifier` warning.
+    RequireCodeSpans,
+}
+
+/// How the errors of a rule are reported.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum RuleSeverity {
+    /// As validation errors (default).
+    #[default]
+    Error,
+    /// As `DowngradedError` warnings, which never affect `ok`.
+    Warning,
+}
+
 /// Core validation config — applies regardless of input source.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 #[non_exhaustive]
+#[allow(clippy::struct_excessive_bools)]
 pub struct ValidationConfig {
     /// Vendor matching policy for all GTS IDs.
     /// Example vendors (acme, globex, etc.) are always tolerated regardless of policy.
     pub vendor_policy: VendorPolicy,
+    /// Segments the vendor policy applies to. `None` uses the policy's
+    /// default: all segments for `Deny`, the first segment otherwise.
+    pub vendor_scope: Option<VendorScope>,
     /// Scan JSON/YAML object keys for GTS identifiers (default: off).
     pub scan_keys: bool,
+    /// Report `null` values at JSON/YAML `$id`, `$ref` and `x-gts-ref` keys,
+    /// usually an identifier that was never filled in, as
+    /// [`crate::rules::NULL_ID`] errors (default: off).
+    pub scan_null_ids: bool,
+    /// Report every JSON/YAML `$id` that defines an identifier already
+    /// defined elsewhere in the scanned files as a
+    /// [`crate::rules::DUPLICATE_DEFINITION`] error (default: off).
+    pub check_duplicate_ids: bool,
     /// Discovery mode for markdown scanning.
     ///
     /// - `StrictSpecOnly` (default): only well-formed GTS patterns are discovered.
     /// - `Heuristic`: a permissive regex catches ALL gts.* strings, including malformed IDs.
     pub discovery_mode: DiscoveryMode,
+    /// Which markdown prose candidates are validated (default: all).
+    pub markdown_profile: MarkdownProfile,
     /// Additional skip tokens for markdown scanning.
     /// If any of these strings appear before a GTS candidate on the same line,
     /// validation is skipped for that candidate. Case-insensitive matching.
     /// Example: `vec!["**given**".to_owned()]` to skip BDD-style bold formatting.
     pub skip_tokens: Vec<String>,
+    /// Maximum tolerated error count per rule ID (see [`crate::rules`]).
+    ///
+    /// A run is `ok` when every rule fires at most its threshold times; rules
+    /// without an entry tolerate no errors. Example: `{"GTS001": 5}` accepts up
+    /// to five vendor mismatches as known technical debt.
+    pub rule_thresholds: HashMap<String, usize>,
+    /// Severity per rule ID (default: every rule is an error). Example:
+    /// `{"GTS001": RuleSeverity::Warning}` reports vendor mismatches as
+    /// warnings. Directory override files can change it for their subtree.
+    pub rule_severities: HashMap<String, RuleSeverity>,
+    /// Settings that directory override files (see
+    /// `FsSourceConfig::directory_overrides`) must not change, by field
+    /// name: any of `vendor_policy`, `skip_tokens`, `discovery_mode` and
+    /// `rule_severities` (default: none). The CLI pins the settings given
+    /// as flags.
+    pub pinned_settings: Vec<String>,
+    /// Record every discovered candidate, skipped or not, in
+    /// `ValidationReport::discovery_trace` (default: off).
+    pub trace_discovery: bool,
+    /// Count distinct GTS IDs per vendor and package into
+    /// `ValidationReport::id_stats`, attributing chained IDs to this segment
+    /// (default: off).
+    pub id_stats: Option<StatsSegment>,
+    /// Keep at most this many errors in `ValidationReport::validation_errors`
+    /// (default: no limit). Counts and `ok` still cover every error; see
+    /// [`ValidationReport::truncate_errors`](crate::ValidationReport::truncate_errors).
+    pub max_reported_errors: Option<usize>,
+    /// Keep at most this many errors per file in
+    /// `ValidationReport::validation_errors` (default: no limit), replacing
+    /// the rest of each file's errors by one
+    /// [`crate::rules::TOO_MANY_ERRORS`] entry. Counts and `ok` still
+    /// cover every error; see
+    /// [`ValidationReport::cap_errors_per_file`](crate::ValidationReport::cap_errors_per_file).
+    pub max_errors_per_file: Option<usize>,
+    /// Set the [`help`](crate::ValidationError::help) of every reported
+    /// validation error, so JSON reports say how to fix each one (default:
+    /// `true`). Turn off to keep large reports small.
+    pub include_help: bool,
+    /// Fail a run that scans no file at all (default: off). Each scan path
+    /// then gets an `EmptyScan` scan error saying why it contributed
+    /// nothing, and `ValidationReport::empty_scan` is set.
+    pub fail_on_empty_scan: bool,
+    /// Also scan JSON/YAML string values that hold a JSON or YAML document
+    /// of their own, such as a `ConfigMap` entry (default: off).
+    pub embedded_documents: Option<EmbeddedDocuments>,
+    /// In JSON/YAML documents declaring `"openapi": "3.*"`, scan only the
+    /// values of `x-gts-*` extension fields, skipping JSON Schema `$ref`s
+    /// and other strings (default: off).
+    pub scan_openapi: bool,
+    /// Check the rules specific to schema and instance documents (default:
+    /// off): a schema's `$id` must be a `gts://` URI
+    /// ([`crate::rules::SCHEMA_ID_NOT_URI`]) and an instance's `type` a bare
+    /// GTS type ID ([`crate::rules::INSTANCE_TYPE_NOT_BARE`]).
+    pub check_document_kinds: bool,
+    /// `(path glob, kind)` pairs fixing the [`DocumentKind`] of matching
+    /// JSON/YAML files instead of classifying them by their root keys. The
+    /// first glob matching the file path or name wins.
+    pub document_kinds: Vec<(String, DocumentKind)>,
+    /// The JSON Schema dialect every schema document must declare in its
+    /// root `$schema` (default: any), as a URI or the short name `07` or
+    /// `2020-12`. A trailing `#` is ignored. Schemas without `$schema` or
+    /// with another dialect are [`crate::rules::WRONG_SCHEMA_DIALECT`]
+    /// errors.
+    pub required_dialect: Option<String>,
+    /// A directory of GTS schemas to validate instance documents against
+    /// (default: none). Every schema in it is checked
+    /// ([`crate::rules::INVALID_SCHEMA`]), the directory must be a
+    /// self-contained bundle ([`crate::rules::BUNDLE_NOT_SELF_CONTAINED`]),
+    /// and every scanned instance must match the schema its `type` names
+    /// ([`crate::rules::SCHEMA_VIOLATION`]).
+    pub schemas_dir: Option<PathBuf>,
+    /// Glob patterns of file paths that must not appear in any output, e.g.
+    /// `data/tenant-*/**` (default: none). A pattern matches a path when it
+    /// matches the whole path or any trailing part of it. Matching paths
+    /// are replaced by `redacted_path_label` in every error, warning and
+    /// trace, including messages that quote them.
+    pub sensitive_paths: Vec<String>,
+    /// What redacted paths are replaced with (default: `<redacted>`).
+    pub redacted_path_label: String,
+    /// Validate an `x-gts-ref` string that lists several references on
+    /// separate lines (e.g. a YAML block scalar) or separated by commas
+    /// entry by entry, with a `RefListString` warning suggesting the array
+    /// form (default: on). When off, such a string is validated as one
+    /// reference.
+    pub split_ref_lists: bool,
+    /// Path globs of JSON/YAML object keys that are GTS patterns rather
+    /// than exact IDs, such as the keys of an event router's routing table
+    /// (default: `$.routes.*` and `$.subscriptions.*`). With `scan_keys`
+    /// on, matching keys may end in a `*` wildcard and are checked against
+    /// the pattern grammar; other keys must be exact IDs.
+    pub pattern_key_paths: Vec<String>,
+    /// Warn about files with more GTS identifiers (definitions plus
+    /// references) than this (default: no limit), with a
+    /// `TooManyIdentifiers` warning. Never an error.
+    pub max_ids_per_file: Option<usize>,
+    /// `(path glob, limit)` pairs replacing `max_ids_per_file` for matching
+    /// files, e.g. a higher limit or `None` to exempt generated catalogs.
+    /// The first glob matching the file path or name wins.
+    pub max_ids_overrides: Vec<(String, Option<usize>)>,
+}
+
+impl Default for ValidationConfig {
+    fn default() -> Self {
+        Self {
+            vendor_policy: VendorPolicy::default(),
+            vendor_scope: None,
+            scan_keys: false,
+            scan_null_ids: false,
+            check_duplicate_ids: false,
+            discovery_mode: DiscoveryMode::default(),
+            markdown_profile: MarkdownProfile::default(),
+            skip_tokens: Vec::new(),
+            rule_thresholds: HashMap::new(),
+            rule_severities: HashMap::new(),
+            pinned_settings: Vec::new(),
+            trace_discovery: false,
+            id_stats: None,
+            max_reported_errors: None,
+            max_errors_per_file: None,
+            include_help: true,
+            fail_on_empty_scan: false,
+            embedded_documents: None,
+            scan_openapi: false,
+            check_document_kinds: false,
+            document_kinds: Vec::new(),
+            required_dialect: None,
+            schemas_dir: None,
+            sensitive_paths: Vec::new(),
+            redacted_path_label: "<redacted>".to_owned(),
+            split_ref_lists: true,
+            pattern_key_paths: vec!["$.routes.*".to_owned(), "$.subscriptions.*".to_owned()],
+            max_ids_per_file: None,
+            max_ids_overrides: Vec::new(),
+        }
+    }
+}
+
+/// Which JSON/YAML string values are parsed and scanned as nested
+/// documents. Values whose text is not a JSON or YAML object or array are
+/// scanned as plain strings.
+///
+/// Errors inside a nested document are reported at a path that joins the
+/// outer and inner paths, e.g. `$.data.config.json -> $.type`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct EmbeddedDocuments {
+    /// Levels of nesting to follow (default: 2).
+    pub max_depth: usize,
+    /// Parse every string of at least this many bytes (default: 256).
+    pub min_size: usize,
+    /// Glob patterns over the path of a value within its document, without
+    /// the leading `$.` (e.g. `data.*.json`). Matching values are parsed
+    /// whatever their size.
+    pub keys: Vec<String>,
+}
+
+impl Default for EmbeddedDocuments {
+    fn default() -> Self {
+        Self {
+            max_depth: 2,
+            min_size: 256,
+            keys: Vec::new(),
+        }
+    }
+}
+
+/// A [`ValidationConfig`] prepared for scanning: built once per run and
+/// shared by every file, so no per-file scan rebuilds it.
+#[derive(Debug, Clone)]
+pub struct CompiledValidationConfig {
+    pub vendor_check: VendorCheck,
+    /// Whether markdown discovery uses the heuristic regex.
+    pub heuristic: bool,
+    pub markdown_profile: MarkdownProfile,
+    pub skip_tokens: SkipTokens,
+    pub json_options: JsonScanOptions,
+    pub id_limits: IdLimits,
+}
+
+impl CompiledValidationConfig {
+    /// # Errors
+    ///
+    /// Returns an error if an embedded document key pattern, a pattern key
+    /// path, a document kind path pattern or an identifier limit path
+    /// pattern is not a valid glob, or if the schemas directory does not
+    /// exist.
+    pub fn new(config: &ValidationConfig) -> anyhow::Result<Self> {
+        let embedded = match &config.embedded_documents {
+            Some(embedded) => {
+                Some(EmbeddedScan::new(embedded).map_err(invalid_setting("embedded_documents"))?)
+            }
+            None => None,
+        };
+        let schemas = match &config.schemas_dir {
+            Some(dir) => Some(Rc::new(
+                SchemaStore::load(dir).map_err(invalid_setting("schemas_dir"))?,
+            )),
+            None => None,
+        };
+        let pattern_keys = config
+            .pattern_key_paths
+            .iter()
+            .map(|glob| {
+                Pattern::new(glob).with_context(|| format!("Invalid pattern key path '{glob}'"))
+            })
+            .collect::<anyhow::Result<_>>()
+            .map_err(invalid_setting("pattern_key_paths"))?;
+        Ok(Self {
+            vendor_check: VendorCheck::new(config.vendor_policy.clone(), config.vendor_scope),
+            heuristic: config.discovery_mode == DiscoveryMode::Heuristic,
+            markdown_profile: config.markdown_profile,
+            skip_tokens: SkipTokens::new(&config.skip_tokens),
+            json_options: JsonScanOptions {
+                scan_keys: config.scan_keys,
+                scan_null_ids: config.scan_null_ids,
+                embedded,
+                openapi: if config.scan_openapi {
+                    OpenApiScanMode::Detect
+                } else {
+                    OpenApiScanMode::Off
+                },
+                document_kinds: DocumentKindOverrides::new(&config.document_kinds)
+                    .map_err(invalid_setting("document_kinds"))?,
+                check_document_kinds: config.check_document_kinds,
+                schemas,
+                split_ref_lists: config.split_ref_lists,
+                pattern_keys,
+                required_dialect: config.required_dialect.as_deref().map(normalize_dialect),
+            },
+            id_limits: IdLimits::new(config.max_ids_per_file, &config.max_ids_overrides)
+                .map_err(invalid_setting("max_ids_overrides"))?,
+        })
+    }
 }
 
 /// Filesystem-specific source options.
@@ -59,6 +369,7 @@ pub struct ValidationConfig {
 /// baked into the library — keeps `gts-validator` repo-layout-agnostic.
 #[derive(Debug, Clone)]
 #[non_exhaustive]
+#[allow(clippy::struct_excessive_bools)]
 pub struct FsSourceConfig {
     /// Paths to scan (files or directories). Required, must be non-empty.
     pub paths: Vec<PathBuf>,
@@ -81,6 +392,50 @@ pub struct FsSourceConfig {
     /// Maximum total bytes to read across all files (default: 512 MB).
     /// Prevents memory exhaustion when many large files are present.
     pub max_total_bytes: u64,
+    /// Report symlinks whose target does not exist as warnings instead of
+    /// silently skipping them (default: `false`).
+    ///
+    /// Warnings land in `ValidationReport::warnings` and do not affect `ok`.
+    pub warn_broken_symlinks: bool,
+    /// Also pick up files without a `.md`/`.json`/`.yaml`/`.yml` extension
+    /// during directory walks, detecting their format with
+    /// `ContentFormat::sniff` (default: `false`).
+    ///
+    /// Files passed explicitly in `paths` are always sniffed when their
+    /// extension is not recognized. Sniffed files that are binary, too large
+    /// or of no recognizable format are skipped, not failed.
+    pub sniff_content: bool,
+    /// Scan `.gts-spec/` directories instead of skipping them (default:
+    /// `false`), for repositories that keep their canonical GTS schemas
+    /// there. The other skipped directories (`target`, `.git`, ...) stay
+    /// skipped.
+    pub include_gts_spec: bool,
+    /// Directory names to skip during walks, in addition to the built-in
+    /// `target`, `node_modules`, `.git`, `vendor` and `.gts-spec`.
+    pub skip_dirs: Vec<String>,
+    /// Trusted registries: directories of canonical GTS schemas (default:
+    /// none). The identifiers their JSON/YAML files define are known to
+    /// the run, so `ValidationConfig::check_duplicate_ids` reports scanned
+    /// files that redefine them, but the files themselves are not
+    /// validated nor counted in `ValidationReport::scanned_files`.
+    pub registry_dirs: Vec<PathBuf>,
+    /// Also use every `.gts-spec` directory found under `paths` as a
+    /// registry (default: `true`), unless `include_gts_spec` scans them.
+    pub default_registry: bool,
+    /// Report directories the walk could not read (`PermissionDenied`) or
+    /// traverse (`WalkError`) as scan errors that fail the run, instead of
+    /// as warnings (default: `false`). Either way nothing under them is
+    /// scanned.
+    pub fail_on_walk_errors: bool,
+    /// Decode files starting with a UTF-16 byte order mark and scan them
+    /// (default: `false`). Otherwise they are skipped with a
+    /// `Utf16Encoding` warning.
+    pub transcode_utf16: bool,
+    /// Apply the `.gts-validator.toml` files found in scanned directories
+    /// to the files under them (default: `true`). Each can override the
+    /// vendor policy, skip tokens, discovery mode and rule severities of
+    /// its subtree, the nearest file winning.
+    pub directory_overrides: bool,
 }
 
 impl Default for FsSourceConfig {
@@ -93,6 +448,95 @@ impl Default for FsSourceConfig {
             max_depth: 64,
             max_files: 100_000,
             max_total_bytes: 536_870_912,
+            warn_broken_symlinks: false,
+            sniff_content: false,
+            include_gts_spec: false,
+            skip_dirs: Vec::new(),
+            registry_dirs: Vec::new(),
+            default_registry: true,
+            fail_on_walk_errors: false,
+            transcode_utf16: false,
+            directory_overrides: true,
         }
     }
 }
+
+impl FsSourceConfig {
+    /// Add a path to scan.
+    pub fn add_path(&mut self, path: impl Into<PathBuf>) -> &mut Self {
+        self.paths.push(path.into());
+        self
+    }
+
+    /// Add an exclude pattern (glob format).
+    pub fn add_exclude(&mut self, pattern: impl Into<String>) -> &mut Self {
+        self.exclude.push(pattern.into());
+        self
+    }
+
+    /// Add a directory name to skip during walks.
+    pub fn add_skip_dir(&mut self, name: impl Into<String>) -> &mut Self {
+        self.skip_dirs.push(name.into());
+        self
+    }
+
+    /// A copy of this config with `path` added, like [`Self::add_path`].
+    #[must_use]
+    pub fn with_path(&self, path: impl Into<PathBuf>) -> Self {
+        let mut config = self.clone();
+        config.add_path(path);
+        config
+    }
+
+    /// A copy of this config with `pattern` added, like [`Self::add_exclude`].
+    #[must_use]
+    pub fn with_exclude(&self, pattern: impl Into<String>) -> Self {
+        let mut config = self.clone();
+        config.add_exclude(pattern);
+        config
+    }
+
+    /// A copy of this config with `name` added, like [`Self::add_skip_dir`].
+    #[must_use]
+    pub fn with_skip_dir(&self, name: impl Into<String>) -> Self {
+        let mut config = self.clone();
+        config.add_skip_dir(name);
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fs_source_config_add_methods_chain() {
+        let mut config = FsSourceConfig::default();
+        config
+            .add_path("docs")
+            .add_path("modules")
+            .add_exclude("**/generated/**")
+            .add_skip_dir("dist");
+        assert_eq!(
+            config.paths,
+            [PathBuf::from("docs"), PathBuf::from("modules")]
+        );
+        assert_eq!(config.exclude, ["**/generated/**"]);
+        assert_eq!(config.skip_dirs, ["dist"]);
+    }
+
+    #[test]
+    fn test_fs_source_config_with_methods_leave_original() {
+        let base = FsSourceConfig::default().with_exclude("*.tmp");
+        let config = base
+            .with_path("docs")
+            .with_exclude("**/generated/**")
+            .with_skip_dir("dist");
+        assert!(base.paths.is_empty());
+        assert_eq!(base.exclude, ["*.tmp"]);
+        assert!(base.skip_dirs.is_empty());
+        assert_eq!(config.paths, [PathBuf::from("docs")]);
+        assert_eq!(config.exclude, ["*.tmp", "**/generated/**"]);
+        assert_eq!(config.skip_dirs, ["dist"]);
+    }
+}
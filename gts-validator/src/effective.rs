@@ -0,0 +1,313 @@
+//! The effective configuration of a run and where each setting came from.
+//!
+//! Settings are merged from several layers — the defaults, the
+//! `[package.metadata.gts]` table of a `Cargo.toml`, command-line flags and
+//! `--vendor auto` inference — so a run can be hard to explain from its
+//! inputs alone. [`EffectiveConfig`] records every setting of
+//! [`FsSourceConfig`] and [`ValidationConfig`] as a [`Sourced`] value: a
+//! resolution layer [records](EffectiveConfig::record) the configs after it
+//! applied its settings, and each setting that changed takes that layer as
+//! its source.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::config::{
+    DiscoveryMode, EmbeddedDocuments, FsSourceConfig, MarkdownProfile, RuleSeverity,
+    ValidationConfig, VendorPolicy, VendorScope,
+};
+use crate::metadata::VendorSource;
+use crate::stats::StatsSegment;
+
+/// Where the value of a setting came from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "detail", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum ConfigSource {
+    /// The library or command-line default.
+    Default,
+    /// The `[package.metadata.gts]` table of this `Cargo.toml`.
+    CargoMetadata(PathBuf),
+    /// This command-line flag or argument, e.g. `--vendor`.
+    CommandLine(String),
+    /// `--vendor auto`, which found the vendor here.
+    Inferred(VendorSource),
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Default => f.write_str("default"),
+            Self::CargoMetadata(path) => {
+                write!(f, "from [package.metadata.gts] in {}", path.display())
+            }
+            Self::CommandLine(flag) => write!(f, "from {flag}"),
+            Self::Inferred(source) => write!(f, "inferred from {source}"),
+        }
+    }
+}
+
+/// A value and where it came from, displayed as `value (source)`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Sourced<T> {
+    pub value: T,
+    pub source: ConfigSource,
+}
+
+impl<T> Sourced<T> {
+    #[must_use]
+    pub const fn new(value: T, source: ConfigSource) -> Self {
+        Self { value, source }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Sourced<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.value, self.source)
+    }
+}
+
+/// Every setting of a run's [`FsSourceConfig`] and [`ValidationConfig`],
+/// named after their fields, with its value as JSON and its source.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct EffectiveConfig {
+    /// The [`FsSourceConfig`] settings.
+    pub fs: BTreeMap<String, Sourced<Value>>,
+    /// The [`ValidationConfig`] settings.
+    pub validation: BTreeMap<String, Sourced<Value>>,
+}
+
+impl EffectiveConfig {
+    /// The settings of `fs_config` and `validation_config`, all with
+    /// [`ConfigSource::Default`].
+    #[must_use]
+    pub fn new(fs_config: &FsSourceConfig, validation_config: &ValidationConfig) -> Self {
+        let sourced = |settings: Vec<(&str, Value)>| {
+            settings
+                .into_iter()
+                .map(|(name, value)| (name.to_owned(), Sourced::new(value, ConfigSource::Default)))
+                .collect()
+        };
+        Self {
+            fs: sourced(fs_settings(fs_config)),
+            validation: sourced(validation_settings(validation_config)),
+        }
+    }
+
+    /// Record the configs as a resolution layer left them: every setting
+    /// whose value changed takes the new value, with `source(name)` as its
+    /// source.
+    pub fn record(
+        &mut self,
+        fs_config: &FsSourceConfig,
+        validation_config: &ValidationConfig,
+        source: impl Fn(&str) -> ConfigSource,
+    ) {
+        let layers = [
+            (&mut self.fs, fs_settings(fs_config)),
+            (&mut self.validation, validation_settings(validation_config)),
+        ];
+        for (recorded, settings) in layers {
+            for (name, value) in settings {
+                if recorded.get(name).is_none_or(|old| old.value != value) {
+                    recorded.insert(name.to_owned(), Sourced::new(value, source(name)));
+                }
+            }
+        }
+    }
+
+    /// The setting called `name`, from either config.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&Sourced<Value>> {
+        self.fs.get(name).or_else(|| self.validation.get(name))
+    }
+
+    /// The setting whose invalid value caused `error`, as `name = value
+    /// (source)`, when the error comes from checking a setting.
+    #[must_use]
+    pub fn explain(&self, error: &anyhow::Error) -> Option<String> {
+        let setting = error.downcast_ref::<InvalidSetting>()?.setting;
+        self.get(setting)
+            .map(|sourced| format!("{setting} = {sourced}"))
+    }
+}
+
+/// An error in the value of one setting, so [`EffectiveConfig::explain`]
+/// can say where it came from. Displays as the underlying error.
+#[derive(Debug)]
+pub struct InvalidSetting {
+    setting: &'static str,
+    error: anyhow::Error,
+}
+
+impl fmt::Display for InvalidSetting {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.error, f)
+    }
+}
+
+impl std::error::Error for InvalidSetting {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.error.source()
+    }
+}
+
+/// Tag an error as caused by the value of `setting`, for `map_err`.
+pub fn invalid_setting(setting: &'static str) -> impl FnOnce(anyhow::Error) -> anyhow::Error {
+    move |error| InvalidSetting { setting, error }.into()
+}
+
+fn fs_settings(config: &FsSourceConfig) -> Vec<(&'static str, Value)> {
+    vec![
+        ("paths", json!(config.paths)),
+        ("exclude", json!(config.exclude)),
+        ("max_file_size", json!(config.max_file_size)),
+        ("follow_links", json!(config.follow_links)),
+        ("max_depth", json!(config.max_depth)),
+        ("max_files", json!(config.max_files)),
+        ("max_total_bytes", json!(config.max_total_bytes)),
+        ("warn_broken_symlinks", json!(config.warn_broken_symlinks)),
+        ("sniff_content", json!(config.sniff_content)),
+        ("include_gts_spec", json!(config.include_gts_spec)),
+        ("skip_dirs", json!(config.skip_dirs)),
+        ("registry_dirs", json!(config.registry_dirs)),
+        ("default_registry", json!(config.default_registry)),
+        ("fail_on_walk_errors", json!(config.fail_on_walk_errors)),
+        ("transcode_utf16", json!(config.transcode_utf16)),
+        ("directory_overrides", json!(config.directory_overrides)),
+    ]
+}
+
+fn validation_settings(config: &ValidationConfig) -> Vec<(&'static str, Value)> {
+    let vendor_policy = match &config.vendor_policy {
+        VendorPolicy::Any => json!("any"),
+        VendorPolicy::MustMatch(vendor) => json!({ "must_match": vendor }),
+        VendorPolicy::AllowList(vendors) => json!({ "allow_list": vendors }),
+        VendorPolicy::Deny(vendors) => json!({ "deny": vendors }),
+    };
+    let vendor_scope = config.vendor_scope.map(|scope| match scope {
+        VendorScope::FirstSegment => "first",
+        VendorScope::AllSegments => "all",
+        VendorScope::LastSegment => "last",
+    });
+    let discovery_mode = match config.discovery_mode {
+        DiscoveryMode::StrictSpecOnly => "strict_spec_only",
+        DiscoveryMode::Heuristic => "heuristic",
+    };
+    let markdown_profile = match config.markdown_profile {
+        MarkdownProfile::Default => "default",
+        MarkdownProfile::CodeSpansOnly => "code_spans_only",
+        MarkdownProfile::RequireCodeSpans => "require_code_spans",
+    };
+    let id_stats = config.id_stats.map(|segment| match segment {
+        StatsSegment::LastSegment => "last",
+        StatsSegment::FirstSegment => "first",
+    });
+    let embedded_documents = config.embedded_documents.as_ref().map(
+        |EmbeddedDocuments {
+             max_depth,
+             min_size,
+             keys,
+         }| json!({ "max_depth": max_depth, "min_size": min_size, "keys": keys }),
+    );
+    let rule_thresholds: BTreeMap<&String, &usize> = config.rule_thresholds.iter().collect();
+    let rule_severities: BTreeMap<&String, &RuleSeverity> = config.rule_severities.iter().collect();
+    vec![
+        ("vendor_policy", vendor_policy),
+        ("vendor_scope", json!(vendor_scope)),
+        ("scan_keys", json!(config.scan_keys)),
+        ("scan_null_ids", json!(config.scan_null_ids)),
+        ("check_duplicate_ids", json!(config.check_duplicate_ids)),
+        ("discovery_mode", json!(discovery_mode)),
+        ("markdown_profile", json!(markdown_profile)),
+        ("skip_tokens", json!(config.skip_tokens)),
+        ("rule_thresholds", json!(rule_thresholds)),
+        ("rule_severities", json!(rule_severities)),
+        ("trace_discovery", json!(config.trace_discovery)),
+        ("id_stats", json!(id_stats)),
+        ("max_reported_errors", json!(config.max_reported_errors)),
+        ("max_errors_per_file", json!(config.max_errors_per_file)),
+        ("include_help", json!(config.include_help)),
+        ("fail_on_empty_scan", json!(config.fail_on_empty_scan)),
+        ("embedded_documents", json!(embedded_documents)),
+        ("scan_openapi", json!(config.scan_openapi)),
+        ("check_document_kinds", json!(config.check_document_kinds)),
+        ("required_dialect", json!(config.required_dialect)),
+        ("document_kinds", json!(config.document_kinds)),
+        ("schemas_dir", json!(config.schemas_dir)),
+        ("sensitive_paths", json!(config.sensitive_paths)),
+        ("redacted_path_label", json!(config.redacted_path_label)),
+        ("split_ref_lists", json!(config.split_ref_lists)),
+        ("pattern_key_paths", json!(config.pattern_key_paths)),
+        ("max_ids_per_file", json!(config.max_ids_per_file)),
+        ("max_ids_overrides", json!(config.max_ids_overrides)),
+    ]
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_attributes_changed_settings() {
+        let mut fs_config = FsSourceConfig::default();
+        let mut validation_config = ValidationConfig::default();
+        let mut effective = EffectiveConfig::new(&fs_config, &validation_config);
+
+        let manifest = ConfigSource::CargoMetadata(PathBuf::from("Cargo.toml"));
+        validation_config.vendor_policy = VendorPolicy::MustMatch("acme".to_owned());
+        validation_config.scan_keys = true;
+        effective.record(&fs_config, &validation_config, |_| manifest.clone());
+
+        fs_config.exclude.push("*/gen/*".to_owned());
+        validation_config.vendor_policy = VendorPolicy::MustMatch("x".to_owned());
+        effective.record(&fs_config, &validation_config, |name| {
+            ConfigSource::CommandLine(format!("--{name}"))
+        });
+
+        let flag = |name: &str| ConfigSource::CommandLine(name.to_owned());
+        assert_eq!(
+            effective.get("vendor_policy").unwrap().source,
+            flag("--vendor_policy")
+        );
+        assert_eq!(
+            effective.get("vendor_policy").unwrap().value,
+            json!({"must_match": "x"})
+        );
+        assert_eq!(effective.get("exclude").unwrap().source, flag("--exclude"));
+        assert_eq!(effective.get("scan_keys").unwrap().source, manifest);
+        assert_eq!(
+            effective.get("max_depth").unwrap().source,
+            ConfigSource::Default
+        );
+        assert_eq!(
+            effective.get("scan_keys").unwrap().to_string(),
+            "true (from [package.metadata.gts] in Cargo.toml)"
+        );
+    }
+
+    #[test]
+    fn test_explain_names_invalid_setting() {
+        let mut validation_config = ValidationConfig::default();
+        let mut effective = EffectiveConfig::new(&FsSourceConfig::default(), &validation_config);
+        validation_config.sensitive_paths = vec!["[".to_owned()];
+        effective.record(&FsSourceConfig::default(), &validation_config, |_| {
+            ConfigSource::CommandLine("--redact-path".to_owned())
+        });
+
+        let error = invalid_setting("sensitive_paths")(anyhow::anyhow!("Invalid pattern '['"));
+        assert_eq!(error.to_string(), "Invalid pattern '['");
+        assert_eq!(
+            effective.explain(&error).unwrap(),
+            "sensitive_paths = [\"[\"] (from --redact-path)"
+        );
+        assert!(effective.explain(&anyhow::anyhow!("other")).is_none());
+    }
+}
@@ -1,11 +1,17 @@
 //! Error types for GTS validation.
 
-use std::path::PathBuf;
+use std::fmt;
+use std::path::{Path, PathBuf};
 
-use serde::Serialize;
+use serde::de::IgnoredAny;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::fix::TextEdit;
+use crate::format::document::DocumentKind;
+use crate::rules::{self, Remediation};
 
 /// The kind of scan-level failure that prevented a file from being validated.
-#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum ScanErrorKind {
     /// An I/O error occurred while reading the file.
@@ -22,10 +28,60 @@ pub enum ScanErrorKind {
     OutsideRepository,
     /// A resource limit (`max_files` or `max_total_bytes`) was reached, truncating the scan.
     LimitExceeded,
-    /// A directory traversal error (permission denied, loop detected, etc.).
+    /// A directory traversal error other than `PermissionDenied`, e.g. a
+    /// symlink loop. Reported as a warning unless
+    /// `FsSourceConfig::fail_on_walk_errors` is set.
     WalkError,
     /// An exclude glob pattern could not be parsed.
     InvalidExcludePattern,
+    /// A symlink whose target does not exist (reported as a warning when
+    /// `FsSourceConfig::warn_broken_symlinks` is set).
+    BrokenSymlink,
+    /// A file that failed to parse but contains template directives, so it
+    /// was scanned line by line instead (reported as a warning).
+    ParseFallback,
+    /// A scan path that contributed no scannable file (reported when
+    /// `ValidationConfig::fail_on_empty_scan` is set and nothing was scanned).
+    EmptyScan,
+    /// A file whose path is not valid Unicode, skipped so that it is never
+    /// reported under a mangled name (reported as a warning).
+    NonUnicodePath,
+    /// An `x-gts-ref` string listing several references on separate lines or
+    /// separated by commas, validated entry by entry (reported as a warning
+    /// suggesting the array form).
+    RefListString,
+    /// A YAML scalar under a key holding GTS identifiers (`$id`, `$ref`,
+    /// `$schema`, `type`, `x-gts-ref`) that resolved to null, a boolean or
+    /// a number, e.g. an unquoted `~` or `1.0`, and so was not validated.
+    /// Reported as a warning suggesting quotes.
+    SuspiciousScalarType,
+    /// A file with more GTS identifiers than
+    /// `ValidationConfig::max_ids_per_file` (or its path override) allows,
+    /// often a copy-paste accident. Reported as a warning.
+    TooManyIdentifiers,
+    /// A GTS identifier in markdown prose outside an inline code span, when
+    /// `ValidationConfig::markdown_profile` is `RequireCodeSpans`. Reported
+    /// as a warning; the identifier is not validated.
+    BareIdentifier,
+    /// A validation error of a rule whose severity is
+    /// `RuleSeverity::Warning` for its file, reported as a warning.
+    DowngradedError,
+    /// A directory that could not be read for lack of permission, so its
+    /// subtree was not scanned. Reported as a warning unless
+    /// `FsSourceConfig::fail_on_walk_errors` is set.
+    PermissionDenied,
+    /// A file with a NUL byte near its start, e.g. an image with a `.json`
+    /// extension, skipped as binary (reported as a warning).
+    BinaryContent,
+    /// A file starting with a UTF-16 byte order mark, skipped unless
+    /// `FsSourceConfig::transcode_utf16` is set (reported as a warning).
+    Utf16Encoding,
+}
+
+/// Serialize a path as a string, replacing sequences that are not valid
+/// Unicode with U+FFFD rather than failing the whole report.
+fn serialize_path<S: Serializer>(path: &Path, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&path.to_string_lossy())
 }
 
 /// A scan-level error: a file that could not be validated at all.
@@ -33,15 +89,22 @@ pub enum ScanErrorKind {
 /// These are distinct from `ValidationError` (which represents a GTS ID that
 /// was found and failed validation). A `ScanError` means the file could not
 /// even be read or parsed — CI must treat these as failures.
-#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[non_exhaustive]
 pub struct ScanError {
-    /// The file path that could not be scanned.
+    /// The file path that could not be scanned. Serialized as a string with
+    /// any sequence that is not valid Unicode replaced by U+FFFD; only
+    /// `NonUnicodePath` warnings carry such paths.
+    #[serde(serialize_with = "serialize_path")]
     pub file: PathBuf,
     /// The kind of failure.
     pub kind: ScanErrorKind,
     /// Human-readable description of the failure.
     pub message: String,
+    /// The real file path when `file` was redacted by
+    /// `ValidationConfig::sensitive_paths`; never serialized.
+    #[serde(skip)]
+    pub original_file: Option<PathBuf>,
 }
 
 impl ScanError {
@@ -50,13 +113,25 @@ impl ScanError {
     pub fn format_human_readable(&self) -> String {
         format!("{}: [scan error] {}", self.file.display(), self.message)
     }
+
+    /// Format the error for human-readable output when reported as a warning.
+    #[must_use]
+    pub fn format_human_readable_warning(&self) -> String {
+        format!("{}: [warning] {}", self.file.display(), self.message)
+    }
 }
 
 /// A single validation error found in a documentation/config file.
+///
+/// Deserialization tolerates reports from older versions: missing fields
+/// take their defaults, and a missing or unknown `rule_id` is derived from
+/// the error message.
 #[derive(Debug, Clone, Serialize, PartialEq, Eq)]
 #[non_exhaustive]
 pub struct ValidationError {
-    /// File path where the error was found
+    /// File path where the error was found; always valid Unicode, so it
+    /// serializes losslessly
+    #[serde(serialize_with = "serialize_path")]
     pub file: PathBuf,
     /// Line number (1-indexed) — for .md files; 0 for structured files
     pub line: usize,
@@ -68,23 +143,105 @@ pub struct ValidationError {
     pub raw_value: String,
     /// The normalized GTS identifier (after stripping gts://, etc.)
     pub normalized_id: String,
+    /// ID of the violated rule (see [`crate::rules`])
+    pub rule_id: &'static str,
     /// Human-readable error description
     pub error: String,
     /// Surrounding context (for .md: the line content; for .json/.yaml: the parent key)
     pub context: String,
+    /// Enclosing markdown heading path (e.g. "Events > Audit") — for .md
+    /// errors below a heading; `None` otherwise
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub section: Option<String>,
+    /// Kind of the JSON/YAML document the error was found in; `None` for
+    /// other files
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub document_kind: Option<DocumentKind>,
+    /// Edit replacing the malformed identifier with a well-formed one, when
+    /// one is close enough to suggest and the scanner knows where the
+    /// identifier is (markdown and templated files)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<TextEdit>,
+    /// How to fix errors of this rule (see [`rules::remediation`]); set on
+    /// the errors of a report with `ValidationConfig::include_help`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub help: Option<&'static Remediation>,
+    /// The real file path when `file` was redacted by
+    /// `ValidationConfig::sensitive_paths`; never serialized
+    #[serde(skip)]
+    pub original_file: Option<PathBuf>,
+}
+
+/// Owned-string form of [`ValidationError`] used for deserialization, since
+/// `rule_id` must resolve to one of the static IDs in [`crate::rules`].
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct RawValidationError {
+    file: PathBuf,
+    line: usize,
+    column: usize,
+    json_path: String,
+    raw_value: String,
+    normalized_id: String,
+    rule_id: String,
+    error: String,
+    context: String,
+    section: Option<String>,
+    document_kind: Option<DocumentKind>,
+    fix: Option<TextEdit>,
+    help: Option<IgnoredAny>,
+}
+
+impl From<RawValidationError> for ValidationError {
+    fn from(raw: RawValidationError) -> Self {
+        let rule_id =
+            rules::lookup(&raw.rule_id).unwrap_or_else(|| rules::rule_for_message(&raw.error));
+        Self {
+            file: raw.file,
+            line: raw.line,
+            column: raw.column,
+            json_path: raw.json_path,
+            raw_value: raw.raw_value,
+            normalized_id: raw.normalized_id,
+            rule_id,
+            error: raw.error,
+            context: raw.context,
+            section: raw.section,
+            document_kind: raw.document_kind,
+            fix: raw.fix,
+            help: raw.help.and(rules::remediation(rule_id)),
+            original_file: None,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ValidationError {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        RawValidationError::deserialize(deserializer).map(Self::from)
+    }
 }
 
 impl ValidationError {
     /// Format the error for human-readable output.
     ///
-    /// For markdown errors: `{file}:{line}:{column}: {error} [{raw_value}]`
+    /// For markdown errors: `{file}:{line}:{column}: {error} [{raw_value}]`,
+    /// with `({section})` after the position when the error is below a heading
     /// For JSON/YAML errors: `{file}: {error} [{raw_value}] (at {json_path})`
+    /// For the [`rules::TOO_MANY_ERRORS`] entry of a capped file:
+    /// `{file}: [suppressed] {error}`
     #[must_use]
     pub fn format_human_readable(&self) -> String {
-        if self.line > 0 && self.column > 0 {
+        if self.rule_id == rules::TOO_MANY_ERRORS {
+            format!("{}: [suppressed] {}", self.file.display(), self.error)
+        } else if self.line > 0 && self.column > 0 {
             // Markdown error with line/column
+            let section = self
+                .section
+                .as_ref()
+                .map(|section| format!(" ({section})"))
+                .unwrap_or_default();
             format!(
-                "{}:{}:{}: {} [{}]",
+                "{}:{}:{}:{section} {} [{}]",
                 self.file.display(),
                 self.line,
                 self.column,
@@ -112,7 +269,34 @@ impl ValidationError {
     }
 }
 
+/// A coverage requirement of [`ValidationReport::verify_coverage`] that a
+/// run did not meet.
+///
+/// [`ValidationReport::verify_coverage`]: crate::ValidationReport::verify_coverage
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CoverageError {
+    /// Fewer files were scanned than required.
+    TooFewFilesScanned { found: usize, expected: usize },
+    /// No file of a required format was scanned.
+    MissingFormat { format: String },
+}
+
+impl fmt::Display for CoverageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooFewFilesScanned { found, expected } => {
+                write!(f, "Scanned {found} file(s), expected at least {expected}")
+            }
+            Self::MissingFormat { format } => write!(f, "No {format} file was scanned"),
+        }
+    }
+}
+
+impl std::error::Error for CoverageError {}
+
 #[cfg(test)]
+#[allow(clippy::unwrap_used)]
 mod tests {
     use super::*;
     use std::path::PathBuf;
@@ -126,8 +310,14 @@ mod tests {
             json_path: String::new(),
             raw_value: "gts.invalid".to_owned(),
             normalized_id: "gts.invalid".to_owned(),
+            rule_id: crate::rules::INVALID_ID,
             error: "Invalid GTS ID".to_owned(),
             context: "Some context".to_owned(),
+            section: None,
+            document_kind: None,
+            fix: None,
+            help: None,
+            original_file: None,
         };
 
         let formatted = err.format_human_readable();
@@ -137,6 +327,35 @@ mod tests {
         assert!(!formatted.contains("(at"));
     }
 
+    #[test]
+    fn test_format_markdown_error_with_section() {
+        let err = ValidationError {
+            file: PathBuf::from("docs/test.md"),
+            line: 42,
+            column: 10,
+            json_path: String::new(),
+            raw_value: "gts.invalid".to_owned(),
+            normalized_id: "gts.invalid".to_owned(),
+            rule_id: crate::rules::INVALID_ID,
+            error: "Invalid GTS ID".to_owned(),
+            context: "Some context".to_owned(),
+            section: Some("Events > Audit".to_owned()),
+            document_kind: None,
+            fix: None,
+            help: None,
+            original_file: None,
+        };
+
+        assert_eq!(
+            err.format_human_readable(),
+            "docs/test.md:42:10: (Events > Audit) Invalid GTS ID [gts.invalid]"
+        );
+        let json = serde_json::to_value(&err).unwrap();
+        assert_eq!(json["section"], "Events > Audit");
+        let parsed: ValidationError = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, err);
+    }
+
     #[test]
     fn test_format_json_error() {
         let err = ValidationError {
@@ -146,8 +365,14 @@ mod tests {
             json_path: "$.properties.type.x-gts-ref".to_owned(),
             raw_value: "gts.invalid".to_owned(),
             normalized_id: "gts.invalid".to_owned(),
+            rule_id: crate::rules::INVALID_ID,
             error: "Invalid GTS ID".to_owned(),
             context: "x-gts-ref".to_owned(),
+            section: None,
+            document_kind: None,
+            fix: None,
+            help: None,
+            original_file: None,
         };
 
         let formatted = err.format_human_readable();
@@ -0,0 +1,210 @@
+//! Machine-applicable fixes for malformed GTS identifiers.
+//!
+//! Scanners that know where an identifier sits in its file attach a
+//! [`TextEdit`] to the error when [`suggest_fix`](crate::suggest_fix) finds
+//! a well-formed replacement. Editors and `gts-validator --fix` apply the
+//! same edits with [`apply_edits`], so both always agree; the CLI writes them
+//! with [`write_edits`].
+
+use std::path::Path;
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+/// A replacement of the text between two positions of a file.
+///
+/// Lines are 1-indexed; columns are 1-indexed byte offsets within the line,
+/// like [`ValidationError::column`](crate::ValidationError::column), so
+/// edits stay exact on lines with multi-byte characters.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct TextEdit {
+    /// Line of the first replaced byte
+    pub start_line: usize,
+    /// Column of the first replaced byte
+    pub start_col: usize,
+    /// Line of the end position
+    pub end_line: usize,
+    /// Column just past the last replaced byte
+    pub end_col: usize,
+    /// Text that replaces the range
+    pub replacement: String,
+}
+
+impl TextEdit {
+    /// An edit replacing `start..end`, each a `(line, column)` pair.
+    #[must_use]
+    pub fn new(start: (usize, usize), end: (usize, usize), replacement: impl Into<String>) -> Self {
+        Self {
+            start_line: start.0,
+            start_col: start.1,
+            end_line: end.0,
+            end_col: end.1,
+            replacement: replacement.into(),
+        }
+    }
+}
+
+/// Apply `edits` to `content`. Edits may come in any order but must not
+/// overlap.
+///
+/// # Errors
+///
+/// Returns an error if an edit lies outside `content`, splits a character,
+/// ends before it starts, or overlaps another edit.
+pub fn apply_edits(content: &str, edits: &[TextEdit]) -> anyhow::Result<String> {
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+        .collect();
+    let offset = |line: usize, col: usize| -> anyhow::Result<usize> {
+        let start = line
+            .checked_sub(1)
+            .and_then(|index| line_starts.get(index))
+            .with_context(|| format!("Line {line} is out of range"))?;
+        let line_end = content[*start..]
+            .find('\n')
+            .map_or(content.len(), |i| start + i);
+        let offset = col
+            .checked_sub(1)
+            .map(|col| start + col)
+            .filter(|&offset| offset <= line_end && content.is_char_boundary(offset))
+            .with_context(|| {
+                format!("Column {col} of line {line} is past its end or splits a character")
+            })?;
+        Ok(offset)
+    };
+
+    let mut ranges = edits
+        .iter()
+        .map(|edit| {
+            let start = offset(edit.start_line, edit.start_col)?;
+            let end = offset(edit.end_line, edit.end_col)?;
+            anyhow::ensure!(
+                start <= end,
+                "Edit ends before it starts at {}:{}",
+                edit.start_line,
+                edit.start_col
+            );
+            Ok((start, end, edit))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    ranges.sort_by_key(|&(start, end, _)| (start, end));
+
+    let mut fixed = String::with_capacity(content.len());
+    let mut copied = 0;
+    for (start, end, edit) in ranges {
+        anyhow::ensure!(
+            start >= copied,
+            "Edit at {}:{} overlaps another edit",
+            edit.start_line,
+            edit.start_col
+        );
+        fixed.push_str(&content[copied..start]);
+        fixed.push_str(&edit.replacement);
+        copied = end;
+    }
+    fixed.push_str(&content[copied..]);
+    Ok(fixed)
+}
+
+/// Apply `edits` to the file at `path` and replace it atomically: the result
+/// is written to a temporary file next to it, which is then renamed over
+/// it, so readers never see a partly written file. A leading byte order
+/// mark is kept and not counted in line 1's columns, as when scanning.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or replaced, or if an edit
+/// does not apply (see [`apply_edits`]).
+pub fn write_edits(path: &Path, edits: &[TextEdit]) -> anyhow::Result<()> {
+    const BOM: &str = "\u{feff}";
+    let content =
+        std::fs::read_to_string(path).with_context(|| format!("Cannot read {}", path.display()))?;
+    let (bom, text) = match content.strip_prefix(BOM) {
+        Some(text) => (BOM, text),
+        None => ("", content.as_str()),
+    };
+    let edited =
+        apply_edits(text, edits).with_context(|| format!("Cannot edit {}", path.display()))?;
+
+    let file_name = path
+        .file_name()
+        .with_context(|| format!("Not a file: {}", path.display()))?;
+    let mut temp_name = std::ffi::OsString::from(".");
+    temp_name.push(file_name);
+    temp_name.push(".gts-tmp");
+    let temp = path.with_file_name(temp_name);
+    let written = std::fs::write(&temp, format!("{bom}{edited}"))
+        .and_then(|()| std::fs::set_permissions(&temp, std::fs::metadata(path)?.permissions()))
+        .and_then(|()| std::fs::rename(&temp, path));
+    if let Err(e) = written {
+        let _ = std::fs::remove_file(&temp);
+        return Err(e).with_context(|| format!("Cannot write {}", path.display()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_edits_multibyte_lines() {
+        let content = "\u{e9} `gts.x.a-b` \u{fc} `gts.x.c-d`\nnext";
+        let edits = [
+            TextEdit::new((1, 20), (1, 29), "x.c_d"),
+            TextEdit::new((1, 5), (1, 14), "x.a_b"),
+        ];
+        assert_eq!(
+            apply_edits(content, &edits).unwrap(),
+            "\u{e9} `x.a_b` \u{fc} `x.c_d`\nnext"
+        );
+        assert_eq!(
+            apply_edits(content, &[TextEdit::new((2, 1), (2, 5), "last")]).unwrap(),
+            "\u{e9} `gts.x.a-b` \u{fc} `gts.x.c-d`\nlast"
+        );
+    }
+
+    #[test]
+    fn test_apply_edits_rejects_bad_ranges() {
+        let content = "\u{e9} abc\n";
+        for (edits, message) in [
+            (
+                vec![TextEdit::new((1, 2), (1, 3), "")],
+                "splits a character",
+            ),
+            (vec![TextEdit::new((3, 1), (3, 1), "")], "out of range"),
+            (
+                vec![TextEdit::new((1, 6), (1, 4), "")],
+                "ends before it starts",
+            ),
+            (
+                vec![
+                    TextEdit::new((1, 4), (1, 6), ""),
+                    TextEdit::new((1, 5), (1, 7), ""),
+                ],
+                "overlaps",
+            ),
+        ] {
+            let err = apply_edits(content, &edits).unwrap_err().to_string();
+            assert!(err.contains(message), "{err}");
+        }
+    }
+
+    #[test]
+    fn test_write_edits_keeps_bom() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("doc.md");
+        std::fs::write(&path, "\u{feff}`gts.x.a-b`\n").unwrap();
+        write_edits(&path, &[TextEdit::new((1, 2), (1, 11), "gts.x.a_b")]).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "\u{feff}`gts.x.a_b`\n"
+        );
+        assert_eq!(std::fs::read_dir(tmp.path()).unwrap().count(), 1);
+
+        let err = write_edits(&path, &[TextEdit::new((9, 1), (9, 1), "")]).unwrap_err();
+        assert!(format!("{err:#}").contains("out of range"), "{err:#}");
+    }
+}
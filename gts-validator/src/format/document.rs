@@ -0,0 +1,389 @@
+//! Document-kind classification for JSON/YAML documents.
+//!
+//! A JSON Schema, a GTS instance and any other (config) document hold GTS
+//! identifiers at different positions, so some rules apply to one kind
+//! only: a schema's `$id` must use the `gts://` URI form, and an instance's
+//! `type` must be a bare GTS type ID. A schema can also be required to
+//! declare one `$schema` dialect.
+
+use std::fmt;
+use std::path::Path;
+
+use anyhow::Context;
+use glob::Pattern;
+use gts::{GTS_ID_PREFIX, GTS_ID_URI_PREFIX, SchemaDraft};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::ValidationError;
+use crate::rules;
+use crate::strategy::fs::glob_matches;
+
+/// What a JSON/YAML document is, as far as kind-specific rules go.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum DocumentKind {
+    /// A JSON Schema: the root has `$schema`, or both `$id` and
+    /// `properties`.
+    Schema,
+    /// A GTS instance: the root has an `id` and a GTS-valued `type`.
+    Instance,
+    /// Any other document.
+    Config,
+}
+
+impl fmt::Display for DocumentKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Schema => "schema",
+            Self::Instance => "instance",
+            Self::Config => "config",
+        })
+    }
+}
+
+impl DocumentKind {
+    /// Classify a parsed document by its root keys.
+    #[must_use]
+    pub fn classify(document: &Value) -> Self {
+        let Value::Object(root) = document else {
+            return Self::Config;
+        };
+        if root.contains_key("$schema")
+            || (root.contains_key("$id") && root.contains_key("properties"))
+        {
+            Self::Schema
+        } else if root.get("id").is_some_and(Value::is_string)
+            && root
+                .get("type")
+                .and_then(Value::as_str)
+                .is_some_and(is_gts_valued)
+        {
+            Self::Instance
+        } else {
+            Self::Config
+        }
+    }
+}
+
+/// Whether `value` is a GTS identifier, bare or in `gts://` URI form.
+fn is_gts_valued(value: &str) -> bool {
+    value
+        .strip_prefix(GTS_ID_URI_PREFIX)
+        .unwrap_or(value)
+        .starts_with(GTS_ID_PREFIX)
+}
+
+/// [`ValidationConfig::document_kinds`](crate::ValidationConfig::document_kinds)
+/// with its path globs compiled.
+#[derive(Debug, Clone, Default)]
+pub struct DocumentKindOverrides(Vec<(Pattern, DocumentKind)>);
+
+impl DocumentKindOverrides {
+    /// # Errors
+    ///
+    /// Returns an error if a path pattern is not a valid glob.
+    pub fn new(overrides: &[(String, DocumentKind)]) -> anyhow::Result<Self> {
+        overrides
+            .iter()
+            .map(|(glob, kind)| {
+                let pattern = Pattern::new(glob)
+                    .with_context(|| format!("Invalid document kind path pattern '{glob}'"))?;
+                Ok((pattern, *kind))
+            })
+            .collect::<anyhow::Result<_>>()
+            .map(Self)
+    }
+
+    /// The kind of `document` in the file at `path`: the first override
+    /// whose glob matches the path or file name, else its classification.
+    pub fn kind_of(&self, path: &Path, document: &Value) -> DocumentKind {
+        self.0
+            .iter()
+            .find(|(pattern, _)| {
+                glob_matches(pattern, path.as_os_str())
+                    || path
+                        .file_name()
+                        .is_some_and(|name| glob_matches(pattern, name))
+            })
+            .map_or_else(|| DocumentKind::classify(document), |&(_, kind)| kind)
+    }
+}
+
+/// Check the rules that apply only to documents of `kind`.
+pub fn check_document_rules(
+    kind: DocumentKind,
+    document: &Value,
+    path: &Path,
+    errors: &mut Vec<ValidationError>,
+) {
+    let error = |key: &str, value: &str, rule_id, error| ValidationError {
+        file: path.to_owned(),
+        line: 0,
+        column: 0,
+        json_path: format!("$.{key}"),
+        raw_value: value.to_owned(),
+        normalized_id: value
+            .strip_prefix(GTS_ID_URI_PREFIX)
+            .unwrap_or(value)
+            .to_owned(),
+        rule_id,
+        error,
+        context: key.to_owned(),
+        section: None,
+        document_kind: Some(kind),
+        fix: None,
+        help: None,
+        original_file: None,
+    };
+    match kind {
+        DocumentKind::Schema => {
+            if let Some(id) = document.get("$id").and_then(Value::as_str)
+                && id.starts_with(GTS_ID_PREFIX)
+            {
+                errors.push(error(
+                    "$id",
+                    id,
+                    rules::SCHEMA_ID_NOT_URI,
+                    format!("Schema $id must use the {GTS_ID_URI_PREFIX} URI form: '{id}'"),
+                ));
+            }
+        }
+        DocumentKind::Instance => {
+            if let Some(type_id) = document.get("type").and_then(Value::as_str) {
+                let message = if type_id.starts_with(GTS_ID_URI_PREFIX) {
+                    Some(format!(
+                        "Instance type must be a bare GTS type ID, not a URI: '{type_id}'"
+                    ))
+                } else if !type_id.ends_with('~') {
+                    Some(format!(
+                        "Instance type must be a GTS type ID ending with '~': '{type_id}'"
+                    ))
+                } else {
+                    None
+                };
+                if let Some(message) = message {
+                    errors.push(error(
+                        "type",
+                        type_id,
+                        rules::INSTANCE_TYPE_NOT_BARE,
+                        message,
+                    ));
+                }
+            }
+        }
+        DocumentKind::Config => {}
+    }
+}
+
+/// `dialect` in the form `$schema` values are compared in: without a
+/// trailing empty fragment (`…/schema#` is `…/schema`), and with the short
+/// names `07` and `2020-12` expanded to their URIs.
+#[must_use]
+pub fn normalize_dialect(dialect: &str) -> String {
+    let dialect = dialect.trim();
+    SchemaDraft::from_name(dialect)
+        .map_or(dialect, |draft| draft.uri())
+        .trim_end_matches('#')
+        .to_owned()
+}
+
+/// Check that a schema `document` declares the `required` dialect (already
+/// [normalized](normalize_dialect)) in its root `$schema`.
+pub fn check_schema_dialect(
+    document: &Value,
+    path: &Path,
+    required: &str,
+    errors: &mut Vec<ValidationError>,
+) {
+    let found = match document.get("$schema") {
+        None => None,
+        Some(Value::String(dialect)) => Some(dialect.clone()),
+        Some(other) => Some(other.to_string()),
+    };
+    let normalized = found.as_deref().map(normalize_dialect);
+    if normalized.as_deref() == Some(required) {
+        return;
+    }
+    let error = match &found {
+        None => format!("Schema has no $schema; the required dialect is '{required}'"),
+        Some(found) => {
+            format!("Schema $schema '{found}' is not the required dialect '{required}'")
+        }
+    };
+    errors.push(ValidationError {
+        file: path.to_owned(),
+        line: 0,
+        column: 0,
+        json_path: "$.$schema".to_owned(),
+        raw_value: found.unwrap_or_default(),
+        normalized_id: normalized.unwrap_or_default(),
+        rule_id: rules::WRONG_SCHEMA_DIALECT,
+        error,
+        context: "$schema".to_owned(),
+        section: None,
+        document_kind: Some(DocumentKind::Schema),
+        fix: None,
+        help: None,
+        original_file: None,
+    });
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_classify() {
+        let schema = json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "$id": "gts://gts.x.core.events.type.v1~",
+        });
+        assert_eq!(DocumentKind::classify(&schema), DocumentKind::Schema);
+        let schema = json!({"$id": "gts.x.core.events.type.v1~", "properties": {}});
+        assert_eq!(DocumentKind::classify(&schema), DocumentKind::Schema);
+
+        let instance = json!({
+            "id": "gts.x.core.events.type.v1~x.core._.created.v1",
+            "type": "gts.x.core.events.type.v1~",
+        });
+        assert_eq!(DocumentKind::classify(&instance), DocumentKind::Instance);
+
+        for config in [
+            json!({"$id": "gts.x.core.events.type.v1~"}),
+            json!({"id": "a", "type": "object"}),
+            json!({"type": "gts.x.core.events.type.v1~"}),
+            json!(["gts.x.core.events.type.v1~"]),
+        ] {
+            assert_eq!(DocumentKind::classify(&config), DocumentKind::Config);
+        }
+    }
+
+    #[test]
+    fn test_overrides_match_path_or_file_name() {
+        let overrides = DocumentKindOverrides::new(&[
+            ("fixtures/*".to_owned(), DocumentKind::Config),
+            ("*.instance.json".to_owned(), DocumentKind::Instance),
+        ])
+        .unwrap();
+        let schema = json!({"$schema": "x"});
+        assert_eq!(
+            overrides.kind_of(Path::new("fixtures/a.json"), &schema),
+            DocumentKind::Config
+        );
+        assert_eq!(
+            overrides.kind_of(Path::new("data/a.instance.json"), &schema),
+            DocumentKind::Instance
+        );
+        assert_eq!(
+            overrides.kind_of(Path::new("schemas/a.json"), &schema),
+            DocumentKind::Schema
+        );
+
+        let err = DocumentKindOverrides::new(&[("[".to_owned(), DocumentKind::Schema)]);
+        assert!(
+            err.unwrap_err()
+                .to_string()
+                .contains("Invalid document kind path pattern '['")
+        );
+    }
+
+    #[test]
+    fn test_document_rules() {
+        let check = |kind, document: Value| {
+            let mut errors = Vec::new();
+            check_document_rules(kind, &document, Path::new("a.json"), &mut errors);
+            errors
+                .iter()
+                .map(|e| (e.rule_id, e.json_path.clone()))
+                .collect::<Vec<_>>()
+        };
+        let bare_id = json!({"$id": "gts.x.core.events.type.v1~"});
+        assert_eq!(
+            check(DocumentKind::Schema, bare_id.clone()),
+            [(rules::SCHEMA_ID_NOT_URI, "$.$id".to_owned())]
+        );
+        assert!(check(DocumentKind::Instance, bare_id.clone()).is_empty());
+        assert!(check(DocumentKind::Config, bare_id).is_empty());
+        assert!(
+            check(
+                DocumentKind::Schema,
+                json!({"$id": "gts://gts.x.core.events.type.v1~"})
+            )
+            .is_empty()
+        );
+
+        for type_id in [
+            "gts://gts.x.core.events.type.v1~",
+            "gts.x.core.events.type.v1",
+        ] {
+            let instance = json!({"id": "a", "type": type_id});
+            assert_eq!(
+                check(DocumentKind::Instance, instance.clone()),
+                [(rules::INSTANCE_TYPE_NOT_BARE, "$.type".to_owned())]
+            );
+            assert!(check(DocumentKind::Schema, instance.clone()).is_empty());
+            assert!(check(DocumentKind::Config, instance).is_empty());
+        }
+        assert!(
+            check(
+                DocumentKind::Instance,
+                json!({"id": "a", "type": "gts.x.core.events.type.v1~"})
+            )
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_normalize_dialect() {
+        let draft_2020_12 = "https://json-schema.org/draft/2020-12/schema";
+        for alias in [
+            "2020-12",
+            draft_2020_12,
+            "https://json-schema.org/draft/2020-12/schema#",
+        ] {
+            assert_eq!(normalize_dialect(alias), draft_2020_12);
+        }
+        assert_eq!(
+            normalize_dialect("07"),
+            "http://json-schema.org/draft-07/schema"
+        );
+    }
+
+    #[test]
+    fn test_schema_dialect() {
+        let required = normalize_dialect("2020-12");
+        let check = |document: Value| {
+            let mut errors = Vec::new();
+            check_schema_dialect(&document, Path::new("a.json"), &required, &mut errors);
+            errors
+                .iter()
+                .map(|e| (e.raw_value.clone(), e.error.clone()))
+                .collect::<Vec<_>>()
+        };
+        assert!(
+            check(json!({"$schema": "https://json-schema.org/draft/2020-12/schema#"})).is_empty()
+        );
+        assert_eq!(
+            check(json!({"$id": "gts://gts.x.core.events.type.v1~", "properties": {}})),
+            [(
+                String::new(),
+                "Schema has no $schema; the required dialect is \
+                 'https://json-schema.org/draft/2020-12/schema'"
+                    .to_owned()
+            )]
+        );
+        assert_eq!(
+            check(json!({"$schema": "http://json-schema.org/draft-07/schema#"})),
+            [(
+                "http://json-schema.org/draft-07/schema#".to_owned(),
+                "Schema $schema 'http://json-schema.org/draft-07/schema#' is not the \
+                 required dialect 'https://json-schema.org/draft/2020-12/schema'"
+                    .to_owned()
+            )]
+        );
+    }
+}
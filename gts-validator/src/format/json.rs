@@ -3,15 +3,131 @@
 //! Uses tree-walking to scan string values (not keys by default).
 
 use std::path::Path;
+use std::rc::Rc;
 
+use anyhow::Context;
+use glob::Pattern;
 use gts::{GTS_ID_PREFIX, GTS_ID_URI_PREFIX};
 use serde_json::Value;
 
+use crate::DocumentKind;
+use crate::config::EmbeddedDocuments;
 use crate::error::{ScanError, ScanErrorKind, ValidationError};
+use crate::format::document::{DocumentKindOverrides, check_document_rules, check_schema_dialect};
+use crate::format::yaml;
 use crate::normalize::normalize_candidate;
-use crate::validator::validate_candidate;
+use crate::outcome::IdKind;
+use crate::rules;
+use crate::schemas::SchemaStore;
+use crate::trace::{CandidateOutcome, DiscoverySource, SkipReason, TraceSink, TracedCandidate};
+use crate::validator::{id_kind, validate_candidate, validate_candidate_as};
+use crate::vendor::VendorCheck;
 
-/// Scan JSON content for GTS identifiers.
+/// Options for walking a parsed JSON or YAML document.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+#[allow(clippy::struct_excessive_bools)]
+pub struct JsonScanOptions {
+    /// Scan object keys for GTS identifiers (default: off).
+    pub scan_keys: bool,
+    /// Report `null` values at `$id`, `$ref` and `x-gts-ref` keys as
+    /// [`rules::NULL_ID`] errors (default: off).
+    pub scan_null_ids: bool,
+    /// Scan string values holding a nested JSON or YAML document (default:
+    /// off).
+    pub embedded: Option<EmbeddedScan>,
+    /// How `OpenAPI` documents are scanned (default: like any other document).
+    pub openapi: OpenApiScanMode,
+    /// Path globs that fix the [`DocumentKind`](crate::DocumentKind) of
+    /// matching files instead of classifying their documents.
+    pub document_kinds: DocumentKindOverrides,
+    /// Check the rules specific to each
+    /// [`DocumentKind`](crate::DocumentKind) (default: off).
+    pub check_document_kinds: bool,
+    /// Schemas to validate instance documents against (default: none).
+    pub schemas: Option<Rc<SchemaStore>>,
+    /// Validate an `x-gts-ref` string listing several references on
+    /// separate lines or separated by commas entry by entry (default: off).
+    pub split_ref_lists: bool,
+    /// Path globs of object keys validated as GTS patterns, which may end
+    /// in a `*` wildcard, rather than as exact IDs (default: none), e.g.
+    /// `$.routes.*`. Only applies when `scan_keys` is on.
+    pub pattern_keys: Vec<Pattern>,
+    /// The `$schema` dialect every schema document must declare, already
+    /// normalized (default: any).
+    pub required_dialect: Option<String>,
+}
+
+/// How documents declaring an `OpenAPI` version are scanned.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OpenApiScanMode {
+    /// Scan every string value, like any other document.
+    #[default]
+    Off,
+    /// In documents whose root has `"openapi": "3.*"`, scan only the values
+    /// of `x-gts-*` extension fields, so JSON Schema `$ref`s and other
+    /// strings are never taken for GTS IDs.
+    Detect,
+}
+
+/// [`EmbeddedDocuments`] with its key patterns compiled.
+#[derive(Debug, Clone)]
+pub struct EmbeddedScan {
+    /// Levels of nesting still to follow.
+    pub depth: usize,
+    pub min_size: usize,
+    pub keys: Vec<Pattern>,
+}
+
+impl EmbeddedScan {
+    /// # Errors
+    ///
+    /// Returns an error if a key pattern is not a valid glob.
+    pub fn new(config: &EmbeddedDocuments) -> anyhow::Result<Self> {
+        let keys = config
+            .keys
+            .iter()
+            .map(|key| {
+                Pattern::new(key)
+                    .with_context(|| format!("Invalid embedded document key pattern '{key}'"))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self {
+            depth: config.max_depth,
+            min_size: config.min_size,
+            keys,
+        })
+    }
+
+    /// The document held by the string `value` at `json_path`, if it is
+    /// selected for scanning and parses as a JSON or YAML object or array.
+    fn document(&self, json_path: &str, value: &str) -> Option<Value> {
+        if self.depth == 0 {
+            return None;
+        }
+        // Keys are matched within the innermost document.
+        let local_path = json_path.rsplit(" -> ").next().unwrap_or(json_path);
+        let local_path = local_path.strip_prefix('$').unwrap_or(local_path);
+        let local_path = local_path.strip_prefix('.').unwrap_or(local_path);
+        let selected =
+            value.len() >= self.min_size || self.keys.iter().any(|key| key.matches(local_path));
+        if !selected || looks_like_gts_candidate(value) {
+            return None;
+        }
+        let document = if value.trim_start().starts_with(['{', '[']) {
+            serde_json::from_str(value).ok()
+        } else {
+            None
+        };
+        let document = document.or_else(|| {
+            serde_saphyr::from_str_with_options::<Value>(value, yaml::parse_options()).ok()
+        })?;
+        matches!(document, Value::Object(_) | Value::Array(_)).then_some(document)
+    }
+}
+
+/// Scan JSON content for GTS identifiers. Warnings about the document, such
+/// as `x-gts-ref` lists held in one string, are pushed to `warnings`.
 ///
 /// # Errors
 ///
@@ -20,36 +136,322 @@ use crate::validator::validate_candidate;
 pub fn scan_json_content(
     content: &str,
     path: &Path,
-    vendor: Option<&str>,
-    scan_keys: bool,
+    vendor_check: &VendorCheck,
+    options: &JsonScanOptions,
+    warnings: &mut Vec<ScanError>,
+    trace: &mut dyn TraceSink,
 ) -> Result<Vec<ValidationError>, ScanError> {
     let value: Value = serde_json::from_str(content).map_err(|e| ScanError {
         file: path.to_owned(),
         kind: ScanErrorKind::JsonParseError,
         message: format!("JSON parse error: {e}"),
+        original_file: None,
     })?;
 
     let mut errors = Vec::new();
-    walk_json_value(&value, path, vendor, &mut errors, "$", scan_keys);
+    walk_document(
+        &value,
+        path,
+        vendor_check,
+        &mut errors,
+        warnings,
+        options,
+        trace,
+    );
     Ok(errors)
 }
 
+/// Walk a whole parsed JSON or YAML document, switching to
+/// [`OpenApiScanMode`] scanning for `OpenAPI` 3.x documents when enabled.
+///
+/// Every error found is tagged with the document's
+/// [`DocumentKind`](crate::DocumentKind), whose specific rules are checked
+/// when enabled. With [`JsonScanOptions::split_ref_lists`], each
+/// `x-gts-ref` string listing several references gets a `RefListString`
+/// warning suggesting the array form.
+pub fn walk_document(
+    value: &Value,
+    path: &Path,
+    vendor_check: &VendorCheck,
+    errors: &mut Vec<ValidationError>,
+    warnings: &mut Vec<ScanError>,
+    options: &JsonScanOptions,
+    trace: &mut dyn TraceSink,
+) {
+    if options.split_ref_lists {
+        ref_list_warnings(value, path, "$", warnings);
+    }
+    let start = errors.len();
+    if options.openapi == OpenApiScanMode::Detect && is_openapi_3(value) {
+        walk_openapi_extensions(value, path, vendor_check, errors, "$", options, trace);
+    } else {
+        walk_json_value(value, path, vendor_check, errors, "$", options, trace);
+    }
+    let kind = options.document_kinds.kind_of(path, value);
+    if options.check_document_kinds {
+        check_document_rules(kind, value, path, errors);
+    }
+    if kind == DocumentKind::Schema
+        && let Some(dialect) = &options.required_dialect
+    {
+        check_schema_dialect(value, path, dialect, errors);
+    }
+    if kind == DocumentKind::Instance
+        && let Some(schemas) = &options.schemas
+    {
+        schemas.check_instance(value, path, errors);
+    }
+    for error in &mut errors[start..] {
+        error.document_kind = Some(kind);
+    }
+}
+
+/// Push a `RefListString` warning for every `x-gts-ref` string under
+/// `value` that lists more than one reference.
+fn ref_list_warnings(value: &Value, path: &Path, json_path: &str, warnings: &mut Vec<ScanError>) {
+    match value {
+        Value::Object(map) => {
+            for (key, val) in map {
+                let child_path = format!("{json_path}.{key}");
+                match val {
+                    Value::String(s) if key == "x-gts-ref" => {
+                        let Some(entries) = ref_list_entries(s).filter(|e| e.len() > 1) else {
+                            continue;
+                        };
+                        warnings.push(ScanError {
+                            file: path.to_owned(),
+                            kind: ScanErrorKind::RefListString,
+                            message: format!(
+                                "{child_path} lists {} references in one string; \
+                                 use an array (x-gts-ref: [{}])",
+                                entries.len(),
+                                entries.join(", ")
+                            ),
+                            original_file: None,
+                        });
+                    }
+                    _ => ref_list_warnings(val, path, &child_path, warnings),
+                }
+            }
+        }
+        Value::Array(arr) => {
+            for (i, val) in arr.iter().enumerate() {
+                ref_list_warnings(val, path, &format!("{json_path}[{i}]"), warnings);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The entries of an `x-gts-ref` string that holds references on separate
+/// lines (e.g. a YAML block scalar) or separated by commas; `None` for a
+/// string holding one reference.
+fn ref_list_entries(value: &str) -> Option<Vec<&str>> {
+    value.contains([',', '\n']).then(|| {
+        value
+            .split([',', '\n'])
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .collect()
+    })
+}
+
+/// Whether `json_path` is an `x-gts-ref` value or an element of an
+/// `x-gts-ref` array.
+fn is_x_gts_ref_path(json_path: &str) -> bool {
+    let key_path = match json_path
+        .strip_suffix(']')
+        .and_then(|path| path.rsplit_once('['))
+    {
+        Some((parent, index)) if index.bytes().all(|b| b.is_ascii_digit()) => parent,
+        _ => json_path,
+    };
+    key_path.ends_with(".x-gts-ref")
+}
+
+/// Whether the document root declares an `OpenAPI` 3.x version.
+fn is_openapi_3(value: &Value) -> bool {
+    value
+        .get("openapi")
+        .and_then(Value::as_str)
+        .is_some_and(|version| version.starts_with("3."))
+}
+
+/// Walk an `OpenAPI` document, validating only the values of `x-gts-*`
+/// extension fields. Object keys are never scanned.
+fn walk_openapi_extensions(
+    value: &Value,
+    path: &Path,
+    vendor_check: &VendorCheck,
+    errors: &mut Vec<ValidationError>,
+    json_path: &str,
+    options: &JsonScanOptions,
+    trace: &mut dyn TraceSink,
+) {
+    match value {
+        Value::Object(map) => {
+            for (key, val) in map {
+                let child_path = format!("{json_path}.{key}");
+                if key.starts_with("x-gts-") {
+                    walk_json_value(val, path, vendor_check, errors, &child_path, options, trace);
+                } else {
+                    walk_openapi_extensions(
+                        val,
+                        path,
+                        vendor_check,
+                        errors,
+                        &child_path,
+                        options,
+                        trace,
+                    );
+                }
+            }
+        }
+        Value::Array(arr) => {
+            for (i, val) in arr.iter().enumerate() {
+                walk_openapi_extensions(
+                    val,
+                    path,
+                    vendor_check,
+                    errors,
+                    &format!("{json_path}[{i}]"),
+                    options,
+                    trace,
+                );
+            }
+        }
+        Value::String(s) if trace.is_enabled() && looks_like_gts_candidate(s) => {
+            trace.record(TracedCandidate {
+                candidate: s.clone(),
+                source: DiscoverySource::Value,
+                line: 0,
+                column: 0,
+                json_path: json_path.to_owned(),
+                outcome: CandidateOutcome::Skipped {
+                    reason: SkipReason::OpenapiNonExtension,
+                },
+            });
+        }
+        _ => {}
+    }
+}
+
 /// Scan a JSON file for GTS identifiers (file-based convenience wrapper for tests).
 #[cfg(test)]
 pub fn scan_json_file(
     path: &Path,
-    vendor: Option<&str>,
+    vendor_check: &VendorCheck,
     max_file_size: u64,
     scan_keys: bool,
 ) -> Result<Vec<ValidationError>, ScanError> {
     use crate::strategy::fs::{ScanResult, read_file_bounded};
 
-    let content = match read_file_bounded(path, max_file_size) {
+    let content = match read_file_bounded(path, max_file_size, false) {
         ScanResult::Ok(c) => c,
         ScanResult::Err(e) => return Err(e),
     };
 
-    scan_json_content(&content, path, vendor, scan_keys)
+    let options = JsonScanOptions {
+        scan_keys,
+        ..JsonScanOptions::default()
+    };
+    scan_json_content(
+        &content,
+        path,
+        vendor_check,
+        &options,
+        &mut Vec::new(),
+        &mut (),
+    )
+}
+
+/// Validate an object key that looks like a GTS identifier.
+/// Whether `key` of the object at `json_path` is under one of the
+/// `pattern_keys` globs, matched within the innermost document.
+fn is_pattern_key(pattern_keys: &[Pattern], json_path: &str, key: &str) -> bool {
+    if pattern_keys.is_empty() {
+        return false;
+    }
+    let local_path = json_path.rsplit(" -> ").next().unwrap_or(json_path);
+    let key_path = format!("{local_path}.{key}");
+    pattern_keys.iter().any(|glob| glob.matches(&key_path))
+}
+
+/// Validate the object key `key` as a GTS pattern when `pattern` is set
+/// (see [`JsonScanOptions::pattern_keys`]), or as an exact ID. Errors say
+/// which validation was applied.
+fn scan_json_key(
+    key: &str,
+    pattern: bool,
+    path: &Path,
+    vendor_check: &VendorCheck,
+    errors: &mut Vec<ValidationError>,
+    json_path: &str,
+    trace: &mut dyn TraceSink,
+) {
+    let key_path = format!("{json_path}.{key}");
+    let traced = |outcome| TracedCandidate {
+        candidate: key.to_owned(),
+        source: DiscoverySource::Key,
+        line: 0,
+        column: 0,
+        json_path: key_path.clone(),
+        outcome,
+    };
+    let validation = if pattern {
+        "pattern key validation"
+    } else {
+        "exact ID key validation"
+    };
+    match normalize_candidate(key) {
+        Ok(candidate) => {
+            let validation_errors = if pattern {
+                validate_candidate_as(&candidate, IdKind::Pattern, vendor_check, true)
+            } else {
+                validate_candidate(&candidate, vendor_check, false)
+            };
+            trace.record(traced(CandidateOutcome::from_errors(&validation_errors)));
+            for err in validation_errors {
+                errors.push(ValidationError {
+                    file: path.to_owned(),
+                    line: 0,
+                    column: 0,
+                    json_path: key_path.clone(),
+                    raw_value: candidate.original.clone(),
+                    normalized_id: candidate.gts_id.clone(),
+                    rule_id: rules::rule_for_message(&err),
+                    error: format!("{err} ({validation})"),
+                    context: format!("key: {key}"),
+                    section: None,
+                    document_kind: None,
+                    fix: None,
+                    help: None,
+                    original_file: None,
+                });
+            }
+        }
+        Err(e) => {
+            trace.record(traced(CandidateOutcome::Invalid {
+                errors: vec![e.clone()],
+            }));
+            errors.push(ValidationError {
+                file: path.to_owned(),
+                line: 0,
+                column: 0,
+                json_path: key_path.clone(),
+                raw_value: key.to_owned(),
+                normalized_id: String::new(),
+                rule_id: rules::INVALID_ID,
+                error: format!("{e} ({validation})"),
+                context: format!("key: {key}"),
+                section: None,
+                document_kind: None,
+                fix: None,
+                help: None,
+                original_file: None,
+            });
+        }
+    }
 }
 
 /// Walk a JSON value tree and validate GTS identifiers in string values.
@@ -60,15 +462,49 @@ pub fn scan_json_file(
 pub fn walk_json_value(
     value: &Value,
     path: &Path,
-    vendor: Option<&str>,
+    vendor_check: &VendorCheck,
     errors: &mut Vec<ValidationError>,
     json_path: &str,
-    scan_keys: bool,
+    options: &JsonScanOptions,
+    trace: &mut dyn TraceSink,
 ) {
+    let traced = |candidate: &str, source, json_path: String, outcome| TracedCandidate {
+        candidate: candidate.to_owned(),
+        source,
+        line: 0,
+        column: 0,
+        json_path,
+        outcome,
+    };
+
     match value {
         Value::String(s) => {
+            if walk_embedded_document(s, path, vendor_check, errors, json_path, options, trace) {
+                return;
+            }
+
+            // A list of references in one string is validated entry by
+            // entry, each at the path it would have in the array form.
+            if options.split_ref_lists
+                && json_path.ends_with(".x-gts-ref")
+                && let Some(entries) = ref_list_entries(s)
+            {
+                for (i, entry) in entries.into_iter().enumerate() {
+                    walk_json_value(
+                        &Value::String(entry.to_owned()),
+                        path,
+                        vendor_check,
+                        errors,
+                        &format!("{json_path}[{i}]"),
+                        options,
+                        trace,
+                    );
+                }
+                return;
+            }
+
             let candidate_str = s.as_str();
-            let is_xgts_ref = json_path.ends_with(".x-gts-ref");
+            let is_xgts_ref = is_x_gts_ref_path(json_path);
 
             // PRE-FILTER: x-gts-ref special values that are NOT GTS identifiers.
             // These must be checked BEFORE normalization to avoid misleading errors.
@@ -78,9 +514,32 @@ pub fn walk_json_value(
             //   - Bare wildcard (*)
             //   - Relative JSON pointer (/$id, /properties/id, etc.)
             if is_xgts_ref && (candidate_str.starts_with('/') || candidate_str == "*") {
+                trace.record(traced(
+                    candidate_str,
+                    DiscoverySource::Value,
+                    json_path.to_owned(),
+                    CandidateOutcome::Skipped {
+                        reason: SkipReason::XGtsRefPointer,
+                    },
+                ));
                 return; // valid x-gts-ref value, not a GTS ID to validate
             }
 
+            // x-gts-generic-field names a property, it is never a GTS ID.
+            if json_path.ends_with(".x-gts-generic-field") {
+                if looks_like_gts_candidate(candidate_str) {
+                    trace.record(traced(
+                        candidate_str,
+                        DiscoverySource::Value,
+                        json_path.to_owned(),
+                        CandidateOutcome::Skipped {
+                            reason: SkipReason::GenericField,
+                        },
+                    ));
+                }
+                return;
+            }
+
             // Only consider strings that look like GTS identifiers
             // Skip filenames that contain GTS IDs (e.g., "gts.x.core.type.v1~.schema.json")
             // A string is likely a filename if it contains a tilde followed by a dot and extension
@@ -92,6 +551,16 @@ pub fn walk_json_value(
 
             // For plain gts. strings, skip if it looks like a filename (e.g., "gts.x.type.v1~.schema.json")
             if looks_like_filename {
+                if looks_like_gts_candidate(candidate_str) {
+                    trace.record(traced(
+                        candidate_str,
+                        DiscoverySource::Value,
+                        json_path.to_owned(),
+                        CandidateOutcome::Skipped {
+                            reason: SkipReason::Filename,
+                        },
+                    ));
+                }
                 return;
             }
 
@@ -99,8 +568,18 @@ pub fn walk_json_value(
                 match normalize_candidate(candidate_str) {
                     Ok(candidate) => {
                         let allow_wildcards = is_xgts_ref;
-                        let validation_errors =
-                            validate_candidate(&candidate, vendor, allow_wildcards);
+                        let validation_errors = validate_candidate_as(
+                            &candidate,
+                            id_kind(&candidate.gts_id, json_path),
+                            vendor_check,
+                            allow_wildcards,
+                        );
+                        trace.record(traced(
+                            candidate_str,
+                            DiscoverySource::Value,
+                            json_path.to_owned(),
+                            CandidateOutcome::from_errors(&validation_errors),
+                        ));
                         for err in validation_errors {
                             errors.push(ValidationError {
                                 file: path.to_owned(),
@@ -109,12 +588,26 @@ pub fn walk_json_value(
                                 json_path: json_path.to_owned(),
                                 raw_value: candidate.original.clone(),
                                 normalized_id: candidate.gts_id.clone(),
+                                rule_id: rules::rule_for_message(&err),
                                 error: err,
                                 context: json_path.to_owned(),
+                                section: None,
+                                document_kind: None,
+                                fix: None,
+                                help: None,
+                                original_file: None,
                             });
                         }
                     }
                     Err(e) => {
+                        trace.record(traced(
+                            candidate_str,
+                            DiscoverySource::Value,
+                            json_path.to_owned(),
+                            CandidateOutcome::Invalid {
+                                errors: vec![e.clone()],
+                            },
+                        ));
                         errors.push(ValidationError {
                             file: path.to_owned(),
                             line: 0,
@@ -122,8 +615,14 @@ pub fn walk_json_value(
                             json_path: json_path.to_owned(),
                             raw_value: candidate_str.to_owned(),
                             normalized_id: String::new(),
+                            rule_id: rules::INVALID_ID,
                             error: e,
                             context: json_path.to_owned(),
+                            section: None,
+                            document_kind: None,
+                            fix: None,
+                            help: None,
+                            original_file: None,
                         });
                     }
                 }
@@ -132,44 +631,18 @@ pub fn walk_json_value(
         Value::Object(map) => {
             for (key, val) in map {
                 // Optionally scan keys
-                if scan_keys && looks_like_gts_candidate(key) {
-                    match normalize_candidate(key) {
-                        Ok(candidate) => {
-                            let validation_errors = validate_candidate(&candidate, vendor, false);
-                            for err in validation_errors {
-                                errors.push(ValidationError {
-                                    file: path.to_owned(),
-                                    line: 0,
-                                    column: 0,
-                                    json_path: format!("{json_path}.{key}"),
-                                    raw_value: candidate.original.clone(),
-                                    normalized_id: candidate.gts_id.clone(),
-                                    error: err,
-                                    context: format!("key: {key}"),
-                                });
-                            }
-                        }
-                        Err(e) => {
-                            errors.push(ValidationError {
-                                file: path.to_owned(),
-                                line: 0,
-                                column: 0,
-                                json_path: format!("{json_path}.{key}"),
-                                raw_value: key.clone(),
-                                normalized_id: String::new(),
-                                error: e,
-                                context: format!("key: {key}"),
-                            });
-                        }
-                    }
+                if options.scan_keys && looks_like_gts_candidate(key) {
+                    let pattern = is_pattern_key(&options.pattern_keys, json_path, key);
+                    scan_json_key(key, pattern, path, vendor_check, errors, json_path, trace);
                 }
                 walk_json_value(
                     val,
                     path,
-                    vendor,
+                    vendor_check,
                     errors,
                     &format!("{json_path}.{key}"),
-                    scan_keys,
+                    options,
+                    trace,
                 );
             }
         }
@@ -178,17 +651,79 @@ pub fn walk_json_value(
                 walk_json_value(
                     val,
                     path,
-                    vendor,
+                    vendor_check,
                     errors,
                     &format!("{json_path}[{i}]"),
-                    scan_keys,
+                    options,
+                    trace,
                 );
             }
         }
+        Value::Null if options.scan_null_ids && is_id_key(json_path) => {
+            errors.push(ValidationError {
+                file: path.to_owned(),
+                line: 0,
+                column: 0,
+                json_path: json_path.to_owned(),
+                raw_value: "null".to_owned(),
+                normalized_id: String::new(),
+                rule_id: rules::NULL_ID,
+                error: format!("Null value at {json_path}: expected a GTS identifier"),
+                context: json_path.to_owned(),
+                section: None,
+                document_kind: None,
+                fix: None,
+                help: None,
+                original_file: None,
+            });
+        }
         _ => {}
     }
 }
 
+/// Walk the document held by the string `value`, if embedded documents are
+/// scanned and it holds one. Returns whether it did.
+fn walk_embedded_document(
+    value: &str,
+    path: &Path,
+    vendor_check: &VendorCheck,
+    errors: &mut Vec<ValidationError>,
+    json_path: &str,
+    options: &JsonScanOptions,
+    trace: &mut dyn TraceSink,
+) -> bool {
+    let Some(embedded) = &options.embedded else {
+        return false;
+    };
+    let Some(document) = embedded.document(json_path, value) else {
+        return false;
+    };
+    let nested = JsonScanOptions {
+        embedded: Some(EmbeddedScan {
+            depth: embedded.depth - 1,
+            ..embedded.clone()
+        }),
+        ..options.clone()
+    };
+    walk_json_value(
+        &document,
+        path,
+        vendor_check,
+        errors,
+        &format!("{json_path} -> $"),
+        &nested,
+        trace,
+    );
+    true
+}
+
+/// Whether `json_path` ends at a key that holds a GTS identifier.
+fn is_id_key(json_path: &str) -> bool {
+    [".$id", ".$ref", ".x-gts-ref"]
+        .iter()
+        .any(|key| json_path.ends_with(key))
+}
+
 /// Returns `true` if a string looks like a GTS identifier worth normalizing,
 /// either as a bare id (`<prefix>...`) or wrapped in the `gts://` URI scheme
 /// (`gts://<prefix>...`). Honors the configured [`GTS_ID_PREFIX`].
@@ -201,9 +736,14 @@ fn looks_like_gts_candidate(s: &str) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::VendorPolicy;
     use std::io::Write;
     use tempfile::NamedTempFile;
 
+    fn must(vendor: &str) -> VendorCheck {
+        VendorPolicy::MustMatch(vendor.to_owned()).into()
+    }
+
     fn create_temp_json(content: &str) -> NamedTempFile {
         let mut file = NamedTempFile::new().unwrap();
         file.write_all(content.as_bytes()).unwrap();
@@ -214,7 +754,8 @@ mod tests {
     fn test_scan_json_valid_id() {
         let content = r#"{"$id": "gts://gts.x.core.events.type.v1~"}"#;
         let file = create_temp_json(content);
-        let errors = scan_json_file(file.path(), None, 10_485_760, false).unwrap();
+        let errors =
+            scan_json_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, false).unwrap();
         assert!(errors.is_empty(), "Unexpected errors: {errors:?}");
     }
 
@@ -222,15 +763,173 @@ mod tests {
     fn test_scan_json_invalid_id() {
         let content = r#"{"$id": "gts.invalid"}"#;
         let file = create_temp_json(content);
-        let errors = scan_json_file(file.path(), None, 10_485_760, false).unwrap();
+        let errors =
+            scan_json_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, false).unwrap();
         assert!(!errors.is_empty());
     }
 
+    #[test]
+    fn test_scan_json_grammar_follows_key() {
+        let content = r#"{
+            "$id": "gts://gts.x.core.events.type.v1~x.core.events.order.v1",
+            "id": "gts.x.core.events.type.v1~x.core.events.order.v1",
+            "event": "gts.x.core.events.type.v1~order.created#2024",
+            "x-gts-ref": "gts.x.core.events.*"
+        }"#;
+        let errors = scan_json_content(
+            content,
+            Path::new("test.json"),
+            &VendorPolicy::Any.into(),
+            &JsonScanOptions::default(),
+            &mut Vec::new(),
+            &mut (),
+        )
+        .unwrap();
+        let found: Vec<(&str, &str)> = errors
+            .iter()
+            .map(|e| (e.json_path.as_str(), e.error.as_str()))
+            .collect();
+        assert_eq!(found.len(), 2, "{found:?}");
+        assert_eq!(found[0].0, "$.$id");
+        assert!(found[0].1.contains("must end with '~'"), "{found:?}");
+        assert_eq!(found[1].0, "$.event");
+        assert!(
+            found[1].1.contains("InstanceSegmentInvalidChar"),
+            "{found:?}"
+        );
+    }
+
+    #[test]
+    fn test_scan_json_root_array_valid() {
+        let content = r#"["gts.x.core.events.type.v1~", "gts.x.core.audit.event.v1~"]"#;
+        let errors = scan_json_content(
+            content,
+            Path::new("test.json"),
+            &must("x"),
+            &JsonScanOptions::default(),
+            &mut Vec::new(),
+            &mut (),
+        )
+        .unwrap();
+        assert!(errors.is_empty(), "Unexpected errors: {errors:?}");
+    }
+
+    #[test]
+    fn test_scan_json_root_array_reports_indexed_paths() {
+        let content = r#"[
+            "gts.x.core.events.type.v1~",
+            "gts.y.core.events.type.v1~",
+            "not an id",
+            "gts.x.core.events.type~",
+            {"$id": "gts://gts.y.core.audit.event.v1~"}
+        ]"#;
+        let errors = scan_json_content(
+            content,
+            Path::new("test.json"),
+            &must("x"),
+            &JsonScanOptions::default(),
+            &mut Vec::new(),
+            &mut (),
+        )
+        .unwrap();
+
+        let paths: Vec<&str> = errors.iter().map(|e| e.json_path.as_str()).collect();
+        assert_eq!(paths, ["$[1]", "$[3]", "$[4].$id"], "{errors:?}");
+        assert!(errors[0].error.contains("Vendor mismatch"), "{errors:?}");
+        assert_eq!(errors[1].rule_id, rules::INVALID_ID);
+    }
+
+    #[test]
+    fn test_scan_json_null_ids() {
+        let content = r#"{
+            "$id": null,
+            "properties": {
+                "type": { "x-gts-ref": null, "$ref": null },
+                "description": null
+            }
+        }"#;
+        let scan = |scan_null_ids| {
+            let options = JsonScanOptions {
+                scan_null_ids,
+                ..JsonScanOptions::default()
+            };
+            scan_json_content(
+                content,
+                Path::new("test.json"),
+                &must("x"),
+                &options,
+                &mut Vec::new(),
+                &mut (),
+            )
+            .unwrap()
+        };
+
+        assert!(scan(false).is_empty());
+
+        let errors = scan(true);
+        let mut paths: Vec<&str> = errors.iter().map(|e| e.json_path.as_str()).collect();
+        paths.sort_unstable();
+        assert_eq!(
+            paths,
+            [
+                "$.$id",
+                "$.properties.type.$ref",
+                "$.properties.type.x-gts-ref"
+            ],
+            "{errors:?}"
+        );
+        assert!(errors.iter().all(|e| e.rule_id == rules::NULL_ID));
+        assert!(errors.iter().all(|e| e.raw_value == "null"));
+    }
+
+    #[test]
+    fn test_scan_json_trace_records_exclusions() {
+        let content = r#"{
+            "$id": "gts://gts.x.core.events.type.v1~",
+            "file": "gts.x.core.events.type.v1~.schema.json",
+            "x-gts-ref": "/$id"
+        }"#;
+        let mut trace = Vec::new();
+        scan_json_content(
+            content,
+            Path::new("test.json"),
+            &VendorPolicy::Any.into(),
+            &JsonScanOptions::default(),
+            &mut Vec::new(),
+            &mut trace,
+        )
+        .unwrap();
+
+        let outcomes: Vec<_> = trace
+            .iter()
+            .map(|c| (c.json_path.as_str(), &c.outcome))
+            .collect();
+        assert_eq!(
+            outcomes,
+            [
+                ("$.$id", &CandidateOutcome::Valid),
+                (
+                    "$.file",
+                    &CandidateOutcome::Skipped {
+                        reason: SkipReason::Filename
+                    }
+                ),
+                (
+                    "$.x-gts-ref",
+                    &CandidateOutcome::Skipped {
+                        reason: SkipReason::XGtsRefPointer
+                    }
+                ),
+            ]
+        );
+    }
+
     #[test]
     fn test_scan_json_xgts_ref_wildcard() {
         let content = r#"{"x-gts-ref": "gts.x.core.*"}"#;
         let file = create_temp_json(content);
-        let errors = scan_json_file(file.path(), None, 10_485_760, false).unwrap();
+        let errors =
+            scan_json_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, false).unwrap();
         assert!(
             errors.is_empty(),
             "Wildcards in x-gts-ref should be allowed"
@@ -241,7 +940,8 @@ mod tests {
     fn test_scan_json_xgts_ref_bare_wildcard() {
         let content = r#"{"x-gts-ref": "*"}"#;
         let file = create_temp_json(content);
-        let errors = scan_json_file(file.path(), None, 10_485_760, false).unwrap();
+        let errors =
+            scan_json_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, false).unwrap();
         assert!(
             errors.is_empty(),
             "Bare wildcard in x-gts-ref should be skipped"
@@ -252,13 +952,95 @@ mod tests {
     fn test_scan_json_xgts_ref_relative_pointer() {
         let content = r#"{"x-gts-ref": "/$id"}"#;
         let file = create_temp_json(content);
-        let errors = scan_json_file(file.path(), None, 10_485_760, false).unwrap();
+        let errors =
+            scan_json_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, false).unwrap();
         assert!(
             errors.is_empty(),
             "Relative pointers in x-gts-ref should be skipped"
         );
     }
 
+    #[test]
+    fn test_scan_json_xgts_ref_array_elements() {
+        let content = r#"{"x-gts-ref": ["gts.x.core.*", "/$id", "gts.x.core.events", "*"]}"#;
+        let mut warnings = Vec::new();
+        let errors = scan_json_content(
+            content,
+            Path::new("test.json"),
+            &VendorPolicy::Any.into(),
+            &JsonScanOptions::default(),
+            &mut warnings,
+            &mut (),
+        )
+        .unwrap();
+        assert_eq!(errors.len(), 1, "{errors:?}");
+        assert_eq!(errors[0].json_path, "$.x-gts-ref[2]");
+        assert_eq!(errors[0].raw_value, "gts.x.core.events");
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_scan_json_xgts_ref_comma_separated_string() {
+        let content = r#"{"a": {"x-gts-ref": "gts.x.core.*, gts.acme.billing.*,gts.x.bad"}}"#;
+        let scan = |split_ref_lists| {
+            let options = JsonScanOptions {
+                split_ref_lists,
+                ..JsonScanOptions::default()
+            };
+            let mut warnings = Vec::new();
+            let errors = scan_json_content(
+                content,
+                Path::new("test.json"),
+                &VendorPolicy::Any.into(),
+                &options,
+                &mut warnings,
+                &mut (),
+            )
+            .unwrap();
+            (errors, warnings)
+        };
+
+        let (errors, warnings) = scan(true);
+        assert_eq!(errors.len(), 1, "{errors:?}");
+        assert_eq!(errors[0].json_path, "$.a.x-gts-ref[2]");
+        assert_eq!(errors[0].raw_value, "gts.x.bad");
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].kind, ScanErrorKind::RefListString);
+        assert!(
+            warnings[0].message.contains(
+                "$.a.x-gts-ref lists 3 references in one string; \
+                 use an array (x-gts-ref: [gts.x.core.*, gts.acme.billing.*, gts.x.bad])"
+            ),
+            "{}",
+            warnings[0].message
+        );
+
+        // Unsplit, the whole string is one (invalid) reference.
+        let (errors, warnings) = scan(false);
+        assert_eq!(errors.len(), 1, "{errors:?}");
+        assert_eq!(errors[0].json_path, "$.a.x-gts-ref");
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_is_x_gts_ref_path() {
+        assert!(is_x_gts_ref_path("$.x-gts-ref"));
+        assert!(is_x_gts_ref_path("$.properties.type.x-gts-ref[12]"));
+        assert!(!is_x_gts_ref_path("$.x-gts-ref[0].name"));
+        assert!(!is_x_gts_ref_path("$.x-gts-refs[0]"));
+        assert!(!is_x_gts_ref_path("$.items[0]"));
+    }
+
+    #[test]
+    fn test_scan_json_generic_field_is_not_a_candidate() {
+        let content = r#"{"x-gts-generic-field": "gts.payload", "other": "gts.payload"}"#;
+        let file = create_temp_json(content);
+        let errors =
+            scan_json_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, false).unwrap();
+        assert_eq!(errors.len(), 1, "{errors:?}");
+        assert_eq!(errors[0].json_path, "$.other");
+    }
+
     #[test]
     fn test_scan_json_nested_values() {
         let content = r#"{
@@ -269,7 +1051,8 @@ mod tests {
             }
         }"#;
         let file = create_temp_json(content);
-        let errors = scan_json_file(file.path(), None, 10_485_760, false).unwrap();
+        let errors =
+            scan_json_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, false).unwrap();
         assert!(
             errors.is_empty(),
             "Nested values should be found and validated"
@@ -285,7 +1068,8 @@ mod tests {
             ]
         }"#;
         let file = create_temp_json(content);
-        let errors = scan_json_file(file.path(), None, 10_485_760, false).unwrap();
+        let errors =
+            scan_json_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, false).unwrap();
         assert!(
             errors.is_empty(),
             "Array values should be found and validated"
@@ -296,7 +1080,7 @@ mod tests {
     fn test_scan_json_invalid_json_is_scan_error() {
         let content = r#"{"invalid": json}"#;
         let file = create_temp_json(content);
-        let result = scan_json_file(file.path(), None, 10_485_760, false);
+        let result = scan_json_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, false);
         assert!(
             result.is_err(),
             "Invalid JSON must produce a ScanError, not silent success"
@@ -309,7 +1093,8 @@ mod tests {
     fn test_scan_json_error_includes_json_path() {
         let content = r#"{"properties": {"type": {"x-gts-ref": "gts.invalid"}}}"#;
         let file = create_temp_json(content);
-        let errors = scan_json_file(file.path(), None, 10_485_760, false).unwrap();
+        let errors =
+            scan_json_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, false).unwrap();
         assert!(!errors.is_empty());
         assert!(errors[0].json_path.contains("properties.type.x-gts-ref"));
     }
@@ -318,7 +1103,7 @@ mod tests {
     fn test_scan_json_vendor_mismatch() {
         let content = r#"{"$id": "gts://gts.hx.core.events.type.v1~"}"#;
         let file = create_temp_json(content);
-        let errors = scan_json_file(file.path(), Some("x"), 10_485_760, false).unwrap();
+        let errors = scan_json_file(file.path(), &must("x"), 10_485_760, false).unwrap();
         assert!(!errors.is_empty());
         assert!(errors[0].error.contains("Vendor mismatch"));
     }
@@ -327,7 +1112,8 @@ mod tests {
     fn test_scan_json_keys_not_scanned_by_default() {
         let content = r#"{"gts.x.core.type.v1~": "value"}"#;
         let file = create_temp_json(content);
-        let errors = scan_json_file(file.path(), None, 10_485_760, false).unwrap();
+        let errors =
+            scan_json_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, false).unwrap();
         assert!(errors.is_empty(), "Keys should not be scanned by default");
     }
 
@@ -335,7 +1121,8 @@ mod tests {
     fn test_scan_json_keys_scanned_when_enabled() {
         let content = r#"{"gts.x.core.events.type.v1~": "value"}"#;
         let file = create_temp_json(content);
-        let errors = scan_json_file(file.path(), None, 10_485_760, true).unwrap();
+        let errors =
+            scan_json_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, true).unwrap();
         assert!(
             errors.is_empty(),
             "Valid GTS ID keys should pass validation"
@@ -346,10 +1133,174 @@ mod tests {
     fn test_scan_json_invalid_key_when_scanning_enabled() {
         let content = r#"{"gts.invalid": "value"}"#;
         let file = create_temp_json(content);
-        let errors = scan_json_file(file.path(), None, 10_485_760, true).unwrap();
+        let errors =
+            scan_json_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, true).unwrap();
         assert!(
             !errors.is_empty(),
             "Invalid GTS ID keys should be caught when key scanning is enabled"
         );
     }
+
+    #[test]
+    fn test_scan_json_pattern_keys() {
+        let content = r#"{
+            "routes": {
+                "gts.x.core.events.*": "handler_a",
+                "gts.x.core.ev*.type.v1~": "handler_b"
+            },
+            "handlers": {"gts.x.core.events.*": "handler_c"}
+        }"#;
+        let options = JsonScanOptions {
+            scan_keys: true,
+            pattern_keys: vec![Pattern::new("$.routes.*").unwrap()],
+            ..JsonScanOptions::default()
+        };
+        let mut errors = scan_json_content(
+            content,
+            Path::new("test.json"),
+            &VendorPolicy::Any.into(),
+            &options,
+            &mut Vec::new(),
+            &mut (),
+        )
+        .unwrap();
+        errors.sort_by(|a, b| a.json_path.cmp(&b.json_path));
+        assert_eq!(errors.len(), 2, "{errors:?}");
+
+        assert_eq!(errors[0].json_path, "$.handlers.gts.x.core.events.*");
+        assert_eq!(errors[0].rule_id, rules::WILDCARD_NOT_ALLOWED);
+        assert!(
+            errors[0].error.ends_with("(exact ID key validation)"),
+            "{}",
+            errors[0].error
+        );
+
+        assert_eq!(errors[1].json_path, "$.routes.gts.x.core.ev*.type.v1~");
+        assert_eq!(errors[1].rule_id, rules::INVALID_ID);
+        assert!(
+            errors[1].error.ends_with("(pattern key validation)"),
+            "{}",
+            errors[1].error
+        );
+    }
+
+    const OPENAPI_DOCUMENT: &str = r#"{
+        "openapi": "3.1.0",
+        "info": {
+            "title": "Events",
+            "version": "1.0.0",
+            "x-gts-service-id": "gts.x.core.svc.events.v1~"
+        },
+        "paths": {
+            "/events": {
+                "get": {
+                    "x-gts-operation-id": "gts.x.core.ops.list_events.v1~",
+                    "responses": {
+                        "200": {
+                            "content": {
+                                "application/json": {
+                                    "schema": {"$ref": "gts.x.core.not_an_id"}
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        },
+        "components": {
+            "schemas": {
+                "Event": {
+                    "$id": "gts.y.core.events.type.v1~",
+                    "x-gts-ref": "gts.x.core.events.type.v1~"
+                },
+                "Audit": {"x-gts-ref": "gts.y.core.events.audit.v1~"},
+                "Broken": {"x-gts-ref": "gts.x.core.events"}
+            }
+        }
+    }"#;
+
+    fn scan_openapi(content: &str, openapi: OpenApiScanMode) -> Vec<ValidationError> {
+        let options = JsonScanOptions {
+            openapi,
+            ..JsonScanOptions::default()
+        };
+        scan_json_content(
+            content,
+            Path::new("api.json"),
+            &must("x"),
+            &options,
+            &mut Vec::new(),
+            &mut (),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_scan_openapi_only_extension_fields() {
+        let errors = scan_openapi(OPENAPI_DOCUMENT, OpenApiScanMode::Detect);
+        let paths: Vec<&str> = errors.iter().map(|e| e.json_path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "$.components.schemas.Audit.x-gts-ref",
+                "$.components.schemas.Broken.x-gts-ref"
+            ],
+            "{errors:?}"
+        );
+        assert!(errors[0].error.contains("Vendor mismatch"), "{errors:?}");
+    }
+
+    #[test]
+    fn test_scan_openapi_off_scans_every_value() {
+        let errors = scan_openapi(OPENAPI_DOCUMENT, OpenApiScanMode::Off);
+        for path in [
+            "$.components.schemas.Event.$id",
+            "$.paths./events.get.responses.200.content.application/json.schema.$ref",
+        ] {
+            assert!(
+                errors.iter().any(|e| e.json_path == path),
+                "{path}: {errors:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_scan_openapi_requires_version_3() {
+        let swagger = OPENAPI_DOCUMENT.replace(r#""openapi": "3.1.0""#, r#""openapi": "2.0""#);
+        let errors = scan_openapi(&swagger, OpenApiScanMode::Detect);
+        assert!(
+            errors
+                .iter()
+                .any(|e| e.json_path == "$.components.schemas.Event.$id"),
+            "{errors:?}"
+        );
+    }
+
+    #[test]
+    fn test_scan_openapi_traces_skipped_values() {
+        let options = JsonScanOptions {
+            openapi: OpenApiScanMode::Detect,
+            ..JsonScanOptions::default()
+        };
+        let mut trace = Vec::new();
+        scan_json_content(
+            OPENAPI_DOCUMENT,
+            Path::new("api.json"),
+            &must("x"),
+            &options,
+            &mut Vec::new(),
+            &mut trace,
+        )
+        .unwrap();
+        let skipped = trace
+            .iter()
+            .find(|c| c.json_path == "$.components.schemas.Event.$id")
+            .unwrap();
+        assert_eq!(
+            skipped.outcome,
+            CandidateOutcome::Skipped {
+                reason: SkipReason::OpenapiNonExtension
+            }
+        );
+    }
 }
@@ -5,15 +5,25 @@
 //! 2. `normalize_candidate()` → `validate_candidate()` validates them
 
 use std::collections::HashSet;
+use std::ops::Range;
 use std::path::Path;
 use std::sync::LazyLock;
 
 use gts::{GTS_ID_PREFIX, GTS_ID_URI_PREFIX};
 use regex::Regex;
 
-use crate::error::ValidationError;
-use crate::normalize::normalize_candidate;
-use crate::validator::{is_bad_example_context, is_wildcard_context, validate_candidate};
+use crate::config::{CompiledValidationConfig, MarkdownProfile};
+use crate::error::{ScanError, ScanErrorKind, ValidationError};
+use crate::fix::TextEdit;
+use crate::normalize::{NormalizedCandidate, normalize_candidate};
+use crate::rules;
+use crate::trace::{CandidateOutcome, DiscoverySource, SkipReason, TraceSink, TracedCandidate};
+use crate::validator::{
+    grammar_name, id_kind, is_bad_example_context, is_wildcard_context, suggest_fix,
+    validate_candidate,
+};
+#[cfg(test)]
+use crate::vendor::VendorCheck;
 
 /// Markdown parsing state for code block tracking
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -44,6 +54,9 @@ fn parse_fence(trimmed_line: &str) -> Option<(char, usize)> {
     }
 }
 
+/// The UUID tail of an anonymous instance ID.
+const UUID_PATTERN: &str = "[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}";
+
 /// Discovery regex (relaxed): finds strings that LOOK like GTS identifiers.
 /// This is intentionally broader than the spec — validation is done by `GtsId::try_new()`.
 ///
@@ -55,16 +68,17 @@ static GTS_DISCOVERY_PATTERN_RELAXED: LazyLock<Regex> = LazyLock::new(|| {
     let pattern = format!(
         concat!(
             r"(?:{uri})?",                       // optional URI prefix
-            r"\b{prefix}", // mandatory configured prefix (word boundary prevents xgts. match)
+            r"{prefix}", // mandatory configured prefix (boundary checked by find_candidates)
             r"(?:[a-z_*][a-z0-9_*.-]*\.){{3,}}", // at least 3 segments (permissive: allows -, .)
             r"[a-z_*][a-z0-9_*.-]*", // final segment before version
-            r"\.v[0-9]+",  // version segment (required anchor)
+            r"\.v[0-9]+", // version segment (required anchor)
             r"(?:\.[0-9]+)?", // optional minor version
             r"(?:~[a-z_][a-z0-9_.-]*)*", // optional chained segments (permissive)
-            r"~?",         // optional trailing tilde (but not if followed by .)
+            r"(?:~{uuid}|~)?", // optional UUID tail or trailing tilde (but not if followed by .)
         ),
         uri = regex::escape(GTS_ID_URI_PREFIX),
         prefix = regex::escape(GTS_ID_PREFIX),
+        uuid = UUID_PATTERN,
     );
     match Regex::new(&pattern) {
         Ok(regex) => regex,
@@ -78,18 +92,19 @@ static GTS_DISCOVERY_PATTERN_WELL_FORMED: LazyLock<Regex> = LazyLock::new(|| {
     let pattern = format!(
         concat!(
             r"(?:{uri})?",           // optional URI prefix
-            r"\b{prefix}", // mandatory configured prefix (word boundary prevents xgts. match)
+            r"{prefix}", // mandatory configured prefix (boundary checked by find_candidates)
             r"[a-z_*][a-z0-9_*]*\.", // vendor
             r"[a-z_*][a-z0-9_*]*\.", // package
             r"[a-z_*][a-z0-9_*]*\.", // namespace
             r"[a-z_*][a-z0-9_*]*\.", // type
-            r"v[0-9]+",    // major version (required)
+            r"v[0-9]+",  // major version (required)
             r"(?:\.[0-9]+)?", // optional minor version
             r"(?:~[a-z_][a-z0-9_]*\.[a-z_][a-z0-9_]*\.[a-z_][a-z0-9_]*\.[a-z_][a-z0-9_]*\.v[0-9]+(?:\.[0-9]+)?)*", // chained segments
-            r"~?", // optional trailing tilde
+            r"(?:~{uuid}|~)?", // optional UUID tail (anonymous instance) or trailing tilde
         ),
         uri = regex::escape(GTS_ID_URI_PREFIX),
         prefix = regex::escape(GTS_ID_PREFIX),
+        uuid = UUID_PATTERN,
     );
     match Regex::new(&pattern) {
         Ok(regex) => regex,
@@ -97,25 +112,594 @@ static GTS_DISCOVERY_PATTERN_WELL_FORMED: LazyLock<Regex> = LazyLock::new(|| {
     }
 });
 
-/// Scan markdown content for GTS identifiers.
+/// Whether a candidate starting at `start` is not glued to a preceding
+/// word: `xgts.` and `my_gts.` are not identifiers, but `_gts.` opening
+/// underscore emphasis is.
+fn starts_at_boundary(text: &str, start: usize) -> bool {
+    let mut before = text[..start].chars().rev();
+    match before.next() {
+        None => true,
+        Some('_') => !before.next().is_some_and(char::is_alphanumeric),
+        Some(c) => !c.is_alphanumeric(),
+    }
+}
+
+/// Regex matches in `text` that start at an identifier boundary, as
+/// `(offset, candidate)`.
+///
+/// Candidates end at their last alphanumeric, `~` or `*` character, so
+/// sentence punctuation, closing emphasis and hyphens after an identifier
+/// are never part of it, while a trailing `~` always is.
+fn find_candidates<'t>(pattern: &Regex, text: &'t str) -> impl Iterator<Item = (usize, &'t str)> {
+    pattern
+        .find_iter(text)
+        .filter(|mat| starts_at_boundary(text, mat.start()))
+        .map(|mat| {
+            let candidate = mat
+                .as_str()
+                .trim_end_matches(|c: char| !(c.is_ascii_alphanumeric() || c == '~' || c == '*'));
+            (mat.start(), candidate)
+        })
+}
+
+/// Obsidian-style wikilink: `[[target]]`, `[[target|alias]]` or
+/// `[[target#heading]]`. Group 1 is the target.
+static WIKILINK_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| match Regex::new(r"\[\[([^\[\]|#]*)[^\[\]]*\]\]") {
+        Ok(regex) => regex,
+        Err(err) => panic!("Invalid wikilink regex: {err}"),
+    });
+
+/// GTS candidates on `line`, in order: `(offset, candidate, source)`.
+///
+/// The target of a wikilink that starts with a GTS prefix is taken whole,
+/// so a malformed ID used as a link is reported even when the discovery
+/// regex would not match it. Other targets are searched with `pattern`;
+/// aliases and headings are not scanned.
+fn line_candidates<'a>(
+    line: &'a str,
+    pattern: &Regex,
+    source: DiscoverySource,
+) -> Vec<(usize, &'a str, DiscoverySource)> {
+    let mut candidates = Vec::new();
+    let mut rest_start = 0;
+    let scan_with_pattern = |start: usize, text: &'a str, candidates: &mut Vec<_>| {
+        candidates.extend(
+            find_candidates(pattern, text).map(|(offset, text)| (start + offset, text, source)),
+        );
+    };
+    for link in WIKILINK_PATTERN.captures_iter(line) {
+        let (Some(whole), Some(target)) = (link.get(0), link.get(1)) else {
+            continue;
+        };
+        scan_with_pattern(
+            rest_start,
+            &line[rest_start..whole.start()],
+            &mut candidates,
+        );
+        rest_start = whole.end();
+
+        let trimmed = target.as_str().trim();
+        if trimmed.starts_with(GTS_ID_PREFIX) || trimmed.starts_with(GTS_ID_URI_PREFIX) {
+            let offset =
+                target.start() + target.as_str().len() - target.as_str().trim_start().len();
+            candidates.push((offset, trimmed, DiscoverySource::Wikilink));
+        } else {
+            scan_with_pattern(target.start(), target.as_str(), &mut candidates);
+        }
+    }
+    scan_with_pattern(rest_start, &line[rest_start..], &mut candidates);
+    candidates
+}
+
+/// Byte ranges of the contents of the inline code spans on `line`. A run of
+/// N backticks opens a span that the next run of exactly N backticks
+/// closes, so ``` `` a `b` `` ``` is one span. A backslash-escaped
+/// backtick opens nothing, and a run that is never closed is literal text.
+fn code_spans(line: &str) -> Vec<Range<usize>> {
+    let bytes = line.as_bytes();
+    let run_at = |start: usize| bytes[start..].iter().take_while(|&&b| b == b'`').count();
+    let mut spans = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'`' => {
+                let len = run_at(i);
+                let open_end = i + len;
+                let mut j = open_end;
+                let mut close = None;
+                while j < bytes.len() {
+                    if bytes[j] == b'`' {
+                        let run = run_at(j);
+                        if run == len {
+                            close = Some(j);
+                            break;
+                        }
+                        j += run;
+                    } else {
+                        j += 1;
+                    }
+                }
+                if let Some(close) = close {
+                    spans.push(open_end..close);
+                    i = close + len;
+                } else {
+                    i = open_end;
+                }
+            }
+            _ => i += 1,
+        }
+    }
+    spans
+}
+
+/// The `BareIdentifier` warning for `candidate`, found outside a code span.
+fn bare_identifier(path: &Path, candidate: &str, (line, column): (usize, usize)) -> ScanError {
+    ScanError {
+        file: path.to_owned(),
+        kind: ScanErrorKind::BareIdentifier,
+        message: format!(
+            "GTS identifier '{candidate}' at line {line}, column {column} is not in an inline \
+             code span, so it is not validated; wrap it in backticks"
+        ),
+        original_file: None,
+    }
+}
+
+/// A cell of a pipe-table body row: its byte range in the row and the
+/// header of its column.
+struct TableCell {
+    start: usize,
+    end: usize,
+    header: Option<String>,
+}
+
+/// Byte ranges of the cells of a pipe-table row. Leading and trailing pipes
+/// are optional; escaped pipes (`\|`) do not split cells.
+fn split_table_cells(line: &str) -> Vec<(usize, usize)> {
+    let mut cells = Vec::new();
+    let mut start = line.len() - line.trim_start().len();
+    if line[start..].starts_with('|') {
+        start += 1;
+    }
+    let first = start;
+    let mut escaped = false;
+    for (i, c) in line[first..].char_indices() {
+        if std::mem::take(&mut escaped) {
+            continue;
+        }
+        match c {
+            '\\' => escaped = true,
+            '|' => {
+                cells.push((start, first + i));
+                start = first + i + 1;
+            }
+            _ => {}
+        }
+    }
+    if !line[start..].trim().is_empty() {
+        cells.push((start, line.len()));
+    }
+    cells
+}
+
+/// Whether `line` has a pipe that is not escaped.
+fn has_unescaped_pipe(line: &str) -> bool {
+    line.match_indices('|')
+        .any(|(i, _)| !line[..i].ends_with('\\'))
+}
+
+/// Whether `line` is the delimiter row of a table with `columns` columns,
+/// such as `|:---|---:|` or `--- | ---`.
+fn is_delimiter_row(line: &str, columns: usize) -> bool {
+    let cells = split_table_cells(line);
+    has_unescaped_pipe(line)
+        && cells.len() == columns
+        && cells.iter().all(|&(start, end)| {
+            let cell = line[start..end].trim();
+            let dashes = cell.trim_start_matches(':').trim_end_matches(':');
+            !dashes.is_empty() && dashes.chars().all(|c| c == '-')
+        })
+}
+
+/// Pipe-table tracking across the lines of a markdown file.
+#[derive(Default)]
+struct TableState {
+    /// Column headers of the table being read.
+    headers: Option<Vec<String>>,
+    /// Whether the next line is the delimiter row of a header just read.
+    delimiter_next: bool,
+}
+
+impl TableState {
+    /// The cells of `line` if it is a table body row. A header row (one
+    /// followed by a delimiter row, `next`) starts a table; a blank line or
+    /// a line without a pipe ends it.
+    fn body_row(&mut self, line: &str, next: Option<&str>) -> Option<Vec<TableCell>> {
+        if std::mem::take(&mut self.delimiter_next) {
+            return None;
+        }
+        if let Some(headers) = &self.headers {
+            if !line.trim().is_empty() && has_unescaped_pipe(line) {
+                let cells = split_table_cells(line)
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, (start, end))| TableCell {
+                        start,
+                        end,
+                        header: headers.get(i).filter(|h| !h.is_empty()).cloned(),
+                    })
+                    .collect();
+                return Some(cells);
+            }
+            self.headers = None;
+        }
+        if has_unescaped_pipe(line) {
+            let cells = split_table_cells(line);
+            if next.is_some_and(|next| is_delimiter_row(next, cells.len())) {
+                self.headers = Some(
+                    cells
+                        .iter()
+                        .map(|&(start, end)| line[start..end].trim().to_owned())
+                        .collect(),
+                );
+                self.delimiter_next = true;
+            }
+        }
+        None
+    }
+}
+
+/// Strip up to three spaces of indentation, as `CommonMark` allows before a
+/// heading; `None` if the line is indented further (a code block).
+fn strip_block_indent(line: &str) -> Option<&str> {
+    let rest = line.trim_start_matches(' ');
+    (line.len() - rest.len() <= 3).then_some(rest)
+}
+
+/// The level and text of an ATX heading (`## Text`, optionally closed by
+/// `#`s).
+fn atx_heading(line: &str) -> Option<(usize, &str)> {
+    let rest = strip_block_indent(line)?;
+    let level = rest.bytes().take_while(|&b| b == b'#').count();
+    let text = &rest[level..];
+    if !(1..=6).contains(&level) || !(text.is_empty() || text.starts_with([' ', '\t'])) {
+        return None;
+    }
+    let text = text.trim();
+    let without_closing = text.trim_end_matches('#');
+    let text = if without_closing.is_empty() || without_closing.ends_with([' ', '\t']) {
+        without_closing.trim_end()
+    } else {
+        text
+    };
+    Some((level, text))
+}
+
+/// The heading level a setext underline (`===` or `---`) gives the line
+/// above it.
+fn setext_level(line: &str) -> Option<usize> {
+    let underline = strip_block_indent(line)?.trim_end();
+    if underline.is_empty() {
+        None
+    } else if underline.bytes().all(|b| b == b'=') {
+        Some(1)
+    } else if underline.bytes().all(|b| b == b'-') {
+        Some(2)
+    } else {
+        None
+    }
+}
+
+/// Whether `line` can be the text of a setext heading: a plain paragraph
+/// line, not a heading, table row, list item or block quote.
+fn is_paragraph_line(line: &str) -> bool {
+    let Some(rest) = strip_block_indent(line) else {
+        return false;
+    };
+    !rest.trim().is_empty()
+        && atx_heading(line).is_none()
+        && setext_level(line).is_none()
+        && !has_unescaped_pipe(line)
+        && !["- ", "* ", "+ ", ">"].iter().any(|m| rest.starts_with(m))
+}
+
+/// The stack of headings enclosing the current markdown line, for the
+/// `section` of its errors. YAML front matter is not searched for headings.
+struct HeadingStack {
+    /// `(level, text)` of each enclosing heading, outermost first.
+    headings: Vec<(usize, String)>,
+    /// Index of the first line after the front matter (0 if there is none).
+    front_matter_end: usize,
+}
+
+impl HeadingStack {
+    fn new(lines: &[&str]) -> Self {
+        let front_matter_end = match lines.first() {
+            Some(first) if first.trim_end() == "---" => lines
+                .iter()
+                .skip(1)
+                .position(|line| matches!(line.trim_end(), "---" | "..."))
+                .map_or(0, |i| i + 2),
+            _ => 0,
+        };
+        Self {
+            headings: Vec::new(),
+            front_matter_end,
+        }
+    }
+
+    /// Update the stack if `lines[index]`, a prose line, is an ATX heading
+    /// or the text of a setext heading.
+    fn observe(&mut self, lines: &[&str], index: usize) {
+        if index < self.front_matter_end {
+            return;
+        }
+        let line = lines[index];
+        let heading = atx_heading(line).or_else(|| {
+            let level = lines.get(index + 1).and_then(|next| setext_level(next))?;
+            is_paragraph_line(line).then(|| (level, line.trim()))
+        });
+        if let Some((level, text)) = heading {
+            self.headings.retain(|&(outer, _)| outer < level);
+            if !text.is_empty() {
+                self.headings.push((level, text.to_owned()));
+            }
+        }
+    }
+
+    /// The heading path, e.g. `"Events > Audit"`, or `None` before the
+    /// first heading.
+    fn section(&self) -> Option<String> {
+        if self.headings.is_empty() {
+            return None;
+        }
+        let texts: Vec<&str> = self
+            .headings
+            .iter()
+            .map(|(_, text)| text.as_str())
+            .collect();
+        Some(texts.join(" > "))
+    }
+}
+
+/// A logical line: one physical line, or several joined where a GTS
+/// identifier was split across them.
+struct LogicalLine {
+    text: String,
+    /// `(offset in text, 1-indexed line number, 0-indexed column)` of each
+    /// joined physical line.
+    segments: Vec<(usize, usize, usize)>,
+    /// Index of the first physical line after this one.
+    next: usize,
+}
+
+impl LogicalLine {
+    /// Physical `(line, column)`, both 1-indexed, of byte `offset` in `text`.
+    fn position(&self, offset: usize) -> (usize, usize) {
+        let (start, line, column) = self
+            .segments
+            .iter()
+            .rev()
+            .find(|(start, _, _)| *start <= offset)
+            .copied()
+            .unwrap_or_default();
+        (line, offset - start + column + 1)
+    }
+}
+
+/// The edit replacing the identifier of `candidate`, found at `match_start`
+/// of `logical`, with its suggested fix. None unless the error is a
+/// [`rules::INVALID_ID`] with a suggestion on a single line.
+fn fix_edit(
+    rule_id: &str,
+    logical: &LogicalLine,
+    match_start: usize,
+    candidate: &NormalizedCandidate,
+) -> Option<TextEdit> {
+    if rule_id != rules::INVALID_ID {
+        return None;
+    }
+    let gts_id = candidate.gts_id.as_str();
+    let replacement = suggest_fix(gts_id)?;
+    let start = match_start + candidate.original.find(gts_id)?;
+    let (start_line, start_col) = logical.position(start);
+    let (end_line, last_col) = logical.position(start + gts_id.len() - 1);
+    (start_line == end_line).then(|| {
+        TextEdit::new(
+            (start_line, start_col),
+            (end_line, last_col + 1),
+            replacement,
+        )
+    })
+}
+
+/// Heuristic matches can be anything ID-shaped, so their invalid-ID errors
+/// say which grammar the candidate was held to (none when it could not be
+/// normalized).
+fn name_grammar(
+    error: String,
+    rule_id: &str,
+    gts_id: &str,
+    config: &CompiledValidationConfig,
+) -> String {
+    if config.heuristic && rule_id == rules::INVALID_ID && !gts_id.is_empty() {
+        format!("{error} (checked as {})", grammar_name(id_kind(gts_id, "")))
+    } else {
+        error
+    }
+}
+
+/// Whether `text` ends in the middle of what looks like a GTS identifier.
+fn ends_with_partial_id(text: &str) -> bool {
+    let tail_start = text
+        .rfind(|c: char| !(c.is_ascii_alphanumeric() || "_.~*:/-".contains(c)))
+        .map_or(0, |i| i + 1);
+    text[tail_start..].contains(GTS_ID_PREFIX)
+}
+
+/// Join `lines[first]` with following lines when a GTS identifier is split
+/// across them: by a trailing `\` continuation, or (in prose) inside an inline
+/// code span whose closing backtick is on a later line. Fence lines are never
+/// joined.
+fn join_split_lines(lines: &[&str], first: usize, in_prose: bool) -> LogicalLine {
+    let mut logical = LogicalLine {
+        text: lines[first].to_owned(),
+        segments: vec![(0, first + 1, 0)],
+        next: first + 1,
+    };
+    while let Some(next_line) = lines.get(logical.next) {
+        let trimmed = logical.text.trim_end();
+        let continued = trimmed
+            .strip_suffix('\\')
+            .filter(|t| ends_with_partial_id(t));
+        let open_span =
+            in_prose && trimmed.matches('`').count() % 2 == 1 && ends_with_partial_id(trimmed);
+        if (continued.is_none() && !open_span) || parse_fence(next_line.trim_start()).is_some() {
+            break;
+        }
+        let keep = continued.unwrap_or(trimmed).len();
+        logical.text.truncate(keep);
+        let rest = next_line.trim_start();
+        logical.segments.push((
+            logical.text.len(),
+            logical.next + 1,
+            next_line.len() - rest.len(),
+        ));
+        logical.text.push_str(rest);
+        logical.next += 1;
+    }
+    logical
+}
+
+/// Consumer-provided skip tokens, lowercased once for case-insensitive
+/// matching.
+#[derive(Debug, Clone, Default)]
+pub struct SkipTokens {
+    /// `(token as configured, lowercased token)`
+    tokens: Vec<(String, String)>,
+}
+
+impl SkipTokens {
+    pub fn new(tokens: &[String]) -> Self {
+        Self {
+            tokens: tokens
+                .iter()
+                .map(|token| (token.clone(), token.to_lowercase()))
+                .collect(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// The first token, as configured, contained in `text_lower`.
+    fn find_in(&self, text_lower: &str) -> Option<&str> {
+        self.tokens
+            .iter()
+            .find(|(_, lower)| text_lower.contains(lower.as_str()))
+            .map(|(token, _)| token.as_str())
+    }
+}
+
+/// Scan markdown content for GTS identifiers (one-off convenience wrapper
+/// around [`scan_markdown`] for tests).
+#[cfg(test)]
 pub fn scan_markdown_content(
     content: &str,
     path: &Path,
-    vendor: Option<&str>,
+    vendor_check: &VendorCheck,
     heuristic: bool,
     skip_tokens: &[String],
+    trace: &mut dyn TraceSink,
+) -> Vec<ValidationError> {
+    let config = CompiledValidationConfig {
+        vendor_check: vendor_check.clone(),
+        heuristic,
+        markdown_profile: MarkdownProfile::Default,
+        skip_tokens: SkipTokens::new(skip_tokens),
+        json_options: crate::format::json::JsonScanOptions::default(),
+        id_limits: crate::outcome::IdLimits::default(),
+    };
+    scan_markdown(content, path, &config, &mut Vec::new(), trace)
+}
+
+/// Scan markdown content for GTS identifiers.
+///
+/// Identifiers split across lines by a trailing `\` or inside an unclosed
+/// inline code span are joined before scanning and reported at the line and
+/// column where they start. In `[[target|alias]]` wikilinks only the
+/// target is scanned. Pipe-table rows are scanned cell by cell, and their
+/// errors name the column header and quote only the cell.
+///
+/// Errors carry the `section` they occur in: the path of enclosing ATX and
+/// setext headings. Heading-like lines inside fenced code blocks and YAML
+/// front matter do not count.
+///
+/// Under a [`MarkdownProfile`] other than the default, prose candidates
+/// outside inline code spans are skipped, and with `RequireCodeSpans`
+/// pushed to `warnings` as `BareIdentifier`s.
+///
+/// Every candidate found, including skipped ones, is reported to `trace`.
+pub fn scan_markdown(
+    content: &str,
+    path: &Path,
+    config: &CompiledValidationConfig,
+    warnings: &mut Vec<ScanError>,
+    trace: &mut dyn TraceSink,
 ) -> Vec<ValidationError> {
-    let pattern = if heuristic {
-        &*GTS_DISCOVERY_PATTERN_RELAXED
+    scan_markdown_with_profile(
+        content,
+        path,
+        config,
+        config.markdown_profile,
+        warnings,
+        trace,
+    )
+}
+
+/// [`scan_markdown`] with `profile` instead of the configured one.
+pub fn scan_markdown_with_profile(
+    content: &str,
+    path: &Path,
+    config: &CompiledValidationConfig,
+    profile: MarkdownProfile,
+    warnings: &mut Vec<ScanError>,
+    trace: &mut dyn TraceSink,
+) -> Vec<ValidationError> {
+    let (pattern, source) = if config.heuristic {
+        (
+            &*GTS_DISCOVERY_PATTERN_RELAXED,
+            DiscoverySource::MarkdownHeuristic,
+        )
     } else {
-        &*GTS_DISCOVERY_PATTERN_WELL_FORMED
+        (
+            &*GTS_DISCOVERY_PATTERN_WELL_FORMED,
+            DiscoverySource::MarkdownStrict,
+        )
+    };
+    let traced = |candidate: &str, source, (line, column), outcome| TracedCandidate {
+        candidate: candidate.to_owned(),
+        source,
+        line,
+        column,
+        json_path: String::new(),
+        outcome,
     };
     let mut errors = Vec::new();
     let mut state = MarkdownState::Prose;
+    let mut fence_language = String::new();
     let mut seen_candidates: HashSet<(usize, String)> = HashSet::new();
+    let mut table = TableState::default();
 
-    for (line_num, line) in content.lines().enumerate() {
-        let line_number = line_num + 1; // 1-indexed
+    let lines: Vec<&str> = content.lines().collect();
+    let mut headings = HeadingStack::new(&lines);
+    let mut next = 0;
+    while let Some(&line) = lines.get(next) {
+        let first = next;
+        next += 1;
 
         // Update markdown state for code blocks (``` and ~~~ per CommonMark spec)
         let trimmed_line = line.trim_start();
@@ -131,11 +715,13 @@ pub fn scan_markdown_content(
                         "ebnf" | "regex" | "bnf" | "abnf" | "grammar"
                     );
 
+                    table = TableState::default();
                     state = MarkdownState::FencedBlock {
                         skip,
                         fence_char,
                         opening_fence_len: fence_len,
                     };
+                    fence_language = language;
                     continue;
                 }
                 MarkdownState::FencedBlock {
@@ -154,51 +740,127 @@ pub fn scan_markdown_content(
 
         // Skip lines inside skip blocks
         if let MarkdownState::FencedBlock { skip: true, .. } = state {
+            if trace.is_enabled() {
+                for (offset, candidate) in find_candidates(pattern, line) {
+                    trace.record(traced(
+                        candidate,
+                        source,
+                        (first + 1, offset + 1),
+                        CandidateOutcome::Skipped {
+                            reason: SkipReason::Fence {
+                                language: fence_language.clone(),
+                            },
+                        },
+                    ));
+                }
+            }
             continue;
         }
 
-        // Find all GTS candidates on this line
-        for mat in pattern.find_iter(line) {
-            let candidate_str = mat.as_str();
-            let match_start = mat.start();
+        let in_prose = state == MarkdownState::Prose;
+        if in_prose {
+            headings.observe(&lines, first);
+        }
+        let section = headings.section();
+        let logical = join_split_lines(&lines, first, in_prose);
+        next = logical.next;
+        let line = logical.text.as_str();
+        let spans = (in_prose && profile != MarkdownProfile::Default).then(|| code_spans(line));
 
-            // Deduplicate: skip if we've seen this candidate on this line
-            if !seen_candidates.insert((line_number, candidate_str.to_owned())) {
-                continue;
-            }
+        // Find all GTS candidates on this line
+        let cells = in_prose
+            .then(|| table.body_row(line, lines.get(logical.next).copied()))
+            .flatten();
+        let candidates: Vec<_> = match &cells {
+            // Table rows are searched cell by cell, so no candidate spans a pipe.
+            Some(cells) => cells
+                .iter()
+                .flat_map(|cell| {
+                    line_candidates(&line[cell.start..cell.end], pattern, source)
+                        .into_iter()
+                        .map(move |(offset, text, source)| {
+                            (cell.start + offset, text, source, Some(cell))
+                        })
+                })
+                .collect(),
+            None => line_candidates(line, pattern, source)
+                .into_iter()
+                .map(|(offset, text, source)| (offset, text, source, None))
+                .collect(),
+        };
 
-            // Skip validation if this is a "bad example" context
-            if is_bad_example_context(line, mat.start()) {
-                continue;
-            }
+        for (match_start, candidate_str, source, cell) in candidates {
+            let (line_number, column) = logical.position(match_start);
+            // In tables, errors name the column and quote only the cell.
+            let error_context = cell.map_or(line, |cell| line[cell.start..cell.end].trim());
+            let in_column = |error: String| match cell.and_then(|c| c.header.as_deref()) {
+                Some(header) => format!("{error} (column '{header}')"),
+                None => error,
+            };
+            let error_at =
+                |raw_value: &str, normalized_id: &str, rule_id, error, fix| ValidationError {
+                    file: path.to_owned(),
+                    line: line_number,
+                    column,
+                    json_path: String::new(),
+                    raw_value: raw_value.to_owned(),
+                    normalized_id: normalized_id.to_owned(),
+                    rule_id,
+                    error: in_column(name_grammar(error, rule_id, normalized_id, config)),
+                    context: error_context.to_owned(),
+                    section: section.clone(),
+                    document_kind: None,
+                    fix,
+                    help: None,
+                    original_file: None,
+                };
 
-            // Check consumer-provided skip tokens
-            if !skip_tokens.is_empty()
-                && let Some(before) = line.get(..mat.start())
+            // Duplicates on this line, "bad example" contexts, consumer-provided
+            // skip tokens and, under a code-span profile, bare prose IDs.
+            let skipped = if !seen_candidates.insert((line_number, candidate_str.to_owned())) {
+                Some(SkipReason::Duplicate)
+            } else if is_bad_example_context(line, match_start) {
+                Some(SkipReason::BadExample)
+            } else if !config.skip_tokens.is_empty()
+                && let Some(before) = line.get(..match_start)
+                && let Some(token) = config.skip_tokens.find_in(&before.to_lowercase())
             {
-                let before_lower = before.to_lowercase();
-                if skip_tokens
-                    .iter()
-                    .any(|token| before_lower.contains(&token.to_lowercase()))
-                {
-                    continue;
+                Some(SkipReason::SkipToken {
+                    token: token.to_owned(),
+                })
+            } else if let Some(spans) = &spans
+                && !spans.iter().any(|span| span.contains(&match_start))
+            {
+                if profile == MarkdownProfile::RequireCodeSpans {
+                    warnings.push(bare_identifier(path, candidate_str, (line_number, column)));
                 }
+                Some(SkipReason::OutsideCodeSpan)
+            } else {
+                None
+            };
+            if let Some(reason) = skipped {
+                trace.record(traced(
+                    candidate_str,
+                    source,
+                    (line_number, column),
+                    CandidateOutcome::Skipped { reason },
+                ));
+                continue;
             }
 
             // Normalize the candidate
             let candidate = match normalize_candidate(candidate_str) {
                 Ok(c) => c,
                 Err(e) => {
-                    errors.push(ValidationError {
-                        file: path.to_owned(),
-                        line: line_number,
-                        column: match_start + 1, // 1-indexed
-                        json_path: String::new(),
-                        raw_value: candidate_str.to_owned(),
-                        normalized_id: String::new(),
-                        error: e,
-                        context: line.to_owned(),
-                    });
+                    trace.record(traced(
+                        candidate_str,
+                        source,
+                        (line_number, column),
+                        CandidateOutcome::Invalid {
+                            errors: vec![e.clone()],
+                        },
+                    ));
+                    errors.push(error_at(candidate_str, "", rules::INVALID_ID, e, None));
                     continue;
                 }
             };
@@ -207,18 +869,24 @@ pub fn scan_markdown_content(
             let allow_wildcards = is_wildcard_context(line, match_start);
 
             // Validate the candidate
-            let validation_errors = validate_candidate(&candidate, vendor, allow_wildcards);
+            let validation_errors =
+                validate_candidate(&candidate, &config.vendor_check, allow_wildcards);
+            trace.record(traced(
+                candidate_str,
+                source,
+                (line_number, column),
+                CandidateOutcome::from_errors(&validation_errors),
+            ));
             for err in validation_errors {
-                errors.push(ValidationError {
-                    file: path.to_owned(),
-                    line: line_number,
-                    column: match_start + 1, // 1-indexed
-                    json_path: String::new(),
-                    raw_value: candidate.original.clone(),
-                    normalized_id: candidate.gts_id.clone(),
-                    error: err,
-                    context: line.to_owned(),
-                });
+                let rule_id = rules::rule_for_message(&err);
+                let fix = fix_edit(rule_id, &logical, match_start, &candidate);
+                errors.push(error_at(
+                    &candidate.original,
+                    &candidate.gts_id,
+                    rule_id,
+                    err,
+                    fix,
+                ));
             }
         }
     }
@@ -230,7 +898,7 @@ pub fn scan_markdown_content(
 #[cfg(test)]
 pub fn scan_markdown_file(
     path: &Path,
-    vendor: Option<&str>,
+    vendor_check: &VendorCheck,
     max_file_size: u64,
     heuristic: bool,
 ) -> Vec<ValidationError> {
@@ -247,15 +915,21 @@ pub fn scan_markdown_file(
         Err(_e) => return vec![],
     };
 
-    scan_markdown_content(&content, path, vendor, heuristic, &[])
+    scan_markdown_content(&content, path, vendor_check, heuristic, &[], &mut ())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::VendorPolicy;
     use std::io::Write;
+    use std::path::PathBuf;
     use tempfile::NamedTempFile;
 
+    fn must(vendor: &str) -> VendorCheck {
+        VendorPolicy::MustMatch(vendor.to_owned()).into()
+    }
+
     fn create_temp_md(content: &str) -> NamedTempFile {
         let mut file = NamedTempFile::new().unwrap();
         file.write_all(content.as_bytes()).unwrap();
@@ -265,14 +939,14 @@ mod tests {
     #[test]
     fn test_scan_markdown_valid_id() {
         let file = create_temp_md("The type is gts.x.core.events.type.v1~");
-        let errors = scan_markdown_file(file.path(), None, 10_485_760, false);
+        let errors = scan_markdown_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, false);
         assert!(errors.is_empty(), "Unexpected errors: {errors:?}");
     }
 
     #[test]
     fn test_scan_markdown_invalid_id() {
         let file = create_temp_md("The type is gts.x.core.events.type.v1");
-        let errors = scan_markdown_file(file.path(), None, 10_485_760, false);
+        let errors = scan_markdown_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, false);
         assert!(
             !errors.is_empty(),
             "Single-segment instance ID should be rejected"
@@ -287,7 +961,7 @@ gts.invalid.pattern.here.v1~
 ```
 ";
         let file = create_temp_md(content);
-        let errors = scan_markdown_file(file.path(), None, 10_485_760, false);
+        let errors = scan_markdown_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, false);
         assert!(errors.is_empty(), "EBNF blocks should be skipped");
     }
 
@@ -299,21 +973,21 @@ gts.invalid.pattern.here.v1~
 ```
 "#;
         let file = create_temp_md(content);
-        let errors = scan_markdown_file(file.path(), None, 10_485_760, false);
+        let errors = scan_markdown_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, false);
         assert!(errors.is_empty(), "JSON blocks should be validated");
     }
 
     #[test]
     fn test_scan_markdown_skip_invalid_context() {
         let file = create_temp_md("\u{274c} gts.invalid.id.here.v1");
-        let errors = scan_markdown_file(file.path(), None, 10_485_760, false);
+        let errors = scan_markdown_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, false);
         assert!(errors.is_empty(), "Invalid examples should be skipped");
     }
 
     #[test]
     fn test_scan_markdown_wildcard_in_pattern_context() {
         let file = create_temp_md("pattern: gts.x.core.events.type.v1~");
-        let errors = scan_markdown_file(file.path(), None, 10_485_760, false);
+        let errors = scan_markdown_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, false);
         assert!(
             errors.is_empty(),
             "Valid IDs in pattern context should be allowed"
@@ -323,14 +997,14 @@ gts.invalid.pattern.here.v1~
     #[test]
     fn test_scan_markdown_wildcard_not_in_pattern_context() {
         let file = create_temp_md("The type is gts.x.core.events.type.v1~");
-        let errors = scan_markdown_file(file.path(), None, 10_485_760, false);
+        let errors = scan_markdown_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, false);
         assert!(errors.is_empty(), "Valid IDs should pass");
     }
 
     #[test]
     fn test_scan_markdown_gts_uri() {
         let file = create_temp_md(r#"Use "$id": "gts://gts.x.core.events.type.v1~""#);
-        let errors = scan_markdown_file(file.path(), None, 10_485_760, false);
+        let errors = scan_markdown_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, false);
         assert!(
             errors.is_empty(),
             "gts:// URIs should be normalized and validated"
@@ -340,7 +1014,7 @@ gts.invalid.pattern.here.v1~
     #[test]
     fn test_scan_markdown_vendor_mismatch() {
         let file = create_temp_md("The type is gts.hx.core.events.type.v1~");
-        let errors = scan_markdown_file(file.path(), Some("x"), 10_485_760, false);
+        let errors = scan_markdown_file(file.path(), &must("x"), 10_485_760, false);
         assert!(!errors.is_empty());
         assert!(errors[0].error.contains("Vendor mismatch"));
     }
@@ -348,7 +1022,7 @@ gts.invalid.pattern.here.v1~
     #[test]
     fn test_scan_markdown_example_vendor_tolerated() {
         let file = create_temp_md("Example: gts.acme.core.events.type.v1~");
-        let errors = scan_markdown_file(file.path(), Some("x"), 10_485_760, false);
+        let errors = scan_markdown_file(file.path(), &must("x"), 10_485_760, false);
         assert!(errors.is_empty(), "Example vendors should be tolerated");
     }
 
@@ -358,7 +1032,7 @@ gts.invalid.pattern.here.v1~
         let file = create_temp_md(
             "gts.wrongvendor.core.events.type.v1~ and gts.wrongvendor.core.events.type.v1~ again",
         );
-        let errors = scan_markdown_file(file.path(), Some("x"), 10_485_760, false);
+        let errors = scan_markdown_file(file.path(), &must("x"), 10_485_760, false);
         assert_eq!(
             errors.len(),
             1,
@@ -369,7 +1043,7 @@ gts.invalid.pattern.here.v1~
     #[test]
     fn test_scan_markdown_error_after_gts_id() {
         let file = create_temp_md("gts.x.core.events.type.v1~ handles error cases");
-        let errors = scan_markdown_file(file.path(), None, 10_485_760, false);
+        let errors = scan_markdown_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, false);
         assert!(
             errors.is_empty(),
             "Valid ID should not be suppressed by 'error' appearing after it"
@@ -379,15 +1053,17 @@ gts.invalid.pattern.here.v1~
     #[test]
     fn test_scan_markdown_invalid_before_gts_id() {
         let file = create_temp_md("invalid: gts.bad.format.here.v1");
-        let errors = scan_markdown_file(file.path(), None, 10_485_760, false);
+        let errors = scan_markdown_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, false);
         assert!(errors.is_empty(), "Invalid examples should be skipped");
     }
 
     #[test]
     fn test_scan_markdown_heuristic_mode_catches_malformed() {
         let file = create_temp_md("The type is gts.my-vendor.core.events.type.v1~");
-        let errors_heuristic = scan_markdown_file(file.path(), None, 10_485_760, true);
-        let errors_normal = scan_markdown_file(file.path(), None, 10_485_760, false);
+        let errors_heuristic =
+            scan_markdown_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, true);
+        let errors_normal =
+            scan_markdown_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, false);
 
         assert!(
             !errors_heuristic.is_empty(),
@@ -399,10 +1075,30 @@ gts.invalid.pattern.here.v1~
         );
     }
 
+    #[test]
+    fn test_scan_markdown_heuristic_mode_names_grammar() {
+        let file = create_temp_md(
+            "Emitted as gts.x.core.events.type.v1~order.created#2024 by gts.my-vendor.core.events.type.v1~",
+        );
+        let errors = scan_markdown_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, true);
+        let messages: Vec<&str> = errors.iter().map(|e| e.error.as_str()).collect();
+        assert_eq!(messages.len(), 2, "{messages:?}");
+        assert!(
+            messages[0].contains("InstanceSegmentTokenCount")
+                && messages[0].ends_with("(checked as instance ID)"),
+            "{messages:?}"
+        );
+        assert!(
+            messages[1].ends_with("(checked as type ID)"),
+            "{messages:?}"
+        );
+    }
+
     #[test]
     fn test_scan_markdown_heuristic_mode_catches_extra_dots() {
         let file = create_temp_md("The type is gts.x.core.events.type.name.v1~");
-        let errors_heuristic = scan_markdown_file(file.path(), None, 10_485_760, true);
+        let errors_heuristic =
+            scan_markdown_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, true);
 
         assert!(
             !errors_heuristic.is_empty(),
@@ -413,7 +1109,7 @@ gts.invalid.pattern.here.v1~
     #[test]
     fn test_scan_markdown_normal_mode_well_formed_only() {
         let file = create_temp_md("Valid: gts.x.core.events.type.v1~ and malformed: gts.bad-id.v1");
-        let errors = scan_markdown_file(file.path(), None, 10_485_760, false);
+        let errors = scan_markdown_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, false);
 
         assert!(
             errors.is_empty(),
@@ -428,9 +1124,10 @@ gts.invalid.pattern.here.v1~
         let errors = scan_markdown_content(
             content,
             Path::new("test.md"),
-            None,
+            &VendorPolicy::Any.into(),
             true, // heuristic mode to ensure the relaxed regex would catch it
             &["**given**".to_owned()],
+            &mut (),
         );
         assert!(
             errors.is_empty(),
@@ -442,9 +1139,10 @@ gts.invalid.pattern.here.v1~
         let errors_no_skip = scan_markdown_content(
             content_mismatch,
             Path::new("test.md"),
-            Some("x"),
+            &must("x"),
             false,
             &[],
+            &mut (),
         );
         assert!(
             !errors_no_skip.is_empty(),
@@ -455,9 +1153,10 @@ gts.invalid.pattern.here.v1~
         let errors_with_skip = scan_markdown_content(
             content_mismatch,
             Path::new("test.md"),
-            Some("x"),
+            &must("x"),
             false,
             &["**given**".to_owned()],
+            &mut (),
         );
         assert!(
             errors_with_skip.is_empty(),
@@ -465,12 +1164,122 @@ gts.invalid.pattern.here.v1~
         );
     }
 
+    /// Markdown documents covering skip tokens, bad examples, wildcards,
+    /// skipped fences and vendor mismatches.
+    fn sample_documents() -> Vec<String> {
+        (0..50)
+            .map(|i| {
+                format!(
+                    "# Doc {i}\n\
+                     **Given** gts.y.core.pkg.given{i}.v1~ is registered\n\
+                     Uses gts.x.core.pkg.type{i}.v1~ and gts.y.core.pkg.other{i}.v1~\n\
+                     Invalid: gts.y.core.pkg.bad{i}.v1~\n\
+                     Pattern: gts.x.core.pkg.*\n\
+                     ```regex\ngts.y.core.pkg.skipped{i}.v1~\n```\n"
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_scan_markdown_shared_config_matches_per_file_config() {
+        let skip_tokens = ["**given**".to_owned()];
+        let compiled = CompiledValidationConfig {
+            vendor_check: must("x"),
+            heuristic: false,
+            markdown_profile: MarkdownProfile::Default,
+            skip_tokens: SkipTokens::new(&skip_tokens),
+            json_options: crate::format::json::JsonScanOptions::default(),
+            id_limits: crate::outcome::IdLimits::default(),
+        };
+        for (i, doc) in sample_documents().iter().enumerate() {
+            let path = PathBuf::from(format!("doc{i}.md"));
+            let mut shared_trace = Vec::new();
+            let shared = scan_markdown(doc, &path, &compiled, &mut Vec::new(), &mut shared_trace);
+            let mut per_file_trace = Vec::new();
+            let per_file = scan_markdown_content(
+                doc,
+                &path,
+                &must("x"),
+                false,
+                &skip_tokens,
+                &mut per_file_trace,
+            );
+            assert_eq!(shared, per_file);
+            assert_eq!(shared_trace, per_file_trace);
+            assert_eq!(shared.len(), 1, "{shared:?}");
+            assert!(shared_trace.iter().any(|c| matches!(
+                &c.outcome,
+                CandidateOutcome::Skipped {
+                    reason: SkipReason::SkipToken { token }
+                } if token == "**given**"
+            )));
+        }
+    }
+
+    #[test]
+    fn test_scan_markdown_trace_records_skips() {
+        let content = "**Given** gts.y.core.pkg.mytype.v1~ is registered\n\
+                       ```regex\ngts.x.core.pkg.mytype.v1~\n```\n\
+                       Uses gts.y.core.pkg.other.v1~\n";
+        let mut trace = Vec::new();
+        let errors = scan_markdown_content(
+            content,
+            Path::new("test.md"),
+            &must("x"),
+            false,
+            &["**given**".to_owned()],
+            &mut trace,
+        );
+        assert_eq!(errors.len(), 1, "{errors:?}");
+
+        let outcomes: Vec<_> = trace
+            .iter()
+            .map(|c| (c.candidate.as_str(), c.line, &c.outcome))
+            .collect();
+        assert_eq!(
+            outcomes,
+            [
+                (
+                    "gts.y.core.pkg.mytype.v1~",
+                    1,
+                    &CandidateOutcome::Skipped {
+                        reason: SkipReason::SkipToken {
+                            token: "**given**".to_owned()
+                        }
+                    }
+                ),
+                (
+                    "gts.x.core.pkg.mytype.v1~",
+                    3,
+                    &CandidateOutcome::Skipped {
+                        reason: SkipReason::Fence {
+                            language: "regex".to_owned()
+                        }
+                    }
+                ),
+                (
+                    "gts.y.core.pkg.other.v1~",
+                    5,
+                    &CandidateOutcome::Invalid {
+                        errors: vec![errors[0].error.clone()]
+                    }
+                ),
+            ]
+        );
+        assert!(
+            trace
+                .iter()
+                .all(|c| c.source == DiscoverySource::MarkdownStrict)
+        );
+    }
+
     #[test]
     fn test_scan_markdown_tilde_fence() {
         // ~~~ fences should be handled the same as ``` fences
         let content = "~~~ebnf\ngts.invalid.pattern.here.v1~\n~~~\n";
         let file = create_temp_md(content);
-        let errors = scan_markdown_file(file.path(), None, 10_485_760, false);
+        let errors = scan_markdown_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, false);
         assert!(
             errors.is_empty(),
             "~~~ EBNF blocks should be skipped: {errors:?}"
@@ -482,7 +1291,7 @@ gts.invalid.pattern.here.v1~
         // ~~~json blocks should be validated (same as ```json)
         let content = "~~~json\n{\"$id\": \"gts://gts.x.core.events.type.v1~\"}\n~~~\n";
         let file = create_temp_md(content);
-        let errors = scan_markdown_file(file.path(), None, 10_485_760, false);
+        let errors = scan_markdown_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, false);
         assert!(
             errors.is_empty(),
             "~~~json blocks should be validated and pass: {errors:?}"
@@ -494,7 +1303,7 @@ gts.invalid.pattern.here.v1~
         // A ~~~ line must not close a ``` block.
         let content = "```ebnf\n~~~\ngts.bad.format.here.v1~\n```\n";
         let file = create_temp_md(content);
-        let errors = scan_markdown_file(file.path(), None, 10_485_760, true);
+        let errors = scan_markdown_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, true);
         assert!(
             errors.is_empty(),
             "Mismatched fence should not close block; content inside ebnf block must be skipped: {errors:?}"
@@ -505,10 +1314,436 @@ gts.invalid.pattern.here.v1~
     fn test_scan_markdown_word_boundary() {
         // Regex should NOT match "xgts.x.core.events.type.v1~" (no word boundary)
         let content = "The identifier xgts.x.core.events.type.v1~ is wrong";
-        let errors = scan_markdown_content(content, Path::new("test.md"), None, false, &[]);
+        let errors = scan_markdown_content(
+            content,
+            Path::new("test.md"),
+            &VendorPolicy::Any.into(),
+            false,
+            &[],
+            &mut (),
+        );
         assert!(
             errors.is_empty(),
             "Word boundary should prevent matching xgts.*: {errors:?}"
         );
     }
+
+    #[test]
+    fn test_scan_markdown_backslash_continuation() {
+        let content = "Intro\nThe type is gts.hx.core.\\\n    events.type.v1~ here\n";
+        let file = create_temp_md(content);
+        let errors = scan_markdown_file(file.path(), &must("x"), 10_485_760, false);
+        assert_eq!(errors.len(), 1, "{errors:?}");
+        assert_eq!(errors[0].raw_value, "gts.hx.core.events.type.v1~");
+        assert_eq!((errors[0].line, errors[0].column), (2, 13));
+        assert!(errors[0].error.contains("Vendor mismatch"));
+
+        let file = create_temp_md("gts.x.core.\\\nevents.type.v1~\n");
+        let errors = scan_markdown_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, false);
+        assert!(errors.is_empty(), "{errors:?}");
+    }
+
+    #[test]
+    fn test_scan_markdown_split_inline_code_span() {
+        let content = "See `gts.hx.core.events.\ntype.v1~` and `gts.hx.other.\nns.item.v1~`.\n";
+        let file = create_temp_md(content);
+        let errors = scan_markdown_file(file.path(), &must("x"), 10_485_760, false);
+        assert_eq!(errors.len(), 2, "{errors:?}");
+        assert_eq!(errors[0].raw_value, "gts.hx.core.events.type.v1~");
+        assert_eq!((errors[0].line, errors[0].column), (1, 6));
+        assert_eq!(errors[1].raw_value, "gts.hx.other.ns.item.v1~");
+        assert_eq!((errors[1].line, errors[1].column), (2, 16));
+    }
+
+    #[test]
+    fn test_code_spans() {
+        fn spans(line: &str) -> Vec<&str> {
+            code_spans(line)
+                .into_iter()
+                .map(|span| &line[span])
+                .collect()
+        }
+        assert_eq!(spans("a `b` c `d`"), ["b", "d"]);
+        assert_eq!(spans("``a `b` c`` `d`"), ["a `b` c", "d"]);
+        assert_eq!(spans("\\`a` `b`"), [" "]);
+        assert_eq!(spans("``a` b"), Vec::<&str>::new());
+        assert_eq!(spans("`a`` b`"), ["a`` b"]);
+    }
+
+    #[test]
+    fn test_scan_markdown_profiles() {
+        let content = "Use `gts.hx.core.events.a.v1~`, ``the `gts.hx.core.events.b.v1~` type`` \
+                       and bare gts.hx.core.events.c.v1~.\n\
+                       ```json\n{\"$id\": \"gts.hx.core.events.d.v1~\"}\n```\n";
+        let scan = |markdown_profile| {
+            let config = CompiledValidationConfig {
+                vendor_check: must("x"),
+                heuristic: false,
+                markdown_profile,
+                skip_tokens: SkipTokens::default(),
+                json_options: crate::format::json::JsonScanOptions::default(),
+                id_limits: crate::outcome::IdLimits::default(),
+            };
+            let mut warnings = Vec::new();
+            let mut trace = Vec::new();
+            let errors = scan_markdown(
+                content,
+                Path::new("doc.md"),
+                &config,
+                &mut warnings,
+                &mut trace,
+            );
+            let validated: Vec<String> = errors.into_iter().map(|e| e.raw_value).collect();
+            let skipped = trace
+                .iter()
+                .filter(|c| {
+                    c.outcome
+                        == CandidateOutcome::Skipped {
+                            reason: SkipReason::OutsideCodeSpan,
+                        }
+                })
+                .count();
+            (validated, skipped, warnings)
+        };
+        let all = [
+            "gts.hx.core.events.a.v1~",
+            "gts.hx.core.events.b.v1~",
+            "gts.hx.core.events.c.v1~",
+            "gts.hx.core.events.d.v1~",
+        ];
+
+        let (validated, skipped, warnings) = scan(MarkdownProfile::Default);
+        assert_eq!(validated, all);
+        assert_eq!(skipped, 0);
+        assert!(warnings.is_empty());
+
+        let (validated, skipped, warnings) = scan(MarkdownProfile::CodeSpansOnly);
+        assert_eq!(validated, [all[0], all[1], all[3]]);
+        assert_eq!(skipped, 1);
+        assert!(warnings.is_empty());
+
+        let (validated, skipped, warnings) = scan(MarkdownProfile::RequireCodeSpans);
+        assert_eq!(validated, [all[0], all[1], all[3]]);
+        assert_eq!(skipped, 1);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].kind, ScanErrorKind::BareIdentifier);
+        assert_eq!(
+            warnings[0].message,
+            "GTS identifier 'gts.hx.core.events.c.v1~' at line 1, column 82 is not in an \
+             inline code span, so it is not validated; wrap it in backticks"
+        );
+    }
+
+    #[test]
+    fn test_scan_markdown_does_not_join_unrelated_lines() {
+        // A hard line break before an ID must not glue it to the previous word
+        let file = create_temp_md("See\\\ngts.hx.core.events.type.v1~\n");
+        let errors = scan_markdown_file(file.path(), &must("x"), 10_485_760, false);
+        assert_eq!(errors.len(), 1, "{errors:?}");
+        assert_eq!((errors[0].line, errors[0].column), (2, 1));
+
+        // Fences are never swallowed by a continuation
+        let content = "```ebnf\ngts.x.core.\\\n```\nevents.type.v1~\n";
+        let file = create_temp_md(content);
+        let errors = scan_markdown_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, false);
+        assert!(errors.is_empty(), "{errors:?}");
+    }
+
+    #[test]
+    fn test_scan_markdown_wikilinks() {
+        let content = "\
+Valid: [[gts.x.core.events.type.v1~]] and [[gts.x.core.events.topic.v1~|Topic schema]].
+Wrong vendor: [[gts.hx.core.events.type.v1~|Event]].
+Malformed: [[gts.x.core.type.v1~]] and [[ gts.x.core.bad.v1~ |Alias]].
+Aliases are not scanned: [[gts.x.core.events.type.v1~|gts.hx.core.old.name.v1~]].
+Other pages are searched: [[Events (gts.hx.core.events.item.v1~)|gts.hx.core.alias.name.v1~]].
+";
+        let mut candidates = Vec::new();
+        let errors = scan_markdown_content(
+            content,
+            Path::new("doc.md"),
+            &must("x"),
+            false,
+            &[],
+            &mut candidates,
+        );
+        let reported: Vec<(&str, usize, usize)> = errors
+            .iter()
+            .map(|e| (e.raw_value.as_str(), e.line, e.column))
+            .collect();
+        assert_eq!(
+            reported,
+            [
+                ("gts.hx.core.events.type.v1~", 2, 17),
+                ("gts.x.core.type.v1~", 3, 14),
+                ("gts.x.core.bad.v1~", 3, 43),
+                ("gts.hx.core.events.item.v1~", 5, 37),
+            ],
+            "{errors:?}"
+        );
+        assert!(errors[0].error.contains("Vendor mismatch"), "{errors:?}");
+        assert_eq!(errors[1].rule_id, rules::INVALID_ID);
+
+        let wikilinks = candidates
+            .iter()
+            .filter(|c| c.source == DiscoverySource::Wikilink)
+            .count();
+        assert_eq!(wikilinks, 6);
+        assert!(
+            !candidates
+                .iter()
+                .any(|c| c.candidate.contains("alias") || c.candidate.contains("old.name"))
+        );
+    }
+
+    #[test]
+    fn test_split_table_cells() {
+        let cells = |line: &str| -> Vec<String> {
+            split_table_cells(line)
+                .into_iter()
+                .map(|(start, end)| line[start..end].trim().to_owned())
+                .collect()
+        };
+        assert_eq!(cells("| a | b |"), ["a", "b"]);
+        assert_eq!(cells("a | b"), ["a", "b"]);
+        assert_eq!(cells("|a|`x \\| y`|"), ["a", "`x \\| y`"]);
+        assert_eq!(cells("| a || c |"), ["a", "", "c"]);
+        assert!(is_delimiter_row("|:---|---:|", 2));
+        assert!(is_delimiter_row("--- | :-:", 2));
+        assert!(
+            !is_delimiter_row("---", 1),
+            "a setext underline, not a table"
+        );
+        assert!(!is_delimiter_row("| --- |", 2));
+    }
+
+    #[test]
+    fn test_scan_markdown_tables_name_the_column() {
+        let content = "\
+# Capabilities
+
+| Capability | Event Type | Topic |
+|:-----------|:----------:|------:|
+| Orders |gts.x.core.events.type.v1~|gts.hx.core.events.topic.v1~|
+| Billing \\| invoices | gts.hx.core.events.invoice.v1~ | n/a |
+
+Capability | Schema
+--- | ---
+Audit | gts.hx.core.audit.entry.v1~
+
+After the table: gts.hx.core.events.other.v1~ | trailing
+";
+        let errors = scan_markdown_content(
+            content,
+            Path::new("matrix.md"),
+            &must("x"),
+            false,
+            &[],
+            &mut (),
+        );
+        let reported: Vec<(&str, usize, &str, &str)> = errors
+            .iter()
+            .map(|e| {
+                (
+                    e.raw_value.as_str(),
+                    e.line,
+                    e.error.as_str(),
+                    e.context.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            reported,
+            [
+                (
+                    "gts.hx.core.events.topic.v1~",
+                    5,
+                    "Vendor mismatch: expected 'x', found 'hx' (column 'Topic')",
+                    "gts.hx.core.events.topic.v1~"
+                ),
+                (
+                    "gts.hx.core.events.invoice.v1~",
+                    6,
+                    "Vendor mismatch: expected 'x', found 'hx' (column 'Event Type')",
+                    "gts.hx.core.events.invoice.v1~"
+                ),
+                (
+                    "gts.hx.core.audit.entry.v1~",
+                    10,
+                    "Vendor mismatch: expected 'x', found 'hx' (column 'Schema')",
+                    "gts.hx.core.audit.entry.v1~"
+                ),
+                (
+                    "gts.hx.core.events.other.v1~",
+                    12,
+                    "Vendor mismatch: expected 'x', found 'hx'",
+                    "After the table: gts.hx.core.events.other.v1~ | trailing"
+                ),
+            ],
+            "{errors:?}"
+        );
+        assert_eq!((errors[0].line, errors[0].column), (5, 38));
+    }
+
+    #[test]
+    fn test_candidate_boundaries() {
+        const TYPE: &str = "gts.x.core.events.type.v1~";
+        const CHAINED: &str = "gts.x.core.events.type.v1~x.core._.orders.v1";
+        const CHAINED_TYPE: &str = "gts.x.core.events.type.v1~x.core._.orders.v1~";
+        const NO_TILDE: &str = "gts.x.core.events.type.v1";
+        const MINOR: &str = "gts.x.core.events.type.v1.2~";
+        const URI: &str = "gts://gts.x.core.events.type.v1~";
+        const UUID_TAIL: &str = "gts.x.core.events.type.v1~7a1d2f34-5678-4abc-9def-0123456789ab";
+        let cases: &[(&str, Option<&str>)] = &[
+            (
+                "Anonymous gts.x.core.events.type.v1~7a1d2f34-5678-4abc-9def-0123456789ab.",
+                Some(UUID_TAIL),
+            ),
+            ("The type is gts.x.core.events.type.v1~.", Some(TYPE)),
+            (
+                "The type is gts.x.core.events.type.v1~x.core._.orders.v1.",
+                Some(CHAINED),
+            ),
+            (
+                "Ends without a tilde: gts.x.core.events.type.v1.",
+                Some(NO_TILDE),
+            ),
+            ("Minor version gts.x.core.events.type.v1.2~.", Some(MINOR)),
+            ("(see gts.x.core.events.type.v1~)", Some(TYPE)),
+            (
+                "(see gts.x.core.events.type.v1~x.core._.orders.v1~).",
+                Some(CHAINED_TYPE),
+            ),
+            (
+                "(gts.x.core.events.type.v1~x.core._.orders.v1)",
+                Some(CHAINED),
+            ),
+            ("**gts.x.core.events.type.v1~**", Some(TYPE)),
+            ("__gts.x.core.events.type.v1~__", Some(TYPE)),
+            (
+                "_gts.x.core.events.type.v1~x.core._.orders.v1_",
+                Some(CHAINED),
+            ),
+            ("*gts.x.core.events.type.v1~*", Some(TYPE)),
+            ("`gts.x.core.events.type.v1~`", Some(TYPE)),
+            ("**`gts.x.core.events.type.v1~`**", Some(TYPE)),
+            ("<gts://gts.x.core.events.type.v1~>", Some(URI)),
+            ("[gts.x.core.events.type.v1~]", Some(TYPE)),
+            ("\"gts.x.core.events.type.v1~\"", Some(TYPE)),
+            ("'gts.x.core.events.type.v1~'", Some(TYPE)),
+            ("gts.x.core.events.type.v1~, then more", Some(TYPE)),
+            (
+                "gts.x.core.events.type.v1~x.core._.orders.v1, then more",
+                Some(CHAINED),
+            ),
+            ("gts.x.core.events.type.v1~; gts.y next", Some(TYPE)),
+            ("Is it gts.x.core.events.type.v1~?", Some(TYPE)),
+            ("Use gts.x.core.events.type.v1~!", Some(TYPE)),
+            ("gts.x.core.events.type.v1~: the base type", Some(TYPE)),
+            (
+                "gts.x.core.events.type.v1~x.core._.orders.v1-",
+                Some(CHAINED),
+            ),
+            (
+                "gts.x.core.events.type.v1~x.core._.orders.v1...",
+                Some(CHAINED),
+            ),
+            ("xgts.x.core.events.type.v1~", None),
+            ("my_gts.x.core.events.type.v1~", None),
+        ];
+        for heuristic in [false, true] {
+            let pattern = if heuristic {
+                &*GTS_DISCOVERY_PATTERN_RELAXED
+            } else {
+                &*GTS_DISCOVERY_PATTERN_WELL_FORMED
+            };
+            for &(phrase, expected) in cases {
+                let found: Vec<&str> = find_candidates(pattern, phrase)
+                    .map(|(_, candidate)| candidate)
+                    .collect();
+                assert_eq!(
+                    found.first().copied(),
+                    expected,
+                    "heuristic={heuristic}: {phrase:?} -> {found:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_scan_markdown_error_sections() {
+        let content = "\
+---
+title: Reference
+---
+Intro gts.y.core.events.intro.v1~ before any heading.
+
+# Events
+
+## Audit
+
+Audit type gts.y.core.events.audit.v1~.
+
+```markdown
+# Not a heading
+```
+
+### Fields #
+
+Field gts.y.core.events.field.v1~.
+
+## Billing
+
+Billing type gts.y.core.events.billing.v1~.
+
+Setext
+======
+
+Top gts.y.core.events.top.v1~.
+
+Nested
+------
+
+Nested gts.y.core.events.nested.v1~.
+";
+        let errors = scan_markdown_content(
+            content,
+            Path::new("doc.md"),
+            &must("x"),
+            false,
+            &[],
+            &mut (),
+        );
+        let sections: Vec<(usize, Option<&str>)> = errors
+            .iter()
+            .map(|e| (e.line, e.section.as_deref()))
+            .collect();
+        assert_eq!(
+            sections,
+            [
+                (4, None),
+                (10, Some("Events > Audit")),
+                (18, Some("Events > Audit > Fields")),
+                (22, Some("Events > Billing")),
+                (27, Some("Setext")),
+                (32, Some("Setext > Nested")),
+            ],
+            "{errors:?}"
+        );
+    }
+
+    #[test]
+    fn test_atx_and_setext_headings() {
+        assert_eq!(atx_heading("## Audit"), Some((2, "Audit")));
+        assert_eq!(atx_heading("   # Closed ##"), Some((1, "Closed")));
+        assert_eq!(atx_heading("# C#"), Some((1, "C#")));
+        assert_eq!(atx_heading("#hashtag"), None);
+        assert_eq!(atx_heading("    # indented code"), None);
+        assert_eq!(atx_heading("####### seven"), None);
+        assert_eq!(setext_level("==="), Some(1));
+        assert_eq!(setext_level("  ---  "), Some(2));
+        assert_eq!(setext_level("-=-"), None);
+        assert!(!is_paragraph_line("- list item"));
+        assert!(!is_paragraph_line("| a | b |"));
+    }
 }
@@ -1,10 +1,14 @@
 //! Format-specific scanners for GTS identifier discovery and validation.
 //!
 //! Each sub-module handles a specific file format:
+//! - `document` — schema/instance/config classification of JSON/YAML documents
 //! - `markdown` — Markdown files with code-block state machine
 //! - `json` — JSON tree-walker
 //! - `yaml` — YAML scanner (delegates to JSON walker via `serde_json::Value`)
+//! - `template` — line-based fallback for templated files (Helm, Jinja)
 
+pub mod document;
 pub mod json;
 pub mod markdown;
+pub mod template;
 pub mod yaml;
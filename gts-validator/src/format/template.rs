@@ -0,0 +1,128 @@
+//! Best-effort scanner for templated files (Helm, Go templates, Jinja).
+//!
+//! Template directives (`{{ .Values.x }}`, `{% if %}`, `{# note #}`) make the
+//! underlying YAML unparseable, so these files are scanned line by line with
+//! the markdown discovery regex instead. Directives are blanked out first,
+//! keeping line and column positions, so only identifiers in literal text
+//! are validated.
+
+use std::path::Path;
+
+use crate::config::{CompiledValidationConfig, MarkdownProfile};
+use crate::error::ValidationError;
+use crate::format::markdown::scan_markdown_with_profile;
+use crate::trace::TraceSink;
+#[cfg(test)]
+use crate::vendor::VendorCheck;
+
+/// Opening and closing delimiters of template directives.
+const DELIMITERS: &[(&str, &str)] = &[("{{", "}}"), ("{%", "%}"), ("{#", "#}")];
+
+/// Whether `content` contains a template directive opener.
+pub fn has_template_directives(content: &str) -> bool {
+    DELIMITERS.iter().any(|(open, _)| content.contains(open))
+}
+
+/// Scan templated content for GTS identifiers outside template directives.
+/// The markdown profile does not apply: templates have no code spans.
+pub fn scan_template(
+    content: &str,
+    path: &Path,
+    config: &CompiledValidationConfig,
+    trace: &mut dyn TraceSink,
+) -> Vec<ValidationError> {
+    scan_markdown_with_profile(
+        &blank_directives(content),
+        path,
+        config,
+        MarkdownProfile::Default,
+        &mut Vec::new(),
+        trace,
+    )
+}
+
+/// Scan templated content for GTS identifiers (one-off convenience wrapper
+/// around [`scan_template`] for tests).
+#[cfg(test)]
+pub fn scan_template_content(
+    content: &str,
+    path: &Path,
+    vendor_check: &VendorCheck,
+    heuristic: bool,
+    skip_tokens: &[String],
+    trace: &mut dyn TraceSink,
+) -> Vec<ValidationError> {
+    crate::format::markdown::scan_markdown_content(
+        &blank_directives(content),
+        path,
+        vendor_check,
+        heuristic,
+        skip_tokens,
+        trace,
+    )
+}
+
+/// Replace every byte of each template directive, delimiters included, with
+/// a space. Line breaks are kept so positions stay the same; an
+/// unterminated directive runs to the end of the content.
+fn blank_directives(content: &str) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some((start, close)) = DELIMITERS
+        .iter()
+        .filter_map(|(open, close)| rest.find(open).map(|i| (i, *close)))
+        .min_by_key(|&(i, _)| i)
+    {
+        out.push_str(&rest[..start]);
+        let directive_len = rest[start + 2..]
+            .find(close)
+            .map_or(rest.len() - start, |end| end + 4);
+        out.extend(
+            rest[start..start + directive_len]
+                .bytes()
+                .map(|b| if b == b'\n' { '\n' } else { ' ' }),
+        );
+        rest = &rest[start + directive_len..];
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::VendorPolicy;
+
+    #[test]
+    fn test_blank_directives() {
+        assert_eq!(
+            blank_directives("id: {{ .Values.id }} # x\n"),
+            "id:                  # x\n"
+        );
+        assert_eq!(
+            blank_directives("{% if a %}\nk: v\n{# multi\nline #}\n"),
+            "          \nk: v\n        \n       \n"
+        );
+        assert_eq!(blank_directives("a: {{ open"), "a:        ");
+        assert_eq!(blank_directives("plain: text\n"), "plain: text\n");
+    }
+
+    #[test]
+    fn test_ids_inside_directives_are_not_scanned() {
+        let content = "\
+image: {{ .Values.image | default \"gts.y.core.pkg.hidden.v1~\" }}
+schema: gts.y.core.pkg.visible.v1~
+";
+        let errors = scan_template_content(
+            content,
+            Path::new("values.yaml.tpl"),
+            &VendorCheck::new(VendorPolicy::MustMatch("x".to_owned()), None),
+            false,
+            &[],
+            &mut (),
+        );
+        assert_eq!(errors.len(), 1, "{errors:?}");
+        assert_eq!(errors[0].raw_value, "gts.y.core.pkg.visible.v1~");
+        assert_eq!((errors[0].line, errors[0].column), (2, 9));
+    }
+}
@@ -1,13 +1,34 @@
 //! YAML file scanner for GTS identifiers.
 //!
 //! Uses tree-walking to scan string values (not keys by default).
+//!
+//! Plain scalars are resolved with the YAML 1.2 core schema: only
+//! `true`/`false` (in any case) are booleans, so YAML 1.1 forms such as
+//! `y`, `on` or `yes` stay strings, while `~`, `null` and empty values are
+//! null and numeric-looking values such as `1.0` are numbers. Such
+//! non-strings under the keys that hold GTS identifiers are never
+//! validated, so they get a `SuspiciousScalarType` warning advising quotes.
 
+use std::collections::HashMap;
 use std::path::Path;
 
 use serde_json::Value;
 
 use crate::error::{ScanError, ScanErrorKind, ValidationError};
-use crate::format::json::walk_json_value;
+use crate::format::json::{JsonScanOptions, walk_document};
+use crate::normalize::normalize_candidate;
+use crate::trace::TraceSink;
+use crate::vendor::VendorCheck;
+
+/// Keys whose values are GTS identifiers or references, so a scalar under
+/// them that YAML resolved to a non-string was most likely meant as one.
+const ID_VALUED_KEYS: &[&str] = &["$id", "$ref", "$schema", "type", "x-gts-ref"];
+
+/// Parser settings: the YAML 1.2 core schema (see the module docs), also
+/// used for YAML documents embedded in string values.
+pub fn parse_options() -> serde_saphyr::Options {
+    serde_saphyr::options! { strict_booleans: true }
+}
 
 fn split_yaml_documents(content: &str) -> Vec<String> {
     let mut documents = Vec::new();
@@ -41,12 +62,21 @@ fn split_yaml_documents(content: &str) -> Vec<String> {
 ///   file-level parse failure if no document could be parsed at all.
 ///
 /// This separation ensures malformed YAML documents are counted in `failed_files`
-/// and never silently mixed into the validation error layer.
+/// and never silently mixed into the validation error layer. Warnings go to
+/// `warnings`, as in [`walk_document`].
+///
+/// Values are scanned as the YAML parser produces them. A plain scalar that
+/// spans lines is folded with a space at each line break, so an ID wrapped
+/// across lines (`gts.x.core.events` / `.type.v1~`) reaches validation as
+/// `gts.x.core.events .type.v1~` and is reported as invalid rather than
+/// silently rejoined.
 pub fn scan_yaml_content(
     content: &str,
     path: &Path,
-    vendor: Option<&str>,
-    scan_keys: bool,
+    vendor_check: &VendorCheck,
+    options: &JsonScanOptions,
+    warnings: &mut Vec<ScanError>,
+    trace: &mut dyn TraceSink,
 ) -> (Vec<ValidationError>, Vec<ScanError>) {
     let mut validation_errors = Vec::new();
     let mut scan_errors = Vec::new();
@@ -54,17 +84,29 @@ pub fn scan_yaml_content(
     // Parse all documents with the YAML stream parser first.
     // If this fails (e.g., one malformed document in the stream), fall back to per-document
     // parsing so valid sibling documents are still validated.
-    let documents: Vec<Value> = match serde_saphyr::from_multiple(content) {
+    let documents: Vec<Value> = match serde_saphyr::from_multiple_with_options(
+        content,
+        parse_options(),
+    ) {
         Ok(docs) => docs,
         Err(stream_err) => {
             let segments = split_yaml_documents(content);
             let mut any_parsed = false;
 
             for (idx, segment) in segments.iter().enumerate() {
-                match serde_saphyr::from_str::<Value>(segment) {
+                match serde_saphyr::from_str_with_options::<Value>(segment, parse_options()) {
                     Ok(doc) => {
                         any_parsed = true;
-                        walk_json_value(&doc, path, vendor, &mut validation_errors, "$", scan_keys);
+                        scalar_type_warnings(&doc, path, "$", options, warnings);
+                        walk_document(
+                            &doc,
+                            path,
+                            vendor_check,
+                            &mut validation_errors,
+                            warnings,
+                            options,
+                            trace,
+                        );
                     }
                     Err(doc_err) => {
                         // Per-document parse failure → ScanError (not ValidationError)
@@ -75,6 +117,7 @@ pub fn scan_yaml_content(
                                 "YAML parse error in document {} of multi-document stream: {doc_err}",
                                 idx + 1
                             ),
+                            original_file: None,
                         });
                     }
                 }
@@ -87,20 +130,289 @@ pub fn scan_yaml_content(
                     file: path.to_owned(),
                     kind: ScanErrorKind::YamlParseError,
                     message: format!("YAML parse error: {stream_err}"),
+                    original_file: None,
                 });
             }
 
+            note_detached_tildes(&mut validation_errors);
             return (validation_errors, scan_errors);
         }
     };
 
-    for value in documents {
-        walk_json_value(&value, path, vendor, &mut validation_errors, "$", scan_keys);
+    // Anchors and merge keys are resolved per document, so annotate each
+    // document's errors against its own text when the split lines up.
+    let segments = split_yaml_documents(content);
+    let per_document_text = segments.len() == documents.len();
+    for (idx, value) in documents.iter().enumerate() {
+        let mut doc_errors = Vec::new();
+        scalar_type_warnings(value, path, "$", options, warnings);
+        walk_document(
+            value,
+            path,
+            vendor_check,
+            &mut doc_errors,
+            warnings,
+            options,
+            trace,
+        );
+        let text = if per_document_text {
+            segments[idx].as_str()
+        } else {
+            content
+        };
+        note_merged_values(text, &mut doc_errors);
+        validation_errors.extend(doc_errors);
     }
 
+    note_detached_tildes(&mut validation_errors);
     (validation_errors, scan_errors)
 }
 
+/// Push a `SuspiciousScalarType` warning for every null, boolean or
+/// number found under an [`ID_VALUED_KEYS`] key of `value`, directly or as
+/// an array entry: an unquoted `~`, `on` or `1.0` that was meant as a
+/// string, and is therefore not validated. A null already reported as a
+/// [`rules::NULL_ID`](crate::rules::NULL_ID) error is not warned about.
+fn scalar_type_warnings(
+    value: &Value,
+    path: &Path,
+    json_path: &str,
+    options: &JsonScanOptions,
+    warnings: &mut Vec<ScanError>,
+) {
+    match value {
+        Value::Object(map) => {
+            for (key, val) in map {
+                let child_path = format!("{json_path}.{key}");
+                if !ID_VALUED_KEYS.contains(&key.as_str()) {
+                    scalar_type_warnings(val, path, &child_path, options, warnings);
+                    continue;
+                }
+                match val {
+                    Value::Array(entries) => {
+                        warnings.extend(entries.iter().enumerate().filter_map(|(i, entry)| {
+                            scalar_type_warning(path, &format!("{child_path}[{i}]"), entry, options)
+                        }));
+                    }
+                    Value::Object(_) => {
+                        scalar_type_warnings(val, path, &child_path, options, warnings);
+                    }
+                    scalar => {
+                        warnings.extend(scalar_type_warning(path, &child_path, scalar, options));
+                    }
+                }
+            }
+        }
+        Value::Array(arr) => {
+            for (i, val) in arr.iter().enumerate() {
+                scalar_type_warnings(val, path, &format!("{json_path}[{i}]"), options, warnings);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The `SuspiciousScalarType` warning for `scalar` at `json_path`, unless
+/// it is a string or a null reported as a `NULL_ID` error.
+fn scalar_type_warning(
+    path: &Path,
+    json_path: &str,
+    scalar: &Value,
+    options: &JsonScanOptions,
+) -> Option<ScanError> {
+    let kind = match scalar {
+        Value::Null
+            if options.scan_null_ids
+                && [".$id", ".$ref", ".x-gts-ref"]
+                    .iter()
+                    .any(|key| json_path.ends_with(key)) =>
+        {
+            return None;
+        }
+        Value::Null => "null (from `~`, `null` or an empty value)",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        _ => return None,
+    };
+    Some(ScanError {
+        file: path.to_owned(),
+        kind: ScanErrorKind::SuspiciousScalarType,
+        message: format!(
+            "{json_path} is {kind}, not a string, so it is not validated; \
+             quote the value if a string was meant (YAML 1.2 core schema)"
+        ),
+        original_file: None,
+    })
+}
+
+/// Explain errors caused by whitespace before an ID's trailing `~`.
+///
+/// A plain scalar keeps a trailing `~` (`type: gts.x.core.events.type.v1~` is
+/// the string it looks like), but `gts.x.core.events.type.v1 ~` is a single
+/// string with a space in it, which fails validation with a confusing version
+/// error. When dropping that whitespace yields a valid type ID, replace the
+/// error with one that tells the author to quote the intended ID.
+fn note_detached_tildes(errors: &mut [ValidationError]) {
+    for err in errors {
+        let Some(body) = err.raw_value.trim_end().strip_suffix('~') else {
+            continue;
+        };
+        let id = body.trim_end();
+        if id.len() == body.len() {
+            continue;
+        }
+        let fixed = format!("{id}~");
+        let is_valid = normalize_candidate(&fixed)
+            .is_ok_and(|candidate| gts::validate_schema_id(&candidate.gts_id).is_ok());
+        if is_valid {
+            err.error = format!(
+                "Unquoted YAML scalar '{}' has whitespace before its trailing '~'; \
+                 write the ID as a quoted string: \"{fixed}\"",
+                err.raw_value
+            );
+        }
+    }
+}
+
+/// Anchor definitions and merge keys found in the raw text of a YAML document.
+#[derive(Default)]
+struct MergeSources {
+    /// Anchor name -> JSON path of the anchored node.
+    anchors: HashMap<String, String>,
+    /// JSON path of a mapping containing `<<: *name` -> merged anchor names.
+    merges: Vec<(String, Vec<String>)>,
+}
+
+/// Best-effort scan of block-style YAML for `&anchor` definitions and `<<`
+/// merge keys, tracking the JSON path of each by indentation. Flow mappings
+/// and other exotic layouts are not followed; they simply yield no notes.
+fn find_merge_sources(text: &str) -> MergeSources {
+    let mut sources = MergeSources::default();
+    // (indent, json path) of the enclosing nodes.
+    let mut stack: Vec<(usize, String)> = Vec::new();
+    let mut seq_counters: HashMap<(String, usize), usize> = HashMap::new();
+
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let mut indent = line.len() - trimmed.len();
+        let mut rest = trimmed;
+
+        while stack.last().is_some_and(|(i, _)| *i >= indent) {
+            stack.pop();
+        }
+        let mut container = stack
+            .last()
+            .map_or_else(|| "$".to_owned(), |(_, p)| p.clone());
+
+        // Sequence item: `- value` / `- key: value`
+        if let Some(item) = rest.strip_prefix("- ").or((rest == "-").then_some("")) {
+            let counter = seq_counters.entry((container.clone(), indent)).or_insert(0);
+            let item_path = format!("{container}[{counter}]");
+            *counter += 1;
+            stack.push((indent, item_path.clone()));
+            let item = item.trim_start();
+            if let Some(name) = anchor_name(item) {
+                sources.anchors.insert(name.to_owned(), item_path.clone());
+            }
+            indent += 2;
+            rest = item;
+            container = item_path;
+        }
+
+        let Some((key, value)) = split_mapping_entry(rest) else {
+            continue;
+        };
+        if key == "<<" {
+            let names: Vec<String> = value
+                .trim_matches(|c| c == '[' || c == ']')
+                .split(',')
+                .filter_map(|alias| alias.trim().strip_prefix('*'))
+                .map(ToOwned::to_owned)
+                .collect();
+            if !names.is_empty() {
+                sources.merges.push((container, names));
+            }
+            continue;
+        }
+        let node_path = format!("{container}.{key}");
+        if let Some(name) = anchor_name(value) {
+            sources.anchors.insert(name.to_owned(), node_path.clone());
+        }
+        stack.push((indent, node_path));
+    }
+
+    sources
+}
+
+/// Split `key: value` (or `key:`) into its unquoted key and trimmed value.
+fn split_mapping_entry(line: &str) -> Option<(&str, &str)> {
+    let (key, value) = match line.find(": ") {
+        Some(pos) => (&line[..pos], &line[pos + 2..]),
+        None => (line.strip_suffix(':')?, ""),
+    };
+    let key = key.trim().trim_matches(|c| c == '"' || c == '\'');
+    (!key.is_empty()).then_some((key, value.trim()))
+}
+
+/// Anchor name of a value starting with `&name`.
+fn anchor_name(value: &str) -> Option<&str> {
+    let name = value.strip_prefix('&')?.split_whitespace().next()?;
+    (!name.is_empty()).then_some(name)
+}
+
+/// Append a note to errors whose value was copied in by a merge key.
+///
+/// The parser expands `<<: *anchor` before the walker sees the document, so a
+/// bad ID inside an anchor is reported at the anchor and again at every merge
+/// site. An error at `<merge path><suffix>` is a merged copy when the anchor's
+/// own path plus the same suffix produced an error for the same raw value.
+fn note_merged_values(text: &str, errors: &mut [ValidationError]) {
+    if !text.contains("<<") {
+        return;
+    }
+    let sources = find_merge_sources(text);
+    if sources.merges.is_empty() {
+        return;
+    }
+
+    let mut notes = Vec::new();
+    for (idx, err) in errors.iter().enumerate() {
+        for (merge_path, names) in &sources.merges {
+            let Some(suffix) = err
+                .json_path
+                .strip_prefix(merge_path.as_str())
+                .filter(|s| s.starts_with('.'))
+            else {
+                continue;
+            };
+            let origin = names.iter().find(|name| {
+                sources
+                    .anchors
+                    .get(name.as_str())
+                    .is_some_and(|anchor_path| {
+                        let origin_path = format!("{anchor_path}{suffix}");
+                        errors
+                            .iter()
+                            .any(|e| e.json_path == origin_path && e.raw_value == err.raw_value)
+                    })
+            });
+            if let Some(name) = origin {
+                notes.push((idx, name.clone()));
+                break;
+            }
+        }
+    }
+    for (idx, name) in notes {
+        errors[idx].error = format!(
+            "{} (value merged from YAML anchor '&{name}' via '<<')",
+            errors[idx].error
+        );
+    }
+}
+
 /// Scan a YAML file for GTS identifiers (file-based convenience wrapper for tests).
 ///
 /// Returns `Err` if the file cannot be read or if any scan-level error occurred.
@@ -108,18 +420,29 @@ pub fn scan_yaml_content(
 #[cfg(test)]
 pub fn scan_yaml_file(
     path: &Path,
-    vendor: Option<&str>,
+    vendor_check: &VendorCheck,
     max_file_size: u64,
     scan_keys: bool,
 ) -> Result<Vec<ValidationError>, ScanError> {
     use crate::strategy::fs::{ScanResult, read_file_bounded};
 
-    let content = match read_file_bounded(path, max_file_size) {
+    let content = match read_file_bounded(path, max_file_size, false) {
         ScanResult::Ok(c) => c,
         ScanResult::Err(e) => return Err(e),
     };
 
-    let (val_errs, scan_errs) = scan_yaml_content(&content, path, vendor, scan_keys);
+    let options = JsonScanOptions {
+        scan_keys,
+        ..JsonScanOptions::default()
+    };
+    let (val_errs, scan_errs) = scan_yaml_content(
+        &content,
+        path,
+        vendor_check,
+        &options,
+        &mut Vec::new(),
+        &mut (),
+    );
     if let Some(first_scan_err) = scan_errs.into_iter().next() {
         return Err(first_scan_err);
     }
@@ -129,9 +452,15 @@ pub fn scan_yaml_file(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::{EmbeddedDocuments, VendorPolicy};
+    use crate::format::json::EmbeddedScan;
     use std::io::Write;
     use tempfile::NamedTempFile;
 
+    fn must(vendor: &str) -> VendorCheck {
+        VendorPolicy::MustMatch(vendor.to_owned()).into()
+    }
+
     fn create_temp_yaml(content: &str) -> NamedTempFile {
         let mut file = NamedTempFile::new().unwrap();
         file.write_all(content.as_bytes()).unwrap();
@@ -144,7 +473,8 @@ mod tests {
 $id: gts://gts.x.core.events.type.v1~
 ";
         let file = create_temp_yaml(content);
-        let errors = scan_yaml_file(file.path(), None, 10_485_760, false).unwrap();
+        let errors =
+            scan_yaml_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, false).unwrap();
         assert!(errors.is_empty(), "Unexpected errors: {errors:?}");
     }
 
@@ -154,7 +484,8 @@ $id: gts://gts.x.core.events.type.v1~
 $id: gts.invalid
 ";
         let file = create_temp_yaml(content);
-        let errors = scan_yaml_file(file.path(), None, 10_485_760, false).unwrap();
+        let errors =
+            scan_yaml_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, false).unwrap();
         assert!(!errors.is_empty());
     }
 
@@ -164,7 +495,8 @@ $id: gts.invalid
 x-gts-ref: gts.x.core.*
 ";
         let file = create_temp_yaml(content);
-        let errors = scan_yaml_file(file.path(), None, 10_485_760, false).unwrap();
+        let errors =
+            scan_yaml_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, false).unwrap();
         assert!(
             errors.is_empty(),
             "Wildcards in x-gts-ref should be allowed"
@@ -177,7 +509,8 @@ x-gts-ref: gts.x.core.*
 x-gts-ref: "*"
 "#;
         let file = create_temp_yaml(content);
-        let errors = scan_yaml_file(file.path(), None, 10_485_760, false).unwrap();
+        let errors =
+            scan_yaml_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, false).unwrap();
         assert!(
             errors.is_empty(),
             "Bare wildcard in x-gts-ref should be skipped"
@@ -192,7 +525,8 @@ properties:
     x-gts-ref: gts.x.core.events.type.v1~
 ";
         let file = create_temp_yaml(content);
-        let errors = scan_yaml_file(file.path(), None, 10_485_760, false).unwrap();
+        let errors =
+            scan_yaml_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, false).unwrap();
         assert!(
             errors.is_empty(),
             "Nested values should be found and validated"
@@ -207,7 +541,8 @@ capabilities:
   - gts.x.core.events.topic.v1~
 ";
         let file = create_temp_yaml(content);
-        let errors = scan_yaml_file(file.path(), None, 10_485_760, false).unwrap();
+        let errors =
+            scan_yaml_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, false).unwrap();
         assert!(
             errors.is_empty(),
             "Array values should be found and validated"
@@ -219,7 +554,7 @@ capabilities:
         // Completely invalid YAML (not parseable as any document) must be a ScanError
         let content = ": : :\n  - [unclosed\n";
         let file = create_temp_yaml(content);
-        let result = scan_yaml_file(file.path(), None, 10_485_760, false);
+        let result = scan_yaml_file(file.path(), &VendorPolicy::Any.into(), 10_485_760, false);
         assert!(
             result.is_err(),
             "Completely invalid YAML must produce a ScanError, not silent success"
@@ -236,8 +571,14 @@ $id: gts.x.core.events.type.v1~
 ---
 $id: gts.invalid
 ";
-        let (val_errs, scan_errs) =
-            scan_yaml_content(content, Path::new("multi.yaml"), None, false);
+        let (val_errs, scan_errs) = scan_yaml_content(
+            content,
+            Path::new("multi.yaml"),
+            &VendorPolicy::Any.into(),
+            &JsonScanOptions::default(),
+            &mut Vec::new(),
+            &mut (),
+        );
         assert!(
             scan_errs.is_empty(),
             "No scan errors expected for well-formed stream: {scan_errs:?}"
@@ -261,8 +602,14 @@ $id: gts.y.core.pkg.mytype.v1~
 ";
         // With vendor "x", both valid docs should produce vendor-mismatch errors.
         // The malformed middle doc must produce a ScanError, not suppress valid docs.
-        let (val_errs, scan_errs) =
-            scan_yaml_content(content, Path::new("multi.yaml"), Some("x"), false);
+        let (val_errs, scan_errs) = scan_yaml_content(
+            content,
+            Path::new("multi.yaml"),
+            &must("x"),
+            &JsonScanOptions::default(),
+            &mut Vec::new(),
+            &mut (),
+        );
         assert!(
             !val_errs.is_empty(),
             "Valid documents must be validated even when a sibling document is malformed, got no errors"
@@ -277,4 +624,438 @@ $id: gts.y.core.pkg.mytype.v1~
             "Malformed doc scan error must have YamlParseError kind"
         );
     }
+
+    #[test]
+    fn test_scan_yaml_merge_key_notes_anchor_source() {
+        let content = "\
+defaults: &event_defaults
+  type: gts.x.core.events.Type.v1~
+  retries: 3
+orders:
+  <<: *event_defaults
+  name: orders
+";
+        let (val_errs, scan_errs) = scan_yaml_content(
+            content,
+            Path::new("merge.yaml"),
+            &VendorPolicy::Any.into(),
+            &JsonScanOptions::default(),
+            &mut Vec::new(),
+            &mut (),
+        );
+        assert!(scan_errs.is_empty(), "{scan_errs:?}");
+
+        let at_anchor: Vec<_> = val_errs
+            .iter()
+            .filter(|e| e.json_path == "$.defaults.type")
+            .collect();
+        let merged: Vec<_> = val_errs
+            .iter()
+            .filter(|e| e.json_path == "$.orders.type")
+            .collect();
+        assert!(!at_anchor.is_empty(), "anchor definition must be reported");
+        assert!(!merged.is_empty(), "merged-in value must still be reported");
+        assert!(
+            at_anchor.iter().all(|e| !e.error.contains("merged from")),
+            "the anchor definition itself is not a merged copy: {at_anchor:?}"
+        );
+        assert!(
+            merged.iter().all(|e| e
+                .error
+                .contains("merged from YAML anchor '&event_defaults'")),
+            "merged copy should name its anchor: {merged:?}"
+        );
+    }
+
+    #[test]
+    fn test_scan_yaml_merge_key_list_and_sequence_items() {
+        let content = "\
+base: &base
+  x-gts-ref: gts.x.core.events.type.v1~
+audit: &audit
+  $id: gts.bad
+handlers:
+  - name: first
+    <<: [*base, *audit]
+";
+        let (val_errs, _) = scan_yaml_content(
+            content,
+            Path::new("merge.yaml"),
+            &VendorPolicy::Any.into(),
+            &JsonScanOptions::default(),
+            &mut Vec::new(),
+            &mut (),
+        );
+        let merged: Vec<_> = val_errs
+            .iter()
+            .filter(|e| e.json_path == "$.handlers[0].$id")
+            .collect();
+        assert!(!merged.is_empty(), "merged-in value must be reported");
+        assert!(
+            merged
+                .iter()
+                .all(|e| e.error.contains("merged from YAML anchor '&audit'")),
+            "{merged:?}"
+        );
+    }
+
+    #[test]
+    fn test_scan_yaml_local_override_is_not_noted() {
+        // The merging mapping overrides the anchored key with its own value.
+        let content = "\
+defaults: &defaults
+  type: gts.x.core.events.type.v1~
+orders:
+  <<: *defaults
+  type: gts.bad
+";
+        let (val_errs, _) = scan_yaml_content(
+            content,
+            Path::new("merge.yaml"),
+            &VendorPolicy::Any.into(),
+            &JsonScanOptions::default(),
+            &mut Vec::new(),
+            &mut (),
+        );
+        assert!(!val_errs.is_empty());
+        assert!(
+            val_errs.iter().all(|e| !e.error.contains("merged from")),
+            "{val_errs:?}"
+        );
+    }
+
+    #[test]
+    fn test_scan_yaml_flow_collections_report_exact_paths() {
+        let content = "\
+flow: {type: gts.y.core.events.type.v1~, other: 'gts.y.core.audit.event.v1~'}
+seq: [gts.y.core.events.type.v1~, &item \"gts.y.core.orders.item.v1~\", *item]
+nested: {inner: {deep: [{t: gts.y.core.deep.thing.v1~}]}}
+block:
+  type: gts.y.core.events.type.v1~
+";
+        let (val_errs, scan_errs) = scan_yaml_content(
+            content,
+            Path::new("flow.yaml"),
+            &must("x"),
+            &JsonScanOptions::default(),
+            &mut Vec::new(),
+            &mut (),
+        );
+        assert!(scan_errs.is_empty(), "{scan_errs:?}");
+
+        let mut paths: Vec<&str> = val_errs.iter().map(|e| e.json_path.as_str()).collect();
+        paths.sort_unstable();
+        assert_eq!(
+            paths,
+            [
+                "$.block.type",
+                "$.flow.other",
+                "$.flow.type",
+                "$.nested.inner.deep[0].t",
+                "$.seq[0]",
+                "$.seq[1]",
+                "$.seq[2]",
+            ]
+        );
+        assert!(
+            val_errs.iter().all(|e| e.error.contains("Vendor mismatch")),
+            "{val_errs:?}"
+        );
+    }
+
+    #[test]
+    fn test_scan_yaml_flow_anchor_with_merge_key() {
+        let content = "\
+list: [&entry {a: gts.y.core.q.thing.v1~}, *entry]
+base: &base {type: gts.y.core.events.type.v1~}
+derived:
+  <<: *base
+";
+        let (val_errs, scan_errs) = scan_yaml_content(
+            content,
+            Path::new("flow.yaml"),
+            &must("x"),
+            &JsonScanOptions::default(),
+            &mut Vec::new(),
+            &mut (),
+        );
+        assert!(scan_errs.is_empty(), "{scan_errs:?}");
+        let mut paths: Vec<&str> = val_errs.iter().map(|e| e.json_path.as_str()).collect();
+        paths.sort_unstable();
+        assert_eq!(
+            paths,
+            [
+                "$.base.type",
+                "$.derived.type",
+                "$.list[0].a",
+                "$.list[1].a"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scan_yaml_trailing_tilde_scalars() {
+        let content = "\
+plain: gts.x.core.events.type.v1~
+single: 'gts.x.core.events.type.v1~'
+double: \"gts.x.core.events.type.v1~\"
+null_value: ~
+detached: gts.x.core.events.type.v1 ~
+";
+        let (val_errs, scan_errs) = scan_yaml_content(
+            content,
+            Path::new("tilde.yaml"),
+            &must("x"),
+            &JsonScanOptions::default(),
+            &mut Vec::new(),
+            &mut (),
+        );
+        assert!(scan_errs.is_empty(), "{scan_errs:?}");
+        assert_eq!(val_errs.len(), 1, "{val_errs:?}");
+        assert_eq!(val_errs[0].json_path, "$.detached");
+        assert_eq!(val_errs[0].rule_id, crate::rules::INVALID_ID);
+        assert!(
+            val_errs[0]
+                .error
+                .contains("write the ID as a quoted string: \"gts.x.core.events.type.v1~\""),
+            "{val_errs:?}"
+        );
+    }
+
+    #[test]
+    fn test_scan_yaml_multiline_plain_scalar_is_reported() {
+        // The plain scalar folds to "gts.x.core.events .type.v1~".
+        let content = "\
+folded: gts.x.core.events
+  .type.v1~
+";
+        let (val_errs, scan_errs) = scan_yaml_content(
+            content,
+            Path::new("folded.yaml"),
+            &must("x"),
+            &JsonScanOptions::default(),
+            &mut Vec::new(),
+            &mut (),
+        );
+        assert!(scan_errs.is_empty(), "{scan_errs:?}");
+        assert_eq!(val_errs.len(), 1, "{val_errs:?}");
+        assert_eq!(val_errs[0].json_path, "$.folded");
+        assert_eq!(val_errs[0].raw_value, "gts.x.core.events .type.v1~");
+        assert_eq!(val_errs[0].rule_id, crate::rules::INVALID_ID);
+    }
+
+    #[test]
+    fn test_scan_yaml_xgts_ref_list_forms() {
+        let content = "\
+flow:
+  x-gts-ref: [gts.x.core.*, gts.acme.billing.*, gts.x.bad]
+block:
+  x-gts-ref:
+    - gts.x.core.*
+    - gts.x.bad
+scalar:
+  x-gts-ref: |
+    gts.x.core.*
+    gts.acme.billing.*
+    gts.x.bad
+comma:
+  x-gts-ref: gts.x.core.*, gts.x.bad
+";
+        let options = JsonScanOptions {
+            split_ref_lists: true,
+            ..JsonScanOptions::default()
+        };
+        let mut warnings = Vec::new();
+        let (val_errs, scan_errs) = scan_yaml_content(
+            content,
+            Path::new("refs.yaml"),
+            &VendorPolicy::Any.into(),
+            &options,
+            &mut warnings,
+            &mut (),
+        );
+        assert!(scan_errs.is_empty(), "{scan_errs:?}");
+        let paths: Vec<&str> = val_errs.iter().map(|e| e.json_path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "$.block.x-gts-ref[1]",
+                "$.comma.x-gts-ref[1]",
+                "$.flow.x-gts-ref[2]",
+                "$.scalar.x-gts-ref[2]",
+            ]
+        );
+        assert!(val_errs.iter().all(|e| e.raw_value == "gts.x.bad"));
+
+        // Only the string forms are worth moving to an array.
+        let warned: Vec<&str> = warnings
+            .iter()
+            .map(|w| {
+                assert_eq!(w.kind, ScanErrorKind::RefListString);
+                w.message.split(' ').next().unwrap()
+            })
+            .collect();
+        assert_eq!(warned, ["$.comma.x-gts-ref", "$.scalar.x-gts-ref"]);
+    }
+
+    #[test]
+    fn test_scan_yaml_embedded_documents() {
+        let content = r#"
+kind: ConfigMap
+data:
+  config.json: '{"type": "gts.x.core.events.type.v1", "nested": "kind: gts.x.bad"}'
+  notes: '{"type": "gts.y.core.events.type.v1~"}'
+"#;
+        let scan = |embedded: Option<EmbeddedDocuments>| {
+            let options = JsonScanOptions {
+                embedded: embedded.map(|embedded| EmbeddedScan::new(&embedded).unwrap()),
+                ..JsonScanOptions::default()
+            };
+            let (val_errs, scan_errs) = scan_yaml_content(
+                content,
+                Path::new("configmap.yaml"),
+                &must("x"),
+                &options,
+                &mut Vec::new(),
+                &mut (),
+            );
+            assert!(scan_errs.is_empty(), "{scan_errs:?}");
+            val_errs
+                .into_iter()
+                .map(|e| e.json_path)
+                .collect::<Vec<_>>()
+        };
+
+        assert!(scan(None).is_empty(), "embedded documents are opt-in");
+
+        // `notes` is neither selected by key nor long enough, and `nested`
+        // is not selected either.
+        let embedded = EmbeddedDocuments {
+            keys: vec!["data.*.json".to_owned()],
+            ..EmbeddedDocuments::default()
+        };
+        assert_eq!(
+            scan(Some(embedded.clone())),
+            ["$.data.config.json -> $.type"]
+        );
+
+        // Keys match within the innermost document; its YAML text is
+        // scanned at the second level.
+        let keys = vec!["data.*.json".to_owned(), "nested".to_owned()];
+        assert_eq!(
+            scan(Some(EmbeddedDocuments {
+                keys: keys.clone(),
+                ..embedded
+            })),
+            [
+                "$.data.config.json -> $.nested -> $.kind",
+                "$.data.config.json -> $.type"
+            ]
+        );
+        assert_eq!(
+            scan(Some(EmbeddedDocuments {
+                max_depth: 1,
+                keys,
+                ..embedded
+            })),
+            ["$.data.config.json -> $.type"]
+        );
+
+        assert_eq!(
+            scan(Some(EmbeddedDocuments {
+                min_size: 1,
+                ..embedded
+            })),
+            [
+                "$.data.config.json -> $.nested -> $.kind",
+                "$.data.config.json -> $.type",
+                "$.data.notes -> $.type"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scan_yaml_suspicious_scalar_types() {
+        let content = "\
+vendor: x
+placeholder:
+  type: ~
+  $ref: gts://gts.x.core.events.type.v1~
+flag:
+  type: on
+  x-gts-ref: y
+versions:
+  type: 1.0
+  x-gts-ref: [v1.0, 2, gts.x.core.*, null]
+  $id: null
+";
+        let scan = |scan_null_ids| {
+            let options = JsonScanOptions {
+                scan_null_ids,
+                ..JsonScanOptions::default()
+            };
+            let mut warnings = Vec::new();
+            let (val_errs, scan_errs) = scan_yaml_content(
+                content,
+                Path::new("scalars.yaml"),
+                &must("x"),
+                &options,
+                &mut warnings,
+                &mut (),
+            );
+            assert!(scan_errs.is_empty(), "{scan_errs:?}");
+            let warned: Vec<String> = warnings
+                .iter()
+                .map(|w| {
+                    assert_eq!(w.kind, ScanErrorKind::SuspiciousScalarType);
+                    let json_path = w.message.split(' ').next().unwrap();
+                    json_path.to_owned()
+                })
+                .collect();
+            (val_errs, warned)
+        };
+
+        // YAML 1.2 core schema: `on`, `y` and `v1.0` stay strings, so only
+        // the null and numeric values are warned about.
+        let (val_errs, warned) = scan(false);
+        assert!(val_errs.is_empty(), "{val_errs:?}");
+        assert_eq!(
+            warned,
+            [
+                "$.placeholder.type",
+                "$.versions.$id",
+                "$.versions.type",
+                "$.versions.x-gts-ref[1]",
+                "$.versions.x-gts-ref[3]",
+            ]
+        );
+        assert!(
+            warnings_mention_quoting(content),
+            "warning should advise quoting"
+        );
+
+        // A null ID is already an error with scan_null_ids, so not a warning.
+        let (val_errs, warned) = scan(true);
+        assert!(val_errs.iter().any(|e| e.rule_id == crate::rules::NULL_ID));
+        assert!(!warned.contains(&"$.versions.$id".to_owned()), "{warned:?}");
+        assert!(
+            warned.contains(&"$.placeholder.type".to_owned()),
+            "{warned:?}"
+        );
+    }
+
+    fn warnings_mention_quoting(content: &str) -> bool {
+        let mut warnings = Vec::new();
+        scan_yaml_content(
+            content,
+            Path::new("scalars.yaml"),
+            &must("x"),
+            &JsonScanOptions::default(),
+            &mut warnings,
+            &mut (),
+        );
+        warnings
+            .iter()
+            .all(|w| w.message.contains("quote the value if a string was meant"))
+    }
 }
@@ -25,29 +25,71 @@
 //! println!("OK: {}", report.ok);
 //! ```
 
+mod annotation;
+#[doc(hidden)]
+pub mod bench;
 mod config;
+mod effective;
 mod error;
+mod fix;
 mod format;
+#[cfg(feature = "lsp-types")]
+mod lsp;
+mod metadata;
 mod normalize;
+mod outcome;
 pub mod output;
+mod overrides;
+mod redact;
+mod registry;
 mod report;
+mod rewrite;
+pub mod rules;
+mod schemas;
+mod stats;
 mod strategy;
+mod trace;
 mod validator;
+mod vendor;
 
-pub use config::{DiscoveryMode, FsSourceConfig, ValidationConfig, VendorPolicy};
-pub use error::{ScanError, ScanErrorKind, ValidationError};
-pub use report::ValidationReport;
+pub use annotation::AnnotationFormat;
+pub use config::{
+    DiscoveryMode, EmbeddedDocuments, FsSourceConfig, MarkdownProfile, RuleSeverity,
+    ValidationConfig, VendorPolicy, VendorScope,
+};
+pub use effective::{ConfigSource, EffectiveConfig, Sourced};
+pub use error::{CoverageError, ScanError, ScanErrorKind, ValidationError};
+pub use fix::{TextEdit, apply_edits, write_edits};
+pub use format::document::DocumentKind;
+pub use metadata::{InferredVendor, VendorSource, infer_vendor};
+pub use outcome::{FoundId, IdKind, IdRole, ScanOutcome, ScanStats, duplicate_definitions};
+pub use registry::RegistrySource;
+pub use report::{
+    DetailedValidationReport, ErrorDistribution, ExceededThreshold, ExitCodeStrategy,
+    ValidationReport,
+};
+pub use rewrite::{FileRewrite, RewriteReport, UnlocatedOccurrence, rewrite_fs};
+pub use stats::{IdCounts, IdStats, StatsGrouping, StatsSegment, VendorIdStats};
+pub use strategy::ContentFormat;
+pub use trace::{
+    CandidateOutcome, DiscoverySource, DiscoveryTrace, FileTrace, SkipReason, TracedCandidate,
+};
+pub use validator::suggest_fix;
+pub use vendor::{VendorViolation, extract_vendor, extract_vendors};
 
-use strategy::ContentFormat;
-use strategy::fs::{ScanResult, content_format_for, find_files, read_file_bounded};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
-fn format_allow_list_mismatch(allowed: &[String], found: &str) -> String {
-    format!(
-        "Vendor mismatch: expected one of '{}', found '{}'",
-        allowed.join(", "),
-        found
-    )
-}
+use config::CompiledValidationConfig;
+use effective::invalid_setting;
+use overrides::DirectoryOverrides;
+use redact::PathRedaction;
+use registry::Registry;
+use strategy::fs::{
+    FoundFiles, ScanResult, content_format_for, explain_empty_root, find_files, list_files,
+    read_file_bounded,
+};
+use trace::TraceSink;
 
 /// Validate GTS identifiers in files on disk.
 ///
@@ -68,60 +110,149 @@ pub fn validate_fs(
     fs_config: &FsSourceConfig,
     validation_config: &ValidationConfig,
 ) -> anyhow::Result<ValidationReport> {
-    if fs_config.paths.is_empty() {
-        anyhow::bail!("No paths provided for validation");
-    }
+    scan_fs(fs_config, validation_config, None, false).map(|detailed| detailed.report)
+}
 
-    for path in &fs_config.paths {
-        if !path.exists() {
-            anyhow::bail!("Path does not exist: {}", path.display());
-        }
+/// Like [`validate_fs`], but over `files` instead of the files found under
+/// `fs_config.paths`, e.g. the files a build system knows have changed.
+///
+/// Nothing is walked: `fs_config.paths` is ignored. Extension routing,
+/// `max_file_size`, the scan limits and `exclude` still apply, the latter
+/// as a safety net against listing files that should never be scanned.
+/// Over the files [`validate_fs`] would find, the report is the same.
+/// Listed paths that are not regular files are reported as scan errors.
+/// Only `fs_config.registry_dirs` are loaded as registries; `.gts-spec`
+/// directories are not discovered.
+///
+/// # Errors
+///
+/// Returns an error if a validation setting is invalid, as [`validate_fs`]
+/// does.
+pub fn validate_files(
+    files: &[PathBuf],
+    fs_config: &FsSourceConfig,
+    validation_config: &ValidationConfig,
+) -> anyhow::Result<ValidationReport> {
+    scan_fs(fs_config, validation_config, Some(files), false).map(|detailed| detailed.report)
+}
+
+/// Like [`validate_fs`], but also returns the [`ScanOutcome`] of every
+/// scanned file: its errors and the GTS identifiers it defines and
+/// references.
+///
+/// # Errors
+///
+/// The same as [`validate_fs`].
+pub fn validate_fs_detailed(
+    fs_config: &FsSourceConfig,
+    validation_config: &ValidationConfig,
+) -> anyhow::Result<DetailedValidationReport> {
+    scan_fs(fs_config, validation_config, None, true)
+}
+
+/// Errors about the run as a whole rather than a single file: duplicate
+/// definitions across files, or of registry identifiers, and invalid
+/// schemas in the schemas directory.
+fn run_errors(
+    validation_config: &ValidationConfig,
+    compiled: &CompiledValidationConfig,
+    registry: &Registry,
+    outcomes: &[ScanOutcome],
+) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+    if validation_config.check_duplicate_ids {
+        errors.extend(outcome::duplicate_definitions_of(
+            &registry.definitions,
+            outcomes,
+        ));
     }
+    if let Some(schemas) = &compiled.json_options.schemas {
+        errors.extend_from_slice(schemas.load_errors());
+    }
+    errors
+}
 
-    let (files, mut scan_errors) = find_files(fs_config);
+/// Shared implementation of [`validate_fs`], [`validate_fs_detailed`] and
+/// [`validate_files`], which passes its `listed` files. Per-file outcomes
+/// and registry definitions are returned only with `keep_outcomes`.
+fn scan_fs(
+    fs_config: &FsSourceConfig,
+    validation_config: &ValidationConfig,
+    listed: Option<&[PathBuf]>,
+    keep_outcomes: bool,
+) -> anyhow::Result<DetailedValidationReport> {
+    let found = discover(fs_config, listed)?;
+    let redaction =
+        PathRedaction::new(validation_config).map_err(invalid_setting("sensitive_paths"))?;
+    let FoundFiles {
+        files,
+        mut scan_errors,
+        mut warnings,
+        registry_dirs,
+    } = found;
 
     if files.is_empty() && scan_errors.is_empty() {
-        return Ok(ValidationReport {
-            scanned_files: 0,
-            failed_files: 0,
-            ok: true,
-            validation_errors: vec![],
-            scan_errors: vec![],
-        });
+        return Ok(empty_run(
+            fs_config,
+            validation_config,
+            warnings,
+            redaction.as_ref(),
+        ));
     }
 
-    let heuristic = validation_config.discovery_mode == DiscoveryMode::Heuristic;
-    // For AllowList, pass a sentinel vendor that no real GTS ID can match.
-    // This causes validate_candidate to emit "Vendor mismatch" for every non-example
-    // vendor, and apply_allow_list_filter then removes the allowed ones — leaving only
-    // genuinely disallowed vendors as errors.
-    let effective_vendor = effective_vendor_for_scanning(&validation_config.vendor_policy);
+    let compiled = CompiledValidationConfig::new(validation_config)?;
+    let mut registry = Registry::load(&registry_dirs, fs_config, &compiled);
+    let mut overrides =
+        DirectoryOverrides::new(fs_config, validation_config, compiled, listed.is_some())?;
 
     let mut validation_errors = Vec::new();
     let mut scanned_files: usize = 0;
+    let mut scanned_by_format: BTreeMap<String, usize> = BTreeMap::new();
     // Discovery-stage failures (walk errors, boundary violations, canonicalization errors)
     // are already in scan_errors from find_files. Count them as failed files upfront.
     let mut failed_files: usize = scan_errors.len();
     let mut total_bytes: u64 = 0;
+    // Identifier statistics are aggregated from the trace, so collect one
+    // for them too.
+    let mut discovery_trace = (validation_config.trace_discovery
+        || validation_config.id_stats.is_some())
+    .then(DiscoveryTrace::default);
+
+    // Cross-file checks work from the identifiers each file defines and
+    // references, which are derived from the scanners' candidates.
+    let collect_outcomes = keep_outcomes || validation_config.check_duplicate_ids;
+    let mut outcomes = Vec::new();
+
+    let mut truncated = false;
 
-    'files: for file_path in &files {
+    'files: for (index, file_path) in files.iter().enumerate() {
+        // Files from this one on, when a limit stops the scan here.
+        let not_scanned = files.len() - index;
         if scanned_files + failed_files >= fs_config.max_files {
             scan_errors.push(ScanError {
                 file: file_path.clone(),
                 kind: ScanErrorKind::LimitExceeded,
                 message: format!(
-                    "Scan aborted: max_files limit ({}) reached; remaining files not scanned",
+                    "Scan aborted: max_files limit ({}) reached; {not_scanned} file(s) were not scanned",
                     fs_config.max_files
                 ),
+                original_file: None,
             });
             failed_files += 1;
+            truncated = true;
             break;
         }
 
-        let content = match read_file_bounded(file_path, fs_config.max_file_size) {
-            ScanResult::Ok(c) => c,
-            ScanResult::Err(e) => {
-                scan_errors.push(e);
+        let by_extension = content_format_for(file_path);
+        let content = match read_for_scan(file_path, by_extension.is_some(), fs_config) {
+            Ok(content) => content,
+            Err(Unread::Skip) => continue,
+            Err(Unread::Warn(warning)) => {
+                warnings.push(warning);
+                continue;
+            }
+            Err(Unread::Fail(error)) => {
+                scan_errors.push(error);
                 failed_files += 1;
                 continue;
             }
@@ -133,30 +264,43 @@ pub fn validate_fs(
                 file: file_path.clone(),
                 kind: ScanErrorKind::LimitExceeded,
                 message: format!(
-                    "Scan aborted: max_total_bytes limit ({}) reached; remaining files not scanned",
+                    "Scan aborted: max_total_bytes limit ({}) reached; {not_scanned} file(s) were not scanned",
                     fs_config.max_total_bytes
                 ),
+                original_file: None,
             });
             failed_files += 1;
+            truncated = true;
             break;
         }
         total_bytes = total_bytes.saturating_add(file_bytes);
 
-        let vendor = effective_vendor.as_deref();
-        let file_errors = match content_format_for(file_path) {
-            Some(ContentFormat::Markdown) => format::markdown::scan_markdown_content(
-                &content,
-                file_path,
-                vendor,
-                heuristic,
-                &validation_config.skip_tokens,
-            ),
-            Some(ContentFormat::Json) => {
+        let file_config = overrides.config_for(file_path)?;
+        let compiled = &file_config.compiled;
+        let mut candidates = Vec::new();
+        let count_ids = compiled.id_limits.is_enabled();
+        let trace: &mut dyn TraceSink =
+            if discovery_trace.is_some() || collect_outcomes || count_ids {
+                &mut candidates
+            } else {
+                &mut ()
+            };
+        // Unrecognized extension and content: counted as skipped below.
+        let Some(format) = by_extension.or_else(|| ContentFormat::sniff(&content)) else {
+            continue;
+        };
+        let file_errors = match format {
+            ContentFormat::Markdown => {
+                format::markdown::scan_markdown(&content, file_path, compiled, &mut warnings, trace)
+            }
+            ContentFormat::Json => {
                 match format::json::scan_json_content(
                     &content,
                     file_path,
-                    vendor,
-                    validation_config.scan_keys,
+                    &compiled.vendor_check,
+                    &compiled.json_options,
+                    &mut warnings,
+                    trace,
                 ) {
                     Ok(errs) => errs,
                     Err(scan_err) => {
@@ -166,147 +310,297 @@ pub fn validate_fs(
                     }
                 }
             }
-            Some(ContentFormat::Yaml) => {
-                let (val_errs, yaml_scan_errs) = format::yaml::scan_yaml_content(
-                    &content,
-                    file_path,
-                    vendor,
-                    validation_config.scan_keys,
-                );
+            ContentFormat::Yaml => {
+                let (val_errs, yaml_scan_errs) =
+                    scan_yaml_file(&content, file_path, compiled, &mut warnings, trace);
                 if !yaml_scan_errs.is_empty() {
                     failed_files += 1;
                     scan_errors.extend(yaml_scan_errs);
                 }
                 val_errs
             }
-            None => continue,
+            ContentFormat::Template => {
+                format::template::scan_template(&content, file_path, compiled, trace)
+            }
         };
+        let file_errors = file_config.apply_severities(file_errors, &mut warnings);
 
         scanned_files += 1;
+        *scanned_by_format
+            .entry(format.name().to_owned())
+            .or_default() += 1;
 
-        // For AllowList: filter out errors where the vendor IS in the allow list.
-        // The sentinel vendor caused mismatches for all vendors; remove the allowed ones.
-        let file_errors = apply_allow_list_filter(file_errors, &validation_config.vendor_policy);
+        if collect_outcomes || count_ids {
+            let outcome = ScanOutcome::new(file_path.clone(), file_errors.clone(), &candidates);
+            warnings.extend(compiled.id_limits.check(&outcome));
+            if collect_outcomes {
+                outcomes.push(outcome);
+            }
+        }
         validation_errors.extend(file_errors);
+        if let Some(discovery_trace) = &mut discovery_trace {
+            discovery_trace.files.push(FileTrace {
+                file: file_path.clone(),
+                candidates,
+            });
+        }
     }
 
-    let ok = validation_errors.is_empty() && scan_errors.is_empty();
-    Ok(ValidationReport {
+    let base = overrides.base();
+    let run_errors = run_errors(validation_config, &base.compiled, &registry, &outcomes);
+    validation_errors.extend(base.apply_severities(run_errors, &mut warnings));
+
+    let (within_thresholds, exceeded_thresholds) =
+        report::check_rule_thresholds(&validation_errors, &validation_config.rule_thresholds);
+    let ok = within_thresholds && scan_errors.is_empty();
+    // Every discovered file was either scanned or skipped (read/parse
+    // failure, or not reached after a limit aborted the scan).
+    let discovered_files = files.len();
+    let id_stats = validation_config.id_stats.map(|segment| {
+        IdStats::from_trace(
+            discovery_trace
+                .as_ref()
+                .unwrap_or(&DiscoveryTrace::default()),
+            segment,
+        )
+    });
+    if !validation_config.trace_discovery {
+        discovery_trace = None;
+    }
+    let mut report = ValidationReport {
+        discovered_files,
         scanned_files,
+        scanned_by_format,
+        skipped_files: discovered_files - scanned_files,
         failed_files,
+        truncated,
         ok,
         validation_errors,
+        errors_truncated: false,
+        omitted_errors: 0,
+        suppressed_errors: 0,
+        empty_scan: false,
         scan_errors,
+        warnings,
+        exceeded_thresholds,
+        discovery_trace,
+        id_stats,
+        inferred_vendor: None,
+        registry: registry.sources,
+        applied_overrides: overrides.into_applied(),
+    };
+    fail_empty_scan(&mut report, fs_config, validation_config);
+    finish_errors(&mut report, validation_config);
+    if !keep_outcomes {
+        outcomes.clear();
+        registry.definitions.clear();
+    }
+    if let Some(redaction) = &redaction {
+        redaction.apply(&mut report, &mut outcomes);
+    }
+    Ok(DetailedValidationReport {
+        report,
+        outcomes,
+        registry_definitions: registry.definitions,
     })
 }
 
-/// Determine the effective vendor string to pass to scanners for a given policy.
-///
-/// - `Any` → `None` (no vendor enforcement).
-/// - `MustMatch(v)` → `Some(v)` (scanner enforces exact match directly).
-/// - `AllowList(_)` → `Some("\x00")` (sentinel that no real GTS vendor can match).
-///   GTS vendors must be lowercase alphanumeric, so `\x00` is guaranteed to never
-///   equal any real vendor. This causes `validate_candidate` to emit "Vendor mismatch"
-///   for every non-example vendor, and `apply_allow_list_filter` then removes the
-///   vendors that are in the allow list — leaving only genuinely disallowed vendors.
-fn effective_vendor_for_scanning(policy: &VendorPolicy) -> Option<String> {
-    match policy {
-        VendorPolicy::Any => None,
-        VendorPolicy::MustMatch(v) => Some(v.clone()),
-        VendorPolicy::AllowList(_) => Some("\x00".to_owned()),
+/// Cap the validation errors of `report` per file and in total, then
+/// attach their help, as `validation_config` says.
+fn finish_errors(report: &mut ValidationReport, validation_config: &ValidationConfig) {
+    if let Some(max) = validation_config.max_errors_per_file {
+        report.cap_errors_per_file(max);
+    }
+    if let Some(max) = validation_config.max_reported_errors {
+        report.truncate_errors(max);
+    }
+    if validation_config.include_help {
+        report.attach_help();
     }
 }
 
-/// For `VendorPolicy::AllowList`, remove validation errors whose vendor IS in the list.
-///
-/// Scanners run with a sentinel vendor (`\x00`) that generates "Vendor mismatch" for
-/// every non-example vendor. This function retains only errors where the vendor is NOT
-/// in the allow list — i.e., genuinely disallowed vendors produce errors.
-fn apply_allow_list_filter(
-    errors: Vec<ValidationError>,
-    policy: &VendorPolicy,
-) -> Vec<ValidationError> {
-    let VendorPolicy::AllowList(allowed) = policy else {
-        return errors;
+/// Why a discovered file was not read for scanning.
+enum Unread {
+    /// Not a GTS source; counted as skipped only.
+    Skip,
+    /// Skipped with this warning.
+    Warn(ScanError),
+    /// Failed with this scan error.
+    Fail(ScanError),
+}
+
+/// Read `file_path` for scanning; `by_extension` says whether its format
+/// is known from its extension rather than to be sniffed.
+fn read_for_scan(
+    file_path: &Path,
+    by_extension: bool,
+    fs_config: &FsSourceConfig,
+) -> Result<String, Unread> {
+    let error = match read_file_bounded(
+        file_path,
+        fs_config.max_file_size,
+        fs_config.transcode_utf16,
+    ) {
+        ScanResult::Ok(content) => return Ok(content),
+        ScanResult::Err(error) => error,
     };
+    Err(match error.kind {
+        // A file picked up for sniffing that turns out to be binary or
+        // huge is not a GTS source: skip it rather than fail the run.
+        ScanErrorKind::InvalidEncoding
+        | ScanErrorKind::FileTooLarge
+        | ScanErrorKind::BinaryContent
+        | ScanErrorKind::Utf16Encoding
+            if !by_extension =>
+        {
+            Unread::Skip
+        }
+        // A mislabeled binary or UTF-16 file is skipped with a warning.
+        ScanErrorKind::BinaryContent | ScanErrorKind::Utf16Encoding => Unread::Warn(error),
+        _ => Unread::Fail(error),
+    })
+}
 
-    errors
-        .into_iter()
-        .filter_map(|mut e| {
-            // Keep the error only if it is NOT a vendor-mismatch for an allowed vendor.
-            // Vendor-mismatch errors contain "Vendor mismatch" in the message.
-            // Extract the actual vendor from normalized_id (first segment before '.').
-            if !e.error.contains("Vendor mismatch") {
-                return Some(e); // non-vendor errors always kept
-            }
-            // normalized_id format: "gts.<vendor>.<rest>..."
-            // The vendor is the second dot-separated segment (index 1).
-            let id_vendor = e.normalized_id.split('.').nth(1).unwrap_or("");
-            if id_vendor == "*" {
-                return None;
-            }
-            if allowed.iter().any(|a| a == id_vendor) {
-                return None;
-            }
-            e.error = format_allow_list_mismatch(allowed, id_vendor);
-            Some(e)
-        })
-        .collect()
+/// The `listed` files, filtered, or else the files found under the scan
+/// paths of `fs_config`, which must exist.
+fn discover(fs_config: &FsSourceConfig, listed: Option<&[PathBuf]>) -> anyhow::Result<FoundFiles> {
+    if let Some(files) = listed {
+        return Ok(list_files(files, fs_config));
+    }
+    check_scan_paths(fs_config).map_err(invalid_setting("paths"))?;
+    Ok(find_files(fs_config))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::path::PathBuf;
-
-    #[test]
-    fn test_apply_allow_list_filter_rewrites_disallowed_vendor_message() {
-        let errors = vec![ValidationError {
-            file: PathBuf::from("docs/test.md"),
-            line: 1,
-            column: 1,
-            json_path: String::new(),
-            raw_value: "gts.w.core.org.department.v1~".to_owned(),
-            normalized_id: "gts.w.core.org.department.v1~".to_owned(),
-            error: "Vendor mismatch: expected '', found 'w'".to_owned(),
-            context: "gts.w.core.org.department.v1~".to_owned(),
-        }];
-
-        let filtered = apply_allow_list_filter(
-            errors,
-            &VendorPolicy::AllowList(vec!["x".to_owned(), "cf".to_owned()]),
-        );
-
-        assert_eq!(filtered.len(), 1);
-        assert_eq!(
-            filtered[0].error,
-            "Vendor mismatch: expected one of 'x, cf', found 'w'"
-        );
+/// Check that `fs_config` has scan paths and that they all exist.
+fn check_scan_paths(fs_config: &FsSourceConfig) -> anyhow::Result<()> {
+    if fs_config.paths.is_empty() {
+        anyhow::bail!("No paths provided for validation");
     }
+    for path in &fs_config.paths {
+        if !path.exists() {
+            anyhow::bail!("Path does not exist: {}", path.display());
+        }
+    }
+    Ok(())
+}
+
+/// The result of a run that found no file to scan.
+fn empty_run(
+    fs_config: &FsSourceConfig,
+    validation_config: &ValidationConfig,
+    warnings: Vec<ScanError>,
+    redaction: Option<&PathRedaction>,
+) -> DetailedValidationReport {
+    let mut report = empty_report(validation_config, warnings);
+    fail_empty_scan(&mut report, fs_config, validation_config);
+    if let Some(redaction) = redaction {
+        redaction.apply(&mut report, &mut []);
+    }
+    DetailedValidationReport {
+        report,
+        ..DetailedValidationReport::default()
+    }
+}
+
+/// The report of a run that found no file to scan.
+fn empty_report(
+    validation_config: &ValidationConfig,
+    warnings: Vec<ScanError>,
+) -> ValidationReport {
+    ValidationReport {
+        discovered_files: 0,
+        scanned_files: 0,
+        scanned_by_format: BTreeMap::new(),
+        skipped_files: 0,
+        failed_files: 0,
+        truncated: false,
+        ok: true,
+        validation_errors: vec![],
+        errors_truncated: false,
+        omitted_errors: 0,
+        suppressed_errors: 0,
+        empty_scan: false,
+        scan_errors: vec![],
+        warnings,
+        exceeded_thresholds: vec![],
+        discovery_trace: validation_config
+            .trace_discovery
+            .then(DiscoveryTrace::default),
+        id_stats: validation_config.id_stats.map(|_| IdStats::default()),
+        inferred_vendor: None,
+        registry: Vec::new(),
+        applied_overrides: Vec::new(),
+    }
+}
 
-    #[test]
-    fn test_apply_allow_list_filter_skips_wildcard_vendor_false_positive() {
-        let errors = vec![ValidationError {
-            file: PathBuf::from("docs/test.yaml"),
-            line: 0,
-            column: 0,
-            json_path: "$.x-gts-ref".to_owned(),
-            raw_value: "gts.*".to_owned(),
-            normalized_id: "gts.*".to_owned(),
-            error: "Vendor mismatch: expected '', found '*'".to_owned(),
-            context: "$.x-gts-ref".to_owned(),
-        }];
-
-        let filtered = apply_allow_list_filter(
-            errors,
-            &VendorPolicy::AllowList(vec![
-                "cf".to_owned(),
-                "vendor".to_owned(),
-                "example".to_owned(),
-                "fabrikam".to_owned(),
-            ]),
-        );
-
-        assert!(filtered.is_empty());
+/// With `fail_on_empty_scan`, fail a run that scanned no file: set
+/// `empty_scan` and explain, per scan path, why it contributed nothing.
+fn fail_empty_scan(
+    report: &mut ValidationReport,
+    fs_config: &FsSourceConfig,
+    validation_config: &ValidationConfig,
+) {
+    if !validation_config.fail_on_empty_scan || report.scanned_files > 0 {
+        return;
+    }
+    report.empty_scan = true;
+    report.ok = false;
+    for root in &fs_config.paths {
+        report.scan_errors.push(ScanError {
+            file: root.clone(),
+            kind: ScanErrorKind::EmptyScan,
+            message: format!(
+                "No scannable files in {}: {}",
+                root.display(),
+                explain_empty_root(root, fs_config)
+            ),
+            original_file: None,
+        });
+    }
+}
+
+/// Scan a YAML file. One that does not parse but contains template
+/// directives (e.g. a Helm chart) is scanned with the template scanner
+/// instead, with a `ParseFallback` warning pushed to `warnings`.
+///
+/// Returns `(validation_errors, scan_errors)` as
+/// [`format::yaml::scan_yaml_content`] does.
+fn scan_yaml_file(
+    content: &str,
+    file_path: &Path,
+    config: &CompiledValidationConfig,
+    warnings: &mut Vec<ScanError>,
+    trace: &mut dyn TraceSink,
+) -> (Vec<ValidationError>, Vec<ScanError>) {
+    // Hold the YAML scan's trace back until we know whether the file falls
+    // back to the template scanner, which traces the same candidates again.
+    let mut yaml_candidates = Vec::new();
+    let yaml_trace: &mut dyn TraceSink = if trace.is_enabled() {
+        &mut yaml_candidates
+    } else {
+        &mut ()
+    };
+    let (val_errs, scan_errs) = format::yaml::scan_yaml_content(
+        content,
+        file_path,
+        &config.vendor_check,
+        &config.json_options,
+        warnings,
+        yaml_trace,
+    );
+    if scan_errs.is_empty() || !format::template::has_template_directives(content) {
+        for candidate in yaml_candidates {
+            trace.record(candidate);
+        }
+        return (val_errs, scan_errs);
     }
+
+    warnings.push(ScanError {
+        file: file_path.to_path_buf(),
+        kind: ScanErrorKind::ParseFallback,
+        message: "not valid YAML, scanned line by line outside template directives".to_owned(),
+        original_file: None,
+    });
+    let errors = format::template::scan_template(content, file_path, config, trace);
+    (errors, Vec::new())
 }
@@ -0,0 +1,213 @@
+//! Conversion of validation results to Language Server Protocol diagnostics.
+//!
+//! Enabled by the `lsp-types` feature, for language servers wrapping the
+//! validator. Positions count UTF-8 code units, the byte columns of
+//! [`ValidationError::column`] and [`TextEdit`](crate::TextEdit), so servers
+//! should negotiate the `utf-8` position encoding with their clients.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use lsp_types::{
+    Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Location, NumberOrString,
+    Position, Range, Uri,
+};
+
+use crate::error::{ScanError, ValidationError};
+use crate::report::ValidationReport;
+
+/// The `source` of every converted diagnostic.
+const SOURCE: &str = "gts-validator";
+
+impl ValidationError {
+    /// Convert the error to an LSP diagnostic.
+    ///
+    /// The range covers `raw_value` at `line`/`column`; errors in JSON/YAML
+    /// files carry no position and sit at the start of the file. The code is
+    /// the rule ID. A [`fix`](Self::fix) becomes related information naming
+    /// the replacement, and an LSP `TextEdit` under the `fix` key of `data`
+    /// that a code action can apply as is.
+    #[must_use]
+    pub fn to_lsp_diagnostic(&self) -> Diagnostic {
+        let start = position(self.line, self.column);
+        let end = if self.line > 0 && self.column > 0 {
+            let width = u32::try_from(self.raw_value.len()).unwrap_or(u32::MAX);
+            Position::new(start.line, start.character.saturating_add(width))
+        } else {
+            start
+        };
+        let mut diagnostic = Diagnostic::new(
+            Range::new(start, end),
+            Some(DiagnosticSeverity::ERROR),
+            Some(NumberOrString::String(self.rule_id.to_owned())),
+            Some(SOURCE.to_owned()),
+            self.error.clone(),
+            None,
+            None,
+        );
+        if let Some(fix) = &self.fix {
+            let range = Range::new(
+                position(fix.start_line, fix.start_col),
+                position(fix.end_line, fix.end_col),
+            );
+            diagnostic.related_information = file_uri(&self.file).map(|uri| {
+                vec![DiagnosticRelatedInformation {
+                    location: Location::new(uri, range),
+                    message: format!("Replace with '{}'", fix.replacement),
+                }]
+            });
+            diagnostic.data = Some(serde_json::json!({
+                "fix": lsp_types::TextEdit::new(range, fix.replacement.clone()),
+            }));
+        }
+        diagnostic
+    }
+}
+
+impl ValidationReport {
+    /// The report's validation errors, scan errors and warnings as LSP
+    /// diagnostics, grouped by file.
+    ///
+    /// Validation and scan errors have severity `ERROR`, warnings `WARNING`.
+    /// Scan errors and warnings sit at the start of the file, with their
+    /// [`ScanErrorKind`](crate::ScanErrorKind) as the code.
+    #[must_use]
+    pub fn diagnostics_by_file(&self) -> BTreeMap<PathBuf, Vec<Diagnostic>> {
+        let mut diagnostics: BTreeMap<PathBuf, Vec<Diagnostic>> = BTreeMap::new();
+        for error in &self.validation_errors {
+            diagnostics
+                .entry(error.file.clone())
+                .or_default()
+                .push(error.to_lsp_diagnostic());
+        }
+        let scan_issues = self
+            .scan_errors
+            .iter()
+            .map(|error| (error, DiagnosticSeverity::ERROR))
+            .chain(
+                self.warnings
+                    .iter()
+                    .map(|warning| (warning, DiagnosticSeverity::WARNING)),
+            );
+        for (error, severity) in scan_issues {
+            diagnostics
+                .entry(error.file.clone())
+                .or_default()
+                .push(scan_diagnostic(error, severity));
+        }
+        diagnostics
+    }
+}
+
+/// The diagnostic of a scan error or warning.
+fn scan_diagnostic(error: &ScanError, severity: DiagnosticSeverity) -> Diagnostic {
+    Diagnostic::new(
+        Range::default(),
+        Some(severity),
+        Some(NumberOrString::String(format!("{:?}", error.kind))),
+        Some(SOURCE.to_owned()),
+        error.message.clone(),
+        None,
+        None,
+    )
+}
+
+/// The zero-based position of a 1-indexed line and column; an unknown (0)
+/// line or column maps to 0.
+fn position(line: usize, column: usize) -> Position {
+    let index = |n: usize| u32::try_from(n.saturating_sub(1)).unwrap_or(u32::MAX);
+    Position::new(index(line), index(column))
+}
+
+/// The `file://` URI of `path`, made absolute against the current directory.
+fn file_uri(path: &Path) -> Option<Uri> {
+    let path = std::path::absolute(path).ok()?;
+    let path = path.to_str()?.replace('\\', "/");
+    let mut uri = String::from("file://");
+    if !path.starts_with('/') {
+        uri.push('/');
+    }
+    for byte in path.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~/:".contains(&byte) {
+            uri.push(char::from(byte));
+        } else {
+            let _ = write!(uri, "%{byte:02X}");
+        }
+    }
+    Uri::from_str(&uri).ok()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::fix::TextEdit;
+    use crate::rules;
+
+    fn error(line: usize, column: usize, fix: Option<TextEdit>) -> ValidationError {
+        ValidationError {
+            file: PathBuf::from("/docs/my notes.md"),
+            line,
+            column,
+            json_path: String::new(),
+            raw_value: "gts.x-y.a.b.c.v1".to_owned(),
+            normalized_id: "gts.x-y.a.b.c.v1".to_owned(),
+            rule_id: rules::INVALID_ID,
+            error: "Invalid GTS ID".to_owned(),
+            context: String::new(),
+            section: None,
+            document_kind: None,
+            fix,
+            help: None,
+            original_file: None,
+        }
+    }
+
+    #[test]
+    fn test_to_lsp_diagnostic_range_and_code() {
+        let diagnostic = error(3, 7, None).to_lsp_diagnostic();
+        assert_eq!(
+            diagnostic.range,
+            Range::new(Position::new(2, 6), Position::new(2, 22))
+        );
+        assert_eq!(diagnostic.severity, Some(DiagnosticSeverity::ERROR));
+        assert_eq!(
+            diagnostic.code,
+            Some(NumberOrString::String("GTS003".to_owned()))
+        );
+        assert_eq!(diagnostic.source.as_deref(), Some("gts-validator"));
+        assert!(diagnostic.related_information.is_none());
+        assert!(diagnostic.data.is_none());
+
+        // Structured files carry no position.
+        assert_eq!(
+            error(0, 0, None).to_lsp_diagnostic().range,
+            Range::default()
+        );
+    }
+
+    #[test]
+    fn test_to_lsp_diagnostic_fix() {
+        let fix = TextEdit::new((3, 7), (3, 23), "gts.x_y.a.b.c.v1~");
+        let diagnostic = error(3, 7, Some(fix)).to_lsp_diagnostic();
+        let related = diagnostic.related_information.unwrap();
+        assert_eq!(
+            related[0].location.uri.as_str(),
+            "file:///docs/my%20notes.md"
+        );
+        assert_eq!(related[0].location.range, diagnostic.range);
+        assert_eq!(related[0].message, "Replace with 'gts.x_y.a.b.c.v1~'");
+        assert_eq!(
+            diagnostic.data.unwrap(),
+            serde_json::json!({"fix": {
+                "range": {
+                    "start": {"line": 2, "character": 6},
+                    "end": {"line": 2, "character": 22},
+                },
+                "newText": "gts.x_y.a.b.c.v1~",
+            }})
+        );
+    }
+}
@@ -8,122 +8,967 @@
     clippy::expect_used
 )]
 
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
 
-use clap::Parser;
+use clap::parser::ValueSource;
+use clap::{ArgMatches, Args, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
 
-use gts_validator::output;
-use gts_validator::{DiscoveryMode, FsSourceConfig, ValidationConfig, VendorPolicy};
+use gts_validator::bench::CorpusSize;
+use gts_validator::output::{self, OutputConfig};
+use gts_validator::{
+    ConfigSource, ContentFormat, DiscoveryMode, DocumentKind, EffectiveConfig, EmbeddedDocuments,
+    ExitCodeStrategy, FsSourceConfig, InferredVendor, MarkdownProfile, RuleSeverity, StatsGrouping,
+    StatsSegment, TextEdit, ValidationConfig, ValidationReport, VendorPolicy, VendorScope,
+};
 
 /// GTS Documentation Validator (DE0903)
 ///
-/// Validates GTS identifiers in .md/.json/.yaml/.yml files.
+/// Validates GTS identifiers in .md/.json/.yaml/.yml files and templates
+/// (.tpl/.gotmpl/.j2).
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
-#[allow(clippy::struct_excessive_bools)]
+#[command(args_conflicts_with_subcommands = true)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    scan: ScanArgs,
+}
+
+/// The settings of a validation run.
+#[derive(Args, Debug)]
+#[allow(clippy::struct_excessive_bools)]
+struct ScanArgs {
     /// Paths to scan (files or directories)
     /// Defaults to: docs, modules, libs, examples
     #[arg(value_name = "PATH")]
     paths: Vec<PathBuf>,
 
+    /// Validate the files listed in LIST, one per line, instead of walking
+    /// paths ('-' reads the list from stdin). Exclude patterns and
+    /// extension routing still apply.
+    #[arg(long, value_name = "LIST", conflicts_with = "paths")]
+    files_from: Option<PathBuf>,
+
+    /// Read the --files-from list as NUL-delimited (e.g. from
+    /// `git diff -z --name-only`)
+    #[arg(short = '0', long, requires = "files_from")]
+    null: bool,
+
     /// Allowed vendor(s) for GTS IDs.
     /// Accepts a single vendor, comma-separated list, or repeated flags.
     /// Examples: --vendor cf   --vendor cf,example   --vendor cf --vendor example
     ///
+    /// `--vendor auto` infers it from the nearest Cargo.toml's
+    /// [package.metadata.gts] vendor or a .gts-vendor file at the
+    /// repository root, and fails if neither is found.
+    ///
     /// Note: Example vendors (acme, globex, example, demo, test, sample, tutorial)
     /// are always tolerated by the underlying validator.
     #[arg(long, action = clap::ArgAction::Append)]
     vendor: Vec<String>,
 
+    /// Segments of chained GTS IDs the vendor check applies to
+    /// (default: first)
+    #[arg(long, value_enum)]
+    vendor_scope: Option<VendorScopeArg>,
+
     /// Exclude patterns (can be specified multiple times)
     #[arg(long, short = 'e', action = clap::ArgAction::Append)]
     exclude: Vec<String>,
 
-    /// Output results as JSON
-    #[arg(long)]
+    /// Replace file paths matching this glob with "<redacted>" in all
+    /// output (repeatable). Example: --redact-path 'data/tenant-*/**'
+    #[arg(long = "redact-path", value_name = "PATTERN", action = clap::ArgAction::Append)]
+    redact_paths: Vec<String>,
+
+    /// Output results as JSON (shorthand for `--output-format json`)
+    #[arg(long, conflicts_with = "output_format")]
     json: bool,
 
+    /// Output format
+    #[arg(long, value_enum, default_value_t = OutputFormat::Human)]
+    output_format: OutputFormat,
+
+    /// Print a histogram of validation errors per file
+    #[arg(long)]
+    histogram: bool,
+
+    /// Rewrite malformed identifiers that have a suggested fix (the `fix`
+    /// edits of the JSON output), then validate again and report the rest
+    #[arg(long)]
+    fix: bool,
+
+    /// Human output: print the pass/fail summary before the error details
+    #[arg(long)]
+    summary_first: bool,
+
+    /// Print every discovered GTS candidate, and why it was skipped or how
+    /// it validated, as JSON instead of the report
+    #[arg(long, conflicts_with_all = ["json", "output_format", "histogram", "stats_by"])]
+    debug_discovery: bool,
+
+    /// Print how many distinct GTS IDs each vendor or package contributes
+    /// (stderr when combined with JSON output, where the report carries them
+    /// as `id_stats`)
+    #[arg(long, value_enum, value_name = "GROUPING")]
+    stats_by: Option<StatsByArg>,
+
+    /// Segment of chained IDs whose vendor and package `--stats-by` counts
+    /// them under (default: last)
+    #[arg(long, value_enum, requires = "stats_by")]
+    stats_segment: Option<StatsSegmentArg>,
+
     /// Show verbose output including file scanning progress
     #[arg(long, short = 'v')]
     verbose: bool,
 
+    /// Print the settings in effect and where each came from to stderr
+    /// before scanning
+    #[arg(long)]
+    print_effective_config: bool,
+
     /// Maximum file size in bytes (default: 10 MB)
     #[arg(long, default_value = "10485760")]
     max_file_size: u64,
 
+    /// Maximum total bytes read across all files; the scan stops and fails
+    /// once reached (default: 512 MB)
+    #[arg(long, value_name = "BYTES")]
+    max_total_size: Option<u64>,
+
     /// Scan JSON/YAML object keys for GTS identifiers (default: off)
     #[arg(long)]
     scan_keys: bool,
 
+    /// With --scan-keys, validate object keys whose path matches GLOB as GTS
+    /// patterns (trailing `*` allowed) rather than exact IDs (repeatable;
+    /// added to the defaults '$.routes.*' and '$.subscriptions.*')
+    #[arg(
+        long = "pattern-key-path",
+        value_name = "GLOB",
+        action = clap::ArgAction::Append
+    )]
+    pattern_key_paths: Vec<String>,
+
+    /// Report null values at JSON/YAML $id, $ref and x-gts-ref keys (GTS013)
+    #[arg(long)]
+    scan_null_ids: bool,
+
+    /// Report $id values defined in more than one JSON/YAML file (GTS004)
+    #[arg(long)]
+    check_duplicate_ids: bool,
+
+    /// Check schema- and instance-specific rules: a schema $id must be a
+    /// gts:// URI (GTS005), an instance type a bare type ID (GTS006)
+    #[arg(long)]
+    check_document_kinds: bool,
+
+    /// Treat JSON/YAML files matching GLOB as documents of KIND instead of
+    /// classifying them (repeatable). Example: --document-kind 'fixtures/*=config'
+    #[arg(
+        long = "document-kind",
+        value_name = "GLOB=KIND",
+        value_parser = parse_document_kind,
+        action = clap::ArgAction::Append
+    )]
+    document_kinds: Vec<(String, DocumentKind)>,
+
+    /// Require every schema document to declare DIALECT in its root $schema
+    /// (GTS016): a URI, or 07 or 2020-12
+    #[arg(long, value_name = "DIALECT")]
+    require_dialect: Option<String>,
+
+    /// Validate every schema in DIR (GTS008), check that DIR is a
+    /// self-contained bundle (GTS014) and validate every scanned instance
+    /// document against the schema its type names (GTS007)
+    #[arg(long = "schemas", value_name = "DIR")]
+    schemas_dir: Option<PathBuf>,
+
+    /// Validate an x-gts-ref string listing several references on separate
+    /// lines or separated by commas as one reference, instead of entry by
+    /// entry with a warning suggesting the array form
+    #[arg(long)]
+    no_split_ref_lists: bool,
+
+    /// In documents declaring "openapi: 3.x", scan only x-gts-* extension fields
+    #[arg(long)]
+    scan_openapi: bool,
+
+    /// Also scan JSON/YAML string values that hold a JSON or YAML document
+    /// of at least --embedded-min-size bytes
+    #[arg(long)]
+    scan_embedded: bool,
+
+    /// Scan string values at paths matching this glob (e.g. 'data.*.json')
+    /// as nested documents whatever their size; implies --scan-embedded
+    /// (repeatable)
+    #[arg(long = "embedded-key", value_name = "PATTERN", action = clap::ArgAction::Append)]
+    embedded_keys: Vec<String>,
+
+    /// Levels of nested documents to follow
+    #[arg(long, value_name = "N", default_value_t = 2)]
+    embedded_depth: usize,
+
+    /// Size in bytes from which string values are parsed as nested documents
+    #[arg(long, value_name = "BYTES", default_value_t = 256)]
+    embedded_min_size: usize,
+
+    /// Start from the settings in [package.metadata.gts] of Cargo.toml in
+    /// the current directory (or --manifest-path). Flags add to them;
+    /// --vendor and --vendor-scope replace them.
+    #[arg(long)]
+    cargo_metadata: bool,
+
+    /// Fail with exit code 2 when no file is scanned, explaining why each
+    /// path contributed nothing
+    #[arg(long)]
+    fail_on_empty: bool,
+
+    /// Fail with exit code 2 unless at least N files were scanned
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    min_scanned: usize,
+
+    /// Fail with exit code 2 unless at least one file of this format was
+    /// scanned (repeatable)
+    #[arg(
+        long = "require-format",
+        value_name = "FORMAT",
+        value_parser = clap::builder::PossibleValuesParser::new(
+            ContentFormat::ALL.iter().map(|format| format.name())
+        ),
+        action = clap::ArgAction::Append
+    )]
+    require_formats: Vec<String>,
+
+    /// How the exit code reflects the outcome
+    #[arg(long, value_enum, default_value_t = ExitCodeStrategyArg::Binary)]
+    exit_code_strategy: ExitCodeStrategyArg,
+
+    /// Cargo.toml read by --cargo-metadata
+    #[arg(long, value_name = "PATH", requires = "cargo_metadata")]
+    manifest_path: Option<PathBuf>,
+
+    /// List at most N validation errors, one per distinct rule and value
+    /// first; error counts still cover every error. 0 lists them all.
+    #[arg(long, value_name = "N", default_value = "10000")]
+    max_reported_errors: usize,
+
+    /// List at most N validation errors per file, replacing the rest of a
+    /// file's errors with one entry saying how many were suppressed; error
+    /// counts still cover every error.
+    #[arg(long, value_name = "N")]
+    max_errors_per_file: Option<usize>,
+
+    /// Warn about files with more than N GTS identifiers (definitions plus
+    /// references), usually a copy-paste accident
+    #[arg(long, value_name = "N")]
+    max_ids_per_file: Option<usize>,
+
+    /// Use limit N, or none, for files matching GLOB instead of
+    /// --max-ids-per-file (repeatable). Example: --max-ids-override 'gen/*=none'
+    #[arg(
+        long = "max-ids-override",
+        value_name = "GLOB=N",
+        value_parser = parse_id_limit_override,
+        action = clap::ArgAction::Append
+    )]
+    max_ids_overrides: Vec<(String, Option<usize>)>,
+
+    /// Leave the per-error `help` object (fix, example, docs URL) out of
+    /// JSON reports
+    #[arg(long)]
+    no_help: bool,
+
+    /// Also scan files without a .md/.json/.yaml/.yml extension found in
+    /// directories, detecting their format from their content. Files passed
+    /// explicitly are always detected this way.
+    #[arg(long)]
+    sniff: bool,
+
+    /// Scan .gts-spec/ directories, which are skipped by default
+    #[arg(long)]
+    include_gts_spec: bool,
+
+    /// Read the identifiers defined in DIR without validating its files, so
+    /// --check-duplicate-ids flags scanned files redefining them (repeatable)
+    #[arg(long = "registry", value_name = "DIR", action = clap::ArgAction::Append)]
+    registry_dirs: Vec<PathBuf>,
+
+    /// Do not use the .gts-spec/ directories found under the scanned paths
+    /// as registries
+    #[arg(long)]
+    no_default_registry: bool,
+
+    /// Fail when a directory cannot be read or traversed (e.g. permission
+    /// denied) instead of warning that its files were not scanned
+    #[arg(long)]
+    fail_on_walk_errors: bool,
+
+    /// Decode and scan files starting with a UTF-16 byte order mark instead
+    /// of skipping them with a warning
+    #[arg(long)]
+    transcode_utf16: bool,
+
+    /// Ignore the .gts-validator.toml files in scanned directories
+    #[arg(long)]
+    no_directory_overrides: bool,
+
     /// Strict mode: catches ALL gts.* strings including malformed IDs.
     #[arg(long)]
     strict: bool,
 
+    /// Which IDs in markdown prose are validated: all of them, only those
+    /// in inline code spans, or only those while warning about the rest
+    #[arg(long, value_enum, value_name = "PROFILE")]
+    markdown_profile: Option<MarkdownProfileArg>,
+
     /// Skip tokens for markdown scanning (repeatable)
     #[arg(long = "skip-token", action = clap::ArgAction::Append)]
     skip_tokens: Vec<String>,
+
+    /// Tolerate up to N errors of a rule, as RULE=N (repeatable).
+    /// Example: --rule-threshold GTS001=5
+    #[arg(
+        long = "rule-threshold",
+        value_name = "RULE=N",
+        value_parser = parse_rule_threshold,
+        action = clap::ArgAction::Append
+    )]
+    rule_thresholds: Vec<(String, usize)>,
+
+    /// Report the errors of a rule as warnings or errors, as RULE=LEVEL
+    /// (repeatable). Example: --severity GTS001=warning
+    #[arg(
+        long = "severity",
+        value_name = "RULE=LEVEL",
+        value_parser = parse_rule_severity,
+        action = clap::ArgAction::Append
+    )]
+    rule_severities: Vec<(String, RuleSeverity)>,
 }
 
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Rewrite every reference to one GTS identifier into another, e.g.
+    /// after publishing a new major version of a type
+    Rewrite(RewriteArgs),
+    /// Print the settings a run with these arguments would use, after
+    /// Cargo.toml metadata, flags and vendor inference, and where each came
+    /// from (as JSON with --json)
+    Config(Box<ScanArgs>),
+    /// Explain a rule: how to fix its errors, a correct example and where it
+    /// is documented
+    Explain {
+        /// Rule ID, e.g. GTS001
+        #[arg(value_name = "RULE")]
+        rule: String,
+    },
+    /// Write the synthetic corpus of the benchmarks (10,000 markdown files,
+    /// a 50 MB JSON and a deep YAML document) into DIR, for timing runs
+    BenchCorpus {
+        /// Directory to write, created if needed
+        #[arg(value_name = "DIR")]
+        dir: PathBuf,
+        /// Write the reduced corpus of the smoke test instead
+        #[arg(long)]
+        smoke: bool,
+    },
+}
+
+#[derive(Args, Debug)]
+struct RewriteArgs {
+    /// Paths to rewrite (files or directories)
+    /// Defaults to: docs, modules, libs, examples
+    #[arg(value_name = "PATH")]
+    paths: Vec<PathBuf>,
+
+    /// Identifier to replace. A type ID (ending with `~`) also matches as
+    /// the prefix of a longer chain
+    #[arg(long, value_name = "ID")]
+    from: String,
+
+    /// Replacement identifier
+    #[arg(long, value_name = "ID")]
+    to: String,
+
+    /// Print a diff of the changes instead of writing them
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Exclude patterns (can be specified multiple times)
+    #[arg(long, short = 'e', action = clap::ArgAction::Append)]
+    exclude: Vec<String>,
+
+    /// Skip tokens for markdown scanning (repeatable)
+    #[arg(long = "skip-token", action = clap::ArgAction::Append)]
+    skip_tokens: Vec<String>,
+
+    /// Also rewrite identifiers used as JSON/YAML object keys
+    #[arg(long)]
+    scan_keys: bool,
+}
+
+/// Parse a `--rule-threshold` value of the form `RULE=N`.
+fn parse_rule_threshold(value: &str) -> Result<(String, usize), String> {
+    let (rule_id, threshold) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected RULE=N, got '{value}'"))?;
+    let threshold = threshold
+        .trim()
+        .parse()
+        .map_err(|e| format!("invalid threshold '{threshold}': {e}"))?;
+    Ok((parse_rule_id(rule_id)?, threshold))
+}
+
+/// Check that `rule_id` names a known rule.
+fn parse_rule_id(rule_id: &str) -> Result<String, String> {
+    let rule_id = rule_id.trim();
+    if !gts_validator::rules::ALL.contains(&rule_id) {
+        return Err(format!(
+            "unknown rule '{rule_id}' (known rules: {})",
+            gts_validator::rules::ALL.join(", ")
+        ));
+    }
+    Ok(rule_id.to_owned())
+}
+
+/// Parse a `--severity` value of the form `RULE=warning` or `RULE=error`.
+fn parse_rule_severity(value: &str) -> Result<(String, RuleSeverity), String> {
+    let (rule_id, severity) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected RULE=LEVEL, got '{value}'"))?;
+    let severity = match severity.trim() {
+        "warning" => RuleSeverity::Warning,
+        "error" => RuleSeverity::Error,
+        other => {
+            return Err(format!(
+                "invalid severity '{other}': expected warning or error"
+            ));
+        }
+    };
+    Ok((parse_rule_id(rule_id)?, severity))
+}
+
+/// Parse a `--max-ids-override` value of the form `GLOB=N` or `GLOB=none`.
+fn parse_id_limit_override(value: &str) -> Result<(String, Option<usize>), String> {
+    let (glob, limit) = value
+        .rsplit_once('=')
+        .ok_or_else(|| format!("expected GLOB=N, got '{value}'"))?;
+    let limit = match limit.trim() {
+        "none" => None,
+        limit => Some(
+            limit
+                .parse()
+                .map_err(|e| format!("invalid limit '{limit}': {e}"))?,
+        ),
+    };
+    Ok((glob.trim().to_owned(), limit))
+}
+
+/// Parse a `--document-kind` value of the form `GLOB=KIND`.
+fn parse_document_kind(value: &str) -> Result<(String, DocumentKind), String> {
+    let (glob, kind) = value
+        .rsplit_once('=')
+        .ok_or_else(|| format!("expected GLOB=KIND, got '{value}'"))?;
+    let kind = match kind.trim() {
+        "schema" => DocumentKind::Schema,
+        "instance" => DocumentKind::Instance,
+        "config" => DocumentKind::Config,
+        other => {
+            return Err(format!(
+                "unknown document kind '{other}' (expected schema, instance or config)"
+            ));
+        }
+    };
+    Ok((glob.trim().to_owned(), kind))
+}
+
+/// Report output formats.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// Human-readable text
+    Human,
+    /// Pretty-printed JSON
+    Json,
+    /// Single-line JSON with no whitespace (faster for large reports)
+    JsonCompact,
+}
+
+/// Segments checked by `--vendor`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum VendorScopeArg {
+    /// Only the first segment (the base type's owner)
+    First,
+    /// Every segment
+    All,
+    /// Only the last segment (the most derived type or the instance)
+    Last,
+}
+
+impl From<VendorScopeArg> for VendorScope {
+    fn from(scope: VendorScopeArg) -> Self {
+        match scope {
+            VendorScopeArg::First => Self::FirstSegment,
+            VendorScopeArg::All => Self::AllSegments,
+            VendorScopeArg::Last => Self::LastSegment,
+        }
+    }
+}
+
+/// Profiles selected by `--markdown-profile`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum MarkdownProfileArg {
+    /// Every ID in prose
+    Default,
+    /// Only IDs inside inline code spans
+    CodeSpansOnly,
+    /// Only IDs inside inline code spans; warn about the others
+    RequireCodeSpans,
+}
+
+impl From<MarkdownProfileArg> for MarkdownProfile {
+    fn from(profile: MarkdownProfileArg) -> Self {
+        match profile {
+            MarkdownProfileArg::Default => Self::Default,
+            MarkdownProfileArg::CodeSpansOnly => Self::CodeSpansOnly,
+            MarkdownProfileArg::RequireCodeSpans => Self::RequireCodeSpans,
+        }
+    }
+}
+
+/// Rows of the `--stats-by` table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum StatsByArg {
+    /// One row per vendor
+    Vendor,
+    /// One row per vendor and package
+    Package,
+}
+
+impl From<StatsByArg> for StatsGrouping {
+    fn from(grouping: StatsByArg) -> Self {
+        match grouping {
+            StatsByArg::Vendor => Self::Vendor,
+            StatsByArg::Package => Self::Package,
+        }
+    }
+}
+
+/// Segment counted by `--stats-by`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum StatsSegmentArg {
+    /// The first segment (the base type's owner)
+    First,
+    /// The last segment (the most derived type or the instance)
+    Last,
+}
+
+impl From<StatsSegmentArg> for StatsSegment {
+    fn from(segment: StatsSegmentArg) -> Self {
+        match segment {
+            StatsSegmentArg::First => Self::FirstSegment,
+            StatsSegmentArg::Last => Self::LastSegment,
+        }
+    }
+}
+
+/// Exit codes selected by `--exit-code-strategy`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum ExitCodeStrategyArg {
+    /// 0 when ok, 1 on errors, 2 when coverage checks fail
+    Binary,
+    /// 0 when clean, 1 on errors or failed coverage checks, 2 on warnings
+    /// or tolerated errors only
+    Severity,
+    /// 0 when clean, 1 on errors, 2 on warnings or tolerated errors only,
+    /// 3 when coverage checks fail, 4 when a scan limit cut the scan short
+    Detailed,
+}
+
+/// Exit code of a run whose paths did not cover what was asked for:
+/// `--fail-on-empty`, `--min-scanned` or `--require-format`.
+const COVERAGE_EXIT_CODE: u8 = 2;
+
+/// Number of buckets in the `--histogram` output.
+const HISTOGRAM_BINS: usize = 10;
+
 /// Default directories to scan if no paths are provided.
 const DEFAULT_SCAN_DIRS: &[&str] = &["docs", "modules", "libs", "examples"];
 
 fn main() -> ExitCode {
-    let cli = Cli::parse();
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|error| error.exit());
+    match cli.command {
+        Some(Command::Rewrite(args)) => run_rewrite(args),
+        Some(Command::Config(args)) => {
+            let matches = matches
+                .subcommand_matches("config")
+                .expect("config arguments were parsed");
+            run_config(&args, matches)
+        }
+        Some(Command::Explain { rule }) => run_explain(&rule),
+        Some(Command::BenchCorpus { dir, smoke }) => run_bench_corpus(&dir, smoke),
+        None => run(&cli.scan, &matches),
+    }
+}
 
-    let paths: Vec<PathBuf> = if cli.paths.is_empty() {
-        DEFAULT_SCAN_DIRS
-            .iter()
-            .map(PathBuf::from)
-            .filter(|path| path.exists())
-            .collect()
-    } else {
-        cli.paths
+/// Print the remediation of `rule` (case-insensitive).
+fn run_explain(rule: &str) -> ExitCode {
+    let Some(remediation) = gts_validator::rules::remediation(&rule.to_uppercase()) else {
+        eprintln!(
+            "Unknown rule '{rule}' (known rules: {})",
+            gts_validator::rules::ALL.join(", ")
+        );
+        return ExitCode::FAILURE;
     };
+    println!("{}: {}", remediation.rule_id, remediation.title);
+    println!("  To fix:  {}", remediation.fix);
+    println!("  Example: {}", remediation.example);
+    println!("  Docs:    {}", remediation.docs_url);
+    ExitCode::SUCCESS
+}
 
-    if paths.is_empty() {
-        eprintln!("No existing paths to scan. Provide paths explicitly.");
+fn run_bench_corpus(dir: &Path, smoke: bool) -> ExitCode {
+    let size = if smoke {
+        CorpusSize::SMOKE
+    } else {
+        CorpusSize::FULL
+    };
+    if let Err(error) = gts_validator::bench::generate_corpus(dir, &size) {
+        eprintln!("Error writing {}: {error}", dir.display());
         return ExitCode::FAILURE;
     }
+    println!(
+        "Wrote {} markdown files, large.json and deep.yaml to {}",
+        size.markdown_files,
+        dir.display()
+    );
+    ExitCode::SUCCESS
+}
 
-    let mut fs_config = FsSourceConfig::default();
-    fs_config.paths = paths;
-    fs_config.exclude = cli.exclude;
-    fs_config.max_file_size = cli.max_file_size;
+/// The configs a run uses and where each of their settings came from.
+struct ResolvedConfig {
+    fs_config: FsSourceConfig,
+    validation_config: ValidationConfig,
+    inferred_vendor: Option<InferredVendor>,
+    effective: EffectiveConfig,
+}
 
+/// Resolve the configs of a run with `args`: the defaults, then the
+/// Cargo.toml metadata (with `--cargo-metadata`), then the flags, then
+/// `--vendor auto`.
+fn resolve(args: &ScanArgs, matches: &ArgMatches) -> anyhow::Result<ResolvedConfig> {
+    let mut fs_config = FsSourceConfig::default();
     let mut validation_config = ValidationConfig::default();
-    validation_config.scan_keys = cli.scan_keys;
-    validation_config.discovery_mode = if cli.strict {
-        DiscoveryMode::Heuristic
-    } else {
-        DiscoveryMode::StrictSpecOnly
-    };
-    validation_config.skip_tokens = cli.skip_tokens;
+    let mut effective = EffectiveConfig::new(&fs_config, &validation_config);
+    if args.cargo_metadata {
+        let manifest_path = args.manifest_path.as_deref();
+        fs_config = FsSourceConfig::from_cargo_toml_metadata(manifest_path)?;
+        validation_config = ValidationConfig::from_cargo_toml_metadata(manifest_path)?;
+        let manifest = manifest_path.map_or_else(|| PathBuf::from("Cargo.toml"), Path::to_path_buf);
+        effective.record(&fs_config, &validation_config, |_| {
+            ConfigSource::CargoMetadata(manifest.clone())
+        });
+    }
 
-    let vendors: Vec<String> = cli
+    fs_config.paths = scan_paths(args.paths.clone()).unwrap_or_default();
+    fs_config.exclude.extend(args.exclude.iter().cloned());
+    fs_config.max_file_size = args.max_file_size;
+    if let Some(max_total_size) = args.max_total_size {
+        fs_config.max_total_bytes = max_total_size;
+    }
+    fs_config.sniff_content |= args.sniff;
+    fs_config.include_gts_spec |= args.include_gts_spec;
+    fs_config
+        .registry_dirs
+        .extend(args.registry_dirs.iter().cloned());
+    fs_config.default_registry &= !args.no_default_registry;
+    fs_config.fail_on_walk_errors |= args.fail_on_walk_errors;
+    fs_config.transcode_utf16 |= args.transcode_utf16;
+    fs_config.directory_overrides &= !args.no_directory_overrides;
+
+    validation_config.scan_keys |= args.scan_keys;
+    validation_config
+        .pattern_key_paths
+        .extend(args.pattern_key_paths.iter().cloned());
+    validation_config.scan_null_ids |= args.scan_null_ids;
+    validation_config.check_duplicate_ids |= args.check_duplicate_ids;
+    validation_config.check_document_kinds |= args.check_document_kinds;
+    validation_config
+        .document_kinds
+        .extend(args.document_kinds.iter().cloned());
+    if args.require_dialect.is_some() {
+        validation_config
+            .required_dialect
+            .clone_from(&args.require_dialect);
+    }
+    validation_config.scan_openapi |= args.scan_openapi;
+    validation_config.split_ref_lists &= !args.no_split_ref_lists;
+    if args.schemas_dir.is_some() {
+        validation_config.schemas_dir.clone_from(&args.schemas_dir);
+    }
+    validation_config.fail_on_empty_scan |= args.fail_on_empty;
+    if args.scan_embedded || !args.embedded_keys.is_empty() {
+        let mut embedded = EmbeddedDocuments::default();
+        embedded.max_depth = args.embedded_depth;
+        embedded.min_size = args.embedded_min_size;
+        embedded.keys.clone_from(&args.embedded_keys);
+        validation_config.embedded_documents = Some(embedded);
+    }
+    validation_config.max_reported_errors =
+        (args.max_reported_errors > 0).then_some(args.max_reported_errors);
+    if args.no_help {
+        validation_config.include_help = false;
+    }
+    if args.max_errors_per_file.is_some() {
+        validation_config.max_errors_per_file = args.max_errors_per_file;
+    }
+    if args.max_ids_per_file.is_some() {
+        validation_config.max_ids_per_file = args.max_ids_per_file;
+    }
+    validation_config
+        .max_ids_overrides
+        .extend(args.max_ids_overrides.iter().cloned());
+    if args.strict {
+        validation_config.discovery_mode = DiscoveryMode::Heuristic;
+    }
+    if let Some(profile) = args.markdown_profile {
+        validation_config.markdown_profile = profile.into();
+    }
+    validation_config
+        .skip_tokens
+        .extend(args.skip_tokens.iter().cloned());
+    validation_config
+        .sensitive_paths
+        .extend(args.redact_paths.iter().cloned());
+    validation_config
+        .rule_thresholds
+        .extend(args.rule_thresholds.iter().cloned());
+    validation_config
+        .rule_severities
+        .extend(args.rule_severities.iter().cloned());
+    validation_config.trace_discovery = args.debug_discovery;
+    validation_config.id_stats = args.stats_by.map(|_| {
+        args.stats_segment
+            .map(StatsSegment::from)
+            .unwrap_or_default()
+    });
+
+    let vendors: Vec<String> = args
         .vendor
         .iter()
         .flat_map(|v| v.split(','))
         .map(|v| v.trim().to_owned())
         .filter(|v| !v.is_empty())
         .collect();
-    validation_config.vendor_policy = match vendors.len() {
-        0 => VendorPolicy::Any,
-        1 => VendorPolicy::MustMatch(vendors.into_iter().next().expect("checked len==1")),
-        _ => VendorPolicy::AllowList(vendors),
+    let inferred_vendor = if vendors == ["auto"] {
+        let start_dir = std::env::current_dir().unwrap_or_default();
+        let Some((vendor, source)) = gts_validator::infer_vendor(&start_dir) else {
+            anyhow::bail!(
+                "--vendor auto found no vendor: no [package.metadata.gts] vendor in the \
+                 nearest Cargo.toml and no .gts-vendor file at the repository root"
+            );
+        };
+        validation_config.vendor_policy = VendorPolicy::MustMatch(vendor.clone());
+        Some(InferredVendor::new(vendor, source))
+    } else {
+        None
     };
+    if inferred_vendor.is_none() {
+        match vendors.len() {
+            0 => {}
+            1 => {
+                validation_config.vendor_policy =
+                    VendorPolicy::MustMatch(vendors.into_iter().next().expect("checked len==1"));
+            }
+            _ => validation_config.vendor_policy = VendorPolicy::AllowList(vendors),
+        }
+    }
+    if let Some(vendor_scope) = args.vendor_scope {
+        validation_config.vendor_scope = Some(vendor_scope.into());
+    }
 
-    if cli.verbose {
-        let path_list: Vec<String> = fs_config
-            .paths
-            .iter()
-            .map(|path| path.display().to_string())
-            .collect();
-        eprintln!("Scanning paths: {}", path_list.join(", "));
+    let flags = ScanArgs::augment_args(clap::Command::new(env!("CARGO_BIN_NAME")));
+    effective.record(
+        &fs_config,
+        &validation_config,
+        |setting| match &inferred_vendor {
+            Some(inferred) if setting == "vendor_policy" => {
+                ConfigSource::Inferred(inferred.source.clone())
+            }
+            _ => command_line_source(setting, matches, &flags),
+        },
+    );
+    // Directory override files never change what a flag set.
+    validation_config.pinned_settings = [
+        "vendor_policy",
+        "skip_tokens",
+        "discovery_mode",
+        "rule_severities",
+    ]
+    .into_iter()
+    .filter(|setting| {
+        effective.get(setting).is_some_and(|sourced| {
+            matches!(
+                sourced.source,
+                ConfigSource::CommandLine(_) | ConfigSource::Inferred(_)
+            )
+        })
+    })
+    .map(str::to_owned)
+    .collect();
+    Ok(ResolvedConfig {
+        fs_config,
+        validation_config,
+        inferred_vendor,
+        effective,
+    })
+}
+
+/// The flag or argument that set `setting` when it was given on the
+/// command line; otherwise the setting took a command-line default.
+fn command_line_source(setting: &str, matches: &ArgMatches, flags: &clap::Command) -> ConfigSource {
+    let ids: &[&str] = match setting {
+        "paths" => &["paths"],
+        "exclude" => &["exclude"],
+        "max_file_size" => &["max_file_size"],
+        "max_total_bytes" => &["max_total_size"],
+        "sniff_content" => &["sniff"],
+        "include_gts_spec" => &["include_gts_spec"],
+        "registry_dirs" => &["registry_dirs"],
+        "default_registry" => &["no_default_registry"],
+        "fail_on_walk_errors" => &["fail_on_walk_errors"],
+        "transcode_utf16" => &["transcode_utf16"],
+        "directory_overrides" => &["no_directory_overrides"],
+        "vendor_policy" => &["vendor"],
+        "vendor_scope" => &["vendor_scope"],
+        "scan_keys" => &["scan_keys"],
+        "pattern_key_paths" => &["pattern_key_paths"],
+        "scan_null_ids" => &["scan_null_ids"],
+        "check_duplicate_ids" => &["check_duplicate_ids"],
+        "check_document_kinds" => &["check_document_kinds"],
+        "document_kinds" => &["document_kinds"],
+        "required_dialect" => &["require_dialect"],
+        "scan_openapi" => &["scan_openapi"],
+        "split_ref_lists" => &["no_split_ref_lists"],
+        "schemas_dir" => &["schemas_dir"],
+        "fail_on_empty_scan" => &["fail_on_empty"],
+        "embedded_documents" => &[
+            "scan_embedded",
+            "embedded_keys",
+            "embedded_depth",
+            "embedded_min_size",
+        ],
+        "max_reported_errors" => &["max_reported_errors"],
+        "max_errors_per_file" => &["max_errors_per_file"],
+        "max_ids_per_file" => &["max_ids_per_file"],
+        "max_ids_overrides" => &["max_ids_overrides"],
+        "include_help" => &["no_help"],
+        "discovery_mode" => &["strict"],
+        "markdown_profile" => &["markdown_profile"],
+        "skip_tokens" => &["skip_tokens"],
+        "sensitive_paths" => &["redact_paths"],
+        "rule_thresholds" => &["rule_thresholds"],
+        "rule_severities" => &["rule_severities"],
+        "trace_discovery" => &["debug_discovery"],
+        "id_stats" => &["stats_by", "stats_segment"],
+        _ => &[],
+    };
+    ids.iter()
+        .filter(|id| matches.value_source(id) == Some(ValueSource::CommandLine))
+        .find_map(|id| flags.get_arguments().find(|arg| arg.get_id() == *id))
+        .map_or(ConfigSource::Default, |arg| {
+            let name = match arg.get_long() {
+                Some(long) => format!("--{long}"),
+                None => arg
+                    .get_value_names()
+                    .and_then(<[_]>::first)
+                    .map_or_else(|| arg.get_id().to_string(), ToString::to_string),
+            };
+            ConfigSource::CommandLine(name)
+        })
+}
+
+/// Run `config`: print the resolved settings and their sources.
+fn run_config(args: &ScanArgs, matches: &ArgMatches) -> ExitCode {
+    let effective = match resolve(args, matches) {
+        Ok(resolved) => resolved.effective,
+        Err(error) => {
+            eprintln!("Error: {error:#}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut stdout = std::io::stdout();
+    let result = if args.json || args.output_format != OutputFormat::Human {
+        output::write_effective_config_json(&effective, &mut stdout)
+    } else {
+        output::write_effective_config_human(&effective, &mut stdout)
+    };
+    if let Err(error) = result {
+        eprintln!("Error writing output: {error}");
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+/// Validate the files of a run with `args` and print the report.
+fn run(args: &ScanArgs, matches: &ArgMatches) -> ExitCode {
+    let ResolvedConfig {
+        fs_config,
+        validation_config,
+        inferred_vendor,
+        effective,
+    } = match resolve(args, matches) {
+        Ok(resolved) => resolved,
+        Err(error) => {
+            eprintln!("Error: {error:#}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let listed = match &args.files_from {
+        Some(list) => match read_file_list(list, args.null) {
+            Ok(files) => Some(files),
+            Err(error) => {
+                eprintln!("Error: {error:#}");
+                return ExitCode::FAILURE;
+            }
+        },
+        None => None,
+    };
+    if fs_config.paths.is_empty() && listed.is_none() {
+        eprintln!("No existing paths to scan. Provide paths explicitly.");
+        return ExitCode::FAILURE;
+    }
+    if args.print_effective_config
+        && let Err(error) = output::write_effective_config_human(&effective, &mut std::io::stderr())
+    {
+        eprintln!("Error writing output: {error}");
+        return ExitCode::FAILURE;
+    }
+
+    if args.verbose {
+        if let Some(files) = &listed {
+            eprintln!("Scanning {} listed file(s)", files.len());
+        } else {
+            let path_list: Vec<String> = fs_config
+                .paths
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect();
+            eprintln!("Scanning paths: {}", path_list.join(", "));
+        }
 
         match &validation_config.vendor_policy {
-            VendorPolicy::MustMatch(vendor) => eprintln!("Expected vendor: {vendor}"),
+            VendorPolicy::MustMatch(vendor) => match &inferred_vendor {
+                Some(inferred) => {
+                    eprintln!("Expected vendor: {vendor} (from {})", inferred.source);
+                }
+                None => eprintln!("Expected vendor: {vendor}"),
+            },
             VendorPolicy::AllowList(vendors) => {
                 eprintln!("Allowed vendors: {}", vendors.join(", "));
             }
@@ -131,33 +976,310 @@ fn main() -> ExitCode {
         }
     }
 
-    let report = match gts_validator::validate_fs(&fs_config, &validation_config) {
+    let mut report = match validate(&fs_config, &validation_config, listed.as_deref(), args.fix) {
         Ok(report) => report,
         Err(error) => {
-            eprintln!("Error: {error}");
+            eprintln!("Error: {error:#}");
+            if let Some(setting) = effective.explain(&error) {
+                eprintln!("  in setting {setting}");
+            }
             return ExitCode::FAILURE;
         }
     };
 
-    if cli.verbose {
-        eprintln!("Scanned {} files", report.scanned_files);
+    report.inferred_vendor = inferred_vendor;
+
+    if args.verbose {
+        for source in &report.registry {
+            eprintln!(
+                "Loaded {} registry ID(s) from {}",
+                source.ids,
+                source.dir.display()
+            );
+        }
+        for path in &report.applied_overrides {
+            eprintln!("Applied directory overrides from {}", path.display());
+        }
+        eprintln!(
+            "Scanned {} of {} files ({} skipped)",
+            report.scanned_files, report.discovered_files, report.skipped_files
+        );
     }
 
+    let required_formats: Vec<&str> = args.require_formats.iter().map(String::as_str).collect();
+    let coverage_met = match report.verify_coverage(args.min_scanned, &required_formats) {
+        Ok(()) => true,
+        Err(error) => {
+            eprintln!("Coverage check failed: {error}");
+            false
+        }
+    };
+
     let mut stdout = std::io::stdout();
-    let result = if cli.json {
-        output::write_json(&report, &mut stdout)
+    if args.debug_discovery {
+        let exit_code = exit_code(&report, coverage_met, args.exit_code_strategy);
+        let trace = report.discovery_trace.unwrap_or_default();
+        if let Err(error) = output::write_discovery_trace(&trace, &mut stdout) {
+            eprintln!("Error writing output: {error}");
+            return ExitCode::FAILURE;
+        }
+        return exit_code;
+    }
+
+    let format = if args.json {
+        OutputFormat::Json
     } else {
-        output::write_human(&report, &mut stdout)
+        args.output_format
+    };
+    let result = match format {
+        OutputFormat::Human => {
+            let mut output_config = OutputConfig::default();
+            output_config.summary_first = args.summary_first;
+            output::write_human_with(&report, &output_config, &mut stdout)
+        }
+        OutputFormat::Json => output::write_json(&report, &mut stdout),
+        OutputFormat::JsonCompact => output::write_json_compact(&report, &mut stdout),
     };
 
+    // Keep JSON stdout machine-readable: the histogram goes to stderr there.
+    let result = result.and_then(|()| match (args.histogram, format) {
+        (false, _) => Ok(()),
+        (true, OutputFormat::Human) => report.print_histogram(HISTOGRAM_BINS, &mut stdout),
+        (true, _) => report.print_histogram(HISTOGRAM_BINS, &mut std::io::stderr()),
+    });
+    let result = result.and_then(|()| {
+        let (Some(grouping), Some(stats)) = (args.stats_by, &report.id_stats) else {
+            return Ok(());
+        };
+        if format == OutputFormat::Human {
+            stats.print_table(grouping.into(), &mut stdout)
+        } else {
+            stats.print_table(grouping.into(), &mut std::io::stderr())
+        }
+    });
+
     if let Err(error) = result {
         eprintln!("Error writing output: {error}");
         return ExitCode::FAILURE;
     }
 
-    if report.ok {
+    exit_code(&report, coverage_met, args.exit_code_strategy)
+}
+
+/// `paths`, or the existing [`DEFAULT_SCAN_DIRS`] when it is empty; `None`
+/// if that leaves nothing to scan.
+fn scan_paths(paths: Vec<PathBuf>) -> Option<Vec<PathBuf>> {
+    let paths: Vec<PathBuf> = if paths.is_empty() {
+        DEFAULT_SCAN_DIRS
+            .iter()
+            .map(PathBuf::from)
+            .filter(|path| path.exists())
+            .collect()
+    } else {
+        paths
+    };
+    (!paths.is_empty()).then_some(paths)
+}
+
+/// Run `rewrite`: print the diff (dry run) or rewrite the files, then a
+/// summary of the files changed per format. Fails when an occurrence could
+/// not be rewritten or a file could not be scanned.
+fn run_rewrite(args: RewriteArgs) -> ExitCode {
+    let Some(paths) = scan_paths(args.paths) else {
+        eprintln!("No existing paths to scan. Provide paths explicitly.");
+        return ExitCode::FAILURE;
+    };
+    let mut fs_config = FsSourceConfig::default();
+    fs_config.paths = paths;
+    fs_config.exclude = args.exclude;
+    let mut validation_config = ValidationConfig::default();
+    validation_config.skip_tokens = args.skip_tokens;
+    validation_config.scan_keys = args.scan_keys;
+
+    let report = match gts_validator::rewrite_fs(
+        &fs_config,
+        &validation_config,
+        &args.from,
+        &args.to,
+        args.dry_run,
+    ) {
+        Ok(report) => report,
+        Err(error) => {
+            eprintln!("Error: {error:#}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if args.dry_run {
+        for file in &report.files {
+            print!("{}", file.diff);
+        }
+    }
+    let by_format: Vec<String> = report
+        .files_by_format()
+        .into_iter()
+        .map(|(format, files)| format!("{format}: {files}"))
+        .collect();
+    let verb = if args.dry_run {
+        "Would rewrite"
+    } else {
+        "Rewrote"
+    };
+    eprintln!(
+        "{verb} {} occurrence(s) in {} file(s){}",
+        report.occurrences(),
+        report.files.len(),
+        if by_format.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", by_format.join(", "))
+        }
+    );
+    for occurrence in &report.unlocated {
+        let at = if occurrence.json_path.is_empty() {
+            String::new()
+        } else {
+            format!(" at {}", occurrence.json_path)
+        };
+        eprintln!(
+            "Not rewritten: {}{at}: '{}' could not be located in the file",
+            occurrence.file.display(),
+            occurrence.candidate
+        );
+    }
+    for error in &report.scan_errors {
+        eprintln!("Not scanned: {}: {}", error.file.display(), error.message);
+    }
+    if report.unlocated.is_empty() && report.scan_errors.is_empty() {
         ExitCode::SUCCESS
     } else {
         ExitCode::FAILURE
     }
 }
+
+/// Validate the `listed` files, or the files under `fs_config.paths`, and
+/// with `fix`, apply the suggested fixes and validate again.
+fn validate(
+    fs_config: &FsSourceConfig,
+    validation_config: &ValidationConfig,
+    listed: Option<&[PathBuf]>,
+    fix: bool,
+) -> anyhow::Result<ValidationReport> {
+    let validate_once = || match listed {
+        Some(files) => gts_validator::validate_files(files, fs_config, validation_config),
+        None => gts_validator::validate_fs(fs_config, validation_config),
+    };
+    let report = validate_once()?;
+    if !fix {
+        return Ok(report);
+    }
+    match apply_fixes(&report)? {
+        (0, _) => Ok(report),
+        (edits, files) => {
+            eprintln!("Fixed {edits} identifier(s) in {files} file(s)");
+            validate_once()
+        }
+    }
+}
+
+/// The files listed in `list` ('-' for stdin), one per line or, with
+/// `nul`, NUL-delimited. Empty entries are ignored.
+fn read_file_list(list: &Path, nul: bool) -> anyhow::Result<Vec<PathBuf>> {
+    use std::io::Read;
+
+    use anyhow::Context;
+
+    let mut bytes = Vec::new();
+    if list == Path::new("-") {
+        std::io::stdin()
+            .read_to_end(&mut bytes)
+            .context("Failed to read the file list from stdin")?;
+    } else {
+        bytes = std::fs::read(list)
+            .with_context(|| format!("Failed to read the file list {}", list.display()))?;
+    }
+    let delimiter = if nul { b'\0' } else { b'\n' };
+    Ok(bytes
+        .split(|&byte| byte == delimiter)
+        .map(|entry| {
+            if nul {
+                entry
+            } else {
+                entry.strip_suffix(b"\r").unwrap_or(entry)
+            }
+        })
+        .filter(|entry| !entry.is_empty())
+        .map(path_from_bytes)
+        .collect())
+}
+
+/// A path from the raw bytes of a file list entry.
+#[cfg(unix)]
+fn path_from_bytes(bytes: &[u8]) -> PathBuf {
+    use std::os::unix::ffi::OsStrExt;
+
+    PathBuf::from(std::ffi::OsStr::from_bytes(bytes))
+}
+
+/// A path from the raw bytes of a file list entry.
+#[cfg(not(unix))]
+fn path_from_bytes(bytes: &[u8]) -> PathBuf {
+    PathBuf::from(String::from_utf8_lossy(bytes).into_owned())
+}
+
+/// Apply the fix edits of `report`'s errors to their files. Returns the
+/// number of edits and of files changed.
+fn apply_fixes(report: &ValidationReport) -> anyhow::Result<(usize, usize)> {
+    let mut by_file: BTreeMap<&std::path::Path, Vec<TextEdit>> = BTreeMap::new();
+    for error in &report.validation_errors {
+        if let Some(fix) = &error.fix {
+            let file = error.original_file.as_ref().unwrap_or(&error.file);
+            let edits = by_file.entry(file).or_default();
+            if !edits.contains(fix) {
+                edits.push(fix.clone());
+            }
+        }
+    }
+    let mut applied = 0;
+    for (file, edits) in &by_file {
+        gts_validator::write_edits(file, edits)?;
+        applied += edits.len();
+    }
+    Ok((applied, by_file.len()))
+}
+
+/// The exit code under `strategy`; see [`ExitCodeStrategyArg`]. A scan
+/// that did not cover what was asked for is a configuration problem, which
+/// `binary` reports as 2.
+fn exit_code(
+    report: &ValidationReport,
+    coverage_met: bool,
+    strategy: ExitCodeStrategyArg,
+) -> ExitCode {
+    let coverage_failed = !coverage_met || report.empty_scan;
+    let code = match strategy {
+        ExitCodeStrategyArg::Binary if coverage_failed => COVERAGE_EXIT_CODE,
+        ExitCodeStrategyArg::Binary => report.to_exit_code(&ExitCodeStrategy::BinaryOk),
+        ExitCodeStrategyArg::Severity if coverage_failed => 1,
+        ExitCodeStrategyArg::Severity => report.to_exit_code(&ExitCodeStrategy::SeverityBased {
+            error_code: 1,
+            warning_code: 2,
+        }),
+        ExitCodeStrategyArg::Detailed => {
+            report.to_exit_code(&ExitCodeStrategy::Custom(Box::new(move |report| {
+                if coverage_failed {
+                    3
+                } else if report.truncated {
+                    4
+                } else if !report.ok {
+                    1
+                } else if report.has_warnings_only() {
+                    2
+                } else {
+                    0
+                }
+            })))
+        }
+    };
+    ExitCode::from(code)
+}
@@ -0,0 +1,405 @@
+//! Validator settings read from the `[package.metadata.gts]` table of a
+//! `Cargo.toml`, so Rust projects can configure the validator in the
+//! manifest they already have:
+//!
+//! ```toml
+//! [package.metadata.gts]
+//! vendor = "x"                 # or a list: ["x", "acme"]
+//! vendor_scope = "all"         # "first" (default), "all" or "last"
+//! exclude = ["target/*"]
+//! scan_keys = true
+//! pattern_key_paths = ["$.routes.*"]
+//! rule_thresholds = { GTS001 = 5 }
+//! ```
+//!
+//! Unknown keys are rejected so typos do not go unnoticed.
+//!
+//! [`infer_vendor`] also finds the project's vendor there, or in a
+//! `.gts-vendor` file at the repository root.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{DiscoveryMode, FsSourceConfig, ValidationConfig, VendorPolicy, VendorScope};
+use crate::rules;
+
+/// The manifest read when no path is given.
+const DEFAULT_MANIFEST: &str = "Cargo.toml";
+
+/// A file at the repository root holding the project's vendor.
+const VENDOR_FILE: &str = ".gts-vendor";
+
+/// The parts of a manifest this module reads.
+#[derive(Debug, Default, Deserialize)]
+struct Manifest {
+    #[serde(default)]
+    package: Package,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Package {
+    #[serde(default)]
+    metadata: Metadata,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Metadata {
+    #[serde(default)]
+    gts: GtsMetadata,
+}
+
+/// The `[package.metadata.gts]` table.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+#[allow(clippy::struct_excessive_bools)]
+struct GtsMetadata {
+    vendor: Option<Vendors>,
+    vendor_scope: Option<VendorScopeName>,
+    exclude: Vec<String>,
+    scan_keys: bool,
+    scan_null_ids: bool,
+    check_duplicate_ids: bool,
+    check_document_kinds: bool,
+    scan_openapi: bool,
+    fail_on_empty_scan: bool,
+    strict: bool,
+    skip_tokens: Vec<String>,
+    rule_thresholds: HashMap<String, usize>,
+    sniff: bool,
+    include_gts_spec: bool,
+    pattern_key_paths: Option<Vec<String>>,
+}
+
+/// `vendor = "x"` or `vendor = ["x", "acme"]`.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum Vendors {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Vendors {
+    /// The policy these vendors stand for: a single vendor must match, an
+    /// empty list accepts any.
+    pub fn policy(&self) -> VendorPolicy {
+        match self {
+            Self::One(vendor) => VendorPolicy::MustMatch(vendor.clone()),
+            Self::Many(vendors) => match vendors.as_slice() {
+                [] => VendorPolicy::Any,
+                [vendor] => VendorPolicy::MustMatch(vendor.clone()),
+                _ => VendorPolicy::AllowList(vendors.clone()),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum VendorScopeName {
+    First,
+    All,
+    Last,
+}
+
+impl GtsMetadata {
+    /// Read the table from `manifest_path`, or from `Cargo.toml` in the
+    /// current directory. A missing table, or a missing `Cargo.toml` when no
+    /// path is given, yields the defaults.
+    fn load(manifest_path: Option<&Path>) -> anyhow::Result<Self> {
+        let path = manifest_path.map_or_else(|| PathBuf::from(DEFAULT_MANIFEST), Path::to_path_buf);
+        if manifest_path.is_none() && !path.is_file() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let manifest: Manifest = toml::from_str(&content)
+            .with_context(|| format!("Invalid [package.metadata.gts] in {}", path.display()))?;
+        let metadata = manifest.package.metadata.gts;
+        if let Some(rule_id) = metadata
+            .rule_thresholds
+            .keys()
+            .find(|rule_id| !rules::ALL.contains(&rule_id.as_str()))
+        {
+            anyhow::bail!(
+                "Unknown rule '{rule_id}' in [package.metadata.gts] of {} (known rules: {})",
+                path.display(),
+                rules::ALL.join(", ")
+            );
+        }
+        Ok(metadata)
+    }
+}
+
+/// Where [`infer_vendor`] found a vendor.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "path", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum VendorSource {
+    /// The `vendor` key of `[package.metadata.gts]` in this `Cargo.toml`.
+    CargoMetadata(PathBuf),
+    /// This `.gts-vendor` file.
+    VendorFile(PathBuf),
+}
+
+impl fmt::Display for VendorSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CargoMetadata(path) => {
+                write!(f, "[package.metadata.gts] in {}", path.display())
+            }
+            Self::VendorFile(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// A vendor found by [`infer_vendor`], as recorded in
+/// `ValidationReport::inferred_vendor`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct InferredVendor {
+    pub vendor: String,
+    pub source: VendorSource,
+}
+
+impl InferredVendor {
+    #[must_use]
+    pub const fn new(vendor: String, source: VendorSource) -> Self {
+        Self { vendor, source }
+    }
+}
+
+/// Find the project's vendor, looking up from `start_dir` at:
+///
+/// 1. a single `vendor` in `[package.metadata.gts]` of the nearest
+///    `Cargo.toml`;
+/// 2. a `.gts-vendor` file at the repository root (the nearest directory
+///    with a `.git` entry).
+///
+/// Manifests that cannot be read or parsed, vendor lists and empty vendor
+/// files are passed over.
+#[must_use]
+pub fn infer_vendor(start_dir: &Path) -> Option<(String, VendorSource)> {
+    let mut manifest_seen = false;
+    for dir in start_dir.ancestors() {
+        let manifest = dir.join(DEFAULT_MANIFEST);
+        if !manifest_seen && manifest.is_file() {
+            manifest_seen = true;
+            let vendor = GtsMetadata::load(Some(&manifest))
+                .ok()
+                .and_then(|metadata| match metadata.vendor? {
+                    Vendors::One(vendor) => Some(vendor),
+                    Vendors::Many(mut vendors) if vendors.len() == 1 => vendors.pop(),
+                    Vendors::Many(_) => None,
+                })
+                .filter(|vendor| !vendor.is_empty());
+            if let Some(vendor) = vendor {
+                return Some((vendor, VendorSource::CargoMetadata(manifest)));
+            }
+        }
+        if dir.join(".git").exists() {
+            let vendor_file = dir.join(VENDOR_FILE);
+            let vendor = std::fs::read_to_string(&vendor_file).ok()?;
+            let vendor = vendor.trim();
+            return (!vendor.is_empty())
+                .then(|| (vendor.to_owned(), VendorSource::VendorFile(vendor_file)));
+        }
+    }
+    None
+}
+
+impl ValidationConfig {
+    /// Build a validation config from the `[package.metadata.gts]` table of
+    /// the manifest at `manifest_path`, or of `Cargo.toml` in the current
+    /// directory if it exists. Settings the table leaves out keep their
+    /// defaults.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the manifest cannot be read or parsed, or the
+    /// table has unknown keys or thresholds for unknown rules.
+    pub fn from_cargo_toml_metadata(manifest_path: Option<&Path>) -> anyhow::Result<Self> {
+        let metadata = GtsMetadata::load(manifest_path)?;
+        let vendor_policy = metadata
+            .vendor
+            .as_ref()
+            .map_or(VendorPolicy::Any, Vendors::policy);
+        let vendor_scope = metadata.vendor_scope.map(|scope| match scope {
+            VendorScopeName::First => VendorScope::FirstSegment,
+            VendorScopeName::All => VendorScope::AllSegments,
+            VendorScopeName::Last => VendorScope::LastSegment,
+        });
+        let discovery_mode = if metadata.strict {
+            DiscoveryMode::Heuristic
+        } else {
+            DiscoveryMode::StrictSpecOnly
+        };
+        Ok(Self {
+            vendor_policy,
+            vendor_scope,
+            scan_keys: metadata.scan_keys,
+            scan_null_ids: metadata.scan_null_ids,
+            check_duplicate_ids: metadata.check_duplicate_ids,
+            discovery_mode,
+            skip_tokens: metadata.skip_tokens,
+            rule_thresholds: metadata.rule_thresholds,
+            fail_on_empty_scan: metadata.fail_on_empty_scan,
+            scan_openapi: metadata.scan_openapi,
+            check_document_kinds: metadata.check_document_kinds,
+            pattern_key_paths: metadata
+                .pattern_key_paths
+                .unwrap_or_else(|| Self::default().pattern_key_paths),
+            ..Self::default()
+        })
+    }
+}
+
+impl FsSourceConfig {
+    /// Build a filesystem source config from the `[package.metadata.gts]`
+    /// table, like [`ValidationConfig::from_cargo_toml_metadata`]. `paths`
+    /// is left empty.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`ValidationConfig::from_cargo_toml_metadata`].
+    pub fn from_cargo_toml_metadata(manifest_path: Option<&Path>) -> anyhow::Result<Self> {
+        let metadata = GtsMetadata::load(manifest_path)?;
+        Ok(Self {
+            exclude: metadata.exclude,
+            sniff_content: metadata.sniff,
+            include_gts_spec: metadata.include_gts_spec,
+            ..Self::default()
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn manifest(content: &str) -> (tempfile::TempDir, PathBuf) {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("Cargo.toml");
+        std::fs::write(&path, content).unwrap();
+        (tmp, path)
+    }
+
+    #[test]
+    fn test_reads_package_metadata() {
+        let (_tmp, path) = manifest(
+            r#"
+[package]
+name = "demo"
+version = "0.1.0"
+
+[package.metadata.gts]
+vendor = "x"
+vendor_scope = "all"
+exclude = ["target/*"]
+scan_keys = true
+pattern_key_paths = ["$.handlers.*"]
+strict = true
+rule_thresholds = { GTS001 = 5 }
+"#,
+        );
+        let config = ValidationConfig::from_cargo_toml_metadata(Some(&path)).unwrap();
+        assert_eq!(
+            config.vendor_policy,
+            VendorPolicy::MustMatch("x".to_owned())
+        );
+        assert_eq!(config.vendor_scope, Some(VendorScope::AllSegments));
+        assert!(config.scan_keys);
+        assert_eq!(config.pattern_key_paths, ["$.handlers.*"]);
+        assert!(!config.scan_null_ids);
+        assert_eq!(config.discovery_mode, DiscoveryMode::Heuristic);
+        assert_eq!(config.rule_thresholds.get("GTS001"), Some(&5));
+
+        let fs_config = FsSourceConfig::from_cargo_toml_metadata(Some(&path)).unwrap();
+        assert_eq!(fs_config.exclude, ["target/*"]);
+        assert!(fs_config.paths.is_empty());
+    }
+
+    #[test]
+    fn test_vendor_list() {
+        let (_tmp, path) = manifest("[package.metadata.gts]\nvendor = [\"x\", \"acme\"]\n");
+        let config = ValidationConfig::from_cargo_toml_metadata(Some(&path)).unwrap();
+        assert_eq!(
+            config.vendor_policy,
+            VendorPolicy::AllowList(vec!["x".to_owned(), "acme".to_owned()])
+        );
+    }
+
+    #[test]
+    fn test_missing_table_yields_defaults() {
+        let (_tmp, path) = manifest("[package]\nname = \"demo\"\n");
+        let config = ValidationConfig::from_cargo_toml_metadata(Some(&path)).unwrap();
+        assert_eq!(config.vendor_policy, VendorPolicy::Any);
+        assert!(!config.scan_keys);
+        assert_eq!(
+            config.pattern_key_paths,
+            ValidationConfig::default().pattern_key_paths
+        );
+    }
+
+    #[test]
+    fn test_rejects_bad_tables() {
+        let (_tmp, path) = manifest("[package.metadata.gts]\nvendors = \"x\"\n");
+        let err = ValidationConfig::from_cargo_toml_metadata(Some(&path)).unwrap_err();
+        assert!(format!("{err:#}").contains("vendors"), "{err:#}");
+
+        let (_tmp, path) = manifest("[package.metadata.gts]\nrule_thresholds = { GTS999 = 1 }\n");
+        let err = ValidationConfig::from_cargo_toml_metadata(Some(&path)).unwrap_err();
+        assert!(err.to_string().contains("Unknown rule 'GTS999'"), "{err}");
+
+        let missing = Path::new("/nonexistent/Cargo.toml");
+        assert!(ValidationConfig::from_cargo_toml_metadata(Some(missing)).is_err());
+    }
+
+    #[test]
+    fn test_infer_vendor_sources() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let root = tmp.path();
+        let crate_dir = root.join("crates").join("api");
+        let docs = crate_dir.join("docs");
+        std::fs::create_dir_all(&docs).unwrap();
+        std::fs::create_dir(root.join(".git")).unwrap();
+        assert_eq!(infer_vendor(&docs), None);
+
+        std::fs::write(root.join(VENDOR_FILE), "acme\n").unwrap();
+        assert_eq!(
+            infer_vendor(&docs),
+            Some((
+                "acme".to_owned(),
+                VendorSource::VendorFile(root.join(VENDOR_FILE))
+            ))
+        );
+
+        // The nearest manifest wins over the vendor file, but only when it
+        // names a single vendor.
+        let manifest = crate_dir.join(DEFAULT_MANIFEST);
+        std::fs::write(
+            &manifest,
+            "[package.metadata.gts]\nvendor = [\"x\", \"y\"]\n",
+        )
+        .unwrap();
+        assert_eq!(infer_vendor(&docs).unwrap().0, "acme");
+        std::fs::write(&manifest, "[package.metadata.gts]\nvendor = \"x\"\n").unwrap();
+        assert_eq!(
+            infer_vendor(&docs),
+            Some(("x".to_owned(), VendorSource::CargoMetadata(manifest)))
+        );
+    }
+
+    #[test]
+    fn test_infer_vendor_stops_at_repository_root() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let repo = tmp.path().join("repo");
+        std::fs::create_dir_all(repo.join(".git")).unwrap();
+        std::fs::write(tmp.path().join(VENDOR_FILE), "outside").unwrap();
+        assert_eq!(infer_vendor(&repo), None);
+    }
+}
@@ -0,0 +1,391 @@
+//! Per-file scan outcomes: the errors a file produced and the GTS identifiers
+//! it defines and references.
+//!
+//! Outcomes are built from the candidates the scanners report (see
+//! [`TraceSink`](crate::trace::TraceSink)), so cross-file checks reuse the
+//! scan instead of parsing every file a second time.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use glob::Pattern;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{ScanError, ScanErrorKind, ValidationError};
+use crate::normalize::normalize_candidate;
+use crate::rules;
+use crate::strategy::fs::glob_matches;
+use crate::trace::{CandidateOutcome, TracedCandidate};
+use crate::validator::id_kind;
+
+/// What a found identifier names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum IdKind {
+    /// A type ID (ends with `~`).
+    Schema,
+    /// An instance ID.
+    Instance,
+    /// A wildcard pattern (`gts.x.core.*`).
+    Pattern,
+}
+
+/// How a file uses a found identifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum IdRole {
+    /// The value of a JSON/YAML `$id` key.
+    Definition,
+    /// Anywhere else.
+    Reference,
+}
+
+/// A GTS identifier found in a file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct FoundId {
+    /// The normalized identifier (without `gts://`).
+    pub id: String,
+    pub kind: IdKind,
+    pub role: IdRole,
+    pub file: PathBuf,
+    /// 1-indexed line (markdown only, 0 otherwise).
+    pub line: usize,
+    /// 1-indexed column (markdown only, 0 otherwise).
+    pub column: usize,
+    /// JSON path (JSON/YAML only, empty otherwise).
+    pub json_path: String,
+}
+
+/// Candidate counts for one file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ScanStats {
+    /// Candidates discovered, skipped or not.
+    pub candidates: usize,
+    /// Candidates that were not validated (bad examples, skip tokens, ...).
+    pub skipped: usize,
+    /// Identifiers found: the file's definitions plus its references.
+    #[serde(default)]
+    pub ids: usize,
+}
+
+/// The result of scanning one file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ScanOutcome {
+    pub file: PathBuf,
+    /// Validation errors reported for the file.
+    pub errors: Vec<ValidationError>,
+    /// Identifiers the file defines, in scan order.
+    pub definitions: Vec<FoundId>,
+    /// Identifiers the file references, in scan order.
+    pub references: Vec<FoundId>,
+    pub stats: ScanStats,
+}
+
+impl ScanOutcome {
+    /// Build the outcome of `file` from its errors and the candidates its
+    /// scan reported. Skipped candidates and candidates that do not
+    /// normalize are counted but not listed.
+    #[must_use]
+    pub fn new(
+        file: PathBuf,
+        errors: Vec<ValidationError>,
+        candidates: &[TracedCandidate],
+    ) -> Self {
+        let mut outcome = Self {
+            file,
+            errors,
+            definitions: Vec::new(),
+            references: Vec::new(),
+            stats: ScanStats {
+                candidates: candidates.len(),
+                ..ScanStats::default()
+            },
+        };
+        for candidate in candidates {
+            if matches!(candidate.outcome, CandidateOutcome::Skipped { .. }) {
+                outcome.stats.skipped += 1;
+                continue;
+            }
+            let Ok(normalized) = normalize_candidate(&candidate.candidate) else {
+                continue;
+            };
+            let kind = id_kind(&normalized.gts_id, &candidate.json_path);
+            let role = if candidate.json_path.ends_with(".$id") {
+                IdRole::Definition
+            } else {
+                IdRole::Reference
+            };
+            let found = FoundId {
+                id: normalized.gts_id,
+                kind,
+                role,
+                file: outcome.file.clone(),
+                line: candidate.line,
+                column: candidate.column,
+                json_path: candidate.json_path.clone(),
+            };
+            match role {
+                IdRole::Definition => outcome.definitions.push(found),
+                IdRole::Reference => outcome.references.push(found),
+            }
+        }
+        outcome.stats.ids = outcome.definitions.len() + outcome.references.len();
+        outcome
+    }
+}
+
+/// `ValidationConfig::max_ids_per_file` with its path overrides compiled.
+#[derive(Debug, Clone, Default)]
+pub struct IdLimits {
+    default: Option<usize>,
+    overrides: Vec<(Pattern, Option<usize>)>,
+}
+
+impl IdLimits {
+    /// # Errors
+    ///
+    /// Returns an error if an override's path pattern is not a valid glob.
+    pub fn new(
+        default: Option<usize>,
+        overrides: &[(String, Option<usize>)],
+    ) -> anyhow::Result<Self> {
+        let overrides = overrides
+            .iter()
+            .map(|(glob, limit)| {
+                let pattern = Pattern::new(glob)
+                    .with_context(|| format!("Invalid identifier limit path pattern '{glob}'"))?;
+                Ok((pattern, *limit))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self { default, overrides })
+    }
+
+    /// Whether any file has a limit, so identifiers must be counted.
+    pub fn is_enabled(&self) -> bool {
+        self.default.is_some() || self.overrides.iter().any(|(_, limit)| limit.is_some())
+    }
+
+    /// The limit of the file at `path`: that of the first override whose
+    /// glob matches the path or file name, else the default.
+    pub fn limit_for(&self, path: &Path) -> Option<usize> {
+        self.overrides
+            .iter()
+            .find(|(pattern, _)| {
+                glob_matches(pattern, path.as_os_str())
+                    || path
+                        .file_name()
+                        .is_some_and(|name| glob_matches(pattern, name))
+            })
+            .map_or(self.default, |&(_, limit)| limit)
+    }
+
+    /// A `TooManyIdentifiers` warning when `outcome` found more identifiers
+    /// than its file's limit.
+    pub fn check(&self, outcome: &ScanOutcome) -> Option<ScanError> {
+        let limit = self.limit_for(&outcome.file)?;
+        (outcome.stats.ids > limit).then(|| ScanError {
+            file: outcome.file.clone(),
+            kind: ScanErrorKind::TooManyIdentifiers,
+            message: format!(
+                "{} GTS identifiers found, more than the limit of {limit} per file; \
+                 check for a copy-paste accident, or exempt generated files",
+                outcome.stats.ids
+            ),
+            original_file: None,
+        })
+    }
+}
+
+/// [`rules::DUPLICATE_DEFINITION`] errors for identifiers defined more than
+/// once across `outcomes`: one per definition after the first.
+#[must_use]
+pub fn duplicate_definitions(outcomes: &[ScanOutcome]) -> Vec<ValidationError> {
+    duplicate_definitions_of(&[], outcomes)
+}
+
+/// Like [`duplicate_definitions`], with the identifiers in `known` (e.g. a
+/// registry's) defined before any of `outcomes`. Duplicates within `known`
+/// are not reported.
+pub fn duplicate_definitions_of(
+    known: &[FoundId],
+    outcomes: &[ScanOutcome],
+) -> Vec<ValidationError> {
+    let mut first_definition: HashMap<&str, &FoundId> = HashMap::new();
+    for found in known {
+        first_definition.entry(&found.id).or_insert(found);
+    }
+    let mut errors = Vec::new();
+    for found in outcomes.iter().flat_map(|o| &o.definitions) {
+        let Some(first) = first_definition.get(found.id.as_str()) else {
+            first_definition.insert(&found.id, found);
+            continue;
+        };
+        errors.push(ValidationError {
+            file: found.file.clone(),
+            line: found.line,
+            column: found.column,
+            json_path: found.json_path.clone(),
+            raw_value: found.id.clone(),
+            normalized_id: found.id.clone(),
+            rule_id: rules::DUPLICATE_DEFINITION,
+            error: format!(
+                "Duplicate definition: '{}' is already defined in {} at {}",
+                found.id,
+                first.file.display(),
+                first.json_path
+            ),
+            context: found.json_path.clone(),
+            section: None,
+            document_kind: None,
+            fix: None,
+            help: None,
+            original_file: None,
+        });
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::{DiscoverySource, SkipReason};
+
+    fn candidate(value: &str, json_path: &str, outcome: CandidateOutcome) -> TracedCandidate {
+        TracedCandidate {
+            candidate: value.to_owned(),
+            source: DiscoverySource::Value,
+            line: 0,
+            column: 0,
+            json_path: json_path.to_owned(),
+            outcome,
+        }
+    }
+
+    fn schema_outcome(file: &str, id: &str) -> ScanOutcome {
+        ScanOutcome::new(
+            PathBuf::from(file),
+            Vec::new(),
+            &[candidate(
+                &format!("gts://{id}"),
+                "$.$id",
+                CandidateOutcome::Valid,
+            )],
+        )
+    }
+
+    #[test]
+    fn test_scan_outcome_classifies_candidates() {
+        let candidates = [
+            candidate(
+                "gts://gts.x.core.events.type.v1~",
+                "$.$id",
+                CandidateOutcome::Valid,
+            ),
+            candidate(
+                "gts.x.core.events.topic.v1~x.core._.orders.v1",
+                "$.properties.topic.default",
+                CandidateOutcome::Valid,
+            ),
+            candidate(
+                "gts.y.core.events.*",
+                "$.properties.type.x-gts-ref",
+                CandidateOutcome::Invalid {
+                    errors: vec!["Vendor mismatch".to_owned()],
+                },
+            ),
+            candidate(
+                "gts.x.core.events.type.v1~.schema.json",
+                "$.file",
+                CandidateOutcome::Skipped {
+                    reason: SkipReason::Filename,
+                },
+            ),
+            candidate("gts.bad", "$.other", CandidateOutcome::Valid),
+        ];
+        let outcome = ScanOutcome::new(PathBuf::from("a.json"), Vec::new(), &candidates);
+
+        assert_eq!(
+            outcome.stats,
+            ScanStats {
+                candidates: 5,
+                skipped: 1,
+                ids: 4,
+            }
+        );
+        assert_eq!(outcome.definitions.len(), 1);
+        assert_eq!(outcome.definitions[0].id, "gts.x.core.events.type.v1~");
+        assert_eq!(outcome.definitions[0].kind, IdKind::Schema);
+        assert_eq!(outcome.definitions[0].role, IdRole::Definition);
+
+        let references: Vec<(&str, IdKind)> = outcome
+            .references
+            .iter()
+            .map(|f| (f.id.as_str(), f.kind))
+            .collect();
+        assert_eq!(
+            references,
+            [
+                (
+                    "gts.x.core.events.topic.v1~x.core._.orders.v1",
+                    IdKind::Instance
+                ),
+                ("gts.y.core.events.*", IdKind::Pattern),
+                ("gts.bad", IdKind::Instance),
+            ]
+        );
+        assert!(
+            outcome
+                .references
+                .iter()
+                .all(|f| f.file.ends_with("a.json"))
+        );
+    }
+
+    #[test]
+    fn test_duplicate_definitions() {
+        let outcomes = [
+            schema_outcome("a.json", "gts.x.core.events.type.v1~"),
+            schema_outcome("b.json", "gts.x.core.events.topic.v1~"),
+            schema_outcome("c.json", "gts.x.core.events.type.v1~"),
+        ];
+        let errors = duplicate_definitions(&outcomes);
+        assert_eq!(errors.len(), 1, "{errors:?}");
+        assert_eq!(errors[0].file, PathBuf::from("c.json"));
+        assert_eq!(errors[0].rule_id, rules::DUPLICATE_DEFINITION);
+        assert_eq!(
+            errors[0].error,
+            "Duplicate definition: 'gts.x.core.events.type.v1~' is already defined in a.json at $.$id"
+        );
+
+        assert!(duplicate_definitions(&outcomes[..2]).is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_definitions_of_known() {
+        let known = [
+            schema_outcome(".gts-spec/a.json", "gts.x.core.events.type.v1~"),
+            schema_outcome(".gts-spec/b.json", "gts.x.core.events.type.v1~"),
+        ]
+        .map(|outcome| outcome.definitions[0].clone());
+        let outcomes = [
+            schema_outcome("c.json", "gts.x.core.events.topic.v1~"),
+            schema_outcome("d.json", "gts.x.core.events.type.v1~"),
+        ];
+        let errors = duplicate_definitions_of(&known, &outcomes);
+        assert_eq!(errors.len(), 1, "{errors:?}");
+        assert_eq!(errors[0].file, PathBuf::from("d.json"));
+        assert!(
+            errors[0]
+                .error
+                .ends_with("already defined in .gts-spec/a.json at $.$id"),
+            "{}",
+            errors[0].error
+        );
+    }
+}
@@ -0,0 +1,271 @@
+//! Directory-scoped validation settings: a `.gts-validator.toml` file in a
+//! scanned directory overrides some settings for every file under it, e.g.
+//! for vendored third-party docs:
+//!
+//! ```toml
+//! vendor = "acme"              # or a list: ["acme", "partner"]
+//! skip_tokens = ["**given**"]  # replaces the inherited tokens
+//! strict = true                # heuristic discovery, like --strict
+//! severity = { GTS001 = "warning" }
+//! ```
+//!
+//! The files from the scan path down to a file's directory all apply, and
+//! the nearest one wins setting by setting. Settings named in
+//! `ValidationConfig::pinned_settings`, such as those given as
+//! command-line flags, are never overridden. Unknown keys are rejected.
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use anyhow::Context;
+use serde::Deserialize;
+
+use crate::config::{CompiledValidationConfig, FsSourceConfig, RuleSeverity, ValidationConfig};
+use crate::effective::invalid_setting;
+use crate::error::{ScanError, ScanErrorKind, ValidationError};
+use crate::format::markdown::SkipTokens;
+use crate::metadata::Vendors;
+use crate::rules;
+use crate::vendor::VendorCheck;
+
+/// The name of a directory override file.
+pub const OVERRIDE_FILE: &str = ".gts-validator.toml";
+
+/// The settings of one override file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct OverrideFile {
+    vendor: Option<Vendors>,
+    skip_tokens: Option<Vec<String>>,
+    strict: Option<bool>,
+    severity: HashMap<String, RuleSeverity>,
+}
+
+impl OverrideFile {
+    fn load(path: &Path) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let file: Self = toml::from_str(&content)
+            .with_context(|| format!("Invalid override file {}", path.display()))?;
+        if let Some(rule_id) = unknown_rule(&file.severity) {
+            anyhow::bail!(
+                "Unknown rule '{rule_id}' in {} (known rules: {})",
+                path.display(),
+                rules::ALL.join(", ")
+            );
+        }
+        Ok(file)
+    }
+}
+
+fn unknown_rule(severities: &HashMap<String, RuleSeverity>) -> Option<&String> {
+    severities
+        .keys()
+        .find(|rule_id| !rules::ALL.contains(&rule_id.as_str()))
+}
+
+/// The settings one file is scanned with.
+#[derive(Debug)]
+pub struct FileConfig {
+    pub compiled: CompiledValidationConfig,
+    severities: HashMap<String, RuleSeverity>,
+}
+
+impl FileConfig {
+    /// Move the `errors` of rules with warning severity to `warnings` as
+    /// `DowngradedError`s, returning the rest.
+    pub fn apply_severities(
+        &self,
+        errors: Vec<ValidationError>,
+        warnings: &mut Vec<ScanError>,
+    ) -> Vec<ValidationError> {
+        if self.severities.is_empty() {
+            return errors;
+        }
+        let (downgraded, errors): (Vec<_>, Vec<_>) = errors
+            .into_iter()
+            .partition(|error| self.severities.get(error.rule_id) == Some(&RuleSeverity::Warning));
+        warnings.extend(downgraded.into_iter().map(|error| {
+            let message = format!(
+                "{} ({} reported as a warning)",
+                error.format_human_readable(),
+                error.rule_id
+            );
+            ScanError {
+                file: error.file,
+                kind: ScanErrorKind::DowngradedError,
+                message,
+                original_file: None,
+            }
+        }));
+        errors
+    }
+}
+
+/// Finds the override files that apply to each scanned file and the
+/// settings they result in.
+pub struct DirectoryOverrides<'a> {
+    validation_config: &'a ValidationConfig,
+    base: Rc<FileConfig>,
+    enabled: bool,
+    /// Where the lookup stops: the scan paths, or the parents of those
+    /// that are files. `None` looks up every ancestor.
+    roots: Option<Vec<PathBuf>>,
+    /// The override file of each directory looked at, if it has one.
+    files: HashMap<PathBuf, Option<Rc<OverrideFile>>>,
+    /// Configs by the override files they apply, outermost first.
+    configs: HashMap<Vec<PathBuf>, Rc<FileConfig>>,
+    applied: BTreeSet<PathBuf>,
+}
+
+impl<'a> DirectoryOverrides<'a> {
+    /// Overrides for the files found under `fs_config.paths`, or for
+    /// `listed` files, which look up every ancestor directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `validation_config.rule_severities` names an
+    /// unknown rule.
+    pub fn new(
+        fs_config: &FsSourceConfig,
+        validation_config: &'a ValidationConfig,
+        compiled: CompiledValidationConfig,
+        listed: bool,
+    ) -> anyhow::Result<Self> {
+        if let Some(rule_id) = unknown_rule(&validation_config.rule_severities) {
+            return Err(invalid_setting("rule_severities")(anyhow::anyhow!(
+                "Unknown rule '{rule_id}' (known rules: {})",
+                rules::ALL.join(", ")
+            )));
+        }
+        let roots = (!listed).then(|| {
+            fs_config
+                .paths
+                .iter()
+                .map(|path| match path.parent() {
+                    Some(parent) if path.is_file() => parent.to_path_buf(),
+                    _ => path.clone(),
+                })
+                .collect()
+        });
+        Ok(Self {
+            validation_config,
+            base: Rc::new(FileConfig {
+                compiled,
+                severities: validation_config.rule_severities.clone(),
+            }),
+            enabled: fs_config.directory_overrides,
+            roots,
+            files: HashMap::new(),
+            configs: HashMap::new(),
+            applied: BTreeSet::new(),
+        })
+    }
+
+    /// The config without any override file.
+    pub fn base(&self) -> &FileConfig {
+        &self.base
+    }
+
+    /// The config `file_path` is scanned with.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an override file that applies cannot be read or
+    /// parsed, or has unknown keys or severities for unknown rules.
+    pub fn config_for(&mut self, file_path: &Path) -> anyhow::Result<Rc<FileConfig>> {
+        if !self.enabled {
+            return Ok(Rc::clone(&self.base));
+        }
+        let mut chain = Vec::new();
+        for dir in file_path.ancestors().skip(1) {
+            if let Some(file) = self.override_in(dir)? {
+                chain.push((dir.join(OVERRIDE_FILE), file));
+            }
+            let is_root = self
+                .roots
+                .as_ref()
+                .is_some_and(|roots| roots.iter().any(|root| root == dir));
+            if is_root {
+                break;
+            }
+        }
+        if chain.is_empty() {
+            return Ok(Rc::clone(&self.base));
+        }
+        chain.reverse();
+        let paths: Vec<PathBuf> = chain.iter().map(|(path, _)| path.clone()).collect();
+        self.applied.extend(paths.iter().cloned());
+        if let Some(config) = self.configs.get(&paths) {
+            return Ok(Rc::clone(config));
+        }
+        let config = Rc::new(self.merge(chain.iter().map(|(_, file)| file.as_ref())));
+        self.configs.insert(paths, Rc::clone(&config));
+        Ok(config)
+    }
+
+    /// The override files that applied to at least one file, sorted.
+    pub fn into_applied(self) -> Vec<PathBuf> {
+        self.applied.into_iter().collect()
+    }
+
+    fn override_in(&mut self, dir: &Path) -> anyhow::Result<Option<Rc<OverrideFile>>> {
+        if let Some(file) = self.files.get(dir) {
+            return Ok(file.clone());
+        }
+        let path = dir.join(OVERRIDE_FILE);
+        let file = if path.is_file() {
+            Some(Rc::new(OverrideFile::load(&path)?))
+        } else {
+            None
+        };
+        self.files.insert(dir.to_path_buf(), file.clone());
+        Ok(file)
+    }
+
+    /// The base config with `files` applied in order, leaving the pinned
+    /// settings alone.
+    fn merge<'f>(&self, files: impl Iterator<Item = &'f OverrideFile>) -> FileConfig {
+        let pinned = |setting: &str| {
+            self.validation_config
+                .pinned_settings
+                .iter()
+                .any(|pinned| pinned == setting)
+        };
+        let mut vendor_policy = None;
+        let mut skip_tokens = None;
+        let mut heuristic = None;
+        let mut severities = self.base.severities.clone();
+        for file in files {
+            if let Some(vendor) = &file.vendor
+                && !pinned("vendor_policy")
+            {
+                vendor_policy = Some(vendor.policy());
+            }
+            if file.skip_tokens.is_some() && !pinned("skip_tokens") {
+                skip_tokens.clone_from(&file.skip_tokens);
+            }
+            if file.strict.is_some() && !pinned("discovery_mode") {
+                heuristic = file.strict;
+            }
+            if !pinned("rule_severities") {
+                severities.extend(file.severity.iter().map(|(k, v)| (k.clone(), *v)));
+            }
+        }
+        let mut compiled = self.base.compiled.clone();
+        if let Some(policy) = vendor_policy {
+            compiled.vendor_check = VendorCheck::new(policy, self.validation_config.vendor_scope);
+        }
+        if let Some(tokens) = skip_tokens {
+            compiled.skip_tokens = SkipTokens::new(&tokens);
+        }
+        if let Some(heuristic) = heuristic {
+            compiled.heuristic = heuristic;
+        }
+        FileConfig {
+            compiled,
+            severities,
+        }
+    }
+}
@@ -0,0 +1,125 @@
+//! Redaction of sensitive file paths from a finished report.
+//!
+//! Paths such as `data/tenant-acme/config.yaml` can name customers, so
+//! paths matching `ValidationConfig::sensitive_paths` are swapped for a
+//! label before the report reaches any output format.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use glob::Pattern;
+
+use crate::config::ValidationConfig;
+use crate::outcome::ScanOutcome;
+use crate::report::ValidationReport;
+
+/// [`ValidationConfig::sensitive_paths`] compiled, with the label that
+/// replaces matching paths.
+#[derive(Debug, Clone)]
+pub struct PathRedaction {
+    patterns: Vec<Pattern>,
+    label: String,
+}
+
+impl PathRedaction {
+    /// `None` when no sensitive path is configured.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a sensitive path pattern is not a valid glob.
+    pub fn new(config: &ValidationConfig) -> anyhow::Result<Option<Self>> {
+        if config.sensitive_paths.is_empty() {
+            return Ok(None);
+        }
+        let patterns = config
+            .sensitive_paths
+            .iter()
+            .map(|glob| {
+                Pattern::new(glob)
+                    .with_context(|| format!("Invalid sensitive path pattern '{glob}'"))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Some(Self {
+            patterns,
+            label: config.redacted_path_label.clone(),
+        }))
+    }
+
+    /// Whether `path`, or any trailing part of it, matches a pattern.
+    fn is_sensitive(&self, path: &Path) -> bool {
+        let components: Vec<_> = path.components().collect();
+        (0..components.len()).any(|start| {
+            let tail: PathBuf = components[start..].iter().collect();
+            self.patterns
+                .iter()
+                .any(|pattern| pattern.matches_path(&tail))
+        })
+    }
+
+    /// Replace every sensitive path in `report` and `outcomes` with the
+    /// label, keeping the real path of each error in `original_file`.
+    pub fn apply(&self, report: &mut ValidationReport, outcomes: &mut [ScanOutcome]) {
+        let traced = report
+            .discovery_trace
+            .iter()
+            .flat_map(|trace| trace.files.iter().map(|file| file.file.as_path()));
+        let sensitive: BTreeSet<PathBuf> = report
+            .validation_errors
+            .iter()
+            .map(|e| e.file.as_path())
+            .chain(report.scan_errors.iter().map(|e| e.file.as_path()))
+            .chain(report.warnings.iter().map(|e| e.file.as_path()))
+            .chain(outcomes.iter().map(|outcome| outcome.file.as_path()))
+            .chain(traced)
+            .filter(|path| self.is_sensitive(path))
+            .map(Path::to_path_buf)
+            .collect();
+        if sensitive.is_empty() {
+            return;
+        }
+        // Longest first, so a path is never left half-replaced because a
+        // shorter sensitive path is its prefix.
+        let mut quoted: Vec<String> = sensitive
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect();
+        quoted.sort_by_key(|path| std::cmp::Reverse(path.len()));
+        let scrub = |text: &mut String| {
+            for path in &quoted {
+                if text.contains(path.as_str()) {
+                    *text = text.replace(path.as_str(), &self.label);
+                }
+            }
+        };
+        let redact = |file: &mut PathBuf| {
+            if sensitive.contains(file) {
+                Some(std::mem::replace(file, PathBuf::from(&self.label)))
+            } else {
+                None
+            }
+        };
+
+        let errors = report.validation_errors.iter_mut().chain(
+            outcomes
+                .iter_mut()
+                .flat_map(|outcome| outcome.errors.iter_mut()),
+        );
+        for error in errors {
+            error.original_file = redact(&mut error.file);
+            scrub(&mut error.error);
+        }
+        for error in report.scan_errors.iter_mut().chain(&mut report.warnings) {
+            error.original_file = redact(&mut error.file);
+            scrub(&mut error.message);
+        }
+        for outcome in outcomes {
+            redact(&mut outcome.file);
+        }
+        if let Some(trace) = &mut report.discovery_trace {
+            for file in &mut trace.files {
+                redact(&mut file.file);
+            }
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! Trusted registries: directories of canonical GTS schemas, such as a
+//! `.gts-spec` checkout, that a run reads for the identifiers they define
+//! without validating them.
+//!
+//! A registry is the source of truth for its identifiers: cross-file checks
+//! treat them as defined before any scanned file, and their files never
+//! produce errors or count as scanned.
+
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{CompiledValidationConfig, FsSourceConfig};
+use crate::format;
+use crate::outcome::{FoundId, ScanOutcome};
+use crate::strategy::ContentFormat;
+use crate::strategy::fs::{ScanResult, content_format_for, find_files, read_file_bounded};
+use crate::trace::TracedCandidate;
+
+/// How many identifiers a registry directory defined.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct RegistrySource {
+    pub dir: PathBuf,
+    /// Distinct identifiers defined by its JSON/YAML files.
+    pub ids: usize,
+}
+
+/// The identifiers defined by a run's registry directories.
+#[derive(Debug, Default)]
+pub struct Registry {
+    /// One entry per registry directory, in the order they were given.
+    pub sources: Vec<RegistrySource>,
+    /// Every definition, in directory then file order.
+    pub definitions: Vec<FoundId>,
+}
+
+impl Registry {
+    /// Read the definitions of the JSON/YAML files under `dirs`, walked
+    /// with the limits and exclusions of `fs_config`. Files that cannot be
+    /// read or parsed are skipped: a registry is trusted, not checked.
+    pub fn load(
+        dirs: &[PathBuf],
+        fs_config: &FsSourceConfig,
+        compiled: &CompiledValidationConfig,
+    ) -> Self {
+        let mut registry = Self::default();
+        for dir in dirs {
+            let walk = FsSourceConfig {
+                paths: vec![dir.clone()],
+                include_gts_spec: true,
+                registry_dirs: Vec::new(),
+                default_registry: false,
+                ..fs_config.clone()
+            };
+            let mut ids = BTreeSet::new();
+            for file in find_files(&walk).files {
+                let Some(format @ (ContentFormat::Json | ContentFormat::Yaml)) =
+                    content_format_for(&file)
+                else {
+                    continue;
+                };
+                let ScanResult::Ok(content) =
+                    read_file_bounded(&file, fs_config.max_file_size, fs_config.transcode_utf16)
+                else {
+                    continue;
+                };
+                let mut candidates: Vec<TracedCandidate> = Vec::new();
+                let options = &compiled.json_options;
+                if format == ContentFormat::Json {
+                    let scanned = format::json::scan_json_content(
+                        &content,
+                        &file,
+                        &compiled.vendor_check,
+                        options,
+                        &mut Vec::new(),
+                        &mut candidates,
+                    );
+                    if scanned.is_err() {
+                        continue;
+                    }
+                } else {
+                    format::yaml::scan_yaml_content(
+                        &content,
+                        &file,
+                        &compiled.vendor_check,
+                        options,
+                        &mut Vec::new(),
+                        &mut candidates,
+                    );
+                }
+                let outcome = ScanOutcome::new(file, Vec::new(), &candidates);
+                ids.extend(outcome.definitions.iter().map(|found| found.id.clone()));
+                registry.definitions.extend(outcome.definitions);
+            }
+            registry.sources.push(RegistrySource {
+                dir: dir.clone(),
+                ids: ids.len(),
+            });
+        }
+        registry
+    }
+}
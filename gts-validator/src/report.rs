@@ -1,40 +1,790 @@
 //! Validation report types.
 
-use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
 
-use crate::error::{ScanError, ValidationError};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{CoverageError, ScanError, ValidationError};
+use crate::metadata::InferredVendor;
+use crate::outcome::{FoundId, ScanOutcome};
+use crate::registry::RegistrySource;
+use crate::rules;
+use crate::stats::IdStats;
+use crate::trace::DiscoveryTrace;
 
 /// Result of a validation run.
 ///
 /// CI pipelines must check both `validation_errors` and `scan_errors`.
 /// A non-empty `scan_errors` means the validator did not fully run —
 /// treat this as a build failure regardless of `validation_errors`.
-#[derive(Debug, Clone, Serialize)]
+///
+/// Reports saved as JSON can be loaded back with
+/// [`from_json_str`](Self::from_json_str); fields added in newer versions
+/// take their defaults when missing. File paths are JSON strings: discovery
+/// skips files whose path is not valid Unicode (with a `NonUnicodePath`
+/// warning), so every other path round-trips losslessly, and the warning's
+/// own path is written with U+FFFD replacement characters.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 #[non_exhaustive]
+#[allow(clippy::struct_excessive_bools)]
 pub struct ValidationReport {
+    /// Number of candidate files found by discovery (supported extension,
+    /// not excluded). Always `scanned_files + skipped_files`.
+    pub discovered_files: usize,
     /// Number of files successfully scanned (read + parsed).
     pub scanned_files: usize,
+    /// Scanned files per format name (see `ContentFormat::name`).
+    pub scanned_by_format: BTreeMap<String, usize>,
+    /// Number of discovered files that were not scanned: unreadable,
+    /// oversized, unparseable, or not reached because a limit was hit.
+    pub skipped_files: usize,
     /// Number of files that could not be scanned (read/parse failures).
     pub failed_files: usize,
+    /// Whether `max_files` or `max_total_bytes` stopped the scan early. The
+    /// limit is also reported as a `LimitExceeded` scan error.
+    pub truncated: bool,
     /// Whether all scanned files passed validation AND no scan errors occurred.
     pub ok: bool,
     /// Individual GTS ID validation errors found in scanned files.
     pub validation_errors: Vec<ValidationError>,
+    /// Whether `validation_errors` was cut down to
+    /// `ValidationConfig::max_reported_errors` (see
+    /// [`truncate_errors`](Self::truncate_errors)).
+    pub errors_truncated: bool,
+    /// Number of validation errors left out of `validation_errors`.
+    pub omitted_errors: usize,
+    /// Number of validation errors left out of `validation_errors` by
+    /// `ValidationConfig::max_errors_per_file` (see
+    /// [`cap_errors_per_file`](Self::cap_errors_per_file)).
+    pub suppressed_errors: usize,
+    /// Whether the run failed because no file was scanned (only with
+    /// `ValidationConfig::fail_on_empty_scan`).
+    pub empty_scan: bool,
     /// Scan-level errors: files that could not be read or parsed.
     /// Non-empty means the validator did not fully cover the repository.
     pub scan_errors: Vec<ScanError>,
+    /// Non-fatal findings (e.g. broken symlinks). These never affect `ok`.
+    pub warnings: Vec<ScanError>,
+    /// Rules whose error count exceeded their configured threshold, as
+    /// `(rule_id, actual, threshold)`, sorted by rule ID.
+    pub exceeded_thresholds: Vec<ExceededThreshold>,
+    /// Every candidate the scanners considered, when
+    /// `ValidationConfig::trace_discovery` is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub discovery_trace: Option<DiscoveryTrace>,
+    /// Distinct GTS IDs per vendor and package, when
+    /// `ValidationConfig::id_stats` is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id_stats: Option<IdStats>,
+    /// The vendor the run checked against and where it was found, when it
+    /// was inferred (see [`crate::infer_vendor`]) rather than given.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inferred_vendor: Option<InferredVendor>,
+    /// The registry directories the run read identifiers from (see
+    /// `FsSourceConfig::registry_dirs`) and how many each defined.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub registry: Vec<RegistrySource>,
+    /// The directory override files (see
+    /// `FsSourceConfig::directory_overrides`) that applied to at least one
+    /// scanned file, sorted.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub applied_overrides: Vec<PathBuf>,
+}
+
+/// Result of [`validate_fs_detailed`](crate::validate_fs_detailed): the
+/// report plus what each scanned file defined and referenced.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct DetailedValidationReport {
+    pub report: ValidationReport,
+    /// One outcome per scanned file, in scan order.
+    pub outcomes: Vec<ScanOutcome>,
+    /// The identifiers the registry directories define, which were not
+    /// validated.
+    pub registry_definitions: Vec<FoundId>,
+}
+
+/// A rule that exceeded its threshold: `(rule_id, actual, threshold)`.
+pub type ExceededThreshold = (String, usize, usize);
+
+/// Check `errors` against per-rule `thresholds`.
+///
+/// Returns whether every rule stays within its threshold (rules without one
+/// tolerate no errors), plus the configured thresholds that were exceeded.
+pub fn check_rule_thresholds(
+    errors: &[ValidationError],
+    thresholds: &HashMap<String, usize>,
+) -> (bool, Vec<ExceededThreshold>) {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for error in errors {
+        *counts.entry(error.rule_id).or_default() += 1;
+    }
+    let mut within = true;
+    let mut exceeded = Vec::new();
+    for (rule_id, count) in counts {
+        let threshold = thresholds.get(rule_id).copied();
+        if count > threshold.unwrap_or(0) {
+            within = false;
+            if let Some(threshold) = threshold {
+                exceeded.push((rule_id.to_owned(), count, threshold));
+            }
+        }
+    }
+    (within, exceeded)
+}
+
+/// How [`ValidationReport::to_exit_code`] turns a report into a process
+/// exit code.
+#[derive(Default)]
+#[non_exhaustive]
+pub enum ExitCodeStrategy {
+    /// 0 when the report is `ok`, 1 otherwise (default).
+    #[default]
+    BinaryOk,
+    /// `error_code` when the report is not `ok`; `warning_code` when it is
+    /// `ok` but has warnings or errors tolerated by
+    /// `ValidationConfig::rule_thresholds`; 0 otherwise.
+    SeverityBased { error_code: u8, warning_code: u8 },
+    /// Any mapping.
+    Custom(ExitCodeFn),
+}
+
+/// The mapping of [`ExitCodeStrategy::Custom`].
+type ExitCodeFn = Box<dyn Fn(&ValidationReport) -> u8>;
+
+impl fmt::Debug for ExitCodeStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BinaryOk => f.write_str("BinaryOk"),
+            Self::SeverityBased {
+                error_code,
+                warning_code,
+            } => f
+                .debug_struct("SeverityBased")
+                .field("error_code", error_code)
+                .field("warning_code", warning_code)
+                .finish(),
+            Self::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
 }
 
 impl ValidationReport {
+    /// Load a report from its JSON serialization.
+    ///
+    /// # Errors
+    /// Returns an error if `s` is not a JSON report.
+    pub fn from_json_str(s: &str) -> anyhow::Result<Self> {
+        Ok(serde_json::from_str(s)?)
+    }
+
+    /// Load a report from a reader yielding its JSON serialization.
+    ///
+    /// # Errors
+    /// Returns an error if reading fails or the content is not a JSON report.
+    pub fn from_json_reader(reader: impl Read) -> anyhow::Result<Self> {
+        Ok(serde_json::from_reader(reader)?)
+    }
+
     /// Total number of files attempted (scanned + failed).
     #[must_use]
     pub fn files_attempted(&self) -> usize {
         self.scanned_files + self.failed_files
     }
 
-    /// Number of validation errors found.
+    /// Check that the run covered what CI expects: at least `min_scanned`
+    /// scanned files, and at least one scanned file of each format named in
+    /// `required_formats` (see `ContentFormat::name`).
+    ///
+    /// # Errors
+    ///
+    /// Returns the first unmet requirement, file count first.
+    pub fn verify_coverage(
+        &self,
+        min_scanned: usize,
+        required_formats: &[&str],
+    ) -> Result<(), CoverageError> {
+        if self.scanned_files < min_scanned {
+            return Err(CoverageError::TooFewFilesScanned {
+                found: self.scanned_files,
+                expected: min_scanned,
+            });
+        }
+        if let Some(format) = required_formats
+            .iter()
+            .find(|format| self.scanned_by_format.get(**format).is_none_or(|&n| n == 0))
+        {
+            return Err(CoverageError::MissingFormat {
+                format: (*format).to_owned(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Number of validation errors found, including omitted and suppressed
+    /// ones. The [`rules::TOO_MANY_ERRORS`] entries standing for suppressed
+    /// errors are not counted themselves.
     #[must_use]
     pub fn errors_count(&self) -> usize {
-        self.validation_errors.len()
+        let placeholders = self
+            .validation_errors
+            .iter()
+            .filter(|error| error.rule_id == rules::TOO_MANY_ERRORS)
+            .count();
+        self.validation_errors.len() - placeholders + self.omitted_errors + self.suppressed_errors
+    }
+
+    /// Whether the report is `ok` but not clean: it has warnings, or errors
+    /// within their rule thresholds.
+    #[must_use]
+    pub fn has_warnings_only(&self) -> bool {
+        self.ok && (!self.warnings.is_empty() || self.errors_count() > 0)
+    }
+
+    /// The process exit code for this report under `strategy`.
+    #[must_use]
+    pub fn to_exit_code(&self, strategy: &ExitCodeStrategy) -> u8 {
+        match strategy {
+            ExitCodeStrategy::BinaryOk => u8::from(!self.ok),
+            ExitCodeStrategy::SeverityBased {
+                error_code,
+                warning_code,
+            } => {
+                if !self.ok {
+                    *error_code
+                } else if self.has_warnings_only() {
+                    *warning_code
+                } else {
+                    0
+                }
+            }
+            ExitCodeStrategy::Custom(to_code) => to_code(self),
+        }
+    }
+
+    /// Keep at most `max` validation errors, recording how many were left
+    /// out in `omitted_errors`.
+    ///
+    /// The sample favors variety: errors are fingerprinted by rule and raw
+    /// value, the first error of each fingerprint is kept, and only then are
+    /// remaining slots filled in scan order. Kept errors stay in scan order.
+    /// Counts, `ok` and `exceeded_thresholds` are unaffected; the error
+    /// distribution and histogram cover the kept errors only.
+    /// [`rules::TOO_MANY_ERRORS`] entries are always kept and do not count
+    /// towards `max`.
+    pub fn truncate_errors(&mut self, max: usize) {
+        let mut keep: Vec<bool> = self
+            .validation_errors
+            .iter()
+            .map(|error| error.rule_id == rules::TOO_MANY_ERRORS)
+            .collect();
+        let total = keep.iter().filter(|&&placeholder| !placeholder).count();
+        if total <= max {
+            return;
+        }
+        let mut kept = 0;
+        let mut fingerprints = HashSet::new();
+        for (i, error) in self.validation_errors.iter().enumerate() {
+            if kept == max {
+                break;
+            }
+            if !keep[i] && fingerprints.insert((error.rule_id, error.raw_value.as_str())) {
+                keep[i] = true;
+                kept += 1;
+            }
+        }
+        for slot in &mut keep {
+            if kept == max {
+                break;
+            }
+            if !*slot {
+                *slot = true;
+                kept += 1;
+            }
+        }
+        let mut keep = keep.into_iter();
+        self.validation_errors
+            .retain(|_| keep.next().unwrap_or(false));
+        self.omitted_errors += total - max;
+        self.errors_truncated = true;
+    }
+
+    /// Set the [`help`](ValidationError::help) of every validation error to
+    /// the [remediation](rules::remediation) of its rule.
+    pub fn attach_help(&mut self) {
+        for error in &mut self.validation_errors {
+            error.help = rules::remediation(error.rule_id);
+        }
+    }
+
+    /// Keep at most the first `max` validation errors of each file, in scan
+    /// order. The errors left out of a file are counted in
+    /// `suppressed_errors` and replaced by one [`rules::TOO_MANY_ERRORS`]
+    /// entry saying how many there were. Counts, `ok` and
+    /// `exceeded_thresholds` are unaffected.
+    pub fn cap_errors_per_file(&mut self, max: usize) {
+        let mut per_file: HashMap<PathBuf, usize> = HashMap::new();
+        for error in &self.validation_errors {
+            *per_file.entry(error.file.clone()).or_default() += 1;
+        }
+        per_file.retain(|_, count| *count > max);
+        if per_file.is_empty() {
+            return;
+        }
+        let mut seen: HashMap<PathBuf, usize> = HashMap::new();
+        let mut capped = Vec::with_capacity(self.validation_errors.len());
+        for error in std::mem::take(&mut self.validation_errors) {
+            let Some(&total) = per_file.get(&error.file) else {
+                capped.push(error);
+                continue;
+            };
+            let seen = seen.entry(error.file.clone()).or_default();
+            *seen += 1;
+            if *seen <= max {
+                capped.push(error);
+                continue;
+            }
+            if *seen == max + 1 {
+                let suppressed = total - max;
+                self.suppressed_errors += suppressed;
+                capped.push(ValidationError {
+                    file: error.file,
+                    line: 0,
+                    column: 0,
+                    json_path: String::new(),
+                    raw_value: String::new(),
+                    normalized_id: String::new(),
+                    rule_id: rules::TOO_MANY_ERRORS,
+                    error: format!(
+                        "{suppressed} more error(s) in this file suppressed (at most {max} reported per file)"
+                    ),
+                    context: String::new(),
+                    section: None,
+                    document_kind: None,
+                    fix: None,
+                    help: None,
+                    original_file: None,
+                });
+            }
+        }
+        self.validation_errors = capped;
+    }
+
+    /// Validation errors per file, for files with at least one error.
+    fn errors_per_file(&self) -> Vec<usize> {
+        let mut counts: HashMap<&Path, usize> = HashMap::new();
+        for error in &self.validation_errors {
+            *counts.entry(error.file.as_path()).or_default() += 1;
+        }
+        let mut counts: Vec<usize> = counts.into_values().collect();
+        counts.sort_unstable();
+        counts
+    }
+
+    /// Summarize how validation errors are spread across files.
+    ///
+    /// Percentiles use the nearest-rank method over files with at least one
+    /// error; all fields are zero when there are no validation errors.
+    #[must_use]
+    pub fn error_distribution(&self) -> ErrorDistribution {
+        let counts = self.errors_per_file();
+        let percentile = |p: usize| {
+            let rank = (p * counts.len()).div_ceil(100).max(1);
+            counts.get(rank - 1).copied().unwrap_or(0)
+        };
+        #[allow(clippy::cast_precision_loss)]
+        let mean = if counts.is_empty() {
+            0.0
+        } else {
+            self.validation_errors.len() as f64 / counts.len() as f64
+        };
+        ErrorDistribution {
+            files_with_errors: counts.len(),
+            mean,
+            p50: percentile(50),
+            p90: percentile(90),
+            p99: percentile(99),
+            max: counts.last().copied().unwrap_or(0),
+        }
+    }
+
+    /// Print an ASCII histogram of validation errors per file.
+    ///
+    /// The range `1..=max` errors per file is split into at most `bins`
+    /// equal-width buckets; each row shows the bucket range, a bar scaled to
+    /// the largest bucket, and the number of files in it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    pub fn print_histogram(&self, bins: usize, writer: &mut dyn Write) -> anyhow::Result<()> {
+        const BAR_WIDTH: usize = 40;
+
+        let dist = self.error_distribution();
+        if dist.files_with_errors == 0 {
+            writeln!(writer, "No files with validation errors")?;
+            return Ok(());
+        }
+        writeln!(
+            writer,
+            "Errors per file ({} files): p50={} p90={} p99={} max={} mean={:.2}",
+            dist.files_with_errors, dist.p50, dist.p90, dist.p99, dist.max, dist.mean
+        )?;
+
+        let bin_width = dist.max.div_ceil(bins.max(1));
+        let mut buckets = vec![0usize; dist.max.div_ceil(bin_width)];
+        for count in self.errors_per_file() {
+            buckets[count.div_ceil(bin_width) - 1] += 1;
+        }
+        let tallest = buckets.iter().copied().max().unwrap_or(0).max(1);
+        let label_width = dist.max.to_string().len();
+        for (i, files) in buckets.iter().enumerate() {
+            let low = i * bin_width + 1;
+            let high = ((i + 1) * bin_width).min(dist.max);
+            let bar = "#".repeat((files * BAR_WIDTH).div_ceil(tallest));
+            writeln!(
+                writer,
+                "  {low:>label_width$}-{high:<label_width$} | {bar:<BAR_WIDTH$} {files}"
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// How validation errors are distributed across files.
+///
+/// Computed by [`ValidationReport::error_distribution`] over files with at
+/// least one validation error.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct ErrorDistribution {
+    /// Number of files with at least one validation error.
+    pub files_with_errors: usize,
+    /// Mean errors per file.
+    pub mean: f64,
+    /// Median errors per file.
+    pub p50: usize,
+    /// 90th percentile of errors per file.
+    pub p90: usize,
+    /// 99th percentile of errors per file.
+    pub p99: usize,
+    /// Most errors in a single file.
+    pub max: usize,
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn error_in(file: &str) -> ValidationError {
+        ValidationError {
+            file: PathBuf::from(file),
+            line: 1,
+            column: 1,
+            json_path: String::new(),
+            raw_value: "gts.bad".to_owned(),
+            normalized_id: "gts.bad".to_owned(),
+            rule_id: crate::rules::INVALID_ID,
+            error: "Invalid GTS ID".to_owned(),
+            context: String::new(),
+            section: None,
+            document_kind: None,
+            fix: None,
+            help: None,
+            original_file: None,
+        }
+    }
+
+    /// One file per entry in `counts`, with that many errors.
+    fn report_with(counts: &[usize]) -> ValidationReport {
+        let validation_errors = counts
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &n)| std::iter::repeat_n(error_in(&format!("f{i}.md")), n))
+            .collect();
+        ValidationReport {
+            discovered_files: counts.len(),
+            scanned_files: counts.len(),
+            scanned_by_format: BTreeMap::from([("markdown".to_owned(), counts.len())]),
+            skipped_files: 0,
+            failed_files: 0,
+            truncated: false,
+            ok: false,
+            validation_errors,
+            errors_truncated: false,
+            omitted_errors: 0,
+            suppressed_errors: 0,
+            empty_scan: false,
+            scan_errors: Vec::new(),
+            warnings: Vec::new(),
+            exceeded_thresholds: Vec::new(),
+            discovery_trace: None,
+            id_stats: None,
+            inferred_vendor: None,
+            registry: Vec::new(),
+            applied_overrides: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_error_distribution_percentiles() {
+        // 1..=100 errors per file: nearest rank picks the value equal to p.
+        let counts: Vec<usize> = (1..=100).collect();
+        let dist = report_with(&counts).error_distribution();
+        assert_eq!(dist.files_with_errors, 100);
+        assert_eq!((dist.p50, dist.p90, dist.p99, dist.max), (50, 90, 99, 100));
+        assert!((dist.mean - 50.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_error_distribution_concentrated() {
+        let dist = report_with(&[1, 1, 1, 1, 1, 1, 1, 1, 1, 41]).error_distribution();
+        assert_eq!(dist.files_with_errors, 10);
+        assert_eq!((dist.p50, dist.p90, dist.p99, dist.max), (1, 1, 41, 41));
+        assert!((dist.mean - 5.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_error_distribution_empty() {
+        let dist = report_with(&[]).error_distribution();
+        assert_eq!(dist.files_with_errors, 0);
+        assert_eq!((dist.p50, dist.p90, dist.p99, dist.max), (0, 0, 0, 0));
+        assert!(dist.mean.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_print_histogram() {
+        let mut buf = Vec::new();
+        report_with(&[1, 1, 2, 3, 8])
+            .print_histogram(4, &mut buf)
+            .unwrap();
+        let out = String::from_utf8(buf).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(
+            lines[0],
+            "Errors per file (5 files): p50=2 p90=8 p99=8 max=8 mean=3.00"
+        );
+        assert_eq!(lines.len(), 5);
+        assert!(lines[1].starts_with("  1-2 | ########################################"));
+        assert!(lines[1].ends_with(" 3"));
+        assert!(lines[2].starts_with("  3-4 | ##########"));
+        assert!(lines[3].ends_with(" 0"));
+        assert!(lines[4].starts_with("  7-8 | #"));
+    }
+
+    #[test]
+    fn test_print_histogram_without_errors() {
+        let mut buf = Vec::new();
+        report_with(&[]).print_histogram(10, &mut buf).unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "No files with validation errors\n"
+        );
+    }
+
+    #[test]
+    fn test_check_rule_thresholds() {
+        let mut vendor = error_in("a.md");
+        vendor.rule_id = crate::rules::VENDOR_MISMATCH;
+        let mut errors = vec![vendor; 3];
+
+        let no_thresholds = HashMap::new();
+        assert_eq!(
+            check_rule_thresholds(&errors, &no_thresholds),
+            (false, vec![])
+        );
+
+        let thresholds = HashMap::from([("GTS001".to_owned(), 3)]);
+        assert_eq!(check_rule_thresholds(&errors, &thresholds), (true, vec![]));
+
+        let thresholds = HashMap::from([("GTS001".to_owned(), 2)]);
+        assert_eq!(
+            check_rule_thresholds(&errors, &thresholds),
+            (false, vec![("GTS001".to_owned(), 3, 2)])
+        );
+
+        // A rule without a threshold still fails the run.
+        errors.push(error_in("b.md"));
+        let thresholds = HashMap::from([("GTS001".to_owned(), 3)]);
+        assert_eq!(check_rule_thresholds(&errors, &thresholds), (false, vec![]));
+    }
+
+    #[test]
+    fn test_truncate_errors_keeps_one_per_fingerprint_first() {
+        let mut report = report_with(&[4, 2]);
+        report.validation_errors[2].raw_value = "gts.other".to_owned();
+        report.validation_errors[3].rule_id = crate::rules::VENDOR_MISMATCH;
+        report.validation_errors[5].raw_value = "gts.third".to_owned();
+
+        report.truncate_errors(4);
+        assert!(report.errors_truncated);
+        assert_eq!(report.omitted_errors, 2);
+        assert_eq!(report.errors_count(), 6);
+        // Fingerprints first (0, 2, 3, 5), in scan order.
+        let kept: Vec<(&str, &str)> = report
+            .validation_errors
+            .iter()
+            .map(|e| (e.file.to_str().unwrap(), e.raw_value.as_str()))
+            .collect();
+        assert_eq!(
+            kept,
+            [
+                ("f0.md", "gts.bad"),
+                ("f0.md", "gts.other"),
+                ("f0.md", "gts.bad"),
+                ("f1.md", "gts.third")
+            ]
+        );
+        assert_eq!(
+            report.validation_errors[2].rule_id,
+            crate::rules::VENDOR_MISMATCH
+        );
+
+        // Fewer fingerprints than slots: the rest is filled in scan order.
+        let mut report = report_with(&[3, 2]);
+        report.truncate_errors(3);
+        assert_eq!(report.validation_errors.len(), 3);
+        assert_eq!(report.omitted_errors, 2);
+        assert!(
+            report
+                .validation_errors
+                .iter()
+                .all(|e| e.file.ends_with("f0.md"))
+        );
+
+        let mut report = report_with(&[2]);
+        report.truncate_errors(2);
+        assert!(!report.errors_truncated);
+        assert_eq!(report.omitted_errors, 0);
+    }
+
+    #[test]
+    fn test_verify_coverage() {
+        let report = report_with(&[1, 0, 2]);
+        assert_eq!(report.verify_coverage(3, &["markdown"]), Ok(()));
+        assert_eq!(
+            report.verify_coverage(4, &["json"]),
+            Err(CoverageError::TooFewFilesScanned {
+                found: 3,
+                expected: 4
+            })
+        );
+        let err = report
+            .verify_coverage(0, &["markdown", "json"])
+            .unwrap_err();
+        assert_eq!(
+            err,
+            CoverageError::MissingFormat {
+                format: "json".to_owned()
+            }
+        );
+        assert_eq!(err.to_string(), "No json file was scanned");
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let mut report = report_with(&[2, 1]);
+        report.validation_errors[0].rule_id = crate::rules::VENDOR_MISMATCH;
+        report.scan_errors.push(ScanError {
+            file: PathBuf::from("broken.json"),
+            kind: crate::ScanErrorKind::JsonParseError,
+            message: "expected value".to_owned(),
+            original_file: None,
+        });
+        report.warnings.push(ScanError {
+            file: PathBuf::from("dangling.md"),
+            kind: crate::ScanErrorKind::BrokenSymlink,
+            message: "broken symlink".to_owned(),
+            original_file: None,
+        });
+        report.exceeded_thresholds = vec![("GTS003".to_owned(), 2, 1)];
+
+        let json = serde_json::to_string_pretty(&report).unwrap();
+        assert_eq!(ValidationReport::from_json_str(&json).unwrap(), report);
+        assert_eq!(
+            ValidationReport::from_json_reader(json.as_bytes()).unwrap(),
+            report
+        );
+    }
+
+    #[test]
+    fn test_from_json_str_accepts_older_format() {
+        // No `warnings`, `exceeded_thresholds` or per-error `rule_id`.
+        let json = r#"{
+            "scanned_files": 3,
+            "failed_files": 0,
+            "ok": false,
+            "validation_errors": [{
+                "file": "docs/a.md",
+                "line": 4,
+                "column": 2,
+                "json_path": "",
+                "raw_value": "gts.hx.core.events.type.v1~",
+                "normalized_id": "gts.hx.core.events.type.v1~",
+                "error": "Vendor mismatch: expected 'x', found 'hx'",
+                "context": ""
+            }],
+            "scan_errors": []
+        }"#;
+        let report = ValidationReport::from_json_str(json).unwrap();
+        assert_eq!(report.scanned_files, 3);
+        assert!(report.warnings.is_empty());
+        assert!(report.exceeded_thresholds.is_empty());
+        assert_eq!(
+            report.validation_errors[0].rule_id,
+            crate::rules::VENDOR_MISMATCH
+        );
+
+        assert!(ValidationReport::from_json_str(r#"{"ok": "yes"}"#).is_err());
+    }
+
+    #[test]
+    fn test_to_exit_code_strategies() {
+        let severity = ExitCodeStrategy::SeverityBased {
+            error_code: 1,
+            warning_code: 2,
+        };
+        let clean = ValidationReport {
+            ok: true,
+            ..ValidationReport::default()
+        };
+        assert_eq!(clean.to_exit_code(&ExitCodeStrategy::default()), 0);
+        assert_eq!(clean.to_exit_code(&severity), 0);
+
+        let warnings_only = ValidationReport {
+            warnings: vec![ScanError {
+                file: PathBuf::from("docs/link.md"),
+                kind: crate::error::ScanErrorKind::BrokenSymlink,
+                message: "Broken symlink".to_owned(),
+                original_file: None,
+            }],
+            ..clean.clone()
+        };
+        assert!(warnings_only.has_warnings_only());
+        assert_eq!(warnings_only.to_exit_code(&ExitCodeStrategy::BinaryOk), 0);
+        assert_eq!(warnings_only.to_exit_code(&severity), 2);
+
+        // Errors tolerated by a rule threshold count as warnings.
+        let tolerated = ValidationReport {
+            validation_errors: vec![error_in("docs/a.md")],
+            ..clean
+        };
+        assert_eq!(tolerated.to_exit_code(&severity), 2);
+
+        let failed = report_with(&[1]);
+        assert_eq!(failed.to_exit_code(&ExitCodeStrategy::BinaryOk), 1);
+        assert_eq!(failed.to_exit_code(&severity), 1);
+
+        let custom = ExitCodeStrategy::Custom(Box::new(|report| {
+            u8::try_from(report.errors_count()).unwrap_or(u8::MAX)
+        }));
+        assert_eq!(report_with(&[3, 4]).to_exit_code(&custom), 7);
+        assert_eq!(format!("{custom:?}"), "Custom(..)");
     }
 }
@@ -0,0 +1,350 @@
+//! Rewriting references from one GTS identifier to another.
+//!
+//! [`rewrite_fs`] runs the regular scan with discovery tracing on and
+//! rewrites exactly the occurrences validation checks: excluded paths,
+//! grammar fences, bad examples and skip tokens are left alone. A type ID
+//! also matches as the prefix of a longer chain, with or without `gts://`.
+//! Only the bytes of the old identifier are replaced, so quoting and
+//! formatting are kept.
+//!
+//! Markdown and template candidates carry their position. JSON and YAML
+//! candidates only carry a JSON path, so their text is located as a whole
+//! (quoted or plain) scalar; when the file holds a different number of such
+//! scalars than the scan found, the occurrences are reported as
+//! [`RewriteReport::unlocated`] and left unchanged.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use gts::GtsId;
+
+use crate::config::{FsSourceConfig, ValidationConfig};
+use crate::error::ScanError;
+use crate::fix::{TextEdit, apply_edits, write_edits};
+use crate::normalize::normalize_candidate;
+use crate::strategy::ContentFormat;
+use crate::strategy::fs::{ScanResult, content_format_for, read_file_bounded};
+use crate::trace::{CandidateOutcome, FileTrace, SkipReason, TracedCandidate};
+
+/// The result of [`rewrite_fs`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RewriteReport {
+    /// Files with at least one rewritten occurrence, in scan order.
+    pub files: Vec<FileRewrite>,
+    /// Occurrences found by the scan but not located in the file text;
+    /// they were left unchanged.
+    pub unlocated: Vec<UnlocatedOccurrence>,
+    /// Files that could not be scanned, and so were not rewritten.
+    pub scan_errors: Vec<ScanError>,
+    /// Whether the edits were written to disk (`false` for a dry run).
+    pub applied: bool,
+}
+
+impl RewriteReport {
+    /// Number of rewritten occurrences across all files.
+    #[must_use]
+    pub fn occurrences(&self) -> usize {
+        self.files.iter().map(|file| file.edits.len()).sum()
+    }
+
+    /// Number of rewritten files per [`ContentFormat::name`].
+    #[must_use]
+    pub fn files_by_format(&self) -> BTreeMap<&'static str, usize> {
+        let mut counts = BTreeMap::new();
+        for file in &self.files {
+            *counts.entry(file.format.name()).or_default() += 1;
+        }
+        counts
+    }
+}
+
+/// The rewrite of one file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FileRewrite {
+    pub file: PathBuf,
+    pub format: ContentFormat,
+    /// One edit per occurrence, in file order.
+    pub edits: Vec<TextEdit>,
+    /// A unified diff of the changed lines.
+    pub diff: String,
+}
+
+/// An occurrence [`rewrite_fs`] found but could not place in the file text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct UnlocatedOccurrence {
+    pub file: PathBuf,
+    /// The candidate as discovered.
+    pub candidate: String,
+    /// JSON path (JSON/YAML only, empty otherwise).
+    pub json_path: String,
+}
+
+/// Rewrite every occurrence of `from` in the files `fs_config` selects to
+/// `to`. A type ID (ending with `~`) also matches as the prefix of a longer
+/// chain. With `dry_run`, nothing is written; the report's diffs show what
+/// would change. Files are replaced atomically with [`write_edits`].
+///
+/// # Errors
+///
+/// Returns an error if `from` or `to` is not a GTS identifier, if one is a
+/// type ID and the other is not, if the scan fails as [`validate_fs`]
+/// would, or if a file cannot be written.
+///
+/// [`validate_fs`]: crate::validate_fs
+pub fn rewrite_fs(
+    fs_config: &FsSourceConfig,
+    validation_config: &ValidationConfig,
+    from: &str,
+    to: &str,
+    dry_run: bool,
+) -> anyhow::Result<RewriteReport> {
+    for id in [from, to] {
+        GtsId::try_new(id).map_err(|e| anyhow::anyhow!("'{id}' is not a GTS identifier: {e}"))?;
+    }
+    anyhow::ensure!(
+        from.ends_with('~') == to.ends_with('~'),
+        "'{from}' and '{to}' must both be type IDs (ending with '~') or both instance IDs"
+    );
+
+    let mut config = validation_config.clone();
+    config.trace_discovery = true;
+    config.id_stats = None;
+    let scan = crate::validate_fs(fs_config, &config)?;
+
+    let mut report = RewriteReport {
+        scan_errors: scan.scan_errors,
+        applied: !dry_run,
+        ..RewriteReport::default()
+    };
+    for file_trace in scan.discovery_trace.unwrap_or_default().files {
+        if let Some(rewrite) = rewrite_file(&file_trace, from, to, fs_config, &mut report)? {
+            if !dry_run {
+                write_edits(&rewrite.file, &rewrite.edits)?;
+            }
+            report.files.push(rewrite);
+        }
+    }
+    Ok(report)
+}
+
+/// The rewrite of the file `file_trace` describes, if it has occurrences
+/// of `from`. Occurrences that cannot be located go to `report`.
+fn rewrite_file(
+    file_trace: &FileTrace,
+    from: &str,
+    to: &str,
+    fs_config: &FsSourceConfig,
+    report: &mut RewriteReport,
+) -> anyhow::Result<Option<FileRewrite>> {
+    let matching: Vec<(&TracedCandidate, String)> = file_trace
+        .candidates
+        .iter()
+        .filter(|candidate| match &candidate.outcome {
+            CandidateOutcome::Skipped { reason } => *reason == SkipReason::Duplicate,
+            _ => true,
+        })
+        .filter_map(|candidate| {
+            let id = normalize_candidate(&candidate.candidate).ok()?.gts_id;
+            let matches = id == from || (from.ends_with('~') && id.starts_with(from));
+            matches.then_some((candidate, id))
+        })
+        .collect();
+    if matching.is_empty() {
+        return Ok(None);
+    }
+
+    let file = &file_trace.file;
+    let content = match read_file_bounded(file, fs_config.max_file_size, false) {
+        ScanResult::Ok(content) => content,
+        ScanResult::Err(e) => anyhow::bail!("Cannot read {}: {}", file.display(), e.message),
+    };
+    let Some(format) = content_format_for(file).or_else(|| ContentFormat::sniff(&content)) else {
+        return Ok(None);
+    };
+
+    let mut offsets = BTreeSet::new();
+    let mut scalars: BTreeMap<&str, Vec<&TracedCandidate>> = BTreeMap::new();
+    for (candidate, id) in &matching {
+        if candidate.line == 0 {
+            scalars
+                .entry(candidate.candidate.as_str())
+                .or_default()
+                .push(candidate);
+            continue;
+        }
+        let located = line_offset(&content, candidate.line, candidate.column)
+            .filter(|&offset| content[offset..].starts_with(candidate.candidate.as_str()))
+            .and_then(|offset| Some(offset + candidate.candidate.find(id.as_str())?));
+        match located {
+            Some(offset) => {
+                offsets.insert(offset);
+            }
+            None => report.unlocated.push(unlocated(file, candidate)),
+        }
+    }
+    for (raw, candidates) in scalars {
+        let found = scalar_offsets(&content, raw, format);
+        let id_start = normalize_candidate(raw)
+            .ok()
+            .and_then(|normalized| raw.find(&normalized.gts_id));
+        match id_start {
+            Some(id_start) if found.len() == candidates.len() => {
+                offsets.extend(found.into_iter().map(|offset| offset + id_start));
+            }
+            _ => {
+                report
+                    .unlocated
+                    .extend(candidates.into_iter().map(|c| unlocated(file, c)));
+            }
+        }
+    }
+    if offsets.is_empty() {
+        return Ok(None);
+    }
+
+    let edits: Vec<TextEdit> = offsets
+        .into_iter()
+        .map(|offset| {
+            let (line, column) = position(&content, offset);
+            TextEdit::new((line, column), (line, column + from.len()), to)
+        })
+        .collect();
+    let rewritten = apply_edits(&content, &edits)?;
+    let diff = unified_diff(file, &content, &rewritten, &edits);
+    Ok(Some(FileRewrite {
+        file: file.clone(),
+        format,
+        edits,
+        diff,
+    }))
+}
+
+fn unlocated(file: &Path, candidate: &TracedCandidate) -> UnlocatedOccurrence {
+    UnlocatedOccurrence {
+        file: file.to_owned(),
+        candidate: candidate.candidate.clone(),
+        json_path: candidate.json_path.clone(),
+    }
+}
+
+/// The byte offset of a 1-indexed line and byte column, if it is a
+/// character boundary within the line.
+fn line_offset(content: &str, line: usize, column: usize) -> Option<usize> {
+    let start = if line == 1 {
+        0
+    } else {
+        content.match_indices('\n').nth(line.checked_sub(2)?)?.0 + 1
+    };
+    let offset = start + column.checked_sub(1)?;
+    let line_end = content[start..]
+        .find('\n')
+        .map_or(content.len(), |i| start + i);
+    (offset <= line_end && content.is_char_boundary(offset)).then_some(offset)
+}
+
+/// The 1-indexed line and byte column of `offset`.
+fn position(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (before.matches('\n').count() + 1, offset - line_start + 1)
+}
+
+/// Offsets of the occurrences of `raw` in `content` that make up a whole
+/// string scalar: double-quoted in JSON; double- or single-quoted, or a
+/// plain scalar, in YAML.
+fn scalar_offsets(content: &str, raw: &str, format: ContentFormat) -> Vec<usize> {
+    content
+        .match_indices(raw)
+        .map(|(offset, _)| offset)
+        .filter(|&offset| {
+            let before = &content[..offset];
+            let after = &content[offset + raw.len()..];
+            let quoted = |quote: char| {
+                before.ends_with(quote)
+                    && !before[..before.len() - 1].ends_with('\\')
+                    && after.starts_with(quote)
+            };
+            match format {
+                ContentFormat::Json => quoted('"'),
+                ContentFormat::Yaml => {
+                    quoted('"') || quoted('\'') || is_plain_yaml_scalar(before, after)
+                }
+                _ => false,
+            }
+        })
+        .collect()
+}
+
+/// Whether the text between `before` and `after` is a whole plain YAML
+/// scalar: a key, a mapping or sequence value, or a flow collection item.
+fn is_plain_yaml_scalar(before: &str, after: &str) -> bool {
+    let line_before = before.rsplit('\n').next().unwrap_or_default();
+    let lead = line_before.trim_end_matches([' ', '\t']);
+    let starts = lead.is_empty()
+        || (lead.len() < line_before.len() && lead.ends_with([':', '-', '?']))
+        || lead.ends_with(['[', '{', ',']);
+
+    let line_after = after.split('\n').next().unwrap_or_default();
+    let line_after = line_after.strip_suffix('\r').unwrap_or(line_after);
+    let rest = line_after.trim_start_matches([' ', '\t']);
+    let ends = rest.is_empty()
+        || (rest.len() < line_after.len() && rest.starts_with('#'))
+        || rest.starts_with([',', ']', '}'])
+        || line_after == ":"
+        || line_after.starts_with(": ");
+    starts && ends
+}
+
+/// A unified diff of the lines `edits` change, one hunk per line.
+fn unified_diff(file: &Path, content: &str, rewritten: &str, edits: &[TextEdit]) -> String {
+    let old: Vec<&str> = content.lines().collect();
+    let new: Vec<&str> = rewritten.lines().collect();
+    let lines: BTreeSet<usize> = edits.iter().map(|edit| edit.start_line).collect();
+    let mut diff = format!("--- {0}\n+++ {0}\n", file.display());
+    for line in lines {
+        let index = line - 1;
+        let _ = write!(
+            diff,
+            "@@ -{line} +{line} @@\n-{}\n+{}\n",
+            old.get(index).copied().unwrap_or_default(),
+            new.get(index).copied().unwrap_or_default()
+        );
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_offset_and_position() {
+        let content = "ab\n\u{e9}cd\n";
+        assert_eq!(line_offset(content, 1, 2), Some(1));
+        assert_eq!(line_offset(content, 2, 3), Some(5));
+        assert_eq!(line_offset(content, 2, 2), None);
+        assert_eq!(line_offset(content, 4, 1), None);
+        assert_eq!(position(content, 5), (2, 3));
+        assert_eq!(position(content, 0), (1, 1));
+    }
+
+    #[test]
+    fn test_scalar_offsets() {
+        let id = "gts.x.a.b.c.v1~";
+        let json = format!(r#"{{"a": "{id}", "b": "see {id}", "c": "\"{id}\""}}"#);
+        assert_eq!(scalar_offsets(&json, id, ContentFormat::Json), [7]);
+
+        let yaml = format!(
+            "a: {id}\nb: '{id}'\nc: see {id} here\nd:\n  - {id} # note\ne: [{id}, x]\n{id}: 1\n"
+        );
+        let lines: Vec<usize> = scalar_offsets(&yaml, id, ContentFormat::Yaml)
+            .into_iter()
+            .map(|offset| position(&yaml, offset).0)
+            .collect();
+        assert_eq!(lines, [1, 2, 5, 6, 7]);
+    }
+}
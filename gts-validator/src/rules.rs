@@ -0,0 +1,273 @@
+//! Rule identifiers for validation errors.
+//!
+//! Every [`ValidationError`](crate::ValidationError) carries the ID of the rule
+//! it violates, so CI can tune or tolerate individual rules (see
+//! `ValidationConfig::rule_thresholds`), and [`remediation`] says how to fix
+//! an error of each rule.
+
+use serde::Serialize;
+
+/// The GTS identifier uses a vendor outside the configured vendor policy.
+pub const VENDOR_MISMATCH: &str = "GTS001";
+
+/// A wildcard pattern appears outside a filter/pattern context.
+pub const WILDCARD_NOT_ALLOWED: &str = "GTS002";
+
+/// The string is not a valid GTS identifier.
+pub const INVALID_ID: &str = "GTS003";
+
+/// The identifier is defined (as a `$id`) more than once across the scanned
+/// files.
+///
+/// Only reported when `ValidationConfig::check_duplicate_ids` is on.
+pub const DUPLICATE_DEFINITION: &str = "GTS004";
+
+/// A schema document's `$id` is a bare GTS identifier instead of a
+/// `gts://` URI.
+///
+/// Only reported when `ValidationConfig::check_document_kinds` is on.
+pub const SCHEMA_ID_NOT_URI: &str = "GTS005";
+
+/// An instance document's `type` is a `gts://` URI or not a type ID
+/// (ending with `~`).
+///
+/// Only reported when `ValidationConfig::check_document_kinds` is on.
+pub const INSTANCE_TYPE_NOT_BARE: &str = "GTS006";
+
+/// An instance document does not match the schema its `type` names, or
+/// that type is not defined in the schemas directory.
+///
+/// Only reported when `ValidationConfig::schemas_dir` is set.
+pub const SCHEMA_VIOLATION: &str = "GTS007";
+
+/// A schema in the schemas directory fails GTS schema validation (bad
+/// `$ref`, incompatible base type, malformed body, ...).
+///
+/// Only reported when `ValidationConfig::schemas_dir` is set.
+pub const INVALID_SCHEMA: &str = "GTS008";
+
+/// The schemas directory is not a self-contained bundle: a `gts://` `$ref`
+/// names a type outside it, an `$id` is defined twice, the `$ref` graph has
+/// a cycle or the schemas declare different `$schema` dialects.
+///
+/// Only reported when `ValidationConfig::schemas_dir` is set.
+pub const BUNDLE_NOT_SELF_CONTAINED: &str = "GTS014";
+
+/// A `$id`, `$ref` or `x-gts-ref` key holds `null` instead of an identifier.
+///
+/// Only reported when `ValidationConfig::scan_null_ids` is on.
+pub const NULL_ID: &str = "GTS013";
+
+/// Not a finding of its own: stands for the errors of one file beyond
+/// `ValidationConfig::max_errors_per_file`, which are left out of the
+/// report and counted in `ValidationReport::suppressed_errors`.
+pub const TOO_MANY_ERRORS: &str = "GTS015";
+
+/// A schema document's `$schema` is missing or names another dialect than
+/// the required one.
+///
+/// Only reported when `ValidationConfig::required_dialect` is set.
+pub const WRONG_SCHEMA_DIALECT: &str = "GTS016";
+
+/// All rule IDs, in order.
+pub const ALL: &[&str] = &[
+    VENDOR_MISMATCH,
+    WILDCARD_NOT_ALLOWED,
+    INVALID_ID,
+    DUPLICATE_DEFINITION,
+    SCHEMA_ID_NOT_URI,
+    INSTANCE_TYPE_NOT_BARE,
+    SCHEMA_VIOLATION,
+    INVALID_SCHEMA,
+    NULL_ID,
+    BUNDLE_NOT_SELF_CONTAINED,
+    TOO_MANY_ERRORS,
+    WRONG_SCHEMA_DIALECT,
+];
+
+/// How to fix the errors of one rule, for people and tooling alike.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[non_exhaustive]
+pub struct Remediation {
+    /// The rule, e.g. [`VENDOR_MISMATCH`].
+    #[serde(skip)]
+    pub rule_id: &'static str,
+    /// Short name of the rule, e.g. "Vendor mismatch".
+    #[serde(skip)]
+    pub title: &'static str,
+    /// What to change, in one sentence.
+    pub fix: &'static str,
+    /// A correct identifier (or URI) of the kind the rule expects.
+    pub example: &'static str,
+    /// Stable anchor of the rule in the crate README, e.g.
+    /// `gts001-vendor-mismatch`.
+    pub docs_slug: &'static str,
+    /// The README section documenting the rule.
+    pub docs_url: &'static str,
+}
+
+macro_rules! remediation {
+    ($rule_id:expr, $title:literal, $slug:literal, $fix:literal, $example:literal $(,)?) => {
+        Remediation {
+            rule_id: $rule_id,
+            title: $title,
+            fix: $fix,
+            example: $example,
+            docs_slug: $slug,
+            docs_url: concat!(
+                "https://github.com/GlobalTypeSystem/gts-rust/blob/main/gts-validator/README.md#",
+                $slug
+            ),
+        }
+    };
+}
+
+/// The remediation of every rule, in the order of [`ALL`].
+pub const REMEDIATIONS: &[Remediation] = &[
+    remediation!(
+        VENDOR_MISMATCH,
+        "Vendor mismatch",
+        "gts001-vendor-mismatch",
+        "Ensure all GTS IDs use the expected vendor",
+        "gts.x.core.events.type.v1~",
+    ),
+    remediation!(
+        WILDCARD_NOT_ALLOWED,
+        "Wildcard not allowed",
+        "gts002-wildcard-not-allowed",
+        "Wildcards (*) only in filter/pattern contexts; name a concrete identifier elsewhere",
+        "gts.x.core.events.type.v1~",
+    ),
+    remediation!(
+        INVALID_ID,
+        "Invalid ID",
+        "gts003-invalid-id",
+        "Schema IDs must end with ~, each segment needs 5 parts \
+         (vendor.package.namespace.type.version), and no hyphens are allowed: use underscores",
+        "gts.x.core.events.type.v1~",
+    ),
+    remediation!(
+        DUPLICATE_DEFINITION,
+        "Duplicate definition",
+        "gts004-duplicate-definition",
+        "Define each identifier in one file only; publish changes under a new version",
+        "gts.x.core.events.type.v2~",
+    ),
+    remediation!(
+        SCHEMA_ID_NOT_URI,
+        "Schema ID not URI",
+        "gts005-schema-id-not-uri",
+        "Write a schema's $id as a gts:// URI",
+        "gts://gts.x.core.events.type.v1~",
+    ),
+    remediation!(
+        INSTANCE_TYPE_NOT_BARE,
+        "Instance type not bare",
+        "gts006-instance-type-not-bare",
+        "Write an instance's type as a bare type ID ending with ~",
+        "gts.x.core.events.type.v1~",
+    ),
+    remediation!(
+        SCHEMA_VIOLATION,
+        "Schema violation",
+        "gts007-schema-violation",
+        "Make the instance match the schema its type names, defined in the schemas directory",
+        "gts.x.core.events.type.v1~",
+    ),
+    remediation!(
+        INVALID_SCHEMA,
+        "Invalid schema",
+        "gts008-invalid-schema",
+        "Fix the schema's $ref targets, base type or body so it passes GTS schema validation",
+        "gts://gts.x.core.events.type.v1~",
+    ),
+    remediation!(
+        NULL_ID,
+        "Null ID",
+        "gts013-null-id",
+        "Replace null with an identifier, or remove the key",
+        "gts://gts.x.core.events.type.v1~",
+    ),
+    remediation!(
+        BUNDLE_NOT_SELF_CONTAINED,
+        "Bundle not self-contained",
+        "gts014-bundle-not-self-contained",
+        "Define every referenced type in the schemas directory, each $id once, \
+         without $ref cycles and with one $schema dialect",
+        "gts://gts.x.core.events.type.v1~",
+    ),
+    remediation!(
+        TOO_MANY_ERRORS,
+        "Too many errors",
+        "gts015-too-many-errors",
+        "Fix the reported errors of the file, or raise max_errors_per_file to list the rest",
+        "gts.x.core.events.type.v1~",
+    ),
+    remediation!(
+        WRONG_SCHEMA_DIALECT,
+        "Wrong schema dialect",
+        "gts016-wrong-schema-dialect",
+        "Declare the required JSON Schema dialect in the schema's root $schema",
+        "https://json-schema.org/draft/2020-12/schema",
+    ),
+];
+
+/// How to fix an error of `rule_id`, if it is a known rule.
+#[must_use]
+pub fn remediation(rule_id: &str) -> Option<&'static Remediation> {
+    REMEDIATIONS
+        .iter()
+        .find(|remediation| remediation.rule_id == rule_id)
+}
+
+/// The static rule ID equal to `rule_id`, if it is a known rule.
+pub(crate) fn lookup(rule_id: &str) -> Option<&'static str> {
+    ALL.iter().copied().find(|&rule| rule == rule_id)
+}
+
+/// The rule violated by an error message from `validate_candidate`.
+pub(crate) fn rule_for_message(message: &str) -> &'static str {
+    if message.starts_with("Vendor mismatch") {
+        VENDOR_MISMATCH
+    } else if message.starts_with("Wildcards not allowed") {
+        WILDCARD_NOT_ALLOWED
+    } else {
+        INVALID_ID
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_rule_has_remediation() {
+        let rule_ids: Vec<&str> = REMEDIATIONS.iter().map(|r| r.rule_id).collect();
+        assert_eq!(rule_ids, ALL);
+        for remediation in REMEDIATIONS {
+            assert!(
+                remediation
+                    .docs_slug
+                    .starts_with(&remediation.rule_id.to_lowercase()),
+                "{remediation:?}"
+            );
+            assert!(remediation.docs_url.ends_with(remediation.docs_slug));
+        }
+        assert_eq!(remediation(NULL_ID).unwrap().title, "Null ID");
+        assert!(remediation("GTS999").is_none());
+    }
+
+    #[test]
+    fn test_rule_for_message() {
+        assert_eq!(
+            rule_for_message("Vendor mismatch: expected 'x', found 'y'"),
+            VENDOR_MISMATCH
+        );
+        assert_eq!(
+            rule_for_message("Wildcards not allowed outside pattern contexts: 'gts.x.*'"),
+            WILDCARD_NOT_ALLOWED
+        );
+        assert_eq!(rule_for_message("Invalid GTS ID"), INVALID_ID);
+    }
+}
@@ -0,0 +1,255 @@
+//! Schema-aware validation against a directory of GTS schemas.
+//!
+//! With `ValidationConfig::schemas_dir` set, every schema in the directory
+//! is compiled once with [`gts::GtsStore`], and each instance document found
+//! by the scan is validated against the schema its `type` names, so a
+//! workspace's schemas and instances are checked together in one run. The
+//! directory is also checked to be a self-contained bundle
+//! ([`GtsStore::verify_bundle`]).
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use gts::{BundleError, BundleFinding, GTS_ID_URI_PREFIX, GtsFileReader, GtsStore, StoreError};
+use serde_json::Value;
+
+use crate::DocumentKind;
+use crate::error::ValidationError;
+use crate::rules;
+
+/// The schemas of a `--schemas` directory, compiled once per run.
+pub struct SchemaStore {
+    store: GtsStore,
+    /// [`rules::INVALID_SCHEMA`] and [`rules::BUNDLE_NOT_SELF_CONTAINED`]
+    /// errors found while loading.
+    load_errors: Vec<ValidationError>,
+}
+
+impl fmt::Debug for SchemaStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SchemaStore")
+            .field("entities", &self.store.items().count())
+            .field("load_errors", &self.load_errors.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl SchemaStore {
+    /// Load and validate every schema under `dir`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `dir` is not a directory.
+    pub fn load(dir: &Path) -> anyhow::Result<Self> {
+        if !dir.is_dir() {
+            anyhow::bail!("Schemas directory does not exist: {}", dir.display());
+        }
+        let reader = GtsFileReader::new(&[dir.to_string_lossy().into_owned()], None);
+        let mut store = GtsStore::with_reader(Box::new(reader));
+
+        let mut schemas: Vec<(String, PathBuf)> = store
+            .items()
+            .filter(|(_, entity)| entity.is_schema)
+            .map(|(id, entity)| {
+                let file = entity
+                    .file
+                    .as_ref()
+                    .map_or_else(|| dir.to_owned(), |file| PathBuf::from(&file.path));
+                (id.clone(), file)
+            })
+            .collect();
+        schemas.sort();
+
+        let mut load_errors = bundle_errors(dir, &schemas);
+        for (id, file) in schemas {
+            if let Err(e) = store.validate_schema(&id) {
+                load_errors.push(ValidationError {
+                    file,
+                    line: 0,
+                    column: 0,
+                    json_path: "$".to_owned(),
+                    raw_value: id.clone(),
+                    normalized_id: id.clone(),
+                    rule_id: rules::INVALID_SCHEMA,
+                    error: format!("Invalid schema '{id}': {e}"),
+                    context: "$id".to_owned(),
+                    section: None,
+                    document_kind: Some(DocumentKind::Schema),
+                    fix: None,
+                    help: None,
+                    original_file: None,
+                });
+            }
+        }
+        Ok(Self { store, load_errors })
+    }
+
+    /// The [`rules::INVALID_SCHEMA`] and [`rules::BUNDLE_NOT_SELF_CONTAINED`]
+    /// errors found by [`Self::load`].
+    pub fn load_errors(&self) -> &[ValidationError] {
+        &self.load_errors
+    }
+
+    /// Validate an instance document against the schema its `type` names,
+    /// reporting each violation as a [`rules::SCHEMA_VIOLATION`] error.
+    pub fn check_instance(&self, document: &Value, path: &Path, errors: &mut Vec<ValidationError>) {
+        let Some(raw_type) = document.get("type").and_then(Value::as_str) else {
+            return;
+        };
+        let type_id = raw_type.strip_prefix(GTS_ID_URI_PREFIX).unwrap_or(raw_type);
+        let error = |json_path: String, error, context: String| ValidationError {
+            file: path.to_owned(),
+            line: 0,
+            column: 0,
+            json_path,
+            raw_value: raw_type.to_owned(),
+            normalized_id: type_id.to_owned(),
+            rule_id: rules::SCHEMA_VIOLATION,
+            error,
+            context,
+            section: None,
+            document_kind: Some(DocumentKind::Instance),
+            fix: None,
+            help: None,
+            original_file: None,
+        };
+
+        match self.store.payload_violations(type_id, document) {
+            Ok(violations) => {
+                for violation in violations {
+                    let pointer = if violation.instance_path.is_empty() {
+                        "/"
+                    } else {
+                        violation.instance_path.as_str()
+                    };
+                    errors.push(error(
+                        json_path_of(document, &violation.instance_path),
+                        format!(
+                            "Instance does not match schema '{type_id}' at {pointer}: {}",
+                            violation.message
+                        ),
+                        violation.instance_path,
+                    ));
+                }
+            }
+            Err(StoreError::SchemaNotFound(_)) => errors.push(error(
+                "$.type".to_owned(),
+                format!("Type '{type_id}' is not defined in the schemas directory"),
+                "type".to_owned(),
+            )),
+            Err(e) => errors.push(error(
+                "$.type".to_owned(),
+                format!("Cannot validate against '{type_id}': {e}"),
+                "type".to_owned(),
+            )),
+        }
+    }
+}
+
+/// The [`rules::BUNDLE_NOT_SELF_CONTAINED`] errors of the bundle in `dir`,
+/// whose schemas are `(id, file)`. A cycle is reported on the file of the
+/// type it starts at.
+fn bundle_errors(dir: &Path, schemas: &[(String, PathBuf)]) -> Vec<ValidationError> {
+    let error =
+        |file: &Path, json_path: String, id: &str, error: String, context: &str| ValidationError {
+            file: file.to_owned(),
+            line: 0,
+            column: 0,
+            json_path,
+            raw_value: id.to_owned(),
+            normalized_id: id.to_owned(),
+            rule_id: rules::BUNDLE_NOT_SELF_CONTAINED,
+            error,
+            context: context.to_owned(),
+            section: None,
+            document_kind: Some(DocumentKind::Schema),
+            fix: None,
+            help: None,
+            original_file: None,
+        };
+    let report = match GtsStore::verify_bundle(dir) {
+        Ok(report) => report,
+        Err(e) => {
+            let (BundleError::Io { path, .. } | BundleError::Json { path, .. }) = &e;
+            return vec![error(path, "$".to_owned(), "", e.to_string(), "")];
+        }
+    };
+    report
+        .findings
+        .iter()
+        .map(|finding| match finding {
+            BundleFinding::MissingRef {
+                file,
+                pointer,
+                target,
+            } => {
+                // Re-read for the path, so array indices render as `[0]`.
+                let schema = std::fs::read_to_string(file)
+                    .ok()
+                    .and_then(|content| serde_json::from_str(&content).ok())
+                    .unwrap_or_default();
+                error(
+                    file,
+                    json_path_of(&schema, pointer),
+                    target,
+                    finding.to_string(),
+                    pointer,
+                )
+            }
+            BundleFinding::DuplicateId { id, second, .. } => {
+                error(second, "$.$id".to_owned(), id, finding.to_string(), "$id")
+            }
+            BundleFinding::Cycle { path } => {
+                let id = path.first().map_or("", String::as_str);
+                let file = schemas
+                    .iter()
+                    .find(|(schema_id, _)| schema_id == id)
+                    .map_or(dir, |(_, file)| file.as_path());
+                error(file, "$".to_owned(), id, finding.to_string(), "$ref")
+            }
+            BundleFinding::DraftMismatch { file, found, .. } => error(
+                file,
+                "$.$schema".to_owned(),
+                found.as_deref().unwrap_or_default(),
+                finding.to_string(),
+                "$schema",
+            ),
+        })
+        .collect()
+}
+
+/// The `$.a.b[0]`-style path of the JSON Pointer `pointer` in `document`.
+fn json_path_of(document: &Value, pointer: &str) -> String {
+    let mut json_path = "$".to_owned();
+    let mut current = Some(document);
+    for token in pointer.split('/').skip(1) {
+        let token = token.replace("~1", "/").replace("~0", "~");
+        if let (Some(Value::Array(items)), Ok(index)) = (current, token.parse::<usize>()) {
+            json_path.push('[');
+            json_path.push_str(&token);
+            json_path.push(']');
+            current = items.get(index);
+        } else {
+            json_path.push('.');
+            json_path.push_str(&token);
+            current = current.and_then(|value| value.get(&token));
+        }
+    }
+    json_path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_json_path_of() {
+        let document = json!({"a": {"b": [{"c": 1}], "1": true}, "x/y": 0});
+        assert_eq!(json_path_of(&document, ""), "$");
+        assert_eq!(json_path_of(&document, "/a/b/0/c"), "$.a.b[0].c");
+        assert_eq!(json_path_of(&document, "/a/1"), "$.a.1");
+        assert_eq!(json_path_of(&document, "/x~1y"), "$.x/y");
+        assert_eq!(json_path_of(&document, "/missing/0"), "$.missing.0");
+    }
+}
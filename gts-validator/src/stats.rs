@@ -0,0 +1,190 @@
+//! Identifier statistics: how many distinct GTS IDs each vendor and package
+//! contributes to the scanned files.
+//!
+//! Built from the candidates the scanners discovered (see [`DiscoveryTrace`]).
+//! Every well-formed GTS ID counts, whether or not it passed the vendor
+//! policy; skipped candidates (bad examples, skip tokens, ...) and patterns
+//! do not.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use gts::GtsId;
+use serde::{Deserialize, Serialize};
+
+use crate::normalize::normalize_candidate;
+use crate::trace::{CandidateOutcome, DiscoveryTrace};
+
+/// Which segment of a chained ID decides its vendor and package.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum StatsSegment {
+    /// The last segment: the most derived type, or the instance (default).
+    /// A trailing anonymous-instance UUID is passed over.
+    #[default]
+    LastSegment,
+    /// The first segment, which owns the base type.
+    FirstSegment,
+}
+
+/// The rows of [`IdStats::print_table`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum StatsGrouping {
+    /// One row per vendor.
+    Vendor,
+    /// One row per vendor and package.
+    Package,
+}
+
+/// Distinct IDs and occurrences per vendor, then per package, sorted by name.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct IdStats {
+    pub vendors: BTreeMap<String, VendorIdStats>,
+}
+
+/// The IDs of one vendor: its totals and a breakdown per package.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct VendorIdStats {
+    /// Number of distinct IDs.
+    pub distinct_ids: usize,
+    /// Number of times those IDs occur across the scanned files.
+    pub occurrences: usize,
+    pub packages: BTreeMap<String, IdCounts>,
+}
+
+/// The IDs of one package.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct IdCounts {
+    /// Number of distinct IDs.
+    pub distinct_ids: usize,
+    /// Number of times those IDs occur across the scanned files.
+    pub occurrences: usize,
+}
+
+impl IdStats {
+    /// Aggregate the validated candidates of `trace`, attributing each ID to
+    /// the vendor and package of `segment`.
+    #[must_use]
+    pub fn from_trace(trace: &DiscoveryTrace, segment: StatsSegment) -> Self {
+        // vendor -> package -> ID -> occurrences
+        let mut counts: BTreeMap<String, BTreeMap<String, BTreeMap<String, usize>>> =
+            BTreeMap::new();
+        let validated = trace
+            .files
+            .iter()
+            .flat_map(|file| &file.candidates)
+            .filter(|c| !matches!(c.outcome, CandidateOutcome::Skipped { .. }));
+        for candidate in validated {
+            let Ok(normalized) = normalize_candidate(&candidate.candidate) else {
+                continue;
+            };
+            let Ok(id) = GtsId::try_new(&normalized.gts_id) else {
+                continue;
+            };
+            let mut concrete = id.segments().iter().filter(|s| s.uuid_tail().is_none());
+            let owner = match segment {
+                StatsSegment::LastSegment => concrete.next_back(),
+                StatsSegment::FirstSegment => concrete.next(),
+            };
+            let Some(owner) = owner else {
+                continue;
+            };
+            *counts
+                .entry(owner.vendor().to_owned())
+                .or_default()
+                .entry(owner.package().to_owned())
+                .or_default()
+                .entry(normalized.gts_id)
+                .or_default() += 1;
+        }
+
+        let vendors = counts
+            .into_iter()
+            .map(|(vendor, packages)| {
+                let packages: BTreeMap<String, IdCounts> = packages
+                    .into_iter()
+                    .map(|(package, ids)| {
+                        let counts = IdCounts {
+                            distinct_ids: ids.len(),
+                            occurrences: ids.values().sum(),
+                        };
+                        (package, counts)
+                    })
+                    .collect();
+                let stats = VendorIdStats {
+                    distinct_ids: packages.values().map(|p| p.distinct_ids).sum(),
+                    occurrences: packages.values().map(|p| p.occurrences).sum(),
+                    packages,
+                };
+                (vendor, stats)
+            })
+            .collect();
+        Self { vendors }
+    }
+
+    /// Print the statistics as a table with one row per vendor or per
+    /// vendor and package, sorted by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    pub fn print_table(
+        &self,
+        grouping: StatsGrouping,
+        writer: &mut dyn Write,
+    ) -> anyhow::Result<()> {
+        if self.vendors.is_empty() {
+            writeln!(writer, "No GTS IDs found")?;
+            return Ok(());
+        }
+        let (header, rows): (&str, Vec<(String, IdCounts)>) = match grouping {
+            StatsGrouping::Vendor => (
+                "Vendor",
+                self.vendors
+                    .iter()
+                    .map(|(vendor, s)| {
+                        let counts = IdCounts {
+                            distinct_ids: s.distinct_ids,
+                            occurrences: s.occurrences,
+                        };
+                        (vendor.clone(), counts)
+                    })
+                    .collect(),
+            ),
+            StatsGrouping::Package => (
+                "Package",
+                self.vendors
+                    .iter()
+                    .flat_map(|(vendor, s)| {
+                        s.packages
+                            .iter()
+                            .map(move |(package, p)| (format!("{vendor}.{package}"), p.clone()))
+                    })
+                    .collect(),
+            ),
+        };
+        let name_width = rows
+            .iter()
+            .map(|(name, ..)| name.len())
+            .chain([header.len()])
+            .max()
+            .unwrap_or(0);
+        writeln!(
+            writer,
+            "{header:<name_width$}  {:>5}  {:>11}",
+            "IDs", "Occurrences"
+        )?;
+        for (name, counts) in rows {
+            writeln!(
+                writer,
+                "{name:<name_width$}  {:>5}  {:>11}",
+                counts.distinct_ids, counts.occurrences
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -6,8 +6,11 @@
 //! - Resolved paths are checked to remain within the repository root
 //! - Device files, pipes, and sockets are skipped
 //! - Maximum directory depth is enforced to prevent infinite recursion
+//! - Broken symlinks are skipped, or reported as warnings with `warn_broken_symlinks`
 //! - Bounded streaming reads prevent TOCTOU and memory `DoS`
 
+use std::collections::HashSet;
+use std::ffi::OsStr;
 use std::io::Read;
 use std::path::{Path, PathBuf};
 
@@ -19,7 +22,22 @@ use crate::error::{ScanError, ScanErrorKind};
 use crate::strategy::ContentFormat;
 
 /// Directories to skip
-pub const SKIP_DIRS: &[&str] = &["target", "node_modules", ".git", "vendor", ".gts-spec"];
+pub const SKIP_DIRS: &[&str] = &["target", "node_modules", ".git", "vendor", GTS_SPEC_DIR];
+
+/// The GTS spec checkout, skipped unless `FsSourceConfig::include_gts_spec`
+/// is set, and otherwise used as a registry with
+/// `FsSourceConfig::default_registry`.
+pub const GTS_SPEC_DIR: &str = ".gts-spec";
+
+/// The UTF-8 byte order mark, stripped from the start of files.
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// The UTF-16 little- and big-endian byte order marks.
+const UTF16LE_BOM: &[u8] = b"\xFF\xFE";
+const UTF16BE_BOM: &[u8] = b"\xFE\xFF";
+
+/// How much of a file is searched for a NUL byte to tell binary files apart.
+const BINARY_SNIFF_LEN: usize = 8192;
 
 /// Files to skip (path suffixes).
 /// NOTE: Repo-specific paths should be passed via `FsSourceConfig.exclude` instead.
@@ -34,28 +52,100 @@ pub enum ScanResult {
     Err(ScanError),
 }
 
+/// Whether `pattern` matches `path`.
+///
+/// Glob patterns are UTF-8. On Unix, each byte of `path` that is not valid
+/// UTF-8 is matched as one character that only a wildcard (`?`, `*` or a
+/// negated class) can match; elsewhere a path that is not valid Unicode
+/// never matches.
+pub fn glob_matches(pattern: &Pattern, path: &OsStr) -> bool {
+    if let Some(text) = path.to_str() {
+        return pattern.matches(text);
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+
+        // U+FFFD stands in for each invalid byte; a pattern spelling it out
+        // literally must not match one.
+        if pattern.as_str().contains(char::REPLACEMENT_CHARACTER) {
+            return false;
+        }
+        let mut text = String::with_capacity(path.len());
+        for chunk in path.as_bytes().utf8_chunks() {
+            text.push_str(chunk.valid());
+            text.extend(chunk.invalid().iter().map(|_| char::REPLACEMENT_CHARACTER));
+        }
+        pattern.matches(&text)
+    }
+    #[cfg(not(unix))]
+    false
+}
+
 /// Check if a path matches any of the exclude patterns
 fn matches_exclude(path: &Path, exclude_patterns: &[Pattern]) -> bool {
-    let path_str = path.to_string_lossy();
-    for pattern in exclude_patterns {
-        if pattern.matches(&path_str)
+    exclude_patterns.iter().any(|pattern| {
+        glob_matches(pattern, path.as_os_str())
             || path
                 .file_name()
-                .is_some_and(|name| pattern.matches(&name.to_string_lossy()))
-        {
-            return true;
-        }
+                .is_some_and(|name| glob_matches(pattern, name))
+    })
+}
+
+/// A `NonUnicodePath` warning for `path` when it is not valid Unicode, so
+/// the file is skipped rather than reported under a mangled name.
+fn non_unicode_warning(path: &Path) -> Option<ScanError> {
+    if path.to_str().is_some() {
+        return None;
+    }
+    Some(ScanError {
+        file: path.to_path_buf(),
+        kind: ScanErrorKind::NonUnicodePath,
+        message: format!(
+            "Skipped {}: the path is not valid Unicode and cannot be reported losslessly",
+            path.display()
+        ),
+        original_file: None,
+    })
+}
+
+/// A `PermissionDenied` or `WalkError` error for `walk_err`, met at `path`.
+fn walk_error(path: PathBuf, walk_err: &walkdir::Error) -> ScanError {
+    let permission_denied = walk_err
+        .io_error()
+        .is_some_and(|e| e.kind() == std::io::ErrorKind::PermissionDenied);
+    let (kind, message) = if permission_denied {
+        (
+            ScanErrorKind::PermissionDenied,
+            format!(
+                "Permission denied reading {}; nothing under it was scanned",
+                path.display()
+            ),
+        )
+    } else {
+        (
+            ScanErrorKind::WalkError,
+            format!("Directory traversal error: {walk_err}"),
+        )
+    };
+    ScanError {
+        file: path,
+        kind,
+        message,
+        original_file: None,
     }
-    false
 }
 
 /// Check if a directory entry is a skip directory (for `WalkDir::filter_entry`).
 /// Returns `true` if the entry should be **included** (i.e., is NOT a skip dir).
-fn is_not_skip_dir(entry: &walkdir::DirEntry) -> bool {
+fn is_not_skip_dir(entry: &walkdir::DirEntry, config: &FsSourceConfig) -> bool {
     if entry.file_type().is_dir()
         && let Some(name) = entry.file_name().to_str()
     {
-        return !SKIP_DIRS.contains(&name);
+        if config.skip_dirs.iter().any(|dir| dir == name) {
+            return false;
+        }
+        return !SKIP_DIRS.contains(&name) || (config.include_gts_spec && name == GTS_SPEC_DIR);
     }
     true
 }
@@ -64,33 +154,61 @@ fn is_not_skip_dir(entry: &walkdir::DirEntry) -> bool {
 fn matches_file_pattern(path: &Path) -> bool {
     matches!(
         path.extension().and_then(|e| e.to_str()),
-        Some("md" | "json" | "yaml" | "yml")
+        Some("md" | "json" | "yaml" | "yml" | "tpl" | "gotmpl" | "j2")
     )
 }
 
+/// If `path` is a symlink whose target does not exist, describe it as a
+/// `BrokenSymlink` warning.
+fn broken_symlink_warning(path: &Path) -> Option<ScanError> {
+    let target = std::fs::read_link(path).ok()?;
+    if path.exists() {
+        return None;
+    }
+    let resolved = path
+        .parent()
+        .map_or_else(|| target.clone(), |p| p.join(&target));
+    Some(ScanError {
+        file: path.to_path_buf(),
+        kind: ScanErrorKind::BrokenSymlink,
+        message: format!(
+            "Broken symlink: {} -> {} (target does not exist)",
+            path.display(),
+            resolved.display()
+        ),
+        original_file: None,
+    })
+}
+
+/// What [`find_files`] found in the scan paths.
+#[derive(Debug, Default)]
+pub struct FoundFiles {
+    /// Paths that passed all filters and are ready to read.
+    pub files: Vec<PathBuf>,
+    /// Boundary violations, and walk errors (permission denied, loop, etc.)
+    /// with `config.fail_on_walk_errors`. CI must treat them as failures.
+    pub scan_errors: Vec<ScanError>,
+    /// Walk errors without `config.fail_on_walk_errors`, broken symlinks
+    /// when `config.warn_broken_symlinks` is set, and files skipped because
+    /// their path is not valid Unicode.
+    pub warnings: Vec<ScanError>,
+    /// `config.registry_dirs`, then the `.gts-spec` directories found under
+    /// the scan paths with `config.default_registry`. Their files are never
+    /// in `files`.
+    pub registry_dirs: Vec<PathBuf>,
+}
+
 /// Find all files to scan in the given paths.
-///
-/// Returns `(files, scan_errors)`:
-/// - `files`: paths that passed all filters and are ready to read.
-/// - `scan_errors`: walk errors (permission denied, loop, etc.) and boundary violations.
-///   These are never silently discarded — CI must treat them as failures.
-pub fn find_files(config: &FsSourceConfig) -> (Vec<PathBuf>, Vec<ScanError>) {
+pub fn find_files(config: &FsSourceConfig) -> FoundFiles {
     let mut files = Vec::new();
     let mut scan_errors = Vec::new();
-
-    let mut exclude_patterns = Vec::with_capacity(config.exclude.len());
-    for pat_str in &config.exclude {
-        match Pattern::new(pat_str) {
-            Ok(pat) => exclude_patterns.push(pat),
-            Err(e) => {
-                scan_errors.push(ScanError {
-                    file: PathBuf::from(pat_str),
-                    kind: ScanErrorKind::InvalidExcludePattern,
-                    message: format!("Invalid exclude glob pattern '{pat_str}': {e}"),
-                });
-            }
-        }
-    }
+    let mut warnings = Vec::new();
+    let mut registry_dirs = config.registry_dirs.clone();
+    let discover_registries = config.default_registry && !config.include_gts_spec;
+    // Files under an explicit registry directory are not scanned, even when
+    // it lies within a scan path.
+    let canonical_registries = canonical_registries(config);
+    let exclude_patterns = exclude_patterns(config, &mut scan_errors);
 
     for root in &config.paths {
         // Canonicalize the root once so we can enforce the boundary for every entry.
@@ -101,14 +219,20 @@ pub fn find_files(config: &FsSourceConfig) -> (Vec<PathBuf>, Vec<ScanError>) {
                     file: root.clone(),
                     kind: ScanErrorKind::IoError,
                     message: format!("Failed to canonicalize root path: {e}"),
+                    original_file: None,
                 });
                 continue;
             }
         };
 
+        // Explicitly listed files are kept whatever their extension; the
+        // format of unrecognized ones is sniffed from their content.
         if root.is_file() {
-            if matches_file_pattern(root) && !matches_exclude(root, &exclude_patterns) {
-                files.push(root.clone());
+            if !matches_exclude(root, &exclude_patterns) {
+                match non_unicode_warning(root) {
+                    Some(warning) => warnings.push(warning),
+                    None => files.push(root.clone()),
+                }
             }
             continue;
         }
@@ -121,20 +245,37 @@ pub fn find_files(config: &FsSourceConfig) -> (Vec<PathBuf>, Vec<ScanError>) {
             .follow_links(config.follow_links)
             .max_depth(config.max_depth)
             .into_iter()
-            .filter_entry(is_not_skip_dir)
+            .filter_entry(|entry| {
+                let keep = is_not_skip_dir(entry, config);
+                if !keep && discover_registries && entry.file_name() == GTS_SPEC_DIR {
+                    registry_dirs.push(entry.path().to_path_buf());
+                }
+                keep
+            })
         {
             let entry = match entry_result {
                 Ok(e) => e,
                 Err(walk_err) => {
-                    // Propagate walk errors (permission denied, loop, etc.) as ScanErrors.
                     let path = walk_err
                         .path()
                         .map_or_else(|| root.clone(), Path::to_path_buf);
-                    scan_errors.push(ScanError {
-                        file: path,
-                        kind: ScanErrorKind::WalkError,
-                        message: format!("Directory traversal error: {walk_err}"),
-                    });
+                    // With follow_links, walkdir fails to stat a dangling link.
+                    if config.warn_broken_symlinks
+                        && let Some(warning) = broken_symlink_warning(&path)
+                    {
+                        if !matches_exclude(&path, &exclude_patterns) {
+                            warnings.push(warning);
+                        }
+                        continue;
+                    }
+                    // Never drop a walk error: the subtree behind it went
+                    // unscanned.
+                    let walk_error = walk_error(path, &walk_err);
+                    if config.fail_on_walk_errors {
+                        scan_errors.push(walk_error);
+                    } else {
+                        warnings.push(walk_error);
+                    }
                     continue;
                 }
             };
@@ -142,6 +283,13 @@ pub fn find_files(config: &FsSourceConfig) -> (Vec<PathBuf>, Vec<ScanError>) {
             let file_path = entry.path();
 
             if !file_path.is_file() {
+                if config.warn_broken_symlinks
+                    && entry.path_is_symlink()
+                    && !matches_exclude(file_path, &exclude_patterns)
+                    && let Some(warning) = broken_symlink_warning(file_path)
+                {
+                    warnings.push(warning);
+                }
                 continue;
             }
 
@@ -150,6 +298,12 @@ pub fn find_files(config: &FsSourceConfig) -> (Vec<PathBuf>, Vec<ScanError>) {
             // is true, and rejects any path that resolves outside the scan root.
             match file_path.canonicalize() {
                 Ok(canonical_path) => {
+                    if canonical_registries
+                        .iter()
+                        .any(|dir| canonical_path.starts_with(dir))
+                    {
+                        continue;
+                    }
                     if !canonical_path.starts_with(&canonical_root) {
                         scan_errors.push(ScanError {
                             file: file_path.to_path_buf(),
@@ -159,6 +313,7 @@ pub fn find_files(config: &FsSourceConfig) -> (Vec<PathBuf>, Vec<ScanError>) {
                                 file_path.display(),
                                 canonical_path.display()
                             ),
+                            original_file: None,
                         });
                         continue;
                     }
@@ -168,6 +323,7 @@ pub fn find_files(config: &FsSourceConfig) -> (Vec<PathBuf>, Vec<ScanError>) {
                         file: file_path.to_path_buf(),
                         kind: ScanErrorKind::IoError,
                         message: format!("Failed to canonicalize path: {e}"),
+                        original_file: None,
                     });
                     continue;
                 }
@@ -187,7 +343,7 @@ pub fn find_files(config: &FsSourceConfig) -> (Vec<PathBuf>, Vec<ScanError>) {
                 }
             }
 
-            if !matches_file_pattern(file_path) {
+            if !config.sniff_content && !matches_file_pattern(file_path) {
                 continue;
             }
 
@@ -195,7 +351,10 @@ pub fn find_files(config: &FsSourceConfig) -> (Vec<PathBuf>, Vec<ScanError>) {
                 continue;
             }
 
-            let rel_path = file_path.to_string_lossy();
+            let Some(rel_path) = file_path.to_str() else {
+                warnings.extend(non_unicode_warning(file_path));
+                continue;
+            };
             if SKIP_FILES.iter().any(|skip| rel_path.ends_with(skip)) {
                 continue;
             }
@@ -206,15 +365,185 @@ pub fn find_files(config: &FsSourceConfig) -> (Vec<PathBuf>, Vec<ScanError>) {
 
     files.sort();
     files.dedup();
-    (files, scan_errors)
+    warnings.sort_by(|a, b| a.file.cmp(&b.file));
+    let mut seen = HashSet::new();
+    registry_dirs.retain(|dir| seen.insert(dir.clone()));
+    FoundFiles {
+        files,
+        scan_errors,
+        warnings,
+        registry_dirs,
+    }
+}
+
+/// Filter a list of files known in advance, such as the files changed in a
+/// build, the way [`find_files`] filters the files it walks, without
+/// walking anything.
+///
+/// Exclude patterns and extension routing still apply as a safety net:
+/// excluded files, files under `config.registry_dirs`, and files without a
+/// supported extension (unless `config.sniff_content` is set) are dropped.
+/// A listed path that is not a regular file is a scan error. Registries
+/// are `config.registry_dirs` only; no `.gts-spec` directory is discovered.
+pub fn list_files(listed: &[PathBuf], config: &FsSourceConfig) -> FoundFiles {
+    let mut files = Vec::new();
+    let mut scan_errors = Vec::new();
+    let mut warnings = Vec::new();
+    let canonical_registries = canonical_registries(config);
+    let exclude_patterns = exclude_patterns(config, &mut scan_errors);
+
+    for file_path in listed {
+        if matches_exclude(file_path, &exclude_patterns)
+            || (!config.sniff_content && !matches_file_pattern(file_path))
+        {
+            continue;
+        }
+        if !file_path.is_file() {
+            scan_errors.push(ScanError {
+                file: file_path.clone(),
+                kind: ScanErrorKind::IoError,
+                message: format!(
+                    "Listed file does not exist or is not a regular file: {}",
+                    file_path.display()
+                ),
+                original_file: None,
+            });
+            continue;
+        }
+        if file_path.canonicalize().is_ok_and(|canonical_path| {
+            canonical_registries
+                .iter()
+                .any(|dir| canonical_path.starts_with(dir))
+        }) {
+            continue;
+        }
+        match non_unicode_warning(file_path) {
+            Some(warning) => warnings.push(warning),
+            None => files.push(file_path.clone()),
+        }
+    }
+
+    files.sort();
+    files.dedup();
+    warnings.sort_by(|a, b| a.file.cmp(&b.file));
+    let mut registry_dirs = config.registry_dirs.clone();
+    let mut seen = HashSet::new();
+    registry_dirs.retain(|dir| seen.insert(dir.clone()));
+    FoundFiles {
+        files,
+        scan_errors,
+        warnings,
+        registry_dirs,
+    }
+}
+
+/// The canonical paths of `config.registry_dirs` that exist.
+fn canonical_registries(config: &FsSourceConfig) -> Vec<PathBuf> {
+    config
+        .registry_dirs
+        .iter()
+        .filter_map(|dir| dir.canonicalize().ok())
+        .collect()
+}
+
+/// Compile `config.exclude`, pushing an `InvalidExcludePattern` scan error
+/// for each pattern that does not compile.
+fn exclude_patterns(config: &FsSourceConfig, scan_errors: &mut Vec<ScanError>) -> Vec<Pattern> {
+    let mut exclude_patterns = Vec::with_capacity(config.exclude.len());
+    for pat_str in &config.exclude {
+        match Pattern::new(pat_str) {
+            Ok(pat) => exclude_patterns.push(pat),
+            Err(e) => {
+                scan_errors.push(ScanError {
+                    file: PathBuf::from(pat_str),
+                    kind: ScanErrorKind::InvalidExcludePattern,
+                    message: format!("Invalid exclude glob pattern '{pat_str}': {e}"),
+                    original_file: None,
+                });
+            }
+        }
+    }
+    exclude_patterns
+}
+
+/// Why `root` contributed no file to the scan, for `EmptyScan` errors.
+///
+/// Walks `root` again without filters and counts what the discovery rules
+/// turned away; only called once a scan came up empty.
+pub fn explain_empty_root(root: &Path, config: &FsSourceConfig) -> String {
+    let exclude_patterns: Vec<Pattern> = config
+        .exclude
+        .iter()
+        .filter_map(|pat| Pattern::new(pat).ok())
+        .collect();
+    if root.is_file() {
+        return if matches_exclude(root, &exclude_patterns) {
+            "file is excluded".to_owned()
+        } else {
+            "file is not recognized as Markdown, JSON, YAML or a template".to_owned()
+        };
+    }
+
+    let (mut files, mut unsupported, mut excluded, mut skipped_dirs) = (0, 0, 0, Vec::new());
+    let walker = WalkDir::new(root)
+        .follow_links(config.follow_links)
+        .max_depth(config.max_depth)
+        .into_iter()
+        .filter_entry(|entry| {
+            let keep = is_not_skip_dir(entry, config);
+            if !keep {
+                skipped_dirs.push(entry.file_name().to_string_lossy().into_owned());
+            }
+            keep
+        });
+    for entry in walker.filter_map(Result::ok) {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        files += 1;
+        if !config.sniff_content && !matches_file_pattern(entry.path()) {
+            unsupported += 1;
+        } else if matches_exclude(entry.path(), &exclude_patterns) {
+            excluded += 1;
+        }
+    }
+
+    let mut reasons = Vec::new();
+    if files == 0 {
+        reasons.push("no files".to_owned());
+    }
+    if unsupported > 0 {
+        reasons.push(format!(
+            "{unsupported} file(s) without a .md/.json/.yaml/.yml or template extension"
+        ));
+    }
+    if excluded > 0 {
+        reasons.push(format!("{excluded} file(s) excluded"));
+    }
+    let unrecognized = files - unsupported - excluded;
+    if unrecognized > 0 {
+        reasons.push(format!(
+            "{unrecognized} file(s) not recognized as Markdown, JSON, YAML or a template"
+        ));
+    }
+    if !skipped_dirs.is_empty() {
+        skipped_dirs.sort();
+        skipped_dirs.dedup();
+        reasons.push(format!("skipped directories: {}", skipped_dirs.join(", ")));
+    }
+    reasons.join("; ")
 }
 
 /// Determine the content format from a file extension.
+///
+/// Returns `None` for other extensions; callers fall back to
+/// [`ContentFormat::sniff`].
 pub fn content_format_for(path: &Path) -> Option<ContentFormat> {
     match path.extension().and_then(|e| e.to_str()) {
         Some("md") => Some(ContentFormat::Markdown),
         Some("json") => Some(ContentFormat::Json),
         Some("yaml" | "yml") => Some(ContentFormat::Yaml),
+        Some("tpl" | "gotmpl" | "j2") => Some(ContentFormat::Template),
         _ => None,
     }
 }
@@ -225,11 +554,16 @@ pub fn content_format_for(path: &Path) -> Option<ContentFormat> {
 /// the kernel size check and the actual read are the same operation.
 /// Never calls `read_to_string` on an unbounded handle.
 ///
+/// A file starting with a UTF-16 byte order mark is decoded when
+/// `transcode_utf16` is set.
+///
 /// Returns `ScanResult::Err` (never silently discards failures) if:
 /// - The file exceeds `max_file_size`
 /// - An I/O error occurs
-/// - The content is not valid UTF-8
-pub fn read_file_bounded(path: &Path, max_file_size: u64) -> ScanResult {
+/// - The file is UTF-16 (`Utf16Encoding`) and `transcode_utf16` is off
+/// - The file has a NUL byte in its first 8 KB (`BinaryContent`)
+/// - The content is not valid UTF-8, or not valid UTF-16 when transcoded
+pub fn read_file_bounded(path: &Path, max_file_size: u64, transcode_utf16: bool) -> ScanResult {
     let file = match std::fs::File::open(path) {
         Ok(f) => f,
         Err(e) => {
@@ -237,6 +571,7 @@ pub fn read_file_bounded(path: &Path, max_file_size: u64) -> ScanResult {
                 file: path.to_owned(),
                 kind: ScanErrorKind::IoError,
                 message: format!("Failed to open file: {e}"),
+                original_file: None,
             });
         }
     };
@@ -250,6 +585,7 @@ pub fn read_file_bounded(path: &Path, max_file_size: u64) -> ScanResult {
                 file: path.to_owned(),
                 kind: ScanErrorKind::IoError,
                 message: format!("Failed to read file: {e}"),
+                original_file: None,
             });
         }
     }
@@ -259,15 +595,69 @@ pub fn read_file_bounded(path: &Path, max_file_size: u64) -> ScanResult {
             file: path.to_owned(),
             kind: ScanErrorKind::FileTooLarge,
             message: format!("File exceeds maximum size of {max_file_size} bytes"),
+            original_file: None,
         });
     }
 
+    let error = |kind, message: String| {
+        ScanResult::Err(ScanError {
+            file: path.to_owned(),
+            kind,
+            message,
+            original_file: None,
+        })
+    };
+    let little_endian = buffer.starts_with(UTF16LE_BOM);
+    if little_endian || buffer.starts_with(UTF16BE_BOM) {
+        let encoding = if little_endian {
+            "UTF-16LE"
+        } else {
+            "UTF-16BE"
+        };
+        if !transcode_utf16 {
+            return error(
+                ScanErrorKind::Utf16Encoding,
+                format!("Skipped {encoding} file; enable UTF-16 transcoding to scan it"),
+            );
+        }
+        let body = &buffer[UTF16LE_BOM.len()..];
+        let units: Vec<u16> = body
+            .chunks_exact(2)
+            .map(|pair| {
+                let pair = [pair[0], pair[1]];
+                if little_endian {
+                    u16::from_le_bytes(pair)
+                } else {
+                    u16::from_be_bytes(pair)
+                }
+            })
+            .collect();
+        return match String::from_utf16(&units) {
+            Ok(content) if body.len() % 2 == 0 => ScanResult::Ok(content),
+            _ => error(
+                ScanErrorKind::InvalidEncoding,
+                format!("File is not valid {encoding}"),
+            ),
+        };
+    }
+    if buffer[..buffer.len().min(BINARY_SNIFF_LEN)].contains(&0) {
+        return error(
+            ScanErrorKind::BinaryContent,
+            "Skipped binary file (NUL byte in the first 8 KB)".to_owned(),
+        );
+    }
+
+    // Some Windows tools start UTF-8 files with a byte order mark, which the
+    // JSON and YAML parsers reject and which would shift columns on line 1.
+    if buffer.starts_with(UTF8_BOM) {
+        buffer.drain(..UTF8_BOM.len());
+    }
+
     match String::from_utf8(buffer) {
         Ok(content) => ScanResult::Ok(content),
-        Err(_) => ScanResult::Err(ScanError {
-            file: path.to_owned(),
-            kind: ScanErrorKind::InvalidEncoding,
-            message: "File is not valid UTF-8".to_owned(),
-        }),
+        Err(_) => error(
+            ScanErrorKind::InvalidEncoding,
+            "File is not valid UTF-8".to_owned(),
+        ),
     }
 }
@@ -9,8 +9,148 @@ pub mod fs;
 
 /// Content format for dispatching to the correct scanner.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum ContentFormat {
     Markdown,
     Json,
     Yaml,
+    /// A templated file (`.tpl`, `.gotmpl`, `.j2`), scanned line by line
+    /// outside its template directives.
+    Template,
+}
+
+impl ContentFormat {
+    /// Every format, in the order of [`name`](Self::name)s listed in help
+    /// output.
+    pub const ALL: &[Self] = &[Self::Markdown, Self::Json, Self::Yaml, Self::Template];
+
+    /// The lowercase name of the format (`markdown`, `json`, `yaml`,
+    /// `template`), as used in `ValidationReport::scanned_by_format`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Markdown => "markdown",
+            Self::Json => "json",
+            Self::Yaml => "yaml",
+            Self::Template => "template",
+        }
+    }
+
+    /// Guess the format of `content` from its first line, for files whose
+    /// extension does not tell.
+    ///
+    /// - a leading `{`, or a `[` opening a JSON array → `Json`
+    /// - a `---` document marker or a `key: value` line → `Yaml`
+    /// - a `#` heading → `Markdown`
+    ///
+    /// Returns `None` for anything else, including content with NUL bytes
+    /// (binary data that happens to be valid UTF-8).
+    #[must_use]
+    pub fn sniff(content: &str) -> Option<Self> {
+        if content.contains('\0') {
+            return None;
+        }
+        let trimmed = content.trim_start_matches('\u{feff}').trim_start();
+        let first_line = trimmed.lines().next()?.trim_end();
+        if first_line.starts_with('{') || opens_json_array(first_line) {
+            Some(Self::Json)
+        } else if first_line == "---"
+            || first_line.starts_with("--- ")
+            || is_yaml_mapping_line(first_line)
+        {
+            Some(Self::Yaml)
+        } else if is_markdown_heading(first_line) {
+            Some(Self::Markdown)
+        } else {
+            None
+        }
+    }
+}
+
+/// `[` followed by something that can start a JSON value (not `[link text]`).
+fn opens_json_array(line: &str) -> bool {
+    line.strip_prefix('[').is_some_and(|rest| {
+        rest.trim_start()
+            .chars()
+            .next()
+            .is_none_or(|c| matches!(c, '{' | '[' | '"' | ']' | '-' | '0'..='9'))
+    })
+}
+
+/// `key:` or `key: value` with a plain identifier-like key.
+fn is_yaml_mapping_line(line: &str) -> bool {
+    let Some((key, value)) = line.split_once(':') else {
+        return false;
+    };
+    let key = key.trim_matches(|c| c == '"' || c == '\'');
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '$'))
+        && (value.is_empty() || value.starts_with(' '))
+}
+
+/// An ATX heading: one to six `#` followed by a space.
+fn is_markdown_heading(line: &str) -> bool {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    (1..=6).contains(&level) && line[level..].starts_with(' ')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sniff_json() {
+        assert_eq!(
+            ContentFormat::sniff("{\"$id\": \"x\"}"),
+            Some(ContentFormat::Json)
+        );
+        assert_eq!(
+            ContentFormat::sniff("\n  [\"gts.x.core.events.type.v1~\"]"),
+            Some(ContentFormat::Json)
+        );
+        assert_eq!(
+            ContentFormat::sniff("[\n  {}\n]"),
+            Some(ContentFormat::Json)
+        );
+    }
+
+    #[test]
+    fn test_sniff_yaml() {
+        assert_eq!(
+            ContentFormat::sniff("---\nid: x\n"),
+            Some(ContentFormat::Yaml)
+        );
+        assert_eq!(
+            ContentFormat::sniff("$id: gts.x.core.events.type.v1~\n"),
+            Some(ContentFormat::Yaml)
+        );
+        assert_eq!(
+            ContentFormat::sniff("owners:\n  - a\n"),
+            Some(ContentFormat::Yaml)
+        );
+    }
+
+    #[test]
+    fn test_sniff_markdown() {
+        assert_eq!(
+            ContentFormat::sniff("# Catalog\n\nUses gts.x.core.events.type.v1~\n"),
+            Some(ContentFormat::Markdown)
+        );
+        assert_eq!(
+            ContentFormat::sniff("[link](https://example.com)"),
+            None,
+            "a markdown link is not a JSON array"
+        );
+    }
+
+    #[test]
+    fn test_sniff_rejects_binary_and_plain_text() {
+        assert_eq!(ContentFormat::sniff("{\0\u{1}\0binary"), None);
+        assert_eq!(ContentFormat::sniff("#!\0\0\0"), None);
+        assert_eq!(ContentFormat::sniff("just some text: no"), None);
+        assert_eq!(ContentFormat::sniff("https://example.com"), None);
+        assert_eq!(ContentFormat::sniff(""), None);
+    }
 }
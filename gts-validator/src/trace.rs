@@ -0,0 +1,141 @@
+//! Discovery tracing: a record of every GTS candidate the scanners consider.
+//!
+//! When `ValidationConfig::trace_discovery` is set, each scanner reports every
+//! candidate it finds — including the ones it skips — to a [`TraceSink`], and
+//! the report carries the result as a [`DiscoveryTrace`]. This is a debugging
+//! aid for "why wasn't this ID checked?" questions.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// Every candidate considered during a run, grouped by file in scan order.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct DiscoveryTrace {
+    /// Files that were scanned, including those without candidates.
+    pub files: Vec<FileTrace>,
+}
+
+/// The candidates considered in one file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct FileTrace {
+    pub file: PathBuf,
+    pub candidates: Vec<TracedCandidate>,
+}
+
+/// One candidate substring and what happened to it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct TracedCandidate {
+    /// The substring or string value as discovered, before normalization.
+    pub candidate: String,
+    /// How the candidate was discovered.
+    pub source: DiscoverySource,
+    /// 1-indexed line (markdown only, 0 otherwise).
+    pub line: usize,
+    /// 1-indexed column (markdown only, 0 otherwise).
+    pub column: usize,
+    /// JSON path (JSON/YAML only, empty otherwise).
+    pub json_path: String,
+    /// Whether the candidate was skipped or validated, and the result.
+    pub outcome: CandidateOutcome,
+}
+
+/// The scanner and pattern that produced a candidate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum DiscoverySource {
+    /// Markdown, well-formed discovery regex (`DiscoveryMode::StrictSpecOnly`).
+    MarkdownStrict,
+    /// Markdown, relaxed discovery regex (`DiscoveryMode::Heuristic`).
+    MarkdownHeuristic,
+    /// The target of a markdown `[[wikilink]]` that starts with a GTS prefix.
+    Wikilink,
+    /// A JSON/YAML string value.
+    Value,
+    /// A JSON/YAML object key (`scan_keys`).
+    Key,
+}
+
+/// What the scanner did with a candidate.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum CandidateOutcome {
+    /// Validated without errors.
+    Valid,
+    /// Validated; these are the error messages reported for it.
+    Invalid { errors: Vec<String> },
+    /// Not validated.
+    Skipped { reason: SkipReason },
+}
+
+/// Why a candidate was not validated.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum SkipReason {
+    /// Already seen on the same line.
+    Duplicate,
+    /// Inside a fenced block of a grammar language (e.g. `ebnf`, `regex`).
+    Fence { language: String },
+    /// Preceded by a built-in "bad example" marker (e.g. `❌`, `invalid:`).
+    BadExample,
+    /// Preceded by a consumer-provided skip token.
+    SkipToken { token: String },
+    /// An `x-gts-ref` JSON pointer or bare `*`, not a GTS ID.
+    XGtsRefPointer,
+    /// An `x-gts-generic-field` value: a property name, not a GTS ID.
+    GenericField,
+    /// Looks like a schema filename (`gts.x.core.type.v1~.schema.json`).
+    Filename,
+    /// In an `OpenAPI` document, outside an `x-gts-*` extension field.
+    OpenapiNonExtension,
+    /// In markdown prose outside an inline code span, under a
+    /// `MarkdownProfile` that validates code spans only.
+    OutsideCodeSpan,
+}
+
+/// Receives trace events from the scanners.
+pub trait TraceSink {
+    /// Record one candidate.
+    fn record(&mut self, candidate: TracedCandidate);
+
+    /// Whether events are kept. Scanners skip work done only for the
+    /// trace (e.g. matching inside skipped fences) when this is `false`.
+    fn is_enabled(&self) -> bool {
+        true
+    }
+}
+
+/// Discards events; used when tracing is off.
+impl TraceSink for () {
+    fn record(&mut self, _candidate: TracedCandidate) {}
+
+    fn is_enabled(&self) -> bool {
+        false
+    }
+}
+
+impl TraceSink for Vec<TracedCandidate> {
+    fn record(&mut self, candidate: TracedCandidate) {
+        self.push(candidate);
+    }
+}
+
+impl CandidateOutcome {
+    /// `Valid` if `errors` is empty, `Invalid` otherwise.
+    #[must_use]
+    pub fn from_errors(errors: &[String]) -> Self {
+        if errors.is_empty() {
+            Self::Valid
+        } else {
+            Self::Invalid {
+                errors: errors.to_vec(),
+            }
+        }
+    }
+}
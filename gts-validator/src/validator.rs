@@ -4,6 +4,8 @@
 //! authoritative `gts` crate. It does NOT re-implement GTS parsing.
 
 use crate::normalize::NormalizedCandidate;
+use crate::outcome::IdKind;
+use crate::vendor::VendorCheck;
 
 /// Contexts where wildcards are allowed (in documentation)
 pub const WILDCARD_ALLOWED_CONTEXTS: &[&str] = &[
@@ -94,13 +96,15 @@ pub fn is_bad_example_context(line: &str, match_start: usize) -> bool {
 
 /// Validate a GTS identifier candidate.
 ///
-/// This function delegates all validation to `gts::GtsId::try_new()` and `gts::GtsIdPattern::try_new()`.
+/// This function delegates all validation to the `gts` crate's
+/// `validate_schema_id`, `validate_instance_id` and `validate_ref_pattern`,
+/// and the vendor check to [`VendorPolicy::evaluate`].
 /// It does NOT re-implement GTS parsing.
 ///
 /// # Arguments
 ///
 /// * `candidate` - A normalized candidate (after stripping gts://, quotes, etc.)
-/// * `expected_vendor` - Optional vendor to check against (with `EXAMPLE_VENDORS` tolerance)
+/// * `vendor_check` - Vendor policy to check valid IDs against
 /// * `allow_wildcards` - Whether wildcard patterns are allowed in this context
 ///
 /// # Returns
@@ -108,75 +112,149 @@ pub fn is_bad_example_context(line: &str, match_start: usize) -> bool {
 /// A vector of error messages. Empty if valid.
 pub fn validate_candidate(
     candidate: &NormalizedCandidate,
-    expected_vendor: Option<&str>,
+    vendor_check: &VendorCheck,
+    allow_wildcards: bool,
+) -> Vec<String> {
+    let kind = id_kind(&candidate.gts_id, "");
+    validate_candidate_as(candidate, kind, vendor_check, allow_wildcards)
+}
+
+/// Validate a GTS identifier candidate against the grammar of `kind` (see
+/// [`id_kind`]).
+pub fn validate_candidate_as(
+    candidate: &NormalizedCandidate,
+    kind: IdKind,
+    vendor_check: &VendorCheck,
     allow_wildcards: bool,
 ) -> Vec<String> {
-    let mut errors = Vec::new();
     let gts_id = &candidate.gts_id;
 
-    // Handle wildcards
+    if gts_id.contains('*') && !allow_wildcards {
+        return vec![format!(
+            "Wildcards not allowed outside pattern contexts: '{}'",
+            candidate.original
+        )];
+    }
+    if let Err(e) = check_syntax(gts_id, kind) {
+        return vec![format!("{e}")];
+    }
+
+    // Vendor check, only on valid IDs to avoid duplicate/misleading errors.
+    match vendor_check.evaluate(gts_id) {
+        Ok(()) => Vec::new(),
+        Err(violation) => vec![violation.message],
+    }
+}
+
+/// The grammar to check `gts_id` against, found at `json_path` (empty
+/// outside JSON/YAML): a pattern if it has a wildcard; a type ID if it ends
+/// with `~` or is the value of a `$id` or `$ref` key, which always name
+/// types; an instance ID otherwise.
+pub fn id_kind(gts_id: &str, json_path: &str) -> IdKind {
+    let key = json_path.rsplit('.').next();
     if gts_id.contains('*') {
-        if !allow_wildcards {
-            return vec![format!(
-                "Wildcards not allowed outside pattern contexts: '{}'",
-                candidate.original
-            )];
-        }
-        // GtsIdPattern::try_new() delegates to GtsId::try_new() internally,
-        // so all spec rules are enforced. Single parse — vendor check
-        // only runs on success to avoid duplicate/misleading errors.
-        match gts::GtsIdPattern::try_new(gts_id) {
-            Ok(parsed) => {
-                if let Some(expected) = expected_vendor
-                    && let Some(first_seg) = parsed.segments().first()
-                    && !first_seg.vendor().contains('*')
-                    && first_seg.vendor() != expected
-                    && !is_example_vendor(first_seg.vendor())
-                {
-                    errors.push(format!(
-                        "Vendor mismatch: expected '{expected}', found '{}'",
-                        first_seg.vendor()
-                    ));
-                }
-            }
-            Err(e) => {
-                errors.push(format!("{e}"));
-            }
-        }
+        IdKind::Pattern
+    } else if gts_id.trim_end().ends_with('~') || matches!(key, Some("$id" | "$ref")) {
+        IdKind::Schema
     } else {
-        // Delegate to gts crate — the single source of truth
-        match gts::GtsId::try_new(gts_id) {
-            Ok(parsed) => {
-                // Vendor check
-                if let Some(expected) = expected_vendor
-                    && let Some(first_seg) = parsed.segments().first()
-                    && first_seg.vendor() != expected
-                    && !is_example_vendor(first_seg.vendor())
-                {
-                    errors.push(format!(
-                        "Vendor mismatch: expected '{expected}', found '{}'",
-                        first_seg.vendor()
-                    ));
-                }
-            }
-            Err(e) => {
-                errors.push(format!("{e}"));
-            }
-        }
+        IdKind::Instance
+    }
+}
+
+/// How the grammar of `kind` is named in diagnostics.
+pub fn grammar_name(kind: IdKind) -> &'static str {
+    match kind {
+        IdKind::Schema => "type ID",
+        IdKind::Instance => "instance ID",
+        IdKind::Pattern => "pattern",
+    }
+}
+
+/// Check `gts_id` against the GTS grammar of `kind`.
+fn check_syntax(gts_id: &str, kind: IdKind) -> Result<(), gts::GtsIdError> {
+    match kind {
+        IdKind::Schema => gts::validate_schema_id(gts_id),
+        IdKind::Instance => gts::validate_instance_id(gts_id),
+        IdKind::Pattern => gts::validate_ref_pattern(gts_id),
     }
+}
 
-    errors
+/// A well-formed identifier close to the malformed `gts_id`, if the
+/// mistake is a common one: hyphens instead of underscores, upper-case
+/// letters, or a type ID missing its trailing `~`.
+#[must_use]
+pub fn suggest_fix(gts_id: &str) -> Option<String> {
+    let fixed = gts_id.replace('-', "_").to_lowercase();
+    let is_valid = |id: &str| check_syntax(id, id_kind(id, "")).is_ok();
+    if fixed != gts_id && is_valid(&fixed) {
+        return Some(fixed);
+    }
+    let as_type = format!("{fixed}~");
+    is_valid(&as_type).then_some(as_type)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::VendorPolicy;
     use crate::normalize::normalize_candidate;
+    use gts_id::corpus::ID_CORPUS;
+
+    fn must(vendor: &str) -> VendorCheck {
+        VendorPolicy::MustMatch(vendor.to_owned()).into()
+    }
+
+    #[test]
+    fn test_validate_candidate_agrees_with_id_corpus() {
+        for &(input, schema, instance, pattern) in ID_CORPUS {
+            let expected = if input.contains('*') {
+                pattern
+            } else if input.trim_end().ends_with('~') {
+                schema
+            } else {
+                instance
+            };
+            let candidate = NormalizedCandidate {
+                gts_id: input.to_owned(),
+                original: input.to_owned(),
+            };
+            let errors = validate_candidate(&candidate, &VendorPolicy::Any.into(), true);
+            assert_eq!(
+                errors.is_empty(),
+                expected.is_none(),
+                "{input:?}: got {errors:?}, expected error {expected:?}"
+            );
+            if let Some(cause) = expected {
+                assert!(errors[0].contains(cause), "{input:?}: {errors:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_suggest_fix() {
+        assert_eq!(
+            suggest_fix("gts.x.core.events.user-created.v1~").as_deref(),
+            Some("gts.x.core.events.user_created.v1~")
+        );
+        assert_eq!(
+            suggest_fix("gts.x.core.events.Type.V1~").as_deref(),
+            Some("gts.x.core.events.type.v1~")
+        );
+        assert_eq!(
+            suggest_fix("gts.x.core.events.type.v1").as_deref(),
+            Some("gts.x.core.events.type.v1~")
+        );
+        assert_eq!(
+            suggest_fix("gts.x-y.core.events.type.V1").as_deref(),
+            Some("gts.x_y.core.events.type.v1~")
+        );
+        assert_eq!(suggest_fix("gts.x.core"), None);
+    }
 
     #[test]
     fn test_validate_candidate_valid_type() {
         let candidate = normalize_candidate("gts.x.idp.users.user.v1.0~").unwrap();
-        let errors = validate_candidate(&candidate, None, false);
+        let errors = validate_candidate(&candidate, &VendorPolicy::Any.into(), false);
         assert!(errors.is_empty(), "Unexpected errors: {errors:?}");
     }
 
@@ -184,21 +262,21 @@ mod tests {
     fn test_validate_candidate_valid_chained() {
         let candidate =
             normalize_candidate("gts.x.core.events.type.v1~ven.app._.custom_event.v1~").unwrap();
-        let errors = validate_candidate(&candidate, None, false);
+        let errors = validate_candidate(&candidate, &VendorPolicy::Any.into(), false);
         assert!(errors.is_empty(), "Unexpected errors: {errors:?}");
     }
 
     #[test]
     fn test_validate_candidate_vendor_match() {
         let candidate = normalize_candidate("gts.x.core.modkit.plugin.v1~").unwrap();
-        let errors = validate_candidate(&candidate, Some("x"), false);
+        let errors = validate_candidate(&candidate, &must("x"), false);
         assert!(errors.is_empty(), "Unexpected errors: {errors:?}");
     }
 
     #[test]
     fn test_validate_candidate_vendor_mismatch() {
         let candidate = normalize_candidate("gts.hx.core.modkit.plugin.v1~").unwrap();
-        let errors = validate_candidate(&candidate, Some("x"), false);
+        let errors = validate_candidate(&candidate, &must("x"), false);
         assert!(!errors.is_empty());
         assert!(errors[0].contains("Vendor mismatch"));
     }
@@ -206,14 +284,14 @@ mod tests {
     #[test]
     fn test_validate_candidate_example_vendor_tolerated() {
         let candidate = normalize_candidate("gts.acme.core.events.user_created.v1~").unwrap();
-        let errors = validate_candidate(&candidate, Some("x"), false);
+        let errors = validate_candidate(&candidate, &must("x"), false);
         assert!(
             errors.is_empty(),
             "Example vendor 'acme' should be tolerated: {errors:?}"
         );
 
         let candidate = normalize_candidate("gts.globex.core.events.order.v1~").unwrap();
-        let errors = validate_candidate(&candidate, Some("x"), false);
+        let errors = validate_candidate(&candidate, &must("x"), false);
         assert!(
             errors.is_empty(),
             "Example vendor 'globex' should be tolerated: {errors:?}"
@@ -223,39 +301,81 @@ mod tests {
     #[test]
     fn test_validate_candidate_invalid_hyphen() {
         let candidate = normalize_candidate("gts.my-vendor.core.events.type.v1~").unwrap();
-        let errors = validate_candidate(&candidate, None, false);
+        let errors = validate_candidate(&candidate, &VendorPolicy::Any.into(), false);
         assert!(!errors.is_empty());
     }
 
     #[test]
     fn test_validate_candidate_invalid_uppercase() {
         let candidate = normalize_candidate("gts.X.core.events.type.v1~").unwrap();
-        let errors = validate_candidate(&candidate, None, false);
+        let errors = validate_candidate(&candidate, &VendorPolicy::Any.into(), false);
         assert!(!errors.is_empty());
     }
 
     #[test]
     fn test_validate_candidate_invalid_digit_start() {
         let candidate = normalize_candidate("gts.1vendor.core.events.type.v1~").unwrap();
-        let errors = validate_candidate(&candidate, None, false);
+        let errors = validate_candidate(&candidate, &VendorPolicy::Any.into(), false);
         assert!(!errors.is_empty());
     }
 
     #[test]
     fn test_validate_candidate_wildcard_allowed() {
         let candidate = normalize_candidate("gts.x.*").unwrap();
-        let errors = validate_candidate(&candidate, None, true);
+        let errors = validate_candidate(&candidate, &VendorPolicy::Any.into(), true);
+        assert!(errors.is_empty(), "Unexpected errors: {errors:?}");
+    }
+
+    #[test]
+    fn test_validate_candidate_bare_wildcard_skips_vendor_check() {
+        let candidate = normalize_candidate("gts.*").unwrap();
+        let errors = validate_candidate(&candidate, &must("x"), true);
         assert!(errors.is_empty(), "Unexpected errors: {errors:?}");
+
+        let candidate = normalize_candidate("gts.y.*").unwrap();
+        let errors = validate_candidate(&candidate, &must("x"), true);
+        assert!(errors[0].contains("Vendor mismatch"), "{errors:?}");
     }
 
     #[test]
     fn test_validate_candidate_wildcard_not_allowed() {
         let candidate = normalize_candidate("gts.x.*").unwrap();
-        let errors = validate_candidate(&candidate, None, false);
+        let errors = validate_candidate(&candidate, &VendorPolicy::Any.into(), false);
         assert!(!errors.is_empty());
         assert!(errors[0].contains("Wildcards"));
     }
 
+    #[test]
+    fn test_id_kind() {
+        for (gts_id, json_path, kind) in [
+            ("gts.x.core.events.type.v1~", "", IdKind::Schema),
+            ("gts.x.core.events.type.v1~ ", "", IdKind::Schema),
+            ("gts.x.core.events.type.v1~x.a.b.c.v1", "", IdKind::Instance),
+            (
+                "gts.x.core.events.type.v1~x.a.b.c.v1",
+                "$.$id",
+                IdKind::Schema,
+            ),
+            (
+                "gts.x.core.events.type.v1~x.a.b.c.v1",
+                "$.a.$ref",
+                IdKind::Schema,
+            ),
+            (
+                "gts.x.core.events.type.v1~x.a.b.c.v1",
+                "$.id",
+                IdKind::Instance,
+            ),
+            ("gts.x.core.*", "$.$ref", IdKind::Pattern),
+        ] {
+            assert_eq!(
+                id_kind(gts_id, json_path),
+                kind,
+                "{gts_id} at {json_path:?}"
+            );
+        }
+    }
+
     #[test]
     fn test_is_example_vendor() {
         assert!(is_example_vendor("acme"));
@@ -0,0 +1,355 @@
+//! Vendor extraction and vendor policy evaluation.
+//!
+//! Every scanner checks valid GTS identifiers with [`VendorPolicy::evaluate`],
+//! so a service can apply exactly the same policy to a single ID without
+//! running a file scan.
+
+use std::fmt;
+
+use gts::{GTS_ID_PREFIX, GTS_ID_URI_PREFIX};
+
+use crate::config::{VendorPolicy, VendorScope};
+use crate::validator::is_example_vendor;
+
+/// The vendor of a GTS identifier or pattern: the first token of its first
+/// segment, e.g. `x` in `gts.x.core.events.type.v1~y.app.orders.created.v1~`.
+///
+/// A `gts://` prefix is accepted. Returns `None` if `id` does not start with
+/// the GTS prefix or has an empty vendor.
+#[must_use]
+pub fn extract_vendor(id: &str) -> Option<&str> {
+    extract_vendors(id).first().copied()
+}
+
+/// The vendor of every segment of a GTS identifier or pattern, in order,
+/// e.g. `["x", "y"]` for `gts.x.core.events.type.v1~y.app.orders.created.v1~`.
+///
+/// A `gts://` prefix is accepted. Returns an empty list if `id` does not
+/// start with the GTS prefix; segments with an empty vendor are skipped.
+#[must_use]
+pub fn extract_vendors(id: &str) -> Vec<&str> {
+    let id = id.trim();
+    let id = id.strip_prefix(GTS_ID_URI_PREFIX).unwrap_or(id);
+    let Some(rest) = id.strip_prefix(GTS_ID_PREFIX) else {
+        return Vec::new();
+    };
+    rest.split('~')
+        .filter_map(|segment| segment.split('.').next())
+        .filter(|vendor| !vendor.is_empty())
+        .collect()
+}
+
+/// A GTS identifier whose vendor violates a [`VendorPolicy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct VendorViolation {
+    /// The vendor found in the identifier.
+    pub vendor: String,
+    /// The 1-based segment the vendor was found in.
+    pub segment: usize,
+    /// The policy that was violated.
+    pub policy: VendorPolicy,
+    /// The message scanners report for this violation.
+    pub message: String,
+}
+
+impl fmt::Display for VendorViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for VendorViolation {}
+
+impl VendorPolicy {
+    /// The segments this policy applies to unless configured otherwise:
+    /// every segment for `Deny`, so a denied vendor cannot hide in a derived
+    /// segment, and the first segment for the other policies.
+    #[must_use]
+    pub fn default_scope(&self) -> VendorScope {
+        match self {
+            Self::Deny(_) => VendorScope::AllSegments,
+            _ => VendorScope::FirstSegment,
+        }
+    }
+
+    /// Check the vendor of `id`, a GTS identifier or pattern, against this
+    /// policy in its [`default_scope`](Self::default_scope).
+    ///
+    /// # Errors
+    /// Returns a [`VendorViolation`] if a vendor is not allowed.
+    pub fn evaluate(&self, id: &str) -> Result<(), VendorViolation> {
+        self.evaluate_in_scope(id, self.default_scope())
+    }
+
+    /// Check the vendors of the segments of `id` selected by `scope` against
+    /// this policy, reporting the first violation.
+    ///
+    /// Wildcard vendors (`gts.*`) satisfy every policy. Example vendors
+    /// (`acme`, `example`, ...) satisfy `MustMatch` and `AllowList` but can
+    /// still be denied explicitly. Messages for chained IDs name the segment.
+    ///
+    /// # Errors
+    /// Returns a [`VendorViolation`] if a vendor is not allowed.
+    pub fn evaluate_in_scope(&self, id: &str, scope: VendorScope) -> Result<(), VendorViolation> {
+        let vendors = extract_vendors(id);
+        let selected = match scope {
+            VendorScope::FirstSegment => 0..vendors.len().min(1),
+            VendorScope::AllSegments => 0..vendors.len(),
+            VendorScope::LastSegment => vendors.len().saturating_sub(1)..vendors.len(),
+        };
+        for index in selected {
+            let vendor = vendors[index];
+            if vendor.contains('*') {
+                continue;
+            }
+            if let Some(mut message) = self.violation_message(vendor) {
+                if vendors.len() > 1 {
+                    message = format!("{message} in segment {}", index + 1);
+                }
+                return Err(VendorViolation {
+                    vendor: vendor.to_owned(),
+                    segment: index + 1,
+                    policy: self.clone(),
+                    message,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// The message for `vendor` violating this policy, if it does.
+    fn violation_message(&self, vendor: &str) -> Option<String> {
+        match self {
+            Self::Any => None,
+            Self::MustMatch(expected) => (vendor != expected && !is_example_vendor(vendor))
+                .then(|| format!("Vendor mismatch: expected '{expected}', found '{vendor}'")),
+            Self::AllowList(allowed) => {
+                (!allowed.iter().any(|a| a == vendor) && !is_example_vendor(vendor)).then(|| {
+                    format!(
+                        "Vendor mismatch: expected one of '{}', found '{vendor}'",
+                        allowed.join(", ")
+                    )
+                })
+            }
+            Self::Deny(denied) => denied
+                .iter()
+                .any(|d| d == vendor)
+                .then(|| format!("Vendor mismatch: vendor '{vendor}' is denied")),
+        }
+    }
+}
+
+/// A vendor policy with the segments it applies to, as used by the scanners.
+#[derive(Debug, Clone)]
+pub struct VendorCheck {
+    pub policy: VendorPolicy,
+    pub scope: VendorScope,
+}
+
+impl VendorCheck {
+    /// Build a check from `policy`, in `scope` or the policy's default scope.
+    pub fn new(policy: VendorPolicy, scope: Option<VendorScope>) -> Self {
+        let scope = scope.unwrap_or_else(|| policy.default_scope());
+        Self { policy, scope }
+    }
+
+    /// See [`VendorPolicy::evaluate_in_scope`].
+    pub fn evaluate(&self, id: &str) -> Result<(), VendorViolation> {
+        self.policy.evaluate_in_scope(id, self.scope)
+    }
+}
+
+impl From<VendorPolicy> for VendorCheck {
+    fn from(policy: VendorPolicy) -> Self {
+        Self::new(policy, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHAINED: &str = "gts.x.core.events.type.v1~y.app.orders.created.v1~";
+
+    fn message(policy: &VendorPolicy, id: &str) -> Option<String> {
+        policy.evaluate(id).err().map(|v| v.message)
+    }
+
+    #[test]
+    fn test_extract_vendor() {
+        assert_eq!(extract_vendor("gts.x.core.events.type.v1~"), Some("x"));
+        assert_eq!(
+            extract_vendor("gts://gts.x.core.events.type.v1~"),
+            Some("x")
+        );
+        assert_eq!(extract_vendor(CHAINED), Some("x"));
+        assert_eq!(extract_vendor("gts.*"), Some("*"));
+        assert_eq!(extract_vendor("gts."), None);
+        assert_eq!(extract_vendor("x.core.events.type.v1~"), None);
+    }
+
+    #[test]
+    fn test_any_allows_every_vendor() {
+        assert!(
+            VendorPolicy::Any
+                .evaluate("gts.w.core.events.type.v1~")
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_must_match() {
+        let policy = VendorPolicy::MustMatch("x".to_owned());
+        assert!(policy.evaluate("gts.x.core.events.type.v1~").is_ok());
+        assert!(policy.evaluate("gts.acme.core.events.type.v1~").is_ok());
+
+        let violation = policy.evaluate("gts.hx.core.events.type.v1~").unwrap_err();
+        assert_eq!(violation.vendor, "hx");
+        assert_eq!(violation.policy, policy);
+        assert_eq!(
+            violation.to_string(),
+            "Vendor mismatch: expected 'x', found 'hx'"
+        );
+    }
+
+    #[test]
+    fn test_allow_list() {
+        let policy = VendorPolicy::AllowList(vec!["x".to_owned(), "cf".to_owned()]);
+        assert!(policy.evaluate("gts.cf.core.events.type.v1~").is_ok());
+        assert!(policy.evaluate("gts.example.core.events.type.v1~").is_ok());
+        assert_eq!(
+            message(&policy, "gts.w.core.org.department.v1~").as_deref(),
+            Some("Vendor mismatch: expected one of 'x, cf', found 'w'")
+        );
+    }
+
+    #[test]
+    fn test_deny() {
+        let policy = VendorPolicy::Deny(vec!["w".to_owned(), "acme".to_owned()]);
+        assert!(policy.evaluate("gts.x.core.events.type.v1~").is_ok());
+        assert_eq!(
+            message(&policy, "gts.w.core.events.type.v1~").as_deref(),
+            Some("Vendor mismatch: vendor 'w' is denied")
+        );
+        assert!(
+            policy.evaluate("gts.acme.core.events.type.v1~").is_err(),
+            "example vendors can be denied explicitly"
+        );
+    }
+
+    #[test]
+    fn test_wildcard_vendor_satisfies_every_policy() {
+        for policy in [
+            VendorPolicy::MustMatch("x".to_owned()),
+            VendorPolicy::AllowList(vec!["cf".to_owned()]),
+            VendorPolicy::Deny(vec!["x".to_owned()]),
+        ] {
+            assert!(policy.evaluate("gts.*").is_ok(), "{policy:?}");
+        }
+    }
+
+    #[test]
+    fn test_extract_vendors() {
+        assert_eq!(extract_vendors(CHAINED), ["x", "y"]);
+        assert_eq!(extract_vendors("gts.x.core.events.type.v1~*"), ["x", "*"]);
+        assert!(extract_vendors("x.core.events.type.v1~").is_empty());
+    }
+
+    #[test]
+    fn test_default_scope() {
+        assert_eq!(VendorPolicy::Any.default_scope(), VendorScope::FirstSegment);
+        assert_eq!(
+            VendorPolicy::MustMatch("x".to_owned()).default_scope(),
+            VendorScope::FirstSegment
+        );
+        assert_eq!(
+            VendorPolicy::Deny(vec![]).default_scope(),
+            VendorScope::AllSegments
+        );
+    }
+
+    #[test]
+    fn test_must_match_scopes_on_two_vendor_chain() {
+        let x = VendorPolicy::MustMatch("x".to_owned());
+        assert!(x.evaluate(CHAINED).is_ok(), "first segment by default");
+        assert!(
+            x.evaluate_in_scope(CHAINED, VendorScope::FirstSegment)
+                .is_ok()
+        );
+
+        let violation = x
+            .evaluate_in_scope(CHAINED, VendorScope::AllSegments)
+            .unwrap_err();
+        assert_eq!((violation.vendor.as_str(), violation.segment), ("y", 2));
+        assert_eq!(
+            violation.message,
+            "Vendor mismatch: expected 'x', found 'y' in segment 2"
+        );
+        assert_eq!(
+            x.evaluate_in_scope(CHAINED, VendorScope::LastSegment),
+            Err(violation)
+        );
+
+        let y = VendorPolicy::MustMatch("y".to_owned());
+        assert_eq!(
+            message(&y, CHAINED).as_deref(),
+            Some("Vendor mismatch: expected 'y', found 'x' in segment 1")
+        );
+        assert!(
+            y.evaluate_in_scope(CHAINED, VendorScope::LastSegment)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_allow_list_scopes_on_two_vendor_chain() {
+        let policy = VendorPolicy::AllowList(vec!["x".to_owned(), "cf".to_owned()]);
+        assert!(policy.evaluate(CHAINED).is_ok());
+        assert_eq!(
+            policy
+                .evaluate_in_scope(CHAINED, VendorScope::AllSegments)
+                .map_err(|v| v.message),
+            Err("Vendor mismatch: expected one of 'x, cf', found 'y' in segment 2".to_owned())
+        );
+        assert!(
+            policy
+                .evaluate_in_scope(CHAINED, VendorScope::LastSegment)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_deny_scopes_on_two_vendor_chain() {
+        let deny_y = VendorPolicy::Deny(vec!["y".to_owned()]);
+        assert_eq!(
+            message(&deny_y, CHAINED).as_deref(),
+            Some("Vendor mismatch: vendor 'y' is denied in segment 2"),
+            "all segments by default"
+        );
+        assert!(
+            deny_y
+                .evaluate_in_scope(CHAINED, VendorScope::FirstSegment)
+                .is_ok()
+        );
+        assert!(
+            deny_y
+                .evaluate_in_scope(CHAINED, VendorScope::LastSegment)
+                .is_err()
+        );
+
+        let deny_x = VendorPolicy::Deny(vec!["x".to_owned()]);
+        assert_eq!(deny_x.evaluate(CHAINED).unwrap_err().segment, 1);
+        assert!(
+            deny_x
+                .evaluate_in_scope(CHAINED, VendorScope::LastSegment)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_wildcard_segment_is_skipped() {
+        let policy = VendorPolicy::Deny(vec!["y".to_owned()]);
+        assert!(policy.evaluate("gts.x.core.events.type.v1~*").is_ok());
+    }
+}
@@ -0,0 +1,60 @@
+//! Smoke variant of the `benches/` suite: the reduced corpus must scan
+//! within a generous time bound, so gross throughput regressions fail the
+//! build without running the benchmarks.
+
+use std::time::{Duration, Instant};
+
+use gts_validator::bench::{
+    ContentScanner, CorpusSize, deep_yaml, generate_corpus, large_json, markdown_file,
+};
+use gts_validator::{FsSourceConfig, ValidationConfig, VendorPolicy, validate_fs};
+use tempfile::TempDir;
+
+/// Far above the few seconds the smoke corpus takes in a debug build, so
+/// only an order-of-magnitude slowdown trips it.
+const TIME_BOUND: Duration = Duration::from_mins(1);
+
+fn validation_config() -> ValidationConfig {
+    let mut config = ValidationConfig::default();
+    config.vendor_policy = VendorPolicy::MustMatch("x".to_owned());
+    config
+}
+
+#[test]
+fn smoke_corpus_scans_within_bound() {
+    let tmp = TempDir::new().unwrap();
+    generate_corpus(tmp.path(), &CorpusSize::SMOKE).unwrap();
+    let mut fs_config = FsSourceConfig::default();
+    fs_config.paths = vec![tmp.path().to_path_buf()];
+
+    let start = Instant::now();
+    let report = validate_fs(&fs_config, &validation_config()).unwrap();
+    let elapsed = start.elapsed();
+
+    assert_eq!(report.scanned_files, CorpusSize::SMOKE.markdown_files + 2);
+    assert!(report.scan_errors.is_empty(), "{:?}", report.scan_errors);
+    assert!(report.errors_count() > 0);
+    assert!(
+        elapsed < TIME_BOUND,
+        "smoke corpus took {elapsed:?}, over the {TIME_BOUND:?} bound"
+    );
+}
+
+#[test]
+fn corpus_is_deterministic() {
+    assert_eq!(markdown_file(42), markdown_file(42));
+    assert_eq!(large_json(64 * 1024), large_json(64 * 1024));
+    assert_eq!(deep_yaml(10), deep_yaml(10));
+    assert!(large_json(64 * 1024).len() >= 64 * 1024);
+}
+
+#[test]
+fn scanners_accept_full_size_documents() {
+    let scanner = ContentScanner::new(&validation_config()).unwrap();
+    let yaml = deep_yaml(CorpusSize::FULL.yaml_depth);
+    let errors = scanner.scan_yaml_content(&yaml).unwrap();
+    assert!(!errors.is_empty());
+    let errors = scanner.scan_json_content(&large_json(256 * 1024)).unwrap();
+    assert!(!errors.is_empty());
+    assert!(scanner.scan_markdown_content(&markdown_file(0)).is_empty());
+}
@@ -1,6 +1,7 @@
 use std::fs;
 use std::process::Command;
 
+use gts_validator::{CandidateOutcome, DiscoveryTrace, SkipReason};
 use tempfile::TempDir;
 
 fn validator_bin() -> &'static str {
@@ -168,3 +169,780 @@ fn cli_uses_default_paths_when_none_provided() {
     assert!(stdout.contains("\"ok\": true"), "stdout: {stdout}");
     assert!(stdout.contains("\"scanned_files\": 1"), "stdout: {stdout}");
 }
+
+#[test]
+fn cli_json_compact_output_is_single_line() {
+    let tmp = TempDir::new().expect("temp dir");
+    let md = tmp.path().join("test.md");
+    fs::write(&md, "# Title\n\nUses `gts.x.core.pkg.mytype.v1~` schema.\n")
+        .expect("write markdown");
+
+    let output = Command::new(validator_bin())
+        .arg("--output-format")
+        .arg("json-compact")
+        .arg(tmp.path())
+        .output()
+        .expect("failed to run gts-validator");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).expect("stdout utf-8");
+    assert_eq!(stdout.lines().count(), 1, "stdout: {stdout}");
+    assert!(stdout.contains("\"ok\":true"), "stdout: {stdout}");
+    assert!(stdout.contains("\"scanned_files\":1"), "stdout: {stdout}");
+}
+
+#[test]
+fn cli_json_conflicts_with_output_format() {
+    let output = Command::new(validator_bin())
+        .arg("--json")
+        .arg("--output-format")
+        .arg("human")
+        .arg(".")
+        .output()
+        .expect("failed to run gts-validator");
+
+    assert!(!output.status.success());
+}
+
+#[test]
+fn cli_histogram_flag_prints_distribution() {
+    let tmp = TempDir::new().expect("temp dir");
+    fs::write(
+        tmp.path().join("a.md"),
+        "`gts.y.core.pkg.one.v1~` and `gts.y.core.pkg.two.v1~`\n",
+    )
+    .expect("write markdown");
+    fs::write(tmp.path().join("b.md"), "`gts.y.core.pkg.three.v1~`\n").expect("write markdown");
+
+    let output = Command::new(validator_bin())
+        .arg("--histogram")
+        .arg("--vendor")
+        .arg("x")
+        .arg(tmp.path())
+        .output()
+        .expect("failed to run gts-validator");
+
+    assert!(!output.status.success());
+    let stdout = String::from_utf8(output.stdout).expect("stdout utf-8");
+    assert!(
+        stdout.contains("Errors per file (2 files): p50=1 p90=2 p99=2 max=2 mean=1.50"),
+        "stdout: {stdout}"
+    );
+}
+
+#[test]
+fn cli_rule_threshold_tolerates_errors_up_to_limit() {
+    let tmp = TempDir::new().expect("temp dir");
+    fs::write(
+        tmp.path().join("test.md"),
+        "Uses `gts.y.core.pkg.a.v1~` and `gts.y.core.pkg.b.v1~`.\n",
+    )
+    .expect("write markdown");
+
+    let run = |threshold: &str| {
+        Command::new(validator_bin())
+            .args(["--vendor", "x", "--rule-threshold", threshold])
+            .arg(tmp.path())
+            .output()
+            .expect("failed to run gts-validator")
+    };
+
+    let within = run("GTS001=2");
+    assert!(within.status.success());
+
+    let exceeded = run("GTS001=1");
+    assert!(!exceeded.status.success());
+    let stdout = String::from_utf8(exceeded.stdout).expect("stdout utf-8");
+    assert!(
+        stdout.contains("WARNING: GTS001 at 2/1 threshold"),
+        "stdout: {stdout}"
+    );
+
+    let unknown = run("GTS999=1");
+    assert!(!unknown.status.success());
+    let stderr = String::from_utf8(unknown.stderr).expect("stderr utf-8");
+    assert!(stderr.contains("unknown rule 'GTS999'"), "stderr: {stderr}");
+}
+
+#[test]
+fn cli_vendor_scope_checks_selected_segments() {
+    let tmp = TempDir::new().expect("temp dir");
+    fs::write(
+        tmp.path().join("test.md"),
+        "Extends `gts.x.core.events.type.v1~y.billing.invoice.created.v1~`.\n",
+    )
+    .expect("write markdown");
+
+    let run = |scope: &str| {
+        Command::new(validator_bin())
+            .args(["--vendor", "x", "--vendor-scope", scope])
+            .arg(tmp.path())
+            .output()
+            .expect("failed to run gts-validator")
+    };
+
+    assert!(run("first").status.success());
+
+    for scope in ["all", "last"] {
+        let output = run(scope);
+        assert!(!output.status.success(), "scope {scope}");
+        let stdout = String::from_utf8(output.stdout).expect("stdout utf-8");
+        assert!(
+            stdout.contains("Vendor mismatch: expected 'x', found 'y' in segment 2"),
+            "stdout: {stdout}"
+        );
+    }
+}
+
+#[test]
+fn cli_debug_discovery_prints_trace() {
+    let tmp = TempDir::new().expect("temp dir");
+    fs::write(
+        tmp.path().join("bdd.md"),
+        "**given** gts.y.core.pkg.mytype.v1~ is registered\n",
+    )
+    .expect("write markdown");
+
+    let output = Command::new(validator_bin())
+        .args([
+            "--debug-discovery",
+            "--vendor",
+            "x",
+            "--skip-token",
+            "**given**",
+        ])
+        .arg(tmp.path())
+        .output()
+        .expect("failed to run gts-validator --debug-discovery");
+    assert!(output.status.success());
+
+    let trace: DiscoveryTrace =
+        serde_json::from_slice(&output.stdout).expect("stdout must be a discovery trace");
+    assert_eq!(trace.files.len(), 1);
+    let candidates = &trace.files[0].candidates;
+    assert_eq!(candidates.len(), 1, "{candidates:?}");
+    assert_eq!(candidates[0].candidate, "gts.y.core.pkg.mytype.v1~");
+    assert_eq!(
+        candidates[0].outcome,
+        CandidateOutcome::Skipped {
+            reason: SkipReason::SkipToken {
+                token: "**given**".to_owned()
+            }
+        }
+    );
+}
+
+#[test]
+fn cli_max_total_size_truncates_scan() {
+    let tmp = TempDir::new().expect("temp dir");
+    for name in ["a.md", "b.md", "c.md"] {
+        fs::write(tmp.path().join(name), "x".repeat(100)).expect("write markdown");
+    }
+
+    let output = Command::new(validator_bin())
+        .args(["--json", "--max-total-size", "150"])
+        .arg(tmp.path())
+        .output()
+        .expect("failed to run gts-validator");
+    assert!(!output.status.success());
+    let stdout = String::from_utf8(output.stdout).expect("stdout utf-8");
+    assert!(stdout.contains("\"truncated\": true"), "stdout: {stdout}");
+    assert!(
+        stdout.contains("2 file(s) were not scanned"),
+        "stdout: {stdout}"
+    );
+}
+
+#[test]
+fn cli_max_reported_errors_caps_json_errors() {
+    let tmp = TempDir::new().expect("temp dir");
+    let md = tmp.path().join("test.md");
+    fs::write(
+        &md,
+        "Uses `gts.y.core.pkg.a.v1~`, `gts.y.core.pkg.b.v1~` and `gts.y.core.pkg.c.v1~`.\n",
+    )
+    .expect("write markdown");
+
+    let output = Command::new(validator_bin())
+        .args(["--vendor", "x", "--json", "--max-reported-errors", "2"])
+        .arg(tmp.path())
+        .output()
+        .expect("failed to run gts-validator");
+
+    assert!(!output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("stdout is JSON");
+    assert_eq!(
+        report["validation_errors"].as_array().map(Vec::len),
+        Some(2)
+    );
+    assert_eq!(report["errors_truncated"], true);
+    assert_eq!(report["omitted_errors"], 1);
+}
+
+#[test]
+fn cli_cargo_metadata_loads_vendor() {
+    let tmp = TempDir::new().expect("temp dir");
+    let manifest = tmp.path().join("Cargo.toml");
+    fs::write(
+        &manifest,
+        "[package]\nname = \"demo\"\n\n[package.metadata.gts]\nvendor = \"x\"\n",
+    )
+    .expect("write manifest");
+    let docs = tmp.path().join("docs");
+    fs::create_dir(&docs).expect("create docs");
+    fs::write(docs.join("test.md"), "Uses `gts.y.core.pkg.mytype.v1~`.\n").expect("write markdown");
+
+    let without = Command::new(validator_bin())
+        .arg(&docs)
+        .output()
+        .expect("failed to run gts-validator");
+    assert!(without.status.success());
+
+    // Cargo.toml is discovered in the current directory.
+    let discovered = Command::new(validator_bin())
+        .current_dir(tmp.path())
+        .args(["--cargo-metadata", "docs"])
+        .output()
+        .expect("failed to run gts-validator");
+    assert!(!discovered.status.success());
+    let stdout = String::from_utf8(discovered.stdout).expect("stdout utf-8");
+    assert!(stdout.contains("Vendor mismatch"), "stdout: {stdout}");
+
+    let explicit = Command::new(validator_bin())
+        .arg("--cargo-metadata")
+        .arg("--manifest-path")
+        .arg(&manifest)
+        .args(["--vendor", "y"])
+        .arg(&docs)
+        .output()
+        .expect("failed to run gts-validator");
+    assert!(
+        explicit.status.success(),
+        "--vendor replaces the manifest vendor"
+    );
+}
+
+#[test]
+fn cli_fail_on_empty_exits_with_config_error() {
+    let tmp = TempDir::new().expect("temp dir");
+
+    let permissive = Command::new(validator_bin())
+        .arg(tmp.path())
+        .output()
+        .expect("failed to run gts-validator");
+    assert!(permissive.status.success());
+
+    let output = Command::new(validator_bin())
+        .arg("--fail-on-empty")
+        .arg(tmp.path())
+        .output()
+        .expect("failed to run gts-validator");
+    assert_eq!(output.status.code(), Some(2));
+    let stdout = String::from_utf8(output.stdout).expect("stdout utf-8");
+    assert!(stdout.contains("No scannable files in"), "stdout: {stdout}");
+    assert!(stdout.contains("no files"), "stdout: {stdout}");
+}
+
+#[test]
+fn cli_coverage_flags_fail_with_exit_code_2() {
+    let tmp = TempDir::new().expect("temp dir");
+    fs::write(tmp.path().join("a.md"), "# A\n").expect("write markdown");
+
+    let met = Command::new(validator_bin())
+        .args(["--min-scanned", "1", "--require-format", "markdown"])
+        .arg(tmp.path())
+        .output()
+        .expect("failed to run gts-validator");
+    assert!(met.status.success());
+
+    let too_few = Command::new(validator_bin())
+        .args(["--min-scanned", "2"])
+        .arg(tmp.path())
+        .output()
+        .expect("failed to run gts-validator");
+    assert_eq!(too_few.status.code(), Some(2));
+    let stderr = String::from_utf8(too_few.stderr).expect("stderr utf-8");
+    assert!(
+        stderr.contains("Scanned 1 file(s), expected at least 2"),
+        "stderr: {stderr}"
+    );
+
+    let missing = Command::new(validator_bin())
+        .args(["--require-format", "yaml"])
+        .arg(tmp.path())
+        .output()
+        .expect("failed to run gts-validator");
+    assert_eq!(missing.status.code(), Some(2));
+    let stderr = String::from_utf8(missing.stderr).expect("stderr utf-8");
+    assert!(
+        stderr.contains("No yaml file was scanned"),
+        "stderr: {stderr}"
+    );
+
+    let unknown = Command::new(validator_bin())
+        .args(["--require-format", "xml"])
+        .arg(tmp.path())
+        .output()
+        .expect("failed to run gts-validator");
+    assert!(!unknown.status.success());
+}
+
+#[test]
+fn cli_vendor_auto_infers_vendor() {
+    let tmp = TempDir::new().expect("temp dir");
+    fs::create_dir(tmp.path().join(".git")).expect("create .git");
+    fs::write(tmp.path().join(".gts-vendor"), "x\n").expect("write vendor file");
+    let docs = tmp.path().join("docs");
+    fs::create_dir(&docs).expect("create docs");
+    fs::write(docs.join("test.md"), "Uses `gts.y.core.pkg.mytype.v1~`.\n").expect("write markdown");
+
+    let from_file = Command::new(validator_bin())
+        .current_dir(&docs)
+        .args(["--vendor", "auto", "--verbose", "--json", "."])
+        .output()
+        .expect("failed to run gts-validator");
+    assert!(!from_file.status.success());
+    let stdout = String::from_utf8(from_file.stdout).expect("stdout utf-8");
+    assert!(
+        stdout.contains("\"kind\": \"vendor_file\""),
+        "stdout: {stdout}"
+    );
+    let stderr = String::from_utf8(from_file.stderr).expect("stderr utf-8");
+    assert!(
+        stderr.contains("Expected vendor: x (from "),
+        "stderr: {stderr}"
+    );
+
+    // The nearest Cargo.toml beats the vendor file.
+    fs::write(
+        tmp.path().join("Cargo.toml"),
+        "[package]\nname = \"demo\"\n\n[package.metadata.gts]\nvendor = \"y\"\n",
+    )
+    .expect("write manifest");
+    let from_manifest = Command::new(validator_bin())
+        .current_dir(&docs)
+        .args(["--vendor", "auto", "--json", "."])
+        .output()
+        .expect("failed to run gts-validator");
+    assert!(from_manifest.status.success());
+    let stdout = String::from_utf8(from_manifest.stdout).expect("stdout utf-8");
+    assert!(
+        stdout.contains("\"kind\": \"cargo_metadata\""),
+        "stdout: {stdout}"
+    );
+
+    // An explicit vendor beats both.
+    let explicit = Command::new(validator_bin())
+        .current_dir(&docs)
+        .args(["--cargo-metadata", "--manifest-path", "../Cargo.toml"])
+        .args(["--vendor", "x", "--json", "."])
+        .output()
+        .expect("failed to run gts-validator");
+    assert!(!explicit.status.success());
+    let stdout = String::from_utf8(explicit.stdout).expect("stdout utf-8");
+    assert!(!stdout.contains("inferred_vendor"), "stdout: {stdout}");
+}
+
+#[test]
+fn cli_vendor_auto_fails_without_source() {
+    let tmp = TempDir::new().expect("temp dir");
+    fs::create_dir(tmp.path().join(".git")).expect("create .git");
+    fs::write(tmp.path().join("test.md"), "# Title\n").expect("write markdown");
+
+    let output = Command::new(validator_bin())
+        .current_dir(tmp.path())
+        .args(["--vendor", "auto", "."])
+        .output()
+        .expect("failed to run gts-validator");
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).expect("stderr utf-8");
+    assert!(
+        stderr.contains("--vendor auto found no vendor"),
+        "stderr: {stderr}"
+    );
+}
+
+#[test]
+fn cli_exit_code_strategies() {
+    let tmp = TempDir::new().expect("temp dir");
+    fs::write(
+        tmp.path().join("test.md"),
+        "Uses `gts.y.core.pkg.mytype.v1~`.\n",
+    )
+    .expect("write markdown");
+    let run = |args: &[&str]| {
+        Command::new(validator_bin())
+            .args(["--vendor", "x"])
+            .args(args)
+            .arg(tmp.path())
+            .output()
+            .expect("failed to run gts-validator")
+            .status
+            .code()
+    };
+
+    assert_eq!(run(&[]), Some(1));
+    assert_eq!(run(&["--exit-code-strategy", "detailed"]), Some(1));
+
+    // The mismatch is tolerated: only a warning.
+    let tolerated = ["--rule-threshold", "GTS001=1"];
+    assert_eq!(run(&tolerated), Some(0));
+    assert_eq!(
+        run(&[&tolerated[..], &["--exit-code-strategy", "severity"]].concat()),
+        Some(2)
+    );
+
+    let uncovered = ["--min-scanned", "5"];
+    assert_eq!(run(&uncovered), Some(2));
+    assert_eq!(
+        run(&[&uncovered[..], &["--exit-code-strategy", "severity"]].concat()),
+        Some(1)
+    );
+    assert_eq!(
+        run(&[&uncovered[..], &["--exit-code-strategy", "detailed"]].concat()),
+        Some(3)
+    );
+}
+
+#[test]
+fn cli_rewrite_dry_run_then_apply() {
+    let tmp = TempDir::new().expect("temp dir");
+    let doc = tmp.path().join("doc.md");
+    fs::write(&doc, "Uses `gts.x.core.pkg.mytype.v1~`.\n").expect("write markdown");
+    let rewrite = |dry_run: bool| {
+        let mut command = Command::new(validator_bin());
+        command
+            .arg("rewrite")
+            .args(["--from", "gts.x.core.pkg.mytype.v1~"])
+            .args(["--to", "gts.x.core.pkg.mytype.v2~"])
+            .arg(tmp.path());
+        if dry_run {
+            command.arg("--dry-run");
+        }
+        command
+            .output()
+            .expect("failed to run gts-validator rewrite")
+    };
+
+    let output = rewrite(true);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).expect("stdout utf-8");
+    assert!(
+        stdout.contains("-Uses `gts.x.core.pkg.mytype.v1~`.\n+Uses `gts.x.core.pkg.mytype.v2~`.\n"),
+        "stdout: {stdout}"
+    );
+    let stderr = String::from_utf8(output.stderr).expect("stderr utf-8");
+    assert!(
+        stderr.contains("Would rewrite 1 occurrence(s) in 1 file(s) (markdown: 1)"),
+        "stderr: {stderr}"
+    );
+    assert!(
+        fs::read_to_string(&doc)
+            .expect("read markdown")
+            .contains("v1~")
+    );
+
+    let output = rewrite(false);
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).expect("stderr utf-8");
+    assert!(
+        stderr.contains("Rewrote 1 occurrence(s)"),
+        "stderr: {stderr}"
+    );
+    assert_eq!(
+        fs::read_to_string(&doc).expect("read markdown"),
+        "Uses `gts.x.core.pkg.mytype.v2~`.\n"
+    );
+}
+
+#[test]
+fn cli_config_reports_setting_sources() {
+    let tmp = TempDir::new().expect("temp dir");
+    fs::write(
+        tmp.path().join("Cargo.toml"),
+        "[package]\nname = \"demo\"\n\n[package.metadata.gts]\nvendor = \"acme\"\nscan_keys = true\n",
+    )
+    .expect("write manifest");
+    fs::create_dir(tmp.path().join("docs")).expect("create docs");
+    let config = |args: &[&str]| {
+        let output = Command::new(validator_bin())
+            .current_dir(tmp.path())
+            .args(["config", "--cargo-metadata", "--json"])
+            .args(args)
+            .output()
+            .expect("failed to run gts-validator");
+        assert!(output.status.success(), "{output:?}");
+        serde_json::from_slice::<serde_json::Value>(&output.stdout).expect("config JSON")
+    };
+
+    // File over default.
+    let json = config(&[]);
+    let vendor = &json["validation"]["vendor_policy"];
+    assert_eq!(vendor["value"], serde_json::json!({"must_match": "acme"}));
+    assert_eq!(
+        vendor["source"],
+        serde_json::json!({"kind": "cargo_metadata", "detail": "Cargo.toml"})
+    );
+    assert_eq!(
+        json["validation"]["scan_keys"]["source"]["kind"],
+        "cargo_metadata"
+    );
+    assert_eq!(
+        json["validation"]["scan_null_ids"]["source"],
+        serde_json::json!({"kind": "default"})
+    );
+    assert_eq!(json["fs"]["paths"]["value"], serde_json::json!(["docs"]));
+    assert_eq!(json["fs"]["paths"]["source"]["kind"], "default");
+
+    // Flag over file.
+    let json = config(&["--vendor", "x", "--strict", "docs"]);
+    let vendor = &json["validation"]["vendor_policy"];
+    assert_eq!(vendor["value"], serde_json::json!({"must_match": "x"}));
+    assert_eq!(
+        vendor["source"],
+        serde_json::json!({"kind": "command_line", "detail": "--vendor"})
+    );
+    assert_eq!(
+        json["validation"]["discovery_mode"]["source"]["detail"],
+        "--strict"
+    );
+    assert_eq!(json["fs"]["paths"]["source"]["detail"], "PATH");
+    assert_eq!(
+        json["validation"]["scan_keys"]["source"]["kind"],
+        "cargo_metadata"
+    );
+}
+
+#[test]
+fn cli_print_effective_config_and_invalid_setting_source() {
+    let tmp = TempDir::new().expect("temp dir");
+    fs::write(tmp.path().join("test.md"), "# Title\n").expect("write markdown");
+
+    let output = Command::new(validator_bin())
+        .args(["--print-effective-config", "--vendor", "x"])
+        .arg(tmp.path())
+        .output()
+        .expect("failed to run gts-validator");
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).expect("stderr utf-8");
+    assert!(
+        stderr.contains("validation.vendor_policy = {\"must_match\":\"x\"} (from --vendor)"),
+        "stderr: {stderr}"
+    );
+    assert!(
+        stderr.contains("fs.max_depth = 64 (default)"),
+        "stderr: {stderr}"
+    );
+
+    let output = Command::new(validator_bin())
+        .args(["--document-kind", "[=config"])
+        .arg(tmp.path())
+        .output()
+        .expect("failed to run gts-validator");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).expect("stderr utf-8");
+    assert!(
+        stderr.contains("in setting document_kinds = [[\"[\",\"config\"]] (from --document-kind)"),
+        "stderr: {stderr}"
+    );
+}
+
+#[test]
+fn cli_verbose_reports_registry_ids() {
+    let tmp = TempDir::new().expect("temp dir");
+    let spec = tmp.path().join(".gts-spec");
+    fs::create_dir(&spec).expect("create .gts-spec");
+    fs::write(
+        spec.join("event.schema.json"),
+        r#"{"$id": "gts://gts.x.core.events.type.v1~"}"#,
+    )
+    .expect("write spec schema");
+    fs::write(
+        tmp.path().join("event.json"),
+        r#"{"$id": "gts://gts.x.core.events.type.v1~"}"#,
+    )
+    .expect("write schema");
+
+    let output = Command::new(validator_bin())
+        .args(["--verbose", "--check-duplicate-ids"])
+        .arg(tmp.path())
+        .output()
+        .expect("failed to run gts-validator");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).expect("stderr utf-8");
+    assert!(
+        stderr.contains(&format!("Loaded 1 registry ID(s) from {}", spec.display())),
+        "stderr: {stderr}"
+    );
+    assert!(stderr.contains("Scanned 1 of 1 files"), "stderr: {stderr}");
+
+    let output = Command::new(validator_bin())
+        .args([
+            "--verbose",
+            "--check-duplicate-ids",
+            "--no-default-registry",
+        ])
+        .arg(tmp.path())
+        .output()
+        .expect("failed to run gts-validator");
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).expect("stderr utf-8");
+    assert!(!stderr.contains("registry"), "stderr: {stderr}");
+}
+
+#[test]
+fn cli_directory_overrides_yield_to_flags() {
+    let tmp = TempDir::new().expect("temp dir");
+    let partners = tmp.path().join("partners");
+    fs::create_dir(&partners).expect("create partners");
+    fs::write(
+        partners.join("a.md"),
+        "`gts.partner.core.events.type.v1~`\n",
+    )
+    .expect("write markdown");
+    let override_file = partners.join(".gts-validator.toml");
+    fs::write(&override_file, "vendor = \"partner\"\n").expect("write override");
+
+    let output = Command::new(validator_bin())
+        .arg("--verbose")
+        .arg(tmp.path())
+        .output()
+        .expect("failed to run gts-validator");
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).expect("stderr utf-8");
+    assert!(
+        stderr.contains(&format!(
+            "Applied directory overrides from {}",
+            override_file.display()
+        )),
+        "stderr: {stderr}"
+    );
+
+    // --vendor pins the vendor policy; --severity still reports as warnings.
+    let output = Command::new(validator_bin())
+        .args(["--json", "--vendor", "x"])
+        .arg(tmp.path())
+        .output()
+        .expect("failed to run gts-validator");
+    assert!(!output.status.success());
+    let output = Command::new(validator_bin())
+        .args(["--json", "--vendor", "x", "--severity", "GTS001=warning"])
+        .arg(tmp.path())
+        .output()
+        .expect("failed to run gts-validator");
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).expect("stdout utf-8");
+    assert!(stdout.contains("\"DowngradedError\""), "stdout: {stdout}");
+}
+
+#[test]
+fn cli_files_from_reads_list() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let tmp = TempDir::new().expect("temp dir");
+    let good = tmp.path().join("good.md");
+    let bad = tmp.path().join("bad.md");
+    fs::write(&good, "Uses `gts.x.core.pkg.mytype.v1~`.").expect("write good");
+    fs::write(&bad, "Uses `gts.y.core.pkg.mytype.v1~`.").expect("write bad");
+    fs::write(
+        tmp.path().join("other.md"),
+        "Uses `gts.y.core.pkg.other.v1~`.",
+    )
+    .expect("write other");
+
+    let list = tmp.path().join("changed.txt");
+    fs::write(&list, format!("{}\n\n{}\n", good.display(), bad.display())).expect("write list");
+    let output = Command::new(validator_bin())
+        .args(["--json", "--vendor", "x", "--files-from"])
+        .arg(&list)
+        .output()
+        .expect("failed to run gts-validator");
+    assert!(!output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json report");
+    assert_eq!(report["scanned_files"], 2);
+    assert_eq!(
+        report["validation_errors"]
+            .as_array()
+            .expect("errors array")
+            .len(),
+        1
+    );
+
+    let mut child = Command::new(validator_bin())
+        .args(["--json", "--vendor", "x", "--files-from", "-", "-0"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to run gts-validator");
+    let mut stdin = child.stdin.take().expect("stdin");
+    stdin
+        .write_all(format!("{}\0", good.display()).as_bytes())
+        .expect("write stdin");
+    drop(stdin);
+    let output = child.wait_with_output().expect("wait for gts-validator");
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).expect("json report");
+    assert_eq!(report["scanned_files"], 1);
+
+    let conflicting = Command::new(validator_bin())
+        .arg("--files-from")
+        .arg(&list)
+        .arg(tmp.path())
+        .output()
+        .expect("failed to run gts-validator");
+    assert!(!conflicting.status.success());
+}
+
+#[test]
+fn cli_explain_prints_remediation() {
+    let output = Command::new(validator_bin())
+        .args(["explain", "gts001"])
+        .output()
+        .expect("failed to run gts-validator explain");
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).expect("stdout utf-8");
+    assert!(
+        stdout.starts_with("GTS001: Vendor mismatch"),
+        "stdout: {stdout}"
+    );
+    assert!(
+        stdout.contains("README.md#gts001-vendor-mismatch"),
+        "stdout: {stdout}"
+    );
+
+    let unknown = Command::new(validator_bin())
+        .args(["explain", "GTS999"])
+        .output()
+        .expect("failed to run gts-validator explain");
+    assert!(!unknown.status.success());
+    let stderr = String::from_utf8(unknown.stderr).expect("stderr utf-8");
+    assert!(stderr.contains("Unknown rule 'GTS999'"), "stderr: {stderr}");
+}
+
+#[test]
+fn cli_bench_corpus_writes_smoke_corpus() {
+    let tmp = TempDir::new().expect("temp dir");
+    let dir = tmp.path().join("corpus");
+
+    let output = Command::new(validator_bin())
+        .args(["bench-corpus", "--smoke"])
+        .arg(&dir)
+        .output()
+        .expect("failed to run gts-validator");
+    assert!(output.status.success());
+    assert!(dir.join("large.json").is_file());
+    assert!(dir.join("deep.yaml").is_file());
+    assert!(dir.join("markdown/00/00000.md").is_file());
+
+    let output = Command::new(validator_bin())
+        .args(["--json", "--vendor", "x"])
+        .arg(&dir)
+        .output()
+        .expect("failed to run gts-validator");
+    let stdout = String::from_utf8(output.stdout).expect("stdout utf-8");
+    assert!(
+        stdout.contains("\"scanned_files\": 502"),
+        "stdout: {stdout}"
+    );
+}
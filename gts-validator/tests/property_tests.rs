@@ -0,0 +1,59 @@
+//! Scanner property tests built on the `gts-id` ID generators: a valid GTS
+//! ID embedded in generated markdown must always be discovered in the
+//! default (spec) discovery mode, and validate cleanly.
+
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use std::fs;
+use std::path::PathBuf;
+
+use gts_id::strategies;
+use gts_validator::{CandidateOutcome, FsSourceConfig, ValidationConfig, validate_fs};
+use proptest::prelude::*;
+use tempfile::TempDir;
+
+/// A line of markdown holding `id` the way documentation writes IDs.
+fn markdown_with(id: String) -> impl Strategy<Value = String> {
+    let words = proptest::collection::vec("[a-z]{1,8}", 0..4).prop_map(|words| words.join(" "));
+    (words.clone(), words, 0..10usize).prop_map(move |(before, after, style)| {
+        let mention = match style {
+            0 => id.clone(),
+            1 => format!("`{id}`"),
+            2 => format!("**{id}**"),
+            3 => format!("({id})"),
+            4 => format!("\"{id}\""),
+            5 => format!("[{id}](https://example.com/types)"),
+            6 => format!("{id},"),
+            7 => format!("- {id}"),
+            8 => format!("| {id} | note |\n|---|---|\n| {id} | note |"),
+            _ => format!("gts://{id}"),
+        };
+        format!("# Types\n\n{before} {mention} {after}\n")
+    })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn generated_ids_in_markdown_are_discovered(
+        (id, markdown) in strategies::id().prop_flat_map(|id| (Just(id.clone()), markdown_with(id)))
+    ) {
+        let tmp = TempDir::new().unwrap();
+        fs::write(tmp.path().join("types.md"), &markdown).unwrap();
+        let mut fs_config = FsSourceConfig::default();
+        fs_config.paths = vec![PathBuf::from(tmp.path())];
+        let mut config = ValidationConfig::default();
+        config.trace_discovery = true;
+
+        let report = validate_fs(&fs_config, &config).unwrap();
+        prop_assert!(report.ok, "{:?}\n{}", report.validation_errors, markdown);
+        let trace = report.discovery_trace.unwrap();
+        let candidates: Vec<_> = trace.files.iter().flat_map(|f| &f.candidates).collect();
+        let found = candidates.iter().any(|c| {
+            c.candidate.trim_start_matches("gts://") == id
+                && !matches!(c.outcome, CandidateOutcome::Invalid { .. })
+        });
+        prop_assert!(found, "{} not discovered in:\n{}\n{:?}", id, markdown, candidates);
+    }
+}
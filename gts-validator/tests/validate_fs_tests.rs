@@ -1,9 +1,14 @@
 //! Integration tests for `gts_validator::validate_fs`.
 
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use gts_validator::{FsSourceConfig, ValidationConfig, VendorPolicy, validate_fs};
+use gts_validator::output::OutputConfig;
+use gts_validator::{
+    CoverageError, DiscoveryMode, DocumentKind, EmbeddedDocuments, FsSourceConfig, IdKind, IdRole,
+    MarkdownProfile, ScanErrorKind, StatsGrouping, StatsSegment, ValidationConfig, VendorPolicy,
+    apply_edits, rewrite_fs, rules, validate_files, validate_fs, validate_fs_detailed,
+};
 use tempfile::TempDir;
 
 fn default_validation_config() -> ValidationConfig {
@@ -135,7 +140,9 @@ fn test_validate_fs_json_output_contract() {
     gts_validator::output::write_json(&report, &mut buf).unwrap();
     let json: serde_json::Value = serde_json::from_slice(&buf).unwrap();
 
+    assert!(json.get("discovered_files").is_some());
     assert!(json.get("scanned_files").is_some());
+    assert!(json.get("skipped_files").is_some());
     assert!(json.get("failed_files").is_some());
     assert!(json.get("ok").is_some());
     assert!(json.get("validation_errors").is_some());
@@ -143,6 +150,99 @@ fn test_validate_fs_json_output_contract() {
     assert!(json["ok"].as_bool().unwrap());
 }
 
+#[test]
+fn test_validate_fs_json_help() {
+    let tmp = TempDir::new().unwrap();
+    fs::write(
+        tmp.path().join("test.md"),
+        "Uses `gts.y.core.pkg.mytype.v1~` and `gts.x-y.core.events.type.v1~`.",
+    )
+    .unwrap();
+
+    let fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+    let mut config = default_validation_config();
+    config.vendor_policy = VendorPolicy::MustMatch("x".to_owned());
+    config.discovery_mode = DiscoveryMode::Heuristic;
+    let report = validate_fs(&fs_config, &config).unwrap();
+    assert_eq!(report.validation_errors.len(), 2);
+    for error in &report.validation_errors {
+        assert_eq!(error.help, rules::remediation(error.rule_id));
+    }
+
+    let mut buf = Vec::new();
+    gts_validator::output::write_json(&report, &mut buf).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&buf).unwrap();
+    let vendor = rules::remediation(rules::VENDOR_MISMATCH).unwrap();
+    assert_eq!(
+        json["validation_errors"][0]["help"],
+        serde_json::json!({
+            "fix": vendor.fix,
+            "example": vendor.example,
+            "docs_slug": "gts001-vendor-mismatch",
+            "docs_url": vendor.docs_url,
+        })
+    );
+    let loaded =
+        gts_validator::ValidationReport::from_json_str(std::str::from_utf8(&buf).unwrap()).unwrap();
+    assert_eq!(loaded.validation_errors, report.validation_errors);
+
+    // The "To fix" hints come from the same table.
+    let mut out = Vec::new();
+    gts_validator::output::write_human(&report, &mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    for rule_id in [rules::VENDOR_MISMATCH, rules::INVALID_ID] {
+        let remediation = rules::remediation(rule_id).unwrap();
+        assert!(out.contains(remediation.fix), "{out}");
+    }
+    assert!(
+        !out.contains(rules::remediation(rules::WILDCARD_NOT_ALLOWED).unwrap().fix),
+        "{out}"
+    );
+
+    config.include_help = false;
+    let report = validate_fs(&fs_config, &config).unwrap();
+    assert!(report.validation_errors.iter().all(|e| e.help.is_none()));
+    let mut buf = Vec::new();
+    gts_validator::output::write_json(&report, &mut buf).unwrap();
+    assert!(!String::from_utf8(buf).unwrap().contains("\"help\""));
+}
+
+#[test]
+fn test_validate_fs_json_indent_options() {
+    let tmp = TempDir::new().unwrap();
+    let md = tmp.path().join("test.md");
+    fs::write(&md, "# Title\n\nUses `gts.x.core.pkg.mytype.v1~` schema.\n").unwrap();
+
+    let fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+    let report = validate_fs(&fs_config, &default_validation_config()).unwrap();
+
+    let mut pretty = Vec::new();
+    gts_validator::output::write_json(&report, &mut pretty).unwrap();
+    let mut compact = Vec::new();
+    gts_validator::output::write_json_compact(&report, &mut compact).unwrap();
+    let mut config = gts_validator::output::OutputConfig::default();
+    config.json_indent = Some(4);
+    let mut four = Vec::new();
+    gts_validator::output::write_json_with(&report, &config, &mut four).unwrap();
+    config.json_indent = None;
+    let mut none = Vec::new();
+    gts_validator::output::write_json_with(&report, &config, &mut none).unwrap();
+
+    let pretty = String::from_utf8(pretty).unwrap();
+    let four = String::from_utf8(four).unwrap();
+    assert!(pretty.contains("\n  \"ok\": true"), "{pretty}");
+    assert!(four.contains("\n    \"ok\": true"), "{four}");
+    assert_eq!(compact, none);
+    assert_eq!(String::from_utf8(compact).unwrap().lines().count(), 1);
+
+    let parsed: Vec<serde_json::Value> = [pretty.as_bytes(), four.as_bytes(), &none]
+        .iter()
+        .map(|buf| serde_json::from_slice(buf).unwrap())
+        .collect();
+    assert_eq!(parsed[0], parsed[1]);
+    assert_eq!(parsed[0], parsed[2]);
+}
+
 #[test]
 fn test_validate_fs_exclude_pattern() {
     let tmp = TempDir::new().unwrap();
@@ -210,6 +310,10 @@ fn test_write_human_success_output() {
         "missing header, got: {output}"
     );
     assert!(output.contains("Files scanned:  1"), "missing file count");
+    assert!(
+        output.contains("Files skipped:  0"),
+        "missing skipped count"
+    );
     assert!(output.contains("Errors found:   0"), "missing error count");
     assert!(
         output.contains("All 1 files passed"),
@@ -254,6 +358,42 @@ fn test_write_human_failure_output() {
     );
 }
 
+#[test]
+fn test_write_human_summary_first_puts_verdict_before_errors() {
+    let tmp = TempDir::new().unwrap();
+    let md = tmp.path().join("test.md");
+    fs::write(&md, "# Title\n\nUses `gts.y.core.pkg.mytype.v1~` schema.\n").unwrap();
+
+    let fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+    let mut config = ValidationConfig::default();
+    config.vendor_policy = VendorPolicy::MustMatch("x".to_owned());
+    let report = validate_fs(&fs_config, &config).unwrap();
+
+    let render = |summary_first: bool| {
+        let mut output_config = OutputConfig::default();
+        output_config.summary_first = summary_first;
+        let mut buf = Vec::new();
+        gts_validator::output::write_human_with(&report, &output_config, &mut buf).unwrap();
+        String::from_utf8(buf).unwrap()
+    };
+
+    let default = render(false);
+    let summary_first = render(true);
+    let position = |output: &str, needle: &str| {
+        output
+            .find(needle)
+            .unwrap_or_else(|| panic!("missing {needle:?} in: {output}"))
+    };
+
+    assert!(position(&default, "VALIDATION ERRORS") < position(&default, "invalid GTS identifier"));
+    assert!(
+        position(&summary_first, "invalid GTS identifier")
+            < position(&summary_first, "VALIDATION ERRORS")
+    );
+    assert!(position(&summary_first, "Files scanned:") < position(&summary_first, "invalid GTS"));
+    assert!(summary_first.contains("Vendor mismatch"));
+}
+
 #[test]
 fn test_validate_fs_no_matching_files_returns_ok() {
     let tmp = TempDir::new().unwrap();
@@ -296,8 +436,8 @@ fn test_validate_fs_max_file_size_produces_scan_error() {
 #[test]
 fn test_validate_fs_non_utf8_file_produces_scan_error() {
     let tmp = TempDir::new().unwrap();
-    let md = tmp.path().join("binary.md");
-    fs::write(&md, [0xFF, 0xFE, 0x00, 0x01, 0x80, 0x81]).unwrap();
+    let md = tmp.path().join("latin1.md");
+    fs::write(&md, b"Caf\xE9 gts.x.core.events.type.v1~\n").unwrap();
 
     let fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
     let report = validate_fs(&fs_config, &default_validation_config()).unwrap();
@@ -345,6 +485,331 @@ fn test_validate_fs_invalid_yaml_produces_scan_error() {
     assert!(!report.ok, "Scan errors must make the report not-ok");
 }
 
+#[test]
+fn test_validate_fs_file_accounting() {
+    let tmp = TempDir::new().unwrap();
+    fs::write(
+        tmp.path().join("ok.md"),
+        "Uses `gts.x.core.pkg.mytype.v1~`.\n",
+    )
+    .unwrap();
+    fs::write(tmp.path().join("big.md"), "x".repeat(1024)).unwrap();
+    fs::write(tmp.path().join("bad.json"), "{").unwrap();
+    // Unreadable only for non-root users; root still reads it.
+    #[cfg(unix)]
+    let unreadable_is_skipped = {
+        use std::os::unix::fs::PermissionsExt;
+        let locked = tmp.path().join("locked.md");
+        fs::write(&locked, "# Locked\n").unwrap();
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o000)).unwrap();
+        fs::read(&locked).is_err()
+    };
+    #[cfg(not(unix))]
+    let unreadable_is_skipped = false;
+
+    let mut fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+    fs_config.max_file_size = 512;
+    let report = validate_fs(&fs_config, &default_validation_config()).unwrap();
+
+    let expected_skipped = 2 + usize::from(unreadable_is_skipped);
+    assert_eq!(report.discovered_files, if cfg!(unix) { 4 } else { 3 });
+    assert_eq!(report.skipped_files, expected_skipped);
+    assert_eq!(
+        report.discovered_files,
+        report.scanned_files + report.skipped_files,
+        "{report:?}"
+    );
+    assert_eq!(report.failed_files, expected_skipped);
+    assert!(!report.ok);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_validate_fs_unreadable_directory() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let tmp = TempDir::new().unwrap();
+    fs::write(tmp.path().join("ok.md"), "# Title\n").unwrap();
+    let locked = tmp.path().join("locked");
+    fs::create_dir(&locked).unwrap();
+    fs::write(locked.join("hidden.json"), r#"{"$id": "gts.bad"}"#).unwrap();
+    fs::set_permissions(&locked, fs::Permissions::from_mode(0o000)).unwrap();
+    // Root reads the directory anyway; there is nothing to check then.
+    let unreadable = fs::read_dir(&locked).is_err();
+
+    let mut fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+    let lenient = validate_fs(&fs_config, &default_validation_config()).unwrap();
+    fs_config.fail_on_walk_errors = true;
+    let strict = validate_fs(&fs_config, &default_validation_config()).unwrap();
+    fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+    if !unreadable {
+        return;
+    }
+
+    assert!(lenient.ok, "got: {lenient:?}");
+    assert_eq!(lenient.scanned_files, 1);
+    assert_eq!(lenient.warnings.len(), 1, "got: {:?}", lenient.warnings);
+    assert_eq!(lenient.warnings[0].kind, ScanErrorKind::PermissionDenied);
+    assert_eq!(lenient.warnings[0].file, locked);
+
+    assert!(!strict.ok);
+    assert!(strict.warnings.is_empty());
+    assert_eq!(strict.scan_errors.len(), 1, "got: {:?}", strict.scan_errors);
+    assert_eq!(strict.scan_errors[0].kind, ScanErrorKind::PermissionDenied);
+    assert_eq!(strict.scan_errors[0].file, locked);
+}
+
+#[test]
+fn test_validate_fs_utf16_and_binary_files() {
+    let tmp = TempDir::new().unwrap();
+    let yaml = "type: gts.x.core.events.type.v1~\nref: gts.y.core.events.type.v1~\n";
+    let mut utf16 = vec![0xFF, 0xFE];
+    utf16.extend(yaml.encode_utf16().flat_map(u16::to_le_bytes));
+    fs::write(tmp.path().join("export.yaml"), utf16).unwrap();
+    fs::write(
+        tmp.path().join("image.json"),
+        b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR",
+    )
+    .unwrap();
+
+    let mut validation_config = default_validation_config();
+    validation_config.vendor_policy = VendorPolicy::MustMatch("x".to_owned());
+    let mut fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+    let report = validate_fs(&fs_config, &validation_config).unwrap();
+    assert!(report.ok, "got: {report:?}");
+    assert_eq!(report.scanned_files, 0);
+    assert_eq!(report.skipped_files, 2);
+    let skipped: Vec<(&str, ScanErrorKind)> = report
+        .warnings
+        .iter()
+        .map(|w| {
+            (
+                w.file.file_name().unwrap().to_str().unwrap(),
+                w.kind.clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        skipped,
+        [
+            ("export.yaml", ScanErrorKind::Utf16Encoding),
+            ("image.json", ScanErrorKind::BinaryContent),
+        ]
+    );
+
+    fs_config.transcode_utf16 = true;
+    let report = validate_fs(&fs_config, &validation_config).unwrap();
+    assert_eq!(report.scanned_files, 1);
+    assert_eq!(report.errors_count(), 1, "got: {report:?}");
+    assert_eq!(report.validation_errors[0].json_path, "$.ref");
+    assert_eq!(report.validation_errors[0].rule_id, rules::VENDOR_MISMATCH);
+    assert_eq!(report.warnings.len(), 1);
+}
+
+#[test]
+fn test_validate_fs_limit_counts_unreached_files_as_skipped() {
+    let tmp = TempDir::new().unwrap();
+    for name in ["a.md", "b.md", "c.md"] {
+        fs::write(tmp.path().join(name), "# Title\n").unwrap();
+    }
+    let mut fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+    fs_config.max_files = 1;
+    let report = validate_fs(&fs_config, &default_validation_config()).unwrap();
+
+    assert_eq!(report.discovered_files, 3);
+    assert_eq!(report.scanned_files, 1);
+    assert_eq!(report.skipped_files, 2);
+    assert!(report.truncated);
+}
+
+#[test]
+fn test_validate_fs_max_total_bytes_stops_scan() {
+    let tmp = TempDir::new().unwrap();
+    // Five 100-byte files; the cap admits two of them.
+    for i in 0..5 {
+        fs::write(tmp.path().join(format!("f{i}.md")), "x".repeat(100)).unwrap();
+    }
+    let mut fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+    fs_config.max_total_bytes = 250;
+    let report = validate_fs(&fs_config, &default_validation_config()).unwrap();
+
+    assert_eq!(report.scanned_files, 2);
+    assert_eq!(report.skipped_files, 3);
+    assert!(report.truncated);
+    assert!(!report.ok, "a truncated scan must fail");
+    assert_eq!(report.scan_errors.len(), 1);
+    let limit = &report.scan_errors[0];
+    assert_eq!(limit.kind, ScanErrorKind::LimitExceeded);
+    assert!(
+        limit.message.contains("3 file(s) were not scanned"),
+        "{}",
+        limit.message
+    );
+
+    fs_config.max_total_bytes = 500;
+    let report = validate_fs(&fs_config, &default_validation_config()).unwrap();
+    assert_eq!(report.scanned_files, 5);
+    assert!(!report.truncated);
+    assert!(report.ok);
+}
+
+#[test]
+fn test_validate_fs_sniffs_explicit_extensionless_files() {
+    let tmp = TempDir::new().unwrap();
+    let catalog = tmp.path().join("CATALOG");
+    fs::write(&catalog, "# Catalog\n\nUses `gts.y.core.pkg.mytype.v1~`.\n").unwrap();
+    let export = tmp.path().join("export");
+    fs::write(&export, r#"{"$id": "gts://gts.y.core.pkg.other.v1~"}"#).unwrap();
+
+    let mut config = ValidationConfig::default();
+    config.vendor_policy = VendorPolicy::MustMatch("x".to_owned());
+
+    // Explicit file paths are sniffed.
+    let explicit = default_fs_config(vec![catalog, export.clone()]);
+    let report = validate_fs(&explicit, &config).unwrap();
+    assert_eq!(report.scanned_files, 2, "{report:?}");
+    assert_eq!(report.errors_count(), 2);
+    let json_error = report
+        .validation_errors
+        .iter()
+        .find(|e| e.file == export)
+        .unwrap();
+    assert_eq!(json_error.json_path, "$.$id", "scanned as JSON");
+
+    // Directory walks only pick them up with `sniff_content`.
+    let walk = default_fs_config(vec![tmp.path().to_path_buf()]);
+    let report = validate_fs(&walk, &config).unwrap();
+    assert_eq!(report.discovered_files, 0);
+
+    let mut sniffing_walk = walk;
+    sniffing_walk.sniff_content = true;
+    let report = validate_fs(&sniffing_walk, &config).unwrap();
+    assert_eq!(report.scanned_files, 2);
+}
+
+#[test]
+fn test_validate_fs_gts_spec_registry() {
+    let tmp = TempDir::new().unwrap();
+    let spec = tmp.path().join(".gts-spec/schemas");
+    fs::create_dir_all(&spec).unwrap();
+    fs::write(
+        spec.join("event.schema.json"),
+        r#"{"$id": "gts://gts.x.core.events.type.v1~", "x-gts-ref": "gts.bad"}"#,
+    )
+    .unwrap();
+    fs::write(
+        spec.join("topic.schema.yaml"),
+        "$id: gts://gts.x.core.events.topic.v1~\n",
+    )
+    .unwrap();
+    let specs = tmp.path().join("specs");
+    fs::create_dir(&specs).unwrap();
+    fs::write(
+        specs.join("audit.schema.json"),
+        r#"{"$id": "gts://gts.x.core.audit.entry.v1~"}"#,
+    )
+    .unwrap();
+    fs::write(
+        tmp.path().join("copy.json"),
+        r#"{"$id": "gts://gts.x.core.events.type.v1~", "other": {"$id": "gts://gts.x.core.audit.entry.v1~"}}"#,
+    )
+    .unwrap();
+
+    let mut config = ValidationConfig::default();
+    config.check_duplicate_ids = true;
+    let mut fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+    fs_config.registry_dirs.push(specs.clone());
+    let detailed = validate_fs_detailed(&fs_config, &config).unwrap();
+    let report = &detailed.report;
+    assert_eq!(report.scanned_files, 1, "registry files are not scanned");
+    assert_eq!(report.errors_count(), 2, "got: {report:?}");
+    assert!(report.validation_errors.iter().all(
+        |e| e.rule_id == rules::DUPLICATE_DEFINITION && e.file == tmp.path().join("copy.json")
+    ));
+    let registry: Vec<(PathBuf, usize)> = report
+        .registry
+        .iter()
+        .map(|source| (source.dir.clone(), source.ids))
+        .collect();
+    assert_eq!(
+        registry,
+        [(specs, 1), (tmp.path().join(".gts-spec"), 2)],
+        "explicit registries come first"
+    );
+    assert_eq!(detailed.registry_definitions.len(), 3);
+
+    fs_config.registry_dirs.clear();
+    fs_config.default_registry = false;
+    let report = validate_fs(&fs_config, &config).unwrap();
+    assert_eq!(report.scanned_files, 2, "specs/ is scanned again");
+    assert_eq!(report.errors_count(), 1, "got: {report:?}");
+    assert!(report.registry.is_empty());
+}
+
+#[test]
+fn test_validate_fs_include_gts_spec() {
+    let tmp = TempDir::new().unwrap();
+    let spec = tmp.path().join(".gts-spec/schemas");
+    fs::create_dir_all(&spec).unwrap();
+    fs::write(
+        spec.join("event.schema.json"),
+        r#"{"$id": "gts://gts.y.core.events.type.v1~"}"#,
+    )
+    .unwrap();
+    fs::create_dir(tmp.path().join("target")).unwrap();
+    fs::write(
+        tmp.path().join("target/build.json"),
+        r#"{"$id": "gts://gts.y.core.build.type.v1~"}"#,
+    )
+    .unwrap();
+
+    let mut config = ValidationConfig::default();
+    config.vendor_policy = VendorPolicy::MustMatch("x".to_owned());
+
+    let mut fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+    let report = validate_fs(&fs_config, &config).unwrap();
+    assert_eq!(report.discovered_files, 0);
+    assert!(report.ok);
+
+    fs_config.include_gts_spec = true;
+    let report = validate_fs(&fs_config, &config).unwrap();
+    assert_eq!(report.scanned_files, 1, "only .gts-spec is included");
+    assert_eq!(report.errors_count(), 1);
+    assert_eq!(
+        report.validation_errors[0].file,
+        spec.join("event.schema.json")
+    );
+}
+
+#[test]
+fn test_validate_fs_sniffing_skips_binary_and_unknown_files() {
+    let tmp = TempDir::new().unwrap();
+    fs::write(
+        tmp.path().join("image.bin"),
+        [0x89, b'P', b'N', b'G', 0xff, 0x00],
+    )
+    .unwrap();
+    fs::write(tmp.path().join("blob"), "{\0\0\0 gts.y.core.pkg.mytype.v1~").unwrap();
+    fs::write(
+        tmp.path().join("notes.txt"),
+        "plain text gts.y.core.pkg.mytype.v1~",
+    )
+    .unwrap();
+    fs::write(tmp.path().join("ok.md"), "# Title\n").unwrap();
+
+    let mut fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+    fs_config.sniff_content = true;
+    let mut config = ValidationConfig::default();
+    config.vendor_policy = VendorPolicy::MustMatch("x".to_owned());
+    let report = validate_fs(&fs_config, &config).unwrap();
+
+    assert!(report.ok, "{report:?}");
+    assert!(report.scan_errors.is_empty(), "{:?}", report.scan_errors);
+    assert_eq!(report.discovered_files, 4);
+    assert_eq!(report.scanned_files, 1);
+    assert_eq!(report.skipped_files, 3);
+}
+
 #[test]
 fn test_validate_fs_skip_tokens_integration() {
     let tmp = TempDir::new().unwrap();
@@ -378,3 +843,1666 @@ fn test_validate_fs_skip_tokens_integration() {
         report_skip.validation_errors
     );
 }
+
+#[cfg(unix)]
+fn dir_with_broken_symlink() -> std::io::Result<(TempDir, PathBuf, PathBuf)> {
+    let tmp = TempDir::new()?;
+    fs::write(tmp.path().join("ok.md"), "# Title\n")?;
+    let target = tmp.path().join("missing.md");
+    let link = tmp.path().join("broken.md");
+    std::os::unix::fs::symlink(&target, &link)?;
+    Ok((tmp, link, target))
+}
+
+#[cfg(unix)]
+#[test]
+fn test_validate_fs_broken_symlink_skipped_by_default() {
+    let (tmp, _, _) = dir_with_broken_symlink().unwrap();
+    let fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+    let report = validate_fs(&fs_config, &default_validation_config()).unwrap();
+
+    assert!(report.ok);
+    assert_eq!(report.scanned_files, 1);
+    assert!(report.warnings.is_empty(), "got: {:?}", report.warnings);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_validate_fs_warn_broken_symlinks() {
+    for follow_links in [false, true] {
+        let (tmp, link, target) = dir_with_broken_symlink().unwrap();
+        let mut fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+        fs_config.warn_broken_symlinks = true;
+        fs_config.follow_links = follow_links;
+        let report = validate_fs(&fs_config, &default_validation_config()).unwrap();
+
+        assert!(report.ok, "warnings must not fail the run: {report:?}");
+        assert_eq!(report.scanned_files, 1);
+        assert!(
+            report.scan_errors.is_empty(),
+            "got: {:?}",
+            report.scan_errors
+        );
+        assert_eq!(report.warnings.len(), 1, "follow_links={follow_links}");
+        let warning = &report.warnings[0];
+        assert_eq!(warning.kind, ScanErrorKind::BrokenSymlink);
+        assert_eq!(warning.file, link);
+        assert!(
+            warning.message.contains(&link.display().to_string())
+                && warning.message.contains(&target.display().to_string()),
+            "got: {}",
+            warning.message
+        );
+
+        let mut out = Vec::new();
+        gts_validator::output::write_human(&report, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("WARNINGS"), "got: {out}");
+        assert!(out.contains("[warning] Broken symlink"), "got: {out}");
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn test_validate_fs_broken_symlink_respects_exclude() {
+    let (tmp, _, _) = dir_with_broken_symlink().unwrap();
+    let mut fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+    fs_config.warn_broken_symlinks = true;
+    fs_config.exclude = vec!["broken.md".to_owned()];
+    let report = validate_fs(&fs_config, &default_validation_config()).unwrap();
+
+    assert!(report.warnings.is_empty(), "got: {:?}", report.warnings);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_validate_fs_non_unicode_file_name() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let tmp = TempDir::new().unwrap();
+    fs::write(tmp.path().join("ok.md"), "# Title\n").unwrap();
+    let bad = tmp.path().join(OsStr::from_bytes(b"bad\xff.md"));
+    fs::write(&bad, "Uses gts.x-y.a.b.c.v1 here.\n").unwrap();
+
+    let fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+    let report = validate_fs(&fs_config, &default_validation_config()).unwrap();
+    assert!(report.ok, "got: {report:?}");
+    assert_eq!(report.scanned_files, 1);
+    assert_eq!(report.warnings.len(), 1, "got: {:?}", report.warnings);
+    assert_eq!(report.warnings[0].kind, ScanErrorKind::NonUnicodePath);
+    assert_eq!(report.warnings[0].file, bad);
+
+    // The report still serializes, with the invalid byte replaced.
+    let mut out = Vec::new();
+    gts_validator::output::write_json(&report, &mut out).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    let file = json["warnings"][0]["file"].as_str().unwrap();
+    assert!(file.ends_with("bad\u{fffd}.md"), "got: {file}");
+
+    // Wildcards match the invalid byte; literals never do.
+    for (exclude, excluded) in [
+        ("bad?.md", true),
+        ("*/bad*", true),
+        ("bad[!a].md", true),
+        ("bad\u{fffd}.md", false),
+    ] {
+        let mut fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+        fs_config.exclude = vec![exclude.to_owned()];
+        let report = validate_fs(&fs_config, &default_validation_config()).unwrap();
+        assert_eq!(report.warnings.is_empty(), excluded, "exclude {exclude:?}");
+    }
+}
+
+#[test]
+fn test_validate_fs_x_gts_ref_list_string_warns() {
+    let tmp = TempDir::new().unwrap();
+    fs::write(
+        tmp.path().join("refs.json"),
+        r#"{"x-gts-ref": "gts.x.core.*,gts.acme.billing.*", "other": {"x-gts-ref": ["gts.x.core.*"]}}"#,
+    )
+    .unwrap();
+
+    let fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+    let report = validate_fs(&fs_config, &default_validation_config()).unwrap();
+    assert!(report.ok, "got: {report:?}");
+    assert_eq!(report.warnings.len(), 1, "got: {:?}", report.warnings);
+    assert_eq!(report.warnings[0].kind, ScanErrorKind::RefListString);
+
+    let mut config = default_validation_config();
+    config.split_ref_lists = false;
+    let report = validate_fs(&fs_config, &config).unwrap();
+    assert!(!report.ok);
+    assert!(report.warnings.is_empty());
+    assert_eq!(report.validation_errors[0].json_path, "$.x-gts-ref");
+}
+
+#[test]
+fn test_validate_fs_pattern_keys_in_event_router() {
+    let tmp = TempDir::new().unwrap();
+    fs::write(
+        tmp.path().join("router.yaml"),
+        concat!(
+            "routes:\n",
+            "  \"gts.x.core.events.*\": handlerA\n",
+            "  gts.x.core.events.order.v1~: handlerB\n",
+            "subscriptions:\n",
+            "  \"gts.x.*\": audit\n",
+            "handlers:\n",
+            "  gts.x.core.events.order.v1~: handlerB\n",
+        ),
+    )
+    .unwrap();
+
+    let fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+    let mut config = default_validation_config();
+    config.scan_keys = true;
+    let report = validate_fs(&fs_config, &config).unwrap();
+    assert!(report.ok, "got: {report:?}");
+
+    config.pattern_key_paths.clear();
+    let report = validate_fs(&fs_config, &config).unwrap();
+    assert_eq!(report.errors_count(), 2, "got: {report:?}");
+    assert!(
+        report
+            .validation_errors
+            .iter()
+            .all(|e| e.rule_id == rules::WILDCARD_NOT_ALLOWED)
+    );
+}
+
+#[test]
+fn test_validate_fs_rule_thresholds() {
+    let tmp = TempDir::new().unwrap();
+    fs::write(
+        tmp.path().join("test.md"),
+        "Uses `gts.y.core.pkg.a.v1~` and `gts.y.core.pkg.b.v1~`.\n",
+    )
+    .unwrap();
+    let fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+    let mut validation_config = default_validation_config();
+    validation_config.vendor_policy = VendorPolicy::MustMatch("x".to_owned());
+
+    validation_config.rule_thresholds = [(rules::VENDOR_MISMATCH.to_owned(), 2)].into();
+    let report = validate_fs(&fs_config, &validation_config).unwrap();
+    assert!(report.ok, "within threshold: {report:?}");
+    assert_eq!(report.errors_count(), 2);
+    assert!(
+        report
+            .validation_errors
+            .iter()
+            .all(|e| e.rule_id == rules::VENDOR_MISMATCH)
+    );
+    assert!(report.exceeded_thresholds.is_empty());
+
+    validation_config.rule_thresholds = [(rules::VENDOR_MISMATCH.to_owned(), 1)].into();
+    let report = validate_fs(&fs_config, &validation_config).unwrap();
+    assert!(!report.ok);
+    assert_eq!(
+        report.exceeded_thresholds,
+        [(rules::VENDOR_MISMATCH.to_owned(), 2, 1)]
+    );
+
+    let mut out = Vec::new();
+    gts_validator::output::write_human(&report, &mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(
+        out.contains("WARNING: GTS001 at 2/1 threshold"),
+        "got: {out}"
+    );
+
+    let json = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["validation_errors"][0]["rule_id"], "GTS001");
+    assert_eq!(
+        json["exceeded_thresholds"][0],
+        serde_json::json!(["GTS001", 2, 1])
+    );
+}
+
+/// Two vendors: `x` owns the base event type, `acme` derives from it.
+fn write_id_stats_fixture(dir: &std::path::Path) -> std::io::Result<()> {
+    fs::write(
+        dir.join("base.schema.json"),
+        r#"{"$id": "gts://gts.x.core.events.type.v1~", "x-gts-ref": "gts.x.core.tenants.tenant.v1~"}"#,
+    )?;
+    fs::write(
+        dir.join("orders.md"),
+        "# Orders\n\n\
+         `gts.x.core.events.type.v1~acme.shop.orders.created.v1~` and\n\
+         `gts.x.core.events.type.v1~acme.shop.orders.paid.v1~` extend `gts.x.core.events.type.v1~`.\n\
+         Created again: `gts.x.core.events.type.v1~acme.shop.orders.created.v1~`.\n\
+         invalid: `gts.x.core.events.type.v1~acme.bad.example.v1~`\n",
+    )
+}
+
+#[test]
+fn test_validate_fs_id_stats_by_last_segment() {
+    let tmp = TempDir::new().unwrap();
+    write_id_stats_fixture(tmp.path()).unwrap();
+
+    let fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+    let mut config = ValidationConfig::default();
+    config.id_stats = Some(StatsSegment::LastSegment);
+    let report = validate_fs(&fs_config, &config).unwrap();
+    assert!(report.discovery_trace.is_none(), "trace is only internal");
+
+    let stats = report.id_stats.unwrap();
+    let vendors: Vec<&str> = stats.vendors.keys().map(String::as_str).collect();
+    assert_eq!(vendors, ["acme", "x"]);
+    let acme = &stats.vendors["acme"];
+    assert_eq!((acme.distinct_ids, acme.occurrences), (2, 3));
+    let x = &stats.vendors["x"];
+    assert_eq!((x.distinct_ids, x.occurrences), (2, 3));
+    assert_eq!(x.packages["core"].distinct_ids, 2);
+
+    let mut table = Vec::new();
+    stats
+        .print_table(StatsGrouping::Package, &mut table)
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(table).unwrap(),
+        "Package      IDs  Occurrences\n\
+         acme.shop      2            3\n\
+         x.core         2            3\n"
+    );
+}
+
+#[test]
+fn test_validate_fs_id_stats_by_first_segment() {
+    let tmp = TempDir::new().unwrap();
+    write_id_stats_fixture(tmp.path()).unwrap();
+
+    let fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+    let mut config = ValidationConfig::default();
+    config.id_stats = Some(StatsSegment::FirstSegment);
+    let report = validate_fs(&fs_config, &config).unwrap();
+
+    let stats = report.id_stats.unwrap();
+    let vendors: Vec<&str> = stats.vendors.keys().map(String::as_str).collect();
+    assert_eq!(vendors, ["x"]);
+    assert_eq!(stats.vendors["x"].distinct_ids, 4);
+    assert_eq!(stats.vendors["x"].occurrences, 6);
+
+    let mut table = Vec::new();
+    stats
+        .print_table(StatsGrouping::Vendor, &mut table)
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(table).unwrap(),
+        "Vendor    IDs  Occurrences\nx           4            6\n"
+    );
+}
+
+/// Only vendor `x` is allowed, so the `y` ID in `HELM_VALUES` is invalid.
+fn vendor_x_config() -> ValidationConfig {
+    let mut config = ValidationConfig::default();
+    config.vendor_policy = VendorPolicy::MustMatch("x".to_owned());
+    config
+}
+
+const HELM_VALUES: &str = "\
+{{- $vendor := .Values.vendor | default \"x\" }}
+event:
+  schema: gts.x.core.events.type.v1~
+  tenant: {{ .Values.tenant | quote }}
+  {{- if .Values.audit }}
+  audit: gts.y.core.audit.event.v1~
+  {{- end }}
+";
+
+#[test]
+fn test_validate_fs_templated_yaml_falls_back_to_line_scan() {
+    let tmp = TempDir::new().unwrap();
+    let values = tmp.path().join("values.yaml");
+    fs::write(&values, HELM_VALUES).unwrap();
+
+    let fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+    let report = validate_fs(&fs_config, &vendor_x_config()).unwrap();
+
+    assert!(report.scan_errors.is_empty(), "{:?}", report.scan_errors);
+    assert_eq!(report.scanned_files, 1);
+    assert_eq!(report.warnings.len(), 1);
+    assert_eq!(report.warnings[0].kind, ScanErrorKind::ParseFallback);
+    assert_eq!(report.warnings[0].file, values);
+
+    assert_eq!(report.errors_count(), 1, "{:?}", report.validation_errors);
+    let error = &report.validation_errors[0];
+    assert_eq!(error.raw_value, "gts.y.core.audit.event.v1~");
+    assert_eq!(error.rule_id, rules::VENDOR_MISMATCH);
+    assert_eq!(error.line, 6);
+}
+
+#[test]
+fn test_validate_fs_template_extensions_use_line_scan() {
+    let tmp = TempDir::new().unwrap();
+    for name in ["values.yaml.tpl", "values.gotmpl", "config.yaml.j2"] {
+        fs::write(tmp.path().join(name), HELM_VALUES).unwrap();
+    }
+
+    let fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+    let report = validate_fs(&fs_config, &vendor_x_config()).unwrap();
+
+    assert_eq!(report.scanned_files, 3);
+    assert!(report.warnings.is_empty(), "no parse was attempted");
+    assert_eq!(report.errors_count(), 3);
+    assert!(report.validation_errors.iter().all(|e| e.line == 6));
+}
+
+#[test]
+fn test_validate_fs_malformed_yaml_without_directives_still_fails() {
+    let tmp = TempDir::new().unwrap();
+    fs::write(
+        tmp.path().join("broken.yaml"),
+        "key: [unclosed\nother: gts.x.core.events.type.v1~\n",
+    )
+    .unwrap();
+
+    let fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+    let report = validate_fs(&fs_config, &default_validation_config()).unwrap();
+    assert!(!report.ok);
+    assert_eq!(report.scan_errors[0].kind, ScanErrorKind::YamlParseError);
+    assert!(report.warnings.is_empty());
+}
+
+#[test]
+fn test_validate_fs_detailed_reports_definitions_and_references() {
+    let tmp = TempDir::new().unwrap();
+    write_id_stats_fixture(tmp.path()).unwrap();
+
+    let fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+    let detailed = validate_fs_detailed(&fs_config, &default_validation_config()).unwrap();
+    assert_eq!(
+        detailed.report,
+        validate_fs(&fs_config, &default_validation_config()).unwrap()
+    );
+    assert_eq!(detailed.outcomes.len(), 2);
+
+    let schema = detailed
+        .outcomes
+        .iter()
+        .find(|o| o.file.ends_with("base.schema.json"))
+        .unwrap();
+    assert_eq!(schema.definitions.len(), 1);
+    assert_eq!(schema.definitions[0].id, "gts.x.core.events.type.v1~");
+    assert_eq!(schema.definitions[0].kind, IdKind::Schema);
+    assert_eq!(schema.definitions[0].json_path, "$.$id");
+    assert_eq!(schema.references.len(), 1);
+    assert_eq!(schema.references[0].id, "gts.x.core.tenants.tenant.v1~");
+
+    let docs = detailed
+        .outcomes
+        .iter()
+        .find(|o| o.file.ends_with("orders.md"))
+        .unwrap();
+    assert!(docs.definitions.is_empty());
+    assert!(docs.references.iter().all(|f| f.role == IdRole::Reference));
+    assert_eq!(docs.references.len(), 4);
+    assert_eq!((docs.references[0].line, docs.references[0].column), (3, 2));
+    assert_eq!(
+        (docs.stats.candidates, docs.stats.skipped, docs.stats.ids),
+        (5, 1, 4)
+    );
+}
+
+/// Human output already prefixes each warning with its file, so warning
+/// messages must not repeat the path.
+#[test]
+fn test_validate_fs_warning_lines_name_the_file_once() {
+    let tmp = TempDir::new().unwrap();
+    let write = |name: &str, content: &str| {
+        fs::write(tmp.path().join(name), content).unwrap();
+    };
+    write("bare.md", "See gts.x.core.events.type.v1~ for details.\n");
+    write(
+        "many.md",
+        "`gts.x.core.events.a.v1~` `gts.x.core.events.b.v1~` \
+         `gts.x.core.events.c.v1~` `gts.x.core.events.d.v1~`\n",
+    );
+    write(
+        "refs.json",
+        r#"{"x-gts-ref": "gts.x.core.*,gts.x.billing.*"}"#,
+    );
+    write("scalars.yaml", "type: ~\n");
+    write("values.yaml", HELM_VALUES);
+
+    let mut config = default_validation_config();
+    config.markdown_profile = MarkdownProfile::RequireCodeSpans;
+    config.max_ids_per_file = Some(3);
+    let report = validate_fs(&default_fs_config(vec![tmp.path().to_path_buf()]), &config).unwrap();
+
+    let mut lines: Vec<String> = report
+        .warnings
+        .iter()
+        .map(gts_validator::ScanError::format_human_readable_warning)
+        .collect();
+    lines.sort();
+    let file = |name: &str| tmp.path().join(name).display().to_string();
+    assert_eq!(
+        lines,
+        [
+            format!(
+                "{}: [warning] GTS identifier 'gts.x.core.events.type.v1~' at line 1, column 5 \
+                 is not in an inline code span, so it is not validated; wrap it in backticks",
+                file("bare.md")
+            ),
+            format!(
+                "{}: [warning] 4 GTS identifiers found, more than the limit of 3 per file; \
+                 check for a copy-paste accident, or exempt generated files",
+                file("many.md")
+            ),
+            format!(
+                "{}: [warning] $.x-gts-ref lists 2 references in one string; \
+                 use an array (x-gts-ref: [gts.x.core.*, gts.x.billing.*])",
+                file("refs.json")
+            ),
+            format!(
+                "{}: [warning] $.type is null (from `~`, `null` or an empty value), \
+                 not a string, so it is not validated; quote the value if a string was \
+                 meant (YAML 1.2 core schema)",
+                file("scalars.yaml")
+            ),
+            format!(
+                "{}: [warning] not valid YAML, scanned line by line outside template directives",
+                file("values.yaml")
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_validate_fs_max_ids_per_file() {
+    let tmp = TempDir::new().unwrap();
+    let ids: Vec<String> = (1..=4)
+        .map(|n| format!("`gts.x.core.events.type{n}.v1~`"))
+        .collect();
+    let content = format!("# Events\n\n{}\n", ids.join(", "));
+    fs::create_dir_all(tmp.path().join("docs")).unwrap();
+    fs::create_dir_all(tmp.path().join("gen")).unwrap();
+    fs::write(tmp.path().join("docs/events.md"), &content).unwrap();
+    fs::write(tmp.path().join("gen/events.md"), &content).unwrap();
+    fs::write(
+        tmp.path().join("docs/few.md"),
+        "`gts.x.core.events.type.v1~`\n",
+    )
+    .unwrap();
+
+    let mut validation_config = default_validation_config();
+    validation_config.max_ids_per_file = Some(3);
+    validation_config.max_ids_overrides = vec![("*/gen/*".to_owned(), None)];
+    let report = validate_fs(
+        &default_fs_config(vec![tmp.path().to_path_buf()]),
+        &validation_config,
+    )
+    .unwrap();
+
+    assert!(report.ok);
+    let too_many: Vec<_> = report
+        .warnings
+        .iter()
+        .filter(|w| w.kind == ScanErrorKind::TooManyIdentifiers)
+        .collect();
+    assert_eq!(too_many.len(), 1, "{:?}", report.warnings);
+    assert!(too_many[0].file.ends_with("docs/events.md"));
+    assert!(
+        too_many[0]
+            .message
+            .contains("4 GTS identifiers found, more than the limit of 3"),
+        "{}",
+        too_many[0].message
+    );
+
+    validation_config.max_ids_overrides = vec![("events.md".to_owned(), Some(4))];
+    let report = validate_fs(
+        &default_fs_config(vec![tmp.path().to_path_buf()]),
+        &validation_config,
+    )
+    .unwrap();
+    assert!(
+        report
+            .warnings
+            .iter()
+            .all(|w| w.kind != ScanErrorKind::TooManyIdentifiers)
+    );
+
+    validation_config.max_ids_overrides = vec![("[".to_owned(), None)];
+    let err = validate_fs(
+        &default_fs_config(vec![tmp.path().to_path_buf()]),
+        &validation_config,
+    )
+    .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Invalid identifier limit path pattern '['"),
+        "{err:#}"
+    );
+}
+
+#[test]
+fn test_validate_fs_duplicate_ids() {
+    let tmp = TempDir::new().unwrap();
+    let schema = r#"{"$id": "gts://gts.x.core.events.type.v1~"}"#;
+    fs::write(tmp.path().join("a.schema.json"), schema).unwrap();
+    fs::write(tmp.path().join("b.schema.json"), schema).unwrap();
+    fs::write(
+        tmp.path().join("topic.yaml"),
+        "$id: gts://gts.x.core.events.topic.v1~\n",
+    )
+    .unwrap();
+
+    let fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+    let report = validate_fs(&fs_config, &default_validation_config()).unwrap();
+    assert!(report.ok, "duplicates are only checked on request");
+
+    let mut config = ValidationConfig::default();
+    config.check_duplicate_ids = true;
+    let report = validate_fs(&fs_config, &config).unwrap();
+    assert!(!report.ok);
+    assert_eq!(report.validation_errors.len(), 1);
+    let error = &report.validation_errors[0];
+    assert_eq!(error.rule_id, rules::DUPLICATE_DEFINITION);
+    assert_eq!(error.normalized_id, "gts.x.core.events.type.v1~");
+    assert!(error.file.ends_with("b.schema.json"), "{error:?}");
+    assert!(error.error.contains("a.schema.json"), "{error:?}");
+}
+
+#[test]
+fn test_validate_fs_max_reported_errors() {
+    let tmp = TempDir::new().unwrap();
+    let ids = |n: usize| {
+        (0..n)
+            .map(|i| format!("Uses `gts.y.core.pkg.type{i}.v1~`."))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    fs::write(tmp.path().join("a.md"), ids(3)).unwrap();
+    fs::write(tmp.path().join("b.md"), ids(5)).unwrap();
+
+    let fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+    let mut config = ValidationConfig::default();
+    config.vendor_policy = VendorPolicy::MustMatch("x".to_owned());
+    let full = validate_fs(&fs_config, &config).unwrap();
+    assert_eq!(full.validation_errors.len(), 8);
+    assert!(!full.errors_truncated);
+
+    config.max_reported_errors = Some(5);
+    let report = validate_fs(&fs_config, &config).unwrap();
+    assert!(!report.ok);
+    assert!(report.errors_truncated);
+    assert_eq!(report.validation_errors.len(), 5);
+    assert_eq!(report.omitted_errors, 3);
+    assert_eq!(report.errors_count(), 8);
+    // One error per distinct value before any repeat.
+    let values: std::collections::HashSet<&str> = report
+        .validation_errors
+        .iter()
+        .map(|e| e.raw_value.as_str())
+        .collect();
+    assert_eq!(values.len(), 5, "{values:?}");
+
+    let mut out = Vec::new();
+    gts_validator::output::write_human(&report, &mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.contains("Errors found:   8"), "{out}");
+    assert!(
+        out.contains("... 3 more error(s) omitted from this report"),
+        "{out}"
+    );
+
+    let mut json = Vec::new();
+    gts_validator::output::write_json(&report, &mut json).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(json["errors_truncated"], true);
+    assert_eq!(json["omitted_errors"], 3);
+}
+
+#[test]
+fn test_validate_fs_max_errors_per_file() {
+    let tmp = TempDir::new().unwrap();
+    // A generated registry with one bad ID per entry, and a small file.
+    let entries: Vec<serde_json::Value> = (0..50)
+        .map(|i| serde_json::json!({ "$id": format!("gts://gts.y.core.pkg.type{i}.v1~") }))
+        .collect();
+    fs::write(
+        tmp.path().join("generated.json"),
+        serde_json::to_string(&entries).unwrap(),
+    )
+    .unwrap();
+    fs::write(
+        tmp.path().join("small.md"),
+        "Uses `gts.y.core.pkg.other.v1~`.",
+    )
+    .unwrap();
+
+    let fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+    let mut config = ValidationConfig::default();
+    config.vendor_policy = VendorPolicy::MustMatch("x".to_owned());
+    config.max_errors_per_file = Some(10);
+    let report = validate_fs(&fs_config, &config).unwrap();
+    assert!(!report.ok);
+    assert_eq!(report.suppressed_errors, 40);
+    assert_eq!(report.errors_count(), 51);
+    assert!(!report.errors_truncated);
+
+    let generated: Vec<_> = report
+        .validation_errors
+        .iter()
+        .filter(|e| e.file.ends_with("generated.json"))
+        .collect();
+    assert_eq!(generated.len(), 11);
+    let placeholder = generated.last().unwrap();
+    assert_eq!(placeholder.rule_id, gts_validator::rules::TOO_MANY_ERRORS);
+    assert!(
+        placeholder.error.starts_with("40 more error(s)"),
+        "{placeholder:?}"
+    );
+    assert!(
+        generated[..10]
+            .iter()
+            .all(|e| e.rule_id != gts_validator::rules::TOO_MANY_ERRORS)
+    );
+    assert_eq!(
+        report
+            .validation_errors
+            .iter()
+            .filter(|e| e.file.ends_with("small.md"))
+            .count(),
+        1
+    );
+
+    let mut out = Vec::new();
+    gts_validator::output::write_human(&report, &mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.contains("Errors found:   51"), "{out}");
+    assert!(
+        out.contains("generated.json: [suppressed] 40 more error(s) in this file suppressed"),
+        "{out}"
+    );
+
+    // The report-wide cap keeps the placeholder on top of its sample.
+    config.max_reported_errors = Some(5);
+    let report = validate_fs(&fs_config, &config).unwrap();
+    assert_eq!(report.errors_count(), 51);
+    assert_eq!(report.omitted_errors, 6);
+    assert!(
+        report
+            .validation_errors
+            .iter()
+            .any(|e| e.rule_id == gts_validator::rules::TOO_MANY_ERRORS)
+    );
+}
+
+#[test]
+fn test_validate_fs_fail_on_empty_scan() {
+    let tmp = TempDir::new().unwrap();
+    let empty = tmp.path().join("empty");
+    fs::create_dir(&empty).unwrap();
+    let other = tmp.path().join("other");
+    fs::create_dir_all(other.join("target")).unwrap();
+    fs::write(other.join("notes.txt"), "gts.x.core.events.type.v1~").unwrap();
+    fs::write(other.join("draft.md"), "# Draft").unwrap();
+    fs::write(other.join("target").join("out.md"), "# Out").unwrap();
+
+    let mut fs_config = default_fs_config(vec![empty.clone(), other.clone()]);
+    fs_config.exclude = vec!["draft.md".to_owned()];
+    let report = validate_fs(&fs_config, &default_validation_config()).unwrap();
+    assert!(report.ok, "empty scans pass by default");
+    assert!(!report.empty_scan);
+
+    let mut config = ValidationConfig::default();
+    config.fail_on_empty_scan = true;
+    let report = validate_fs(&fs_config, &config).unwrap();
+    assert!(!report.ok);
+    assert!(report.empty_scan);
+    let messages: Vec<(&PathBuf, &str)> = report
+        .scan_errors
+        .iter()
+        .filter(|e| e.kind == ScanErrorKind::EmptyScan)
+        .map(|e| (&e.file, e.message.as_str()))
+        .collect();
+    assert_eq!(messages.len(), 2, "{messages:?}");
+    assert_eq!(messages[0].0, &empty);
+    assert!(messages[0].1.ends_with(": no files"), "{messages:?}");
+    assert_eq!(messages[1].0, &other);
+    assert!(
+        messages[1].1.ends_with(
+            ": 1 file(s) without a .md/.json/.yaml/.yml or template extension; \
+             1 file(s) excluded; skipped directories: target"
+        ),
+        "{messages:?}"
+    );
+
+    // A run that scans something is unaffected.
+    fs::write(empty.join("ok.md"), "# Title").unwrap();
+    let report = validate_fs(&fs_config, &config).unwrap();
+    assert!(report.ok);
+    assert!(!report.empty_scan);
+}
+
+#[test]
+fn test_validate_fs_scanned_by_format_and_coverage() {
+    let tmp = TempDir::new().unwrap();
+    fs::write(tmp.path().join("a.md"), "# A").unwrap();
+    fs::write(tmp.path().join("b.md"), "# B").unwrap();
+    fs::write(tmp.path().join("c.json"), "{}").unwrap();
+
+    let fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+    let report = validate_fs(&fs_config, &default_validation_config()).unwrap();
+    let by_format: Vec<(&str, usize)> = report
+        .scanned_by_format
+        .iter()
+        .map(|(format, &n)| (format.as_str(), n))
+        .collect();
+    assert_eq!(by_format, [("json", 1), ("markdown", 2)]);
+
+    assert_eq!(report.verify_coverage(3, &["markdown", "json"]), Ok(()));
+    assert_eq!(
+        report.verify_coverage(0, &["yaml"]),
+        Err(CoverageError::MissingFormat {
+            format: "yaml".to_owned()
+        })
+    );
+}
+
+#[test]
+fn test_validate_fs_embedded_documents() {
+    let tmp = TempDir::new().unwrap();
+    fs::write(
+        tmp.path().join("configmap.yaml"),
+        "kind: ConfigMap\ndata:\n  config.json: '{\"type\": \"gts.x.core.events.type.v1\"}'\n",
+    )
+    .unwrap();
+    let fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+
+    let report = validate_fs(&fs_config, &default_validation_config()).unwrap();
+    assert!(report.ok, "embedded documents are not scanned by default");
+
+    let mut embedded = EmbeddedDocuments::default();
+    embedded.keys = vec!["data.*.json".to_owned()];
+    let mut config = ValidationConfig::default();
+    config.embedded_documents = Some(embedded.clone());
+    let report = validate_fs(&fs_config, &config).unwrap();
+    assert!(!report.ok);
+    assert_eq!(report.validation_errors.len(), 1);
+    assert_eq!(
+        report.validation_errors[0].json_path,
+        "$.data.config.json -> $.type"
+    );
+
+    embedded.keys = vec!["data.[".to_owned()];
+    config.embedded_documents = Some(embedded);
+    let err = validate_fs(&fs_config, &config).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Invalid embedded document key pattern 'data.['"),
+        "{err}"
+    );
+}
+
+#[test]
+fn test_validate_fs_scan_openapi() {
+    let tmp = TempDir::new().unwrap();
+    fs::write(
+        tmp.path().join("openapi.yaml"),
+        "\
+openapi: 3.0.3
+info:
+  title: Events
+  version: 1.0.0
+  x-gts-service-id: gts.x.core.svc.events.v1~
+components:
+  schemas:
+    Event:
+      $ref: gts.x.core.events
+    Audit:
+      x-gts-ref: gts.x.core.events.audit.v1
+",
+    )
+    .unwrap();
+    let fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+
+    let report = validate_fs(&fs_config, &default_validation_config()).unwrap();
+    assert_eq!(report.validation_errors.len(), 2, "{report:?}");
+
+    let mut config = default_validation_config();
+    config.scan_openapi = true;
+    let report = validate_fs(&fs_config, &config).unwrap();
+    assert_eq!(report.validation_errors.len(), 1, "{report:?}");
+    assert_eq!(
+        report.validation_errors[0].json_path,
+        "$.components.schemas.Audit.x-gts-ref"
+    );
+}
+
+#[test]
+fn test_validate_fs_document_kinds() {
+    let tmp = TempDir::new().unwrap();
+    // Each fixture has a bare $id and a gts:// type: only the schema's $id
+    // and the instance's type break a kind-specific rule.
+    fs::write(
+        tmp.path().join("schema.json"),
+        r#"{
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "$id": "gts.x.core.events.type.v1~",
+            "type": "object",
+            "properties": {"type": {"const": "gts://gts.x.core.events.type.v1~"}}
+        }"#,
+    )
+    .unwrap();
+    fs::write(
+        tmp.path().join("instance.yaml"),
+        "id: gts.x.core.events.type.v1~x.core._.created.v1\n\
+         type: gts://gts.x.core.events.type.v1~\n\
+         $id: gts.x.core.events.other.v1~\n",
+    )
+    .unwrap();
+    fs::write(
+        tmp.path().join("config.json"),
+        r#"{"$id": "gts.x.core.events.type.v1~", "type": "gts://gts.x.core.events.type.v1~"}"#,
+    )
+    .unwrap();
+    let fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+
+    let report = validate_fs(&fs_config, &default_validation_config()).unwrap();
+    assert!(
+        report.ok,
+        "kind-specific rules are off by default: {report:?}"
+    );
+
+    let mut config = default_validation_config();
+    config.check_document_kinds = true;
+    let report = validate_fs(&fs_config, &config).unwrap();
+    let mut fired = report
+        .validation_errors
+        .iter()
+        .map(|e| {
+            let file = e.file.file_name().unwrap().to_string_lossy().into_owned();
+            (file, e.rule_id, e.json_path.as_str(), e.document_kind)
+        })
+        .collect::<Vec<_>>();
+    fired.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(
+        fired,
+        [
+            (
+                "instance.yaml".to_owned(),
+                rules::INSTANCE_TYPE_NOT_BARE,
+                "$.type",
+                Some(DocumentKind::Instance)
+            ),
+            (
+                "schema.json".to_owned(),
+                rules::SCHEMA_ID_NOT_URI,
+                "$.$id",
+                Some(DocumentKind::Schema)
+            ),
+        ]
+    );
+
+    config.document_kinds = vec![
+        ("schema.json".to_owned(), DocumentKind::Config),
+        ("*/config.json".to_owned(), DocumentKind::Schema),
+    ];
+    let report = validate_fs(&fs_config, &config).unwrap();
+    let mut fired: Vec<(String, &str)> = report
+        .validation_errors
+        .iter()
+        .map(|e| {
+            let file = e.file.file_name().unwrap().to_string_lossy().into_owned();
+            (file, e.rule_id)
+        })
+        .collect();
+    fired.sort();
+    assert_eq!(
+        fired,
+        [
+            ("config.json".to_owned(), rules::SCHEMA_ID_NOT_URI),
+            ("instance.yaml".to_owned(), rules::INSTANCE_TYPE_NOT_BARE),
+        ]
+    );
+}
+
+#[test]
+fn test_validate_fs_required_dialect() {
+    let tmp = TempDir::new().unwrap();
+    fs::write(
+        tmp.path().join("matching.schema.json"),
+        r#"{"$schema": "https://json-schema.org/draft/2020-12/schema#",
+            "$id": "gts://gts.x.core.events.type.v1~", "type": "object"}"#,
+    )
+    .unwrap();
+    fs::write(
+        tmp.path().join("mismatching.schema.json"),
+        r#"{"$schema": "http://json-schema.org/draft-07/schema#",
+            "$id": "gts://gts.x.core.events.other.v1~", "type": "object"}"#,
+    )
+    .unwrap();
+    fs::write(
+        tmp.path().join("missing.schema.yaml"),
+        "$id: gts://gts.x.core.events.third.v1~\nproperties: {}\n",
+    )
+    .unwrap();
+    fs::write(
+        tmp.path().join("instance.json"),
+        r#"{"id": "gts.x.core.events.type.v1~x.core._.created.v1",
+            "type": "gts.x.core.events.type.v1~"}"#,
+    )
+    .unwrap();
+    let fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+
+    let report = validate_fs(&fs_config, &default_validation_config()).unwrap();
+    assert!(report.ok, "any dialect is accepted by default: {report:?}");
+
+    let mut config = default_validation_config();
+    config.required_dialect = Some("https://json-schema.org/draft/2020-12/schema".to_owned());
+    let report = validate_fs(&fs_config, &config).unwrap();
+    let mut fired = report
+        .validation_errors
+        .iter()
+        .map(|e| {
+            let file = e.file.file_name().unwrap().to_string_lossy().into_owned();
+            (file, e.rule_id, e.json_path.as_str(), e.raw_value.as_str())
+        })
+        .collect::<Vec<_>>();
+    fired.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(
+        fired,
+        [
+            (
+                "mismatching.schema.json".to_owned(),
+                rules::WRONG_SCHEMA_DIALECT,
+                "$.$schema",
+                "http://json-schema.org/draft-07/schema#"
+            ),
+            (
+                "missing.schema.yaml".to_owned(),
+                rules::WRONG_SCHEMA_DIALECT,
+                "$.$schema",
+                ""
+            ),
+        ]
+    );
+    assert!(report.validation_errors.iter().any(|e| {
+        e.error
+            .contains("'http://json-schema.org/draft-07/schema#'")
+    }));
+
+    config.required_dialect = Some("07".to_owned());
+    let report = validate_fs(&fs_config, &config).unwrap();
+    let mut files = report
+        .validation_errors
+        .iter()
+        .map(|e| e.file.file_name().unwrap().to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    files.sort();
+    assert_eq!(files, ["matching.schema.json", "missing.schema.yaml"]);
+}
+#[test]
+fn test_validate_fs_skip_dirs() {
+    let tmp = TempDir::new().unwrap();
+    let generated = tmp.path().join("generated");
+    fs::create_dir(&generated).unwrap();
+    fs::write(generated.join("bad.md"), "`gts.x.core.events.type.v1`").unwrap();
+
+    let fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+    let report = validate_fs(&fs_config, &default_validation_config()).unwrap();
+    assert_eq!(report.scanned_files, 1);
+    assert!(!report.ok);
+
+    let fs_config = fs_config.with_skip_dir("generated");
+    let mut config = ValidationConfig::default();
+    config.fail_on_empty_scan = true;
+    let report = validate_fs(&fs_config, &config).unwrap();
+    assert_eq!(report.scanned_files, 0);
+    assert!(
+        report.scan_errors[0]
+            .message
+            .ends_with(": no files; skipped directories: generated"),
+        "{:?}",
+        report.scan_errors
+    );
+}
+
+#[test]
+fn test_validate_fs_strips_byte_order_mark() {
+    let tmp = TempDir::new().unwrap();
+    let bom = "\u{feff}";
+    fs::write(
+        tmp.path().join("schema.json"),
+        format!("{bom}{{\"$id\": \"gts.y.core.events.type.v1~\"}}"),
+    )
+    .unwrap();
+    fs::write(
+        tmp.path().join("instance.yaml"),
+        format!("{bom}type: gts.y.core.events.type.v1~\n"),
+    )
+    .unwrap();
+    fs::write(
+        tmp.path().join("readme.md"),
+        format!("{bom}`gts.y.core.events.type.v1~`\n"),
+    )
+    .unwrap();
+    let fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+
+    let report = validate_fs(&fs_config, &default_validation_config()).unwrap();
+    assert!(report.ok, "{report:?}");
+    assert_eq!(report.scanned_files, 3);
+    assert_eq!(report.failed_files, 0);
+    assert_eq!(report.skipped_files, 0);
+
+    let mut config = ValidationConfig::default();
+    config.vendor_policy = VendorPolicy::MustMatch("x".to_owned());
+    let report = validate_fs(&fs_config, &config).unwrap();
+    assert!(report.scan_errors.is_empty(), "{:?}", report.scan_errors);
+    let mut found: Vec<(String, &str, usize)> = report
+        .validation_errors
+        .iter()
+        .map(|e| {
+            let name = e.file.file_name().unwrap().to_string_lossy().into_owned();
+            (name, e.json_path.as_str(), e.column)
+        })
+        .collect();
+    found.sort();
+    assert_eq!(
+        found,
+        [
+            ("instance.yaml".to_owned(), "$.type", 0),
+            ("readme.md".to_owned(), "", 2),
+            ("schema.json".to_owned(), "$.$id", 0),
+        ]
+    );
+}
+
+#[test]
+fn test_validate_fs_schemas_dir() {
+    let tmp = TempDir::new().unwrap();
+    let schemas = tmp.path().join("schemas");
+    let data = tmp.path().join("data");
+    fs::create_dir_all(&schemas).unwrap();
+    fs::create_dir_all(&data).unwrap();
+    fs::write(
+        schemas.join("base.schema.json"),
+        r#"{
+            "$id": "gts://gts.x.test.events.type.v1~",
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "type": "object",
+            "required": ["id", "type", "payload"],
+            "properties": {
+                "id": {"type": "string"},
+                "type": {"type": "string"},
+                "payload": {"type": "object"}
+            },
+            "x-gts-abstract": true,
+            "x-gts-generic-field": "payload"
+        }"#,
+    )
+    .unwrap();
+    fs::write(
+        schemas.join("placed.schema.json"),
+        r#"{
+            "$id": "gts://gts.x.test.events.type.v1~x.test.orders.placed.v1~",
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "allOf": [
+                {"$ref": "gts://gts.x.test.events.type.v1~"},
+                {
+                    "type": "object",
+                    "properties": {
+                        "payload": {
+                            "type": "object",
+                            "required": ["order_id"],
+                            "properties": {
+                                "order_id": {"type": "string"},
+                                "items": {"type": "array", "items": {"type": "integer"}}
+                            }
+                        }
+                    }
+                }
+            ]
+        }"#,
+    )
+    .unwrap();
+    fs::write(
+        schemas.join("broken.schema.json"),
+        r#"{
+            "$id": "gts://gts.x.test.events.type.v1~x.test.orders.broken.v1~",
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "allOf": [{"$ref": "gts://gts.x.test.events.missing.v1~"}]
+        }"#,
+    )
+    .unwrap();
+    let placed = "gts.x.test.events.type.v1~x.test.orders.placed.v1~";
+    fs::write(
+        data.join("good.json"),
+        format!(
+            r#"{{"id": "o1", "type": "{placed}", "payload": {{"order_id": "a", "items": [1]}}}}"#
+        ),
+    )
+    .unwrap();
+    fs::write(
+        data.join("bad.yaml"),
+        format!("id: o2\ntype: {placed}\npayload:\n  items: [1, two]\n"),
+    )
+    .unwrap();
+    fs::write(
+        data.join("unknown.json"),
+        r#"{"id": "o3", "type": "gts.x.test.events.type.v1~x.test.orders.gone.v1~"}"#,
+    )
+    .unwrap();
+    let fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+
+    let report = validate_fs(&fs_config, &default_validation_config()).unwrap();
+    assert!(report.ok, "schemas are not checked by default: {report:?}");
+
+    let mut config = default_validation_config();
+    config.schemas_dir = Some(schemas);
+    let report = validate_fs(&fs_config, &config).unwrap();
+    let mut fired = report
+        .validation_errors
+        .iter()
+        .map(|e| {
+            let file = e.file.file_name().unwrap().to_string_lossy().into_owned();
+            (file, e.rule_id, e.json_path.clone())
+        })
+        .collect::<Vec<_>>();
+    fired.sort();
+    assert_eq!(
+        fired,
+        [
+            (
+                "bad.yaml".to_owned(),
+                rules::SCHEMA_VIOLATION,
+                "$.payload".to_owned()
+            ),
+            (
+                "bad.yaml".to_owned(),
+                rules::SCHEMA_VIOLATION,
+                "$.payload.items[1]".to_owned()
+            ),
+            (
+                "broken.schema.json".to_owned(),
+                rules::INVALID_SCHEMA,
+                "$".to_owned()
+            ),
+            (
+                "broken.schema.json".to_owned(),
+                rules::BUNDLE_NOT_SELF_CONTAINED,
+                "$.allOf[0].$ref".to_owned()
+            ),
+            (
+                "unknown.json".to_owned(),
+                rules::SCHEMA_VIOLATION,
+                "$.type".to_owned()
+            ),
+        ],
+        "{:#?}",
+        report.validation_errors
+    );
+    assert!(!report.ok);
+
+    config.schemas_dir = Some(tmp.path().join("missing"));
+    let err = validate_fs(&fs_config, &config).unwrap_err();
+    assert!(err.to_string().contains("Schemas directory does not exist"));
+}
+
+#[test]
+fn test_validate_fs_schemas_dir_bundle_findings() {
+    let tmp = TempDir::new().unwrap();
+    let schemas = tmp.path().join("schemas");
+    fs::create_dir_all(schemas.join("copy")).unwrap();
+    let schema = |id: &str, draft: &str, reference: &str| {
+        format!(
+            r#"{{"$id": "gts://{id}", "$schema": "{draft}", "type": "object",
+                "properties": {{"next": {{"$ref": "gts://{reference}"}}}}}}"#
+        )
+    };
+    let draft_07 = "http://json-schema.org/draft-07/schema#";
+    let (a, b) = ("gts.x.test.bundle.a.v1~", "gts.x.test.bundle.b.v1~");
+    fs::write(schemas.join("a.schema.json"), schema(a, draft_07, b)).unwrap();
+    fs::write(schemas.join("b.schema.json"), schema(b, draft_07, a)).unwrap();
+    fs::write(
+        schemas.join("copy/b.schema.json"),
+        schema(b, "https://json-schema.org/draft/2020-12/schema", a),
+    )
+    .unwrap();
+    let fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+
+    let mut config = default_validation_config();
+    config.schemas_dir = Some(schemas);
+    let report = validate_fs(&fs_config, &config).unwrap();
+    let mut bundle = report
+        .validation_errors
+        .iter()
+        .filter(|e| e.rule_id == rules::BUNDLE_NOT_SELF_CONTAINED)
+        .map(|e| {
+            let file = e.file.strip_prefix(tmp.path()).unwrap();
+            (
+                file.to_string_lossy().replace('\\', "/"),
+                e.json_path.clone(),
+            )
+        })
+        .collect::<Vec<_>>();
+    bundle.sort();
+    assert_eq!(
+        bundle,
+        [
+            ("schemas/a.schema.json".to_owned(), "$".to_owned()),
+            ("schemas/copy/b.schema.json".to_owned(), "$.$id".to_owned()),
+            (
+                "schemas/copy/b.schema.json".to_owned(),
+                "$.$schema".to_owned()
+            ),
+        ],
+        "{:#?}",
+        report.validation_errors
+    );
+    assert!(!report.ok);
+}
+
+#[test]
+fn test_validate_fs_redacts_sensitive_paths() {
+    let tmp = TempDir::new().unwrap();
+    let tenant = tmp.path().join("data").join("tenant-acme");
+    let public = tmp.path().join("public");
+    fs::create_dir_all(&tenant).unwrap();
+    fs::create_dir_all(&public).unwrap();
+    // The same $id in both files, so the duplicate-definition message
+    // quotes the other file's path.
+    let document = r#"{"$id": "gts://gts.x.core.events.type.v1~", "ref": "gts.y.core.bad.v1~"}"#;
+    fs::write(tenant.join("config.json"), document).unwrap();
+    fs::write(public.join("config.json"), document).unwrap();
+    let fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+    let mut config = default_validation_config();
+    config.vendor_policy = VendorPolicy::MustMatch("x".to_owned());
+    config.check_duplicate_ids = true;
+    config.sensitive_paths = vec!["data/tenant-*/**".to_owned()];
+
+    let report = validate_fs(&fs_config, &config).unwrap();
+    let tenant_file = tenant.join("config.json");
+    let redacted: Vec<_> = report
+        .validation_errors
+        .iter()
+        .filter(|e| e.file == Path::new("<redacted>"))
+        .collect();
+    assert!(!redacted.is_empty(), "{report:?}");
+    assert!(
+        redacted
+            .iter()
+            .all(|e| e.original_file.as_deref() == Some(tenant_file.as_path()))
+    );
+    assert!(
+        report
+            .validation_errors
+            .iter()
+            .any(|e| e.file == public.join("config.json") && e.original_file.is_none())
+    );
+
+    let mut json = Vec::new();
+    gts_validator::output::write_json(&report, &mut json).unwrap();
+    let mut human = Vec::new();
+    gts_validator::output::write_human(&report, &mut human).unwrap();
+    for output in [
+        String::from_utf8(json).unwrap(),
+        String::from_utf8(human).unwrap(),
+    ] {
+        assert!(!output.contains("tenant-acme"), "{output}");
+        assert!(output.contains("<redacted>"), "{output}");
+        assert!(output.contains("public"), "{output}");
+    }
+
+    let mut config = default_validation_config();
+    config.vendor_policy = VendorPolicy::MustMatch("x".to_owned());
+    config.sensitive_paths = vec!["tenant-*/**".to_owned()];
+    config.redacted_path_label = "[tenant]".to_owned();
+    let report = validate_fs(&fs_config, &config).unwrap();
+    assert!(
+        report
+            .validation_errors
+            .iter()
+            .any(|e| e.file == Path::new("[tenant]"))
+    );
+
+    config.sensitive_paths = vec!["[".to_owned()];
+    let err = validate_fs(&fs_config, &config).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Invalid sensitive path pattern '['")
+    );
+}
+
+#[test]
+fn test_validate_fs_fix_edits_apply_cleanly() {
+    let tmp = TempDir::new().unwrap();
+    fs::write(
+        tmp.path().join("doc.md"),
+        "# \u{c9}v\u{e9}nements \u{2713}\n\
+         Le type `gts.x.core.events.user-created.v1~` \u{2014} puis \u{ab} gts.x-y.core.events.type.v1 \u{bb}.\n\
+         | Nom | Type |\n\
+         |-----|------|\n\
+         | \u{e9} | `gts://gts.x.core.events.type.v1` |\n",
+    )
+    .unwrap();
+    fs::write(
+        tmp.path().join("values.tpl"),
+        "kind: {{ .Values.nom | default \"h\u{e9}\" }} gts.x.core.events.order-placed.v1~\n",
+    )
+    .unwrap();
+    let fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+    // Hyphens and upper case are only discovered by the heuristic regex.
+    let mut config = default_validation_config();
+    config.discovery_mode = DiscoveryMode::Heuristic;
+
+    let report = validate_fs(&fs_config, &config).unwrap();
+    assert_eq!(report.errors_count(), 4, "{:#?}", report.validation_errors);
+    assert!(report.validation_errors.iter().all(|e| e.fix.is_some()));
+    let mut json = Vec::new();
+    gts_validator::output::write_json(&report, &mut json).unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(
+        json["validation_errors"][0]["fix"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .collect::<Vec<_>>(),
+        [
+            "end_col",
+            "end_line",
+            "replacement",
+            "start_col",
+            "start_line"
+        ]
+    );
+
+    for file in ["doc.md", "values.tpl"] {
+        let path = tmp.path().join(file);
+        let edits: Vec<_> = report
+            .validation_errors
+            .iter()
+            .filter(|e| e.file == path)
+            .filter_map(|e| e.fix.clone())
+            .collect();
+        let content = fs::read_to_string(&path).unwrap();
+        fs::write(&path, apply_edits(&content, &edits).unwrap()).unwrap();
+    }
+    assert!(
+        fs::read_to_string(tmp.path().join("doc.md"))
+            .unwrap()
+            .contains("| \u{e9} | `gts://gts.x.core.events.type.v1~` |")
+    );
+
+    let report = validate_fs(&fs_config, &config).unwrap();
+    assert_eq!(report.errors_count(), 0, "{:#?}", report.validation_errors);
+}
+
+#[cfg(feature = "lsp-types")]
+#[test]
+fn test_validate_fs_lsp_diagnostics_ranges() {
+    use lsp_types::{DiagnosticSeverity, NumberOrString, Position, Range};
+
+    let tmp = TempDir::new().unwrap();
+    fs::write(
+        tmp.path().join("doc.md"),
+        "# Events\n\nUse `gts.x-y.core.events.type.v1~` here.\n",
+    )
+    .unwrap();
+    fs::write(tmp.path().join("bad.json"), "{").unwrap();
+    let fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+    let mut config = default_validation_config();
+    config.discovery_mode = DiscoveryMode::Heuristic;
+
+    let report = validate_fs(&fs_config, &config).unwrap();
+    let diagnostics = report.diagnostics_by_file();
+    assert_eq!(diagnostics.len(), 2, "{diagnostics:#?}");
+
+    let doc = &diagnostics[&tmp.path().join("doc.md")];
+    assert_eq!(doc.len(), 1, "{doc:#?}");
+    let id_range = Range::new(Position::new(2, 5), Position::new(2, 33));
+    assert_eq!(doc[0].range, id_range);
+    assert_eq!(doc[0].severity, Some(DiagnosticSeverity::ERROR));
+    assert_eq!(
+        doc[0].code,
+        Some(NumberOrString::String(rules::INVALID_ID.to_owned()))
+    );
+    let fix = &doc[0].data.as_ref().unwrap()["fix"];
+    assert_eq!(fix["newText"], "gts.x_y.core.events.type.v1~");
+    assert_eq!(fix["range"], serde_json::to_value(id_range).unwrap());
+    assert_eq!(
+        doc[0].related_information.as_ref().unwrap()[0]
+            .location
+            .range,
+        id_range
+    );
+
+    let bad = &diagnostics[&tmp.path().join("bad.json")];
+    assert_eq!(bad.len(), 1, "{bad:#?}");
+    assert_eq!(bad[0].range, Range::default());
+    assert_eq!(
+        bad[0].code,
+        Some(NumberOrString::String("JsonParseError".to_owned()))
+    );
+}
+
+#[test]
+fn test_rewrite_fs_markdown_json_yaml() {
+    let tmp = TempDir::new().unwrap();
+    let v1 = "gts.x.core.events.type.v1~";
+    let v2 = "gts.x.core.events.type.v2~";
+    let markdown = format!(
+        "Use `{v1}` or `gts://{v1}x.app.orders.placed.v1~`, twice: `{v1}` `{v1}`.\n\
+         Not `gts.x.core.events.type.v10~` nor `gts.x.core.events.type.v1.1~`.\n\
+         \n```ebnf\nid = \"{v1}\"\n```\n"
+    );
+    let json = format!(
+        "{{\n  \"$id\": \"gts://{v1}x.app.orders.placed.v1~\",\n  \"type\": \"{v1}\",\n  \
+         \"description\": \"see {v1}\"\n}}\n"
+    );
+    let yaml = format!("type: {v1}\nrefs:\n  - 'gts://{v1}'\n  - {v1}x.app.a.v1~ # chained\n");
+    fs::write(tmp.path().join("doc.md"), &markdown).unwrap();
+    fs::write(tmp.path().join("schema.json"), &json).unwrap();
+    fs::write(tmp.path().join("config.yaml"), &yaml).unwrap();
+    fs::create_dir(tmp.path().join("excluded")).unwrap();
+    fs::write(tmp.path().join("excluded/doc.md"), &markdown).unwrap();
+    let mut fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+    fs_config.exclude = vec!["*/excluded/*".to_owned()];
+
+    let dry = rewrite_fs(&fs_config, &default_validation_config(), v1, v2, true).unwrap();
+    assert!(!dry.applied);
+    assert_eq!(dry.occurrences(), 9);
+    assert!(dry.unlocated.is_empty(), "{:?}", dry.unlocated);
+    assert_eq!(
+        dry.files_by_format().into_iter().collect::<Vec<_>>(),
+        [("json", 1), ("markdown", 1), ("yaml", 1)]
+    );
+    let json_diff = &dry
+        .files
+        .iter()
+        .find(|file| file.file.ends_with("schema.json"))
+        .unwrap()
+        .diff;
+    assert!(
+        json_diff.contains(&format!(
+            "@@ -3 +3 @@\n-  \"type\": \"{v1}\",\n+  \"type\": \"{v2}\",\n"
+        )),
+        "{json_diff}"
+    );
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("doc.md")).unwrap(),
+        markdown,
+        "a dry run writes nothing"
+    );
+
+    let report = rewrite_fs(&fs_config, &default_validation_config(), v1, v2, false).unwrap();
+    assert!(report.applied);
+    assert_eq!(report.occurrences(), 9);
+    let rewritten = |name: &str| fs::read_to_string(tmp.path().join(name)).unwrap();
+    assert_eq!(
+        rewritten("doc.md"),
+        markdown
+            .replacen(v1, v2, 4)
+            .replace(&format!("gts://{v1}"), &format!("gts://{v2}"))
+    );
+    assert!(rewritten("doc.md").contains(&format!("id = \"{v1}\"")));
+    assert_eq!(
+        rewritten("schema.json"),
+        json.replacen(v1, v2, 2),
+        "prose in a description is not an identifier"
+    );
+    assert_eq!(rewritten("config.yaml"), yaml.replace(v1, v2));
+    assert_eq!(rewritten("excluded/doc.md"), markdown);
+    assert_eq!(
+        rewrite_fs(&fs_config, &default_validation_config(), v1, v2, false)
+            .unwrap()
+            .occurrences(),
+        0
+    );
+}
+
+#[test]
+fn test_rewrite_fs_rejects_mismatched_ids() {
+    let tmp = TempDir::new().unwrap();
+    let fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+    let config = default_validation_config();
+    let err = rewrite_fs(
+        &fs_config,
+        &config,
+        "gts.x.a.b.c.v1~",
+        "gts.x.a.b.c.v1~x.a.b.d.v1",
+        true,
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(err.contains("must both be type IDs"), "{err}");
+    let err = rewrite_fs(&fs_config, &config, "gts.x.a.b.*", "gts.x.a.b.c.v1~", true)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("is not a GTS identifier"), "{err}");
+}
+
+#[test]
+fn test_validate_files_matches_validate_fs() {
+    let tmp = TempDir::new().unwrap();
+    let docs = tmp.path().join("docs");
+    fs::create_dir_all(docs.join("gen")).unwrap();
+    fs::write(docs.join("a.md"), "Uses `gts.y.core.pkg.a.v1~`.").unwrap();
+    fs::write(
+        docs.join("b.json"),
+        r#"{"$id": "gts://gts.x.core.pkg.b.v1~", "ref": "gts.y.core.pkg.c.v1~"}"#,
+    )
+    .unwrap();
+    fs::write(docs.join("c.yaml"), "type: gts.x.core.pkg.c.v1~\n").unwrap();
+    fs::write(docs.join("broken.json"), "{").unwrap();
+    fs::write(
+        docs.join("gen").join("d.md"),
+        "Uses `gts.y.core.pkg.d.v1~`.",
+    )
+    .unwrap();
+    fs::write(docs.join("notes.txt"), "gts.y.core.pkg.e.v1~").unwrap();
+
+    let mut fs_config = default_fs_config(vec![docs.clone()]);
+    fs_config.exclude = vec!["*/gen/*".to_owned()];
+    let mut config = default_validation_config();
+    config.vendor_policy = VendorPolicy::MustMatch("x".to_owned());
+    let walked = validate_fs(&fs_config, &config).unwrap();
+    assert_eq!(walked.discovered_files, 4);
+
+    // Every file, in any order and with duplicates: the excluded and
+    // unsupported ones are dropped as a walk would.
+    let mut listed: Vec<PathBuf> = fs::read_dir(&docs)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.is_file())
+        .collect();
+    listed.reverse();
+    listed.push(docs.join("gen").join("d.md"));
+    listed.push(docs.join("a.md"));
+    fs_config.paths.clear();
+    let from_list = validate_files(&listed, &fs_config, &config).unwrap();
+    assert_eq!(from_list, walked);
+
+    let changed = [docs.join("a.md"), docs.join("missing.md")];
+    let report = validate_files(&changed, &fs_config, &config).unwrap();
+    assert_eq!(report.scanned_files, 1);
+    assert_eq!(report.validation_errors.len(), 1);
+    assert_eq!(report.scan_errors.len(), 1);
+    assert_eq!(report.scan_errors[0].kind, ScanErrorKind::IoError);
+    assert!(report.scan_errors[0].file.ends_with("missing.md"));
+    assert!(!report.ok);
+}
+
+#[test]
+fn test_validate_fs_directory_overrides() {
+    let tmp = TempDir::new().unwrap();
+    let id = "`gts.partner.core.events.type.v1~`\n";
+    for dir in ["docs", "partners/legacy"] {
+        fs::create_dir_all(tmp.path().join(dir)).unwrap();
+    }
+    fs::write(tmp.path().join("docs/a.md"), id).unwrap();
+    fs::write(tmp.path().join("partners/a.md"), id).unwrap();
+    fs::write(tmp.path().join("partners/legacy/a.md"), id).unwrap();
+    fs::write(
+        tmp.path().join("partners/.gts-validator.toml"),
+        "vendor = \"partner\"\n",
+    )
+    .unwrap();
+    fs::write(
+        tmp.path().join("partners/legacy/.gts-validator.toml"),
+        "vendor = [\"x\"]\nseverity = { GTS001 = \"warning\" }\n",
+    )
+    .unwrap();
+
+    let fs_config = default_fs_config(vec![tmp.path().to_path_buf()]);
+    let mut validation_config = default_validation_config();
+    validation_config.vendor_policy = VendorPolicy::MustMatch("x".to_owned());
+    let report = validate_fs(&fs_config, &validation_config).unwrap();
+
+    let files: Vec<_> = report.validation_errors.iter().map(|e| &e.file).collect();
+    assert_eq!(files.len(), 1, "{:?}", report.validation_errors);
+    assert!(files[0].ends_with("docs/a.md"));
+    let downgraded: Vec<_> = report
+        .warnings
+        .iter()
+        .filter(|w| w.kind == ScanErrorKind::DowngradedError)
+        .collect();
+    assert_eq!(downgraded.len(), 1, "{:?}", report.warnings);
+    assert!(downgraded[0].file.ends_with("partners/legacy/a.md"));
+    assert!(
+        downgraded[0]
+            .message
+            .contains("GTS001 reported as a warning")
+    );
+    assert_eq!(
+        report.applied_overrides,
+        [
+            tmp.path().join("partners/.gts-validator.toml"),
+            tmp.path().join("partners/legacy/.gts-validator.toml"),
+        ]
+    );
+
+    // A pinned vendor policy holds in every directory.
+    validation_config.pinned_settings = vec!["vendor_policy".to_owned()];
+    let report = validate_fs(&fs_config, &validation_config).unwrap();
+    assert_eq!(report.validation_errors.len(), 2);
+    assert_eq!(report.warnings.len(), 1);
+
+    let mut fs_config = fs_config;
+    fs_config.directory_overrides = false;
+    let report = validate_fs(&fs_config, &validation_config).unwrap();
+    assert_eq!(report.validation_errors.len(), 3);
+    assert!(report.applied_overrides.is_empty());
+}
+
+#[test]
+fn test_validate_fs_invalid_directory_override() {
+    let tmp = TempDir::new().unwrap();
+    fs::write(tmp.path().join("a.md"), "`gts.x.core.events.type.v1~`\n").unwrap();
+    fs::write(tmp.path().join(".gts-validator.toml"), "vendors = \"x\"\n").unwrap();
+
+    let error = validate_fs(
+        &default_fs_config(vec![tmp.path().to_path_buf()]),
+        &default_validation_config(),
+    )
+    .unwrap_err();
+    assert!(
+        format!("{error:#}").contains("unknown field `vendors`"),
+        "{error:#}"
+    );
+    assert!(error.to_string().contains(".gts-validator.toml"), "{error}");
+}
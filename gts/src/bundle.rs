@@ -0,0 +1,449 @@
+//! Offline verification of a schema bundle.
+//!
+//! [`GtsStore::verify_bundle`](crate::GtsStore::verify_bundle) checks that a
+//! directory of schemas is self-contained before it is published: every
+//! `gts://` `$ref` resolves to a schema in the directory, no `$id` is defined
+//! twice, the `$ref` graph has no cycles, and every schema declares the
+//! same `$schema` dialect. The files are only read; nothing is registered or
+//! fetched.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+use thiserror::Error;
+use walkdir::WalkDir;
+
+use crate::gts::GTS_ID_URI_PREFIX;
+use crate::schema_refs::{RefKind, classify_ref};
+
+#[derive(Debug, Error)]
+pub enum BundleError {
+    #[error("Failed to read '{}': {source}", path.display())]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Failed to parse '{}' as JSON: {source}", path.display())]
+    Json {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+}
+
+/// A reason a bundle is not self-contained.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BundleFinding {
+    /// A `gts://` `$ref` names a type that no schema in the bundle defines.
+    MissingRef {
+        /// The schema file holding the `$ref`.
+        file: PathBuf,
+        /// JSON Pointer of the `$ref` within `file`.
+        pointer: String,
+        /// The referenced type ID, without `gts://` or a fragment.
+        target: String,
+    },
+    /// Two files define the same `$id`.
+    DuplicateId {
+        id: String,
+        first: PathBuf,
+        second: PathBuf,
+    },
+    /// The `$ref` graph has a cycle. `path` lists the type IDs along it and
+    /// ends with the ID it starts with.
+    Cycle { path: Vec<String> },
+    /// A schema's `$schema` is missing or differs from the dialect most of
+    /// the bundle declares.
+    DraftMismatch {
+        file: PathBuf,
+        found: Option<String>,
+        expected: String,
+    },
+}
+
+impl fmt::Display for BundleFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingRef {
+                file,
+                pointer,
+                target,
+            } => write!(
+                f,
+                "{}: $ref at '{pointer}' to '{target}' does not resolve within the bundle",
+                file.display()
+            ),
+            Self::DuplicateId { id, first, second } => write!(
+                f,
+                "'{id}' is defined by both {} and {}",
+                first.display(),
+                second.display()
+            ),
+            Self::Cycle { path } => write!(f, "$ref cycle: {}", path.join(" -> ")),
+            Self::DraftMismatch {
+                file,
+                found: Some(found),
+                expected,
+            } => write!(
+                f,
+                "{}: $schema '{found}' differs from the bundle's '{expected}'",
+                file.display()
+            ),
+            Self::DraftMismatch {
+                file,
+                found: None,
+                expected,
+            } => write!(
+                f,
+                "{}: missing $schema; the bundle uses '{expected}'",
+                file.display()
+            ),
+        }
+    }
+}
+
+/// The result of [`GtsStore::verify_bundle`](crate::GtsStore::verify_bundle).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BundleReport {
+    /// Number of schema files found.
+    pub schemas: usize,
+    /// Findings, grouped by kind in the order of [`BundleFinding`]'s
+    /// variants, each group sorted by file or ID.
+    pub findings: Vec<BundleFinding>,
+}
+
+impl BundleReport {
+    /// Whether the bundle has no findings.
+    #[must_use]
+    pub fn is_self_contained(&self) -> bool {
+        self.findings.is_empty()
+    }
+}
+
+/// A schema file of the bundle.
+struct BundleSchema {
+    id: String,
+    file: PathBuf,
+    schema: Value,
+}
+
+/// Verify every `*.json` file under `dir` whose `$id` is a `gts://` URI.
+pub(crate) fn verify_dir(dir: &Path) -> Result<BundleReport, BundleError> {
+    let schemas = read_schemas(dir)?;
+    let mut findings = Vec::new();
+
+    let mut defined: BTreeMap<&str, &Path> = BTreeMap::new();
+    let mut duplicates = Vec::new();
+    for schema in &schemas {
+        if let Some(first) = defined.insert(&schema.id, &schema.file) {
+            defined.insert(&schema.id, first);
+            duplicates.push(BundleFinding::DuplicateId {
+                id: schema.id.clone(),
+                first: first.to_owned(),
+                second: schema.file.clone(),
+            });
+        }
+    }
+
+    let mut graph: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    for schema in &schemas {
+        let edges = graph.entry(&schema.id).or_default();
+        let mut refs = Vec::new();
+        collect_refs(&schema.schema, &mut String::new(), &mut refs);
+        for (pointer, target) in refs {
+            match defined.get_key_value(target) {
+                Some((&target, _)) => {
+                    edges.insert(target);
+                }
+                None => findings.push(BundleFinding::MissingRef {
+                    file: schema.file.clone(),
+                    pointer,
+                    target: target.to_owned(),
+                }),
+            }
+        }
+    }
+    findings.extend(duplicates);
+    findings.extend(
+        find_cycles(&graph)
+            .into_iter()
+            .map(|path| BundleFinding::Cycle { path }),
+    );
+    findings.extend(draft_mismatches(&schemas));
+
+    Ok(BundleReport {
+        schemas: schemas.len(),
+        findings,
+    })
+}
+
+/// The schemas under `dir`, sorted by path.
+fn read_schemas(dir: &Path) -> Result<Vec<BundleSchema>, BundleError> {
+    let mut schemas = Vec::new();
+    for entry in WalkDir::new(dir).sort_by_file_name() {
+        let entry = entry.map_err(|e| BundleError::Io {
+            path: e
+                .path()
+                .map_or_else(|| dir.to_path_buf(), Path::to_path_buf),
+            source: e.into(),
+        })?;
+        let path = entry.path();
+        if !entry.file_type().is_file() || path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        let content = std::fs::read_to_string(path).map_err(|source| BundleError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let schema: Value = serde_json::from_str(&content).map_err(|source| BundleError::Json {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let Some(id) = schema
+            .get("$id")
+            .and_then(Value::as_str)
+            .and_then(|id| id.strip_prefix(GTS_ID_URI_PREFIX))
+        else {
+            continue;
+        };
+        schemas.push(BundleSchema {
+            id: id.to_owned(),
+            file: path.to_path_buf(),
+            schema,
+        });
+    }
+    Ok(schemas)
+}
+
+/// Collect the `(JSON Pointer, type ID)` of every external `$ref` in
+/// `value`. Malformed refs are left to schema validation, and data-valued
+/// keywords are skipped as in [`crate::extract_gts_refs`].
+fn collect_refs<'a>(value: &'a Value, pointer: &mut String, out: &mut Vec<(String, &'a str)>) {
+    match value {
+        Value::Object(map) => {
+            for (key, v) in map {
+                let len = pointer.len();
+                pointer.push('/');
+                pointer.push_str(&key.replace('~', "~0").replace('/', "~1"));
+                if key == "$ref" {
+                    if let Some(Ok(RefKind::External { id })) = v.as_str().map(classify_ref) {
+                        out.push((pointer.clone(), id));
+                    }
+                } else if !matches!(key.as_str(), "const" | "default" | "examples" | "enum") {
+                    collect_refs(v, pointer, out);
+                }
+                pointer.truncate(len);
+            }
+        }
+        Value::Array(items) => {
+            for (index, v) in items.iter().enumerate() {
+                let len = pointer.len();
+                pointer.push('/');
+                pointer.push_str(&index.to_string());
+                collect_refs(v, pointer, out);
+                pointer.truncate(len);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Every cycle reached by a depth-first walk of `graph`, one per back edge.
+fn find_cycles(graph: &BTreeMap<&str, BTreeSet<&str>>) -> Vec<Vec<String>> {
+    /// `false` while the node is on the walk's stack, `true` once finished.
+    fn visit<'a>(
+        node: &'a str,
+        graph: &BTreeMap<&'a str, BTreeSet<&'a str>>,
+        done: &mut BTreeMap<&'a str, bool>,
+        stack: &mut Vec<&'a str>,
+        cycles: &mut Vec<Vec<String>>,
+    ) {
+        done.insert(node, false);
+        stack.push(node);
+        for &next in graph.get(node).into_iter().flatten() {
+            match done.get(next) {
+                Some(false) => {
+                    let start = stack.iter().rposition(|&n| n == next).unwrap_or(0);
+                    let mut path: Vec<String> =
+                        stack[start..].iter().map(|&n| n.to_owned()).collect();
+                    path.push(next.to_owned());
+                    cycles.push(path);
+                }
+                Some(true) => {}
+                None => visit(next, graph, done, stack, cycles),
+            }
+        }
+        stack.pop();
+        done.insert(node, true);
+    }
+
+    let mut done = BTreeMap::new();
+    let mut cycles = Vec::new();
+    for &node in graph.keys() {
+        if !done.contains_key(node) {
+            visit(node, graph, &mut done, &mut Vec::new(), &mut cycles);
+        }
+    }
+    cycles
+}
+
+/// Schemas whose `$schema` is missing or differs from the most common one.
+/// A trailing empty fragment is ignored, so `…/schema#` matches `…/schema`.
+fn draft_mismatches(schemas: &[BundleSchema]) -> Vec<BundleFinding> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for draft in schemas.iter().filter_map(declared_draft) {
+        *counts.entry(draft.trim_end_matches('#')).or_default() += 1;
+    }
+    // Most common first; ties go to the lexically smallest dialect.
+    let Some((&expected, _)) = counts
+        .iter()
+        .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+    else {
+        return Vec::new();
+    };
+    schemas
+        .iter()
+        .filter(|schema| {
+            declared_draft(schema).map(|draft| draft.trim_end_matches('#')) != Some(expected)
+        })
+        .map(|schema| BundleFinding::DraftMismatch {
+            file: schema.file.clone(),
+            found: declared_draft(schema).map(str::to_owned),
+            expected: expected.to_owned(),
+        })
+        .collect()
+}
+
+fn declared_draft(schema: &BundleSchema) -> Option<&str> {
+    schema.schema.get("$schema").and_then(Value::as_str)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::GtsStore;
+    use serde_json::json;
+
+    const DRAFT_07: &str = "http://json-schema.org/draft-07/schema#";
+
+    fn write(dir: &Path, name: &str, schema: &Value) {
+        std::fs::write(
+            dir.join(name),
+            serde_json::to_string_pretty(schema).unwrap(),
+        )
+        .unwrap();
+    }
+
+    fn schema(id: &str, refs: &[&str]) -> Value {
+        let properties: serde_json::Map<String, Value> = refs
+            .iter()
+            .enumerate()
+            .map(|(i, r)| (format!("p{i}"), json!({"$ref": format!("gts://{r}")})))
+            .collect();
+        json!({
+            "$schema": DRAFT_07,
+            "$id": format!("gts://{id}"),
+            "type": "object",
+            "properties": properties,
+        })
+    }
+
+    #[test]
+    fn test_verify_bundle_self_contained() {
+        let tmp = tempfile::tempdir().unwrap();
+        write(
+            tmp.path(),
+            "a.json",
+            &schema("gts.x.b.c.a.v1~", &["gts.x.b.c.b.v1~"]),
+        );
+        write(tmp.path(), "b.json", &schema("gts.x.b.c.b.v1~", &[]));
+        std::fs::write(tmp.path().join("instance.json"), r#"{"id": "x"}"#).unwrap();
+
+        let report = GtsStore::verify_bundle(tmp.path()).unwrap();
+        assert_eq!(report.schemas, 2);
+        assert!(report.is_self_contained(), "{:?}", report.findings);
+    }
+
+    #[test]
+    fn test_verify_bundle_broken() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        std::fs::create_dir(dir.join("nested")).unwrap();
+        write(
+            dir,
+            "a.json",
+            &schema(
+                "gts.x.b.c.a.v1~",
+                &["gts.x.b.c.b.v1~", "gts.x.b.c.gone.v1~"],
+            ),
+        );
+        write(
+            dir,
+            "b.json",
+            &schema("gts.x.b.c.b.v1~", &["gts.x.b.c.a.v1~"]),
+        );
+        write(dir, "c.json", &schema("gts.x.b.c.c.v1~", &[]));
+        let mut other_draft = schema("gts.x.b.c.c.v1~", &[]);
+        other_draft["$schema"] = json!("https://json-schema.org/draft/2020-12/schema");
+        write(&dir.join("nested"), "c.json", &other_draft);
+        let mut no_draft = schema("gts.x.b.c.d.v1~", &[]);
+        no_draft["properties"] = json!({"d": {"items": [
+            {"const": {"$ref": "gts://gts.x.b.c.data.v1~"}},
+            {"$ref": "gts://gts.x.b.c.d.v1~#/properties"},
+        ]}});
+        no_draft.as_object_mut().unwrap().remove("$schema");
+        write(dir, "d.json", &no_draft);
+
+        let report = GtsStore::verify_bundle(dir).unwrap();
+        assert_eq!(report.schemas, 5);
+        assert_eq!(
+            report.findings,
+            [
+                BundleFinding::MissingRef {
+                    file: dir.join("a.json"),
+                    pointer: "/properties/p1/$ref".to_owned(),
+                    target: "gts.x.b.c.gone.v1~".to_owned(),
+                },
+                BundleFinding::DuplicateId {
+                    id: "gts.x.b.c.c.v1~".to_owned(),
+                    first: dir.join("c.json"),
+                    second: dir.join("nested/c.json"),
+                },
+                BundleFinding::Cycle {
+                    path: vec![
+                        "gts.x.b.c.a.v1~".to_owned(),
+                        "gts.x.b.c.b.v1~".to_owned(),
+                        "gts.x.b.c.a.v1~".to_owned(),
+                    ],
+                },
+                BundleFinding::Cycle {
+                    path: vec!["gts.x.b.c.d.v1~".to_owned(), "gts.x.b.c.d.v1~".to_owned()],
+                },
+                BundleFinding::DraftMismatch {
+                    file: dir.join("d.json"),
+                    found: None,
+                    expected: DRAFT_07.trim_end_matches('#').to_owned(),
+                },
+                BundleFinding::DraftMismatch {
+                    file: dir.join("nested/c.json"),
+                    found: Some("https://json-schema.org/draft/2020-12/schema".to_owned()),
+                    expected: DRAFT_07.trim_end_matches('#').to_owned(),
+                },
+            ]
+        );
+        assert_eq!(
+            report.findings[2].to_string(),
+            "$ref cycle: gts.x.b.c.a.v1~ -> gts.x.b.c.b.v1~ -> gts.x.b.c.a.v1~"
+        );
+    }
+
+    #[test]
+    fn test_verify_bundle_reports_invalid_json() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join("broken.json"), "{").unwrap();
+        let err = GtsStore::verify_bundle(tmp.path()).unwrap_err();
+        assert!(matches!(err, BundleError::Json { .. }), "{err}");
+    }
+}
@@ -17,7 +17,10 @@ use std::fmt;
 pub use gts_id::{
     DEFAULT_GTS_ID_PREFIX, GTS_ID_MAX_LENGTH, GTS_ID_PREFIX, GTS_ID_PREFIX_ENV, GtsId, GtsIdError,
     GtsIdPattern, GtsIdPatternSegment, GtsIdSegment, GtsIdSegmentParts, GtsUuidTail,
+    InstanceSegmentError, validate_instance_id, validate_instance_segment, validate_ref_pattern,
+    validate_schema_id,
 };
+use gts_id::{is_valid_segment_token, parse_u32_exact};
 
 /// A type-safe wrapper for GTS entity identifiers.
 ///
@@ -196,14 +199,88 @@ impl GtsInstanceId {
     /// assert!(GtsInstanceId::try_new("gts.x.core.events.event.v1").is_err());
     /// ```
     pub fn try_new(instance_id: &str) -> Result<Self, GtsIdError> {
-        let parsed = GtsId::try_new(instance_id)?;
-        if parsed.is_type() {
+        validate_instance_id(instance_id)?;
+        Ok(Self(GtsEntityId::new(instance_id.trim())))
+    }
+
+    /// Builds an instance ID under `type_id` from the parts of its instance
+    /// segment, validating each part.
+    ///
+    /// `version` is `vMAJOR` or `vMAJOR.MINOR`. The same parts always produce
+    /// the same ID.
+    ///
+    /// # Errors
+    /// Returns [`GtsIdError`] naming the first invalid part, or the underlying
+    /// error if the assembled ID is invalid (e.g. `type_id` is not a type ID).
+    ///
+    /// # Example
+    /// ```
+    /// use gts::GtsInstanceId;
+    ///
+    /// let id = GtsInstanceId::from_parts(
+    ///     "gts.x.core.events.topic.v1~", "x", "commerce", "orders", "created", "v1.0",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(id, "gts.x.core.events.topic.v1~x.commerce.orders.created.v1.0");
+    ///
+    /// let err = GtsInstanceId::from_parts(
+    ///     "gts.x.core.events.topic.v1~", "x", "Commerce", "orders", "created", "v1",
+    /// )
+    /// .unwrap_err();
+    /// assert!(err.cause.contains("package"));
+    /// ```
+    pub fn from_parts(
+        type_id: &str,
+        vendor: &str,
+        package: &str,
+        namespace: &str,
+        type_name: &str,
+        version: &str,
+    ) -> Result<Self, GtsIdError> {
+        for (part, value) in [
+            ("vendor", vendor),
+            ("package", package),
+            ("namespace", namespace),
+            ("type", type_name),
+        ] {
+            check_segment_token(part, value)?;
+        }
+        check_segment_version(version)?;
+        Self::try_new(&format!(
+            "{type_id}{vendor}.{package}.{namespace}.{type_name}.{version}"
+        ))
+    }
+
+    /// Builds a fresh instance ID under `type_id` whose segment is
+    /// `{prefix}.{unique}.v1`, where `prefix` is `vendor.package.namespace`
+    /// and `unique` is a random UUID rendered as a valid segment token.
+    ///
+    /// # Errors
+    /// Returns [`GtsIdError`] naming the invalid prefix part, or the underlying
+    /// error if the assembled ID is invalid.
+    ///
+    /// # Example
+    /// ```
+    /// use gts::GtsInstanceId;
+    ///
+    /// let a = GtsInstanceId::unique("gts.x.core.events.topic.v1~", "x.commerce.orders").unwrap();
+    /// let b = GtsInstanceId::unique("gts.x.core.events.topic.v1~", "x.commerce.orders").unwrap();
+    /// assert_ne!(a, b);
+    /// assert!(a.starts_with("gts.x.core.events.topic.v1~x.commerce.orders.u"));
+    /// ```
+    pub fn unique(type_id: &str, prefix: &str) -> Result<Self, GtsIdError> {
+        let parts: Vec<&str> = prefix.split('.').collect();
+        let [vendor, package, namespace] = parts[..] else {
             return Err(GtsIdError::new(
-                instance_id,
-                "GTS instance IDs must not end with '~' (a trailing '~' denotes a type id)",
+                prefix,
+                format!(
+                    "instance ID prefix must be vendor.package.namespace (got {} part(s))",
+                    parts.len()
+                ),
             ));
-        }
-        Ok(Self(GtsEntityId::new(parsed.as_ref())))
+        };
+        let unique = format!("u{}", uuid::Uuid::new_v4().simple());
+        Self::from_parts(type_id, vendor, package, namespace, &unique, "v1")
     }
 
     /// Returns the underlying string representation of the instance ID.
@@ -213,6 +290,37 @@ impl GtsInstanceId {
     }
 }
 
+/// Checks one name token of an instance segment, naming `part` on failure.
+fn check_segment_token(part: &str, value: &str) -> Result<(), GtsIdError> {
+    if is_valid_segment_token(value) {
+        Ok(())
+    } else {
+        Err(GtsIdError::new(
+            value,
+            format!("invalid {part} '{value}': must start with [a-z_] and contain only [a-z0-9_]"),
+        ))
+    }
+}
+
+/// Checks a `vMAJOR[.MINOR]` segment version.
+fn check_segment_version(version: &str) -> Result<(), GtsIdError> {
+    let valid = version.strip_prefix('v').is_some_and(|numbers| {
+        let (major, minor) = match numbers.split_once('.') {
+            Some((major, minor)) => (major, Some(minor)),
+            None => (numbers, None),
+        };
+        parse_u32_exact(major).is_some() && minor.is_none_or(|m| parse_u32_exact(m).is_some())
+    });
+    if valid {
+        Ok(())
+    } else {
+        Err(GtsIdError::new(
+            version,
+            format!("invalid version '{version}': expected vMAJOR or vMAJOR.MINOR"),
+        ))
+    }
+}
+
 impl fmt::Display for GtsInstanceId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.0)
@@ -293,7 +401,7 @@ impl<'de> serde::Deserialize<'de> for GtsTypeId {
         D: serde::Deserializer<'de>,
     {
         let s = String::deserialize(deserializer)?;
-        GtsTypeId::try_new(&s).map_err(serde::de::Error::custom)
+        GtsTypeId::try_from_str(&s).map_err(serde::de::Error::custom)
     }
 }
 
@@ -393,11 +501,75 @@ impl GtsTypeId {
     /// assert!(GtsTypeId::try_new("gts.x.core.events.event.v1~a.b.c.d.v1.0").is_err());
     /// ```
     pub fn try_new(type_id: &str) -> Result<Self, GtsIdError> {
-        let parsed = GtsId::try_new(type_id)?;
-        if !parsed.is_type() {
-            return Err(GtsIdError::new(type_id, "GTS type IDs must end with '~'"));
+        validate_schema_id(type_id)?;
+        Ok(Self(GtsEntityId::new(type_id.trim())))
+    }
+
+    /// Parses a type ID from any of the forms produced by its display
+    /// variants: the raw ID, the [`display_uri`](Self::display_uri) form, or
+    /// the [`display_path`](Self::display_path) form. The result is validated
+    /// like [`GtsTypeId::try_new`].
+    ///
+    /// The [`display_short`](Self::display_short) form drops the version and
+    /// the parent segments, so it cannot be parsed back.
+    ///
+    /// # Errors
+    /// Returns [`GtsIdError`] if the string is in none of the accepted forms or
+    /// is not a valid type ID.
+    ///
+    /// # Example
+    /// ```
+    /// use gts::GtsTypeId;
+    ///
+    /// let raw = GtsTypeId::try_from_str("gts.x.core.events.topic.v1~").unwrap();
+    /// let uri = GtsTypeId::try_from_str("gts://gts.x.core.events.topic.v1~").unwrap();
+    /// let path = GtsTypeId::try_from_str("x/core/events/topic/v1").unwrap();
+    /// assert_eq!(raw, uri);
+    /// assert_eq!(raw, path);
+    /// ```
+    pub fn try_from_str(s: &str) -> Result<Self, GtsIdError> {
+        let s = s.trim();
+        let s = s.strip_prefix(GTS_ID_URI_PREFIX).unwrap_or(s);
+        if !s.contains('/') {
+            return Self::try_new(s);
         }
-        Ok(Self(GtsEntityId::new(parsed.as_ref())))
+
+        let tokens: Vec<&str> = s.split('/').collect();
+        if !tokens.len().is_multiple_of(PATH_TOKENS_PER_SEGMENT) {
+            return Err(GtsIdError::new(
+                s,
+                "GTS type ID paths must have the form vendor/package/namespace/type/vN per segment",
+            ));
+        }
+        let mut id = GTS_ID_PREFIX.to_owned();
+        for segment in tokens.chunks(PATH_TOKENS_PER_SEGMENT) {
+            id.push_str(&segment.join("."));
+            id.push('~');
+        }
+        Self::try_new(&id)
+    }
+
+    /// Displays the ID as a URI, e.g. `gts://gts.x.core.events.topic.v1~`,
+    /// as used in JSON Schema `$id` and `$ref`.
+    #[must_use]
+    pub fn display_uri(&self) -> impl fmt::Display + '_ {
+        DisplayUri(self.as_ref())
+    }
+
+    /// Displays the ID as a relative path, e.g. `x/core/events/topic/v1`.
+    ///
+    /// Each segment becomes `vendor/package/namespace/type/vN`; chained
+    /// segments are appended in order.
+    #[must_use]
+    pub fn display_path(&self) -> impl fmt::Display + '_ {
+        DisplayPath(self.as_ref())
+    }
+
+    /// Displays the leaf segment without its version, e.g. `x.core.events.topic`,
+    /// for compact human-facing output.
+    #[must_use]
+    pub fn display_short(&self) -> impl fmt::Display + '_ {
+        DisplayShort(self.as_ref())
     }
 
     /// Returns the underlying string representation of the type ID.
@@ -407,6 +579,60 @@ impl GtsTypeId {
     }
 }
 
+/// `vendor`, `package`, `namespace`, `type`, `version`.
+const PATH_TOKENS_PER_SEGMENT: usize = 5;
+
+/// Segments of a type ID with the prefix and `~` separators removed.
+fn type_id_segments(id: &str) -> impl Iterator<Item = &str> {
+    id.strip_prefix(GTS_ID_PREFIX)
+        .unwrap_or(id)
+        .split('~')
+        .filter(|segment| !segment.is_empty())
+}
+
+/// Splits a segment into its `vendor.package.namespace.type` name and version.
+fn split_segment_version(segment: &str) -> (&str, &str) {
+    segment
+        .match_indices('.')
+        .nth(PATH_TOKENS_PER_SEGMENT - 2)
+        .map_or((segment, ""), |(i, _)| (&segment[..i], &segment[i + 1..]))
+}
+
+struct DisplayUri<'a>(&'a str);
+
+impl fmt::Display for DisplayUri<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{GTS_ID_URI_PREFIX}{}", self.0)
+    }
+}
+
+struct DisplayPath<'a>(&'a str);
+
+impl fmt::Display for DisplayPath<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, segment) in type_id_segments(self.0).enumerate() {
+            if i > 0 {
+                f.write_str("/")?;
+            }
+            let (name, version) = split_segment_version(segment);
+            f.write_str(&name.replace('.', "/"))?;
+            if !version.is_empty() {
+                write!(f, "/{version}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+struct DisplayShort<'a>(&'a str);
+
+impl fmt::Display for DisplayShort<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let leaf = type_id_segments(self.0).last().unwrap_or_default();
+        f.write_str(split_segment_version(leaf).0)
+    }
+}
+
 impl fmt::Display for GtsTypeId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.0)
@@ -486,6 +712,84 @@ mod tests {
         assert!(GtsInstanceId::try_new("not a valid cti").is_err());
     }
 
+    const TOPIC: &str = "gts.x.core.events.topic.v1~";
+
+    #[test]
+    fn test_instance_id_from_parts_is_deterministic_and_parses() {
+        let a = GtsInstanceId::from_parts(TOPIC, "x", "commerce", "orders", "created", "v1.2")
+            .expect("test");
+        let b = GtsInstanceId::from_parts(TOPIC, "x", "commerce", "orders", "created", "v1.2")
+            .expect("test");
+        assert_eq!(a, b);
+        assert_eq!(
+            a,
+            "gts.x.core.events.topic.v1~x.commerce.orders.created.v1.2"
+        );
+        assert_eq!(GtsInstanceId::try_new(&a).expect("round-trip"), a);
+    }
+
+    #[test]
+    fn test_instance_id_from_parts_names_invalid_part() {
+        let cases = [
+            (
+                ["X", "commerce", "orders", "created", "v1"],
+                "invalid vendor 'X'",
+            ),
+            (
+                ["x", "com-merce", "orders", "created", "v1"],
+                "invalid package",
+            ),
+            (
+                ["x", "commerce", "", "created", "v1"],
+                "invalid namespace ''",
+            ),
+            (
+                ["x", "commerce", "orders", "1st", "v1"],
+                "invalid type '1st'",
+            ),
+            (
+                ["x", "commerce", "orders", "created", "1"],
+                "invalid version '1'",
+            ),
+            (
+                ["x", "commerce", "orders", "created", "v01"],
+                "invalid version",
+            ),
+            (
+                ["x", "commerce", "orders", "created", "v1.2.3"],
+                "invalid version",
+            ),
+        ];
+        for ([vendor, package, namespace, type_name, version], expected) in cases {
+            let err =
+                GtsInstanceId::from_parts(TOPIC, vendor, package, namespace, type_name, version)
+                    .expect_err(expected);
+            assert!(err.cause.contains(expected), "{expected}: {err}");
+        }
+
+        // Valid parts under an invalid type id fail in the full-ID parse.
+        assert!(
+            GtsInstanceId::from_parts("gts.x.core.events.topic.v1", "x", "a", "b", "c", "v1")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_unique_instance_id() {
+        let a = GtsInstanceId::unique(TOPIC, "x.commerce.orders").expect("test");
+        let b = GtsInstanceId::unique(TOPIC, "x.commerce.orders").expect("test");
+        assert_ne!(a, b);
+        assert_eq!(GtsInstanceId::try_new(&a).expect("round-trip"), a);
+        let segment = a.strip_prefix(TOPIC).expect("test");
+        assert!(segment.starts_with("x.commerce.orders.u"), "{segment}");
+        assert_eq!(segment.rsplit('.').next(), Some("v1"), "{segment}");
+
+        let err = GtsInstanceId::unique(TOPIC, "x.commerce").expect_err("two parts");
+        assert!(err.cause.contains("vendor.package.namespace"), "{err}");
+        let err = GtsInstanceId::unique(TOPIC, "x.Commerce.orders").expect_err("bad part");
+        assert!(err.cause.contains("invalid package"), "{err}");
+    }
+
     #[test]
     fn test_deserialize_routes_through_validated_constructors() {
         // Deserialization must reuse the validating `try_new` constructors, not
@@ -514,6 +818,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_type_id_display_variants() {
+        let id = GtsTypeId::try_new("gts.x.core.events.topic.v1~").expect("test");
+        assert_eq!(id.to_string(), "gts.x.core.events.topic.v1~");
+        assert_eq!(
+            id.display_uri().to_string(),
+            "gts://gts.x.core.events.topic.v1~"
+        );
+        assert_eq!(id.display_path().to_string(), "x/core/events/topic/v1");
+        assert_eq!(id.display_short().to_string(), "x.core.events.topic");
+
+        let chained =
+            GtsTypeId::try_new("gts.x.core.events.topic.v1~vendor.app.orders.topic.v1.2~")
+                .expect("test");
+        assert_eq!(
+            chained.display_path().to_string(),
+            "x/core/events/topic/v1/vendor/app/orders/topic/v1.2"
+        );
+        assert_eq!(
+            chained.display_short().to_string(),
+            "vendor.app.orders.topic"
+        );
+    }
+
+    #[test]
+    fn test_type_id_try_from_str_accepts_display_forms() {
+        let id = GtsTypeId::try_new("gts.x.core.events.topic.v1~vendor.app.orders.topic.v1.2~")
+            .expect("test");
+        for form in [
+            id.to_string(),
+            id.display_uri().to_string(),
+            id.display_path().to_string(),
+        ] {
+            assert_eq!(GtsTypeId::try_from_str(&form).expect(&form), id);
+            let json = serde_json::to_string(&form).expect("test");
+            assert_eq!(serde_json::from_str::<GtsTypeId>(&json).expect(&form), id);
+        }
+        assert_eq!(
+            serde_json::to_string(&id).expect("test"),
+            "\"gts.x.core.events.topic.v1~vendor.app.orders.topic.v1.2~\""
+        );
+
+        assert!(GtsTypeId::try_from_str("x/core/events/topic").is_err());
+        assert!(GtsTypeId::try_from_str("x.core.events.topic").is_err());
+        assert!(GtsTypeId::try_from_str("gts://gts.x.core.events.topic.v1~a.b.c.d.v1").is_err());
+    }
+
     #[test]
     fn test_json_schema_values_use_configured_id_prefix() {
         let expected = format!("{GTS_ID_PREFIX}*");
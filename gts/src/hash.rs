@@ -0,0 +1,178 @@
+//! Content hashes for schemas and instances.
+//!
+//! [`schema_hash`] identifies a JSON document by content rather than by bytes:
+//! it hashes a canonical encoding, so two producers that format or order keys
+//! differently still agree on the hash. Registries use it to tell whether a
+//! schema changed since it was last published.
+//!
+//! The canonical encoding is compact JSON with:
+//! - object keys sorted by their UTF-8 bytes, at every depth;
+//! - no whitespace between tokens;
+//! - integers (including floats with no fractional part, such as `1.0`,
+//!   within ±2^53) written as plain integers, `-0` as `0`;
+//! - other numbers in their shortest round-trip decimal form, without an
+//!   exponent;
+//! - strings escaped as `serde_json` escapes them.
+//!
+//! Array order is significant and is kept as is.
+
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+/// Prefix of every hash produced by this module.
+pub const HASH_PREFIX: &str = "sha256:";
+
+/// Largest magnitude at which every integer is exactly representable as `f64`.
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_992.0;
+
+/// `sha256:<hex>` digest of the canonical encoding of `value`.
+///
+/// Works for any JSON document; schemas and instances alike.
+#[must_use]
+pub fn schema_hash(value: &Value) -> String {
+    sha256_hash(canonical_json(value).as_bytes())
+}
+
+/// The canonical encoding of `value` that [`schema_hash`] digests.
+#[must_use]
+pub fn canonical_json(value: &Value) -> String {
+    let mut out = String::new();
+    write_canonical(value, &mut out);
+    out
+}
+
+/// `sha256:<hex>` digest of `bytes`.
+pub(crate) fn sha256_hash(bytes: &[u8]) -> String {
+    let digest = Sha256::digest(bytes);
+    let mut hash = String::with_capacity(HASH_PREFIX.len() + digest.len() * 2);
+    hash.push_str(HASH_PREFIX);
+    for byte in digest {
+        hash.push(char::from(HEX[usize::from(byte >> 4)]));
+        hash.push(char::from(HEX[usize::from(byte & 0x0f)]));
+    }
+    hash
+}
+
+const HEX: &[u8; 16] = b"0123456789abcdef";
+
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => write_number(n, out),
+        Value::String(s) => write_string(s, out),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            // Sort explicitly: with `preserve_order` enabled anywhere in the
+            // build, the map keeps insertion order.
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+            out.push('{');
+            for (i, (key, item)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_string(key, out);
+                out.push(':');
+                write_canonical(item, out);
+            }
+            out.push('}');
+        }
+    }
+}
+
+fn write_number(n: &serde_json::Number, out: &mut String) {
+    use std::fmt::Write as _;
+
+    if let Some(i) = n.as_i64() {
+        let _ = write!(out, "{i}");
+    } else if let Some(u) = n.as_u64() {
+        let _ = write!(out, "{u}");
+    } else if let Some(f) = n.as_f64() {
+        if f.fract() == 0.0 && f.abs() <= MAX_SAFE_INTEGER {
+            // Exact: `f` is an integer within ±2^53.
+            #[allow(clippy::cast_possible_truncation)]
+            let i = f as i64;
+            let _ = write!(out, "{i}");
+        } else {
+            let _ = write!(out, "{f}");
+        }
+    }
+}
+
+fn write_string(s: &str, out: &mut String) {
+    // Serializing a `str` cannot fail.
+    out.push_str(&serde_json::to_string(s).unwrap_or_default());
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_canonical_json() {
+        let value: Value = serde_json::from_str(
+            r#"{ "b": [1.0, -0.0, 2.5, 1e3], "a": { "z": null, "y": "\u0041\t" } }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            canonical_json(&value),
+            r#"{"a":{"y":"A\t","z":null},"b":[1,0,2.5,1000]}"#
+        );
+    }
+
+    #[test]
+    fn test_key_order_and_formatting_do_not_matter() {
+        let compact: Value =
+            serde_json::from_str(r#"{"type":"object","properties":{"id":{"type":"string"}}}"#)
+                .unwrap();
+        let pretty: Value = serde_json::from_str(
+            "{\n  \"properties\": {\n    \"id\": { \"type\": \"string\" }\n  },\n  \"type\": \"object\"\n}",
+        )
+        .unwrap();
+        assert_eq!(schema_hash(&compact), schema_hash(&pretty));
+        assert_eq!(
+            schema_hash(&json!({ "n": 1 })),
+            schema_hash(&json!({ "n": 1.0 }))
+        );
+    }
+
+    #[test]
+    fn test_content_changes_change_the_hash() {
+        let schema = json!({ "type": "object", "description": "An event" });
+        let edited = json!({ "type": "object", "description": "An event." });
+        assert_ne!(schema_hash(&schema), schema_hash(&edited));
+        assert_ne!(schema_hash(&json!([1, 2])), schema_hash(&json!([2, 1])));
+    }
+
+    #[test]
+    fn test_schema_hash_is_stable() {
+        let schema = json!({
+            "$id": "gts://gts.x.core.events.type.v1~",
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "description": "Base event type definition",
+            "type": "object",
+            "properties": { "id": { "type": "string", "format": "uuid" } },
+            "required": ["id"]
+        });
+        assert_eq!(
+            canonical_json(&schema),
+            r#"{"$id":"gts://gts.x.core.events.type.v1~","$schema":"http://json-schema.org/draft-07/schema#","description":"Base event type definition","properties":{"id":{"format":"uuid","type":"string"}},"required":["id"],"type":"object"}"#
+        );
+        assert_eq!(
+            schema_hash(&schema),
+            "sha256:237d97ffe1af746fedea597fc4df9a4b4e2488b7079d3b26bf2577178cf59d74"
+        );
+    }
+}
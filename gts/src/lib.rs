@@ -1,11 +1,17 @@
+pub mod bundle;
 pub mod entities;
 pub mod files_reader;
 pub mod gts;
+pub mod hash;
+pub mod manifest;
 pub mod ops;
 pub mod path_resolver;
 pub mod schema;
 pub mod schema_cast;
 pub mod schema_compat;
+pub mod schema_draft;
+pub mod schema_dyn;
+pub mod schema_generic;
 pub mod schema_modifiers;
 pub mod schema_narrow;
 pub mod schema_refs;
@@ -14,9 +20,11 @@ pub mod schema_traits;
 pub mod store;
 #[doc(hidden)]
 pub mod testing;
+pub mod type_registry;
 pub mod x_gts_ref;
 
 // Re-export commonly used types
+pub use bundle::{BundleError, BundleFinding, BundleReport};
 pub use entities::{GtsConfig, GtsEntity, GtsFile, ValidationError, ValidationResult};
 pub use files_reader::GtsFileReader;
 #[allow(deprecated)]
@@ -24,18 +32,29 @@ pub use gts::GtsSchemaId;
 pub use gts::{
     DEFAULT_GTS_ID_PREFIX, GTS_ID_MAX_LENGTH, GTS_ID_PREFIX, GTS_ID_PREFIX_ENV, GTS_ID_URI_PREFIX,
     GtsId, GtsIdError, GtsIdPattern, GtsIdPatternSegment, GtsIdSegment, GtsIdSegmentParts,
-    GtsInstanceId, GtsTypeId, GtsUuidTail,
+    GtsInstanceId, GtsTypeId, GtsUuidTail, InstanceSegmentError, validate_instance_id,
+    validate_instance_segment, validate_ref_pattern, validate_schema_id,
 };
+pub use hash::schema_hash;
+pub use manifest::{ManifestError, SchemaManifest, SchemaManifestEntry};
 pub use ops::GtsOps;
 pub use path_resolver::JsonPathResolver;
 pub use schema::{
     GtsDeserialize, GtsDeserializeWrapper, GtsNoDirectDeserialize, GtsNoDirectSerialize, GtsSchema,
-    GtsSerialize, GtsSerializeWrapper, JSON_SCHEMA_DRAFT_07, TraitSchemaState, deserialize_gts,
-    serialize_gts, strip_schema_metadata,
+    GtsSerialize, GtsSerializeWrapper, JSON_SCHEMA_DRAFT_07, TraitSchemaState, bundle_gts_schema,
+    canonical_schema_to_string, canonical_schema_to_string_pretty, canonicalize_schema,
+    deserialize_gts, inline_gts_schema, inline_local_refs, serialize_gts, strip_schema_metadata,
 };
 pub use schema_cast::{GtsEntityCastResult, SchemaCastError};
+pub use schema_draft::{JSON_SCHEMA_DRAFT_2020_12, SchemaDraft, convert_schema_draft};
+pub use schema_dyn::{GtsSchemaDyn, erase};
 pub use schema_narrow::{NarrowError, try_narrow};
 pub use schema_refs::{ExtractRefsError, InvalidRefReason, extract_gts_refs};
 pub use schema_traits::{GtsTraitsSchema, inline_traits_schema_of};
 pub use store::{GtsReader, GtsStore, GtsStoreQueryResult, ResolvedType, StoreError};
+pub use type_registry::{
+    DuplicateTypeId, GtsTypeRegistration, SchemaIndexEntry, assert_unique_type_ids,
+    duplicate_type_ids, generate_schema_index_json, registered_schema, registered_type_ids,
+    schema_index,
+};
 pub use x_gts_ref::{XGtsRefValidationError, XGtsRefValidator};
@@ -0,0 +1,269 @@
+//! Schema manifests: a deterministic index of every schema in a bundle.
+//!
+//! A [`SchemaManifest`] lists each schema's ID, file path, base ID,
+//! description and content hash, sorted by ID, so registries can ingest a
+//! bundle from one document and detect changed schemas without parsing them.
+//! The hash is [`schema_hash`], so reformatting a file does not change it.
+//!
+//! ```json
+//! {
+//!   "schemas": [
+//!     {
+//!       "id": "gts.x.core.events.type.v1~",
+//!       "path": "gts.x.core.events.type.v1~.schema.json",
+//!       "description": "Base event type definition",
+//!       "hash": "sha256:…"
+//!     }
+//!   ]
+//! }
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+use walkdir::WalkDir;
+
+use crate::gts::GTS_ID_URI_PREFIX;
+use crate::hash::{HASH_PREFIX, schema_hash, sha256_hash};
+
+/// Prefix of [`SchemaManifestEntry::hash`] values.
+pub const MANIFEST_HASH_PREFIX: &str = HASH_PREFIX;
+
+#[derive(Debug, Error)]
+pub enum ManifestError {
+    #[error("Failed to read '{}': {source}", path.display())]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Failed to parse '{}' as JSON: {source}", path.display())]
+    Json {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+}
+
+/// An index of the schemas in a bundle, sorted by ID.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaManifest {
+    pub schemas: Vec<SchemaManifestEntry>,
+}
+
+/// One schema in a [`SchemaManifest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaManifestEntry {
+    /// The schema's GTS type ID, without the `gts://` prefix.
+    pub id: String,
+    /// Path of the schema file relative to the bundle root, `/`-separated.
+    pub path: String,
+    /// The parent type ID for derived schemas.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_id: Option<String>,
+    /// The schema's `description`, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// [`schema_hash`] of the parsed schema.
+    pub hash: String,
+}
+
+impl SchemaManifestEntry {
+    /// Describe the schema file at `path` with the given raw `content`.
+    ///
+    /// Returns `None` if `content` is not a JSON Schema with a `gts://` `$id`.
+    #[must_use]
+    pub fn from_schema_file(path: impl Into<String>, content: &str) -> Option<Self> {
+        let schema: Value = serde_json::from_str(content).ok()?;
+        Self::from_parsed(path.into(), &schema)
+    }
+
+    fn from_parsed(path: String, schema: &Value) -> Option<Self> {
+        let id = schema
+            .get("$id")?
+            .as_str()?
+            .strip_prefix(GTS_ID_URI_PREFIX)?
+            .to_owned();
+        let base_id = base_type_id(&id).map(str::to_owned);
+        Some(Self {
+            id,
+            path,
+            base_id,
+            description: schema
+                .get("description")
+                .and_then(Value::as_str)
+                .map(str::to_owned),
+            hash: schema_hash(schema),
+        })
+    }
+}
+
+impl SchemaManifest {
+    /// Build a manifest from `entries`, sorted by ID.
+    #[must_use]
+    pub fn new(mut entries: Vec<SchemaManifestEntry>) -> Self {
+        entries.sort_by(|a, b| a.id.cmp(&b.id).then_with(|| a.path.cmp(&b.path)));
+        Self { schemas: entries }
+    }
+
+    /// Build a manifest from every `*.json` file under `dir` whose `$id` is a
+    /// `gts://` URI. Other JSON files (instances, the manifest itself) are
+    /// skipped. Paths are relative to `dir`.
+    ///
+    /// # Errors
+    /// Returns [`ManifestError`] if the directory cannot be walked or a JSON
+    /// file cannot be read or parsed.
+    pub fn from_dir(dir: &Path) -> Result<Self, ManifestError> {
+        let mut entries = Vec::new();
+        for entry in WalkDir::new(dir).sort_by_file_name() {
+            let entry = entry.map_err(|e| ManifestError::Io {
+                path: e
+                    .path()
+                    .map_or_else(|| dir.to_path_buf(), Path::to_path_buf),
+                source: e.into(),
+            })?;
+            let path = entry.path();
+            if !entry.file_type().is_file() || path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            let content = std::fs::read_to_string(path).map_err(|source| ManifestError::Io {
+                path: path.to_path_buf(),
+                source,
+            })?;
+            let schema: Value =
+                serde_json::from_str(&content).map_err(|source| ManifestError::Json {
+                    path: path.to_path_buf(),
+                    source,
+                })?;
+            let relative = path.strip_prefix(dir).unwrap_or(path);
+            let relative = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            entries.extend(SchemaManifestEntry::from_parsed(relative, &schema));
+        }
+        Ok(Self::new(entries))
+    }
+
+    /// Look up an entry by schema ID.
+    #[must_use]
+    pub fn get(&self, id: &str) -> Option<&SchemaManifestEntry> {
+        self.schemas.iter().find(|entry| entry.id == id)
+    }
+
+    /// The manifest as pretty-printed JSON with a trailing newline.
+    ///
+    /// # Errors
+    /// Returns an error if serialization fails.
+    pub fn to_json_pretty(&self) -> Result<String, serde_json::Error> {
+        Ok(serde_json::to_string_pretty(self)? + "\n")
+    }
+}
+
+/// `sha256:<hex>` digest of the raw bytes of `content`.
+#[must_use]
+pub fn content_hash(content: &[u8]) -> String {
+    sha256_hash(content)
+}
+
+/// The parent of a chained type ID (`a~b~` -> `a~`), or `None` for a base type.
+fn base_type_id(id: &str) -> Option<&str> {
+    let without_last = id.strip_suffix('~').unwrap_or(id);
+    without_last.rfind('~').map(|i| &id[..=i])
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const BASE: &str = "gts.x.core.events.type.v1~";
+    const DERIVED: &str = "gts.x.core.events.type.v1~x.core.audit.event.v1~";
+
+    fn schema_file(id: &str, description: &str) -> String {
+        serde_json::to_string_pretty(&json!({
+            "$id": format!("gts://{id}"),
+            "description": description,
+            "type": "object"
+        }))
+        .unwrap()
+            + "\n"
+    }
+
+    #[test]
+    fn test_content_hash() {
+        assert_eq!(
+            content_hash(b"abc"),
+            "sha256:ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_base_type_id() {
+        assert_eq!(base_type_id(BASE), None);
+        assert_eq!(base_type_id(DERIVED), Some(BASE));
+    }
+
+    #[test]
+    fn test_entry_from_schema_file() {
+        let content = schema_file(DERIVED, "Audit event");
+        let entry = SchemaManifestEntry::from_schema_file("audit.schema.json", &content).unwrap();
+        assert_eq!(entry.id, DERIVED);
+        assert_eq!(entry.base_id.as_deref(), Some(BASE));
+        assert_eq!(entry.description.as_deref(), Some("Audit event"));
+        assert_eq!(
+            entry.hash,
+            schema_hash(&serde_json::from_str(&content).unwrap())
+        );
+
+        let compact =
+            serde_json::to_string(&serde_json::from_str::<Value>(&content).unwrap()).unwrap();
+        let reformatted = SchemaManifestEntry::from_schema_file("audit.json", &compact).unwrap();
+        assert_eq!(reformatted.hash, entry.hash);
+
+        assert!(SchemaManifestEntry::from_schema_file("x.json", r#"{"id": 1}"#).is_none());
+        assert!(SchemaManifestEntry::from_schema_file("x.json", "not json").is_none());
+    }
+
+    #[test]
+    fn test_from_dir_is_sorted_and_skips_non_schemas() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::create_dir(tmp.path().join("nested")).unwrap();
+        std::fs::write(
+            tmp.path().join("nested/derived.schema.json"),
+            schema_file(DERIVED, "Audit event"),
+        )
+        .unwrap();
+        std::fs::write(
+            tmp.path().join("z-base.schema.json"),
+            schema_file(BASE, "Base event"),
+        )
+        .unwrap();
+        std::fs::write(tmp.path().join("instance.json"), r#"{"id": "x"}"#).unwrap();
+        std::fs::write(tmp.path().join("notes.md"), "not json").unwrap();
+
+        let manifest = SchemaManifest::from_dir(tmp.path()).unwrap();
+        let ids: Vec<&str> = manifest.schemas.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, [BASE, DERIVED]);
+        assert_eq!(
+            manifest.get(DERIVED).unwrap().path,
+            "nested/derived.schema.json"
+        );
+
+        let json = manifest.to_json_pretty().unwrap();
+        assert!(json.ends_with("}\n"));
+        let parsed: SchemaManifest = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, manifest);
+        assert!(!json.contains("base_id\": null"));
+    }
+
+    #[test]
+    fn test_from_dir_reports_invalid_json() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(tmp.path().join("broken.json"), "{").unwrap();
+        let err = SchemaManifest::from_dir(tmp.path()).unwrap_err();
+        assert!(matches!(err, ManifestError::Json { .. }), "{err}");
+    }
+}
@@ -6,6 +6,7 @@
 use serde_json::Value;
 
 use crate::GTS_ID_URI_PREFIX;
+use crate::gts::{GtsId, GtsIdError, GtsInstanceId};
 
 /// The JSON Schema **draft-07** dialect URI that GTS Type Schemas declare via
 /// `$schema`. Single source of truth for the value emitted by the schema
@@ -93,9 +94,44 @@ pub trait GtsSchema {
     /// prohibits traits ([`TraitSchemaState::Prohibited`]).
     const TRAIT_SCHEMA: TraitSchemaState = TraitSchemaState::Absent;
 
+    /// Serialized name of the base type's GTS Type field when the base
+    /// declares `inject_type_field = true`, inherited by every derived type.
+    /// Set by `#[struct_to_gts_schema]`; the field is pinned with `const` in
+    /// each level's generated schema.
+    const TYPE_FIELD: Option<&'static str> = None;
+
     /// Returns the JSON schema for this type with $ref references intact.
     fn gts_schema_with_refs() -> Value;
 
+    /// Content hash of this type's schema: [`schema_hash`](crate::hash::schema_hash)
+    /// of [`Self::gts_schema_with_refs_allof`] in canonical form
+    /// ([`canonicalize_schema`]), the document written to this type's schema
+    /// file. Equal to that file's manifest hash.
+    #[must_use]
+    fn gts_schema_hash() -> String {
+        let mut schema = Self::gts_schema_with_refs_allof();
+        canonicalize_schema(&mut schema);
+        crate::hash::schema_hash(&schema)
+    }
+
+    /// Major version of the last segment of [`Self::TYPE_ID`]: `1` for
+    /// `gts.x.core.events.type.v1~`, `2` for `...v2.1~`.
+    ///
+    /// `#[struct_to_gts_schema]` generates this as a literal. The default
+    /// parses `TYPE_ID` and returns `0` when it is not a GTS ID (the `()`
+    /// and `Value` placeholders).
+    #[must_use]
+    fn gts_schema_version() -> u32 {
+        leaf_version(Self::TYPE_ID).map_or(0, |(major, _)| major)
+    }
+
+    /// Minor version of the last segment of [`Self::TYPE_ID`], if it has
+    /// one: `Some(1)` for `...v2.1~`, `None` for `...v1~`.
+    #[must_use]
+    fn gts_schema_minor_version() -> Option<u32> {
+        leaf_version(Self::TYPE_ID).and_then(|(_, minor)| minor)
+    }
+
     /// Returns the composed JSON schema for this type.
     /// For types with generic parameters that implement `GtsSchema`,
     /// this returns the schema with the generic field's type replaced
@@ -162,6 +198,58 @@ pub trait GtsSchema {
         None
     }
 
+    /// Own schemas of every ancestor in this type's `$id` chain, outermost
+    /// first. Empty for base types. Overridden by `#[struct_to_gts_schema]`
+    /// for derived types; feeds [`bundle_gts_schema`] so the chain can be
+    /// inlined without a registry.
+    #[must_use]
+    fn gts_ancestor_schemas() -> Vec<Value> {
+        Vec::new()
+    }
+
+    /// Fully resolved schema for this type: [`Self::gts_schema_with_refs_allof`]
+    /// with every `$ref` to an ancestor replaced by that ancestor's schema
+    /// (see [`inline_gts_schema`]). Accepts the same instances as the `$ref`
+    /// form, with no registry, resolver or `$defs`.
+    #[must_use]
+    fn gts_schema_inline() -> Value {
+        inline_gts_schema(
+            &Self::gts_schema_with_refs_allof(),
+            &Self::gts_ancestor_schemas(),
+        )
+    }
+
+    /// Build an instance ID of this type from validated segment parts.
+    /// See [`GtsInstanceId::from_parts`].
+    ///
+    /// # Errors
+    /// Returns [`GtsIdError`] naming the first invalid part.
+    fn gts_make_instance_id_from_parts(
+        vendor: &str,
+        package: &str,
+        namespace: &str,
+        type_name: &str,
+        version: &str,
+    ) -> Result<GtsInstanceId, GtsIdError> {
+        GtsInstanceId::from_parts(
+            Self::TYPE_ID,
+            vendor,
+            package,
+            namespace,
+            type_name,
+            version,
+        )
+    }
+
+    /// Build a fresh, UUID-suffixed instance ID of this type under the
+    /// `vendor.package.namespace` `prefix`. See [`GtsInstanceId::unique`].
+    ///
+    /// # Errors
+    /// Returns [`GtsIdError`] naming the invalid prefix part.
+    fn gts_make_unique_instance_id(prefix: &str) -> Result<GtsInstanceId, GtsIdError> {
+        GtsInstanceId::unique(Self::TYPE_ID, prefix)
+    }
+
     /// Collect the nesting path (generic field names) from outer to inner types.
     /// For `BaseEventV1<AuditPayloadV1<PlaceOrderDataV1>>`, returns `["payload", "data"]`.
     #[must_use]
@@ -258,6 +346,13 @@ pub trait GtsSchema {
     }
 }
 
+/// `(major, minor)` version of the last segment of `type_id`.
+fn leaf_version(type_id: &str) -> Option<(u32, Option<u32>)> {
+    let id = GtsId::try_new(type_id).ok()?;
+    let leaf = id.segments().last()?;
+    Some((leaf.ver_major(), leaf.ver_minor()))
+}
+
 /// Marker implementation for () to allow `BaseEventV1<()>` etc.
 impl GtsSchema for () {
     const TYPE_ID: &'static str = "";
@@ -444,12 +539,376 @@ impl<'de, T: GtsDeserialize<'de>> serde::Deserialize<'de> for GtsDeserializeWrap
 /// //   ]
 /// // }
 /// ```
+///
+/// Pass `draft = "07"` or `draft = "2020-12"` to emit the schema in a specific
+/// JSON Schema dialect (see [`crate::schema_draft`]):
+///
+/// ```ignore
+/// let schema = gts_schema_for!(BaseEventV1<()>, draft = "2020-12");
+/// ```
 #[macro_export]
 macro_rules! gts_schema_for {
     ($base:ty) => {{
         use $crate::GtsSchema;
         <$base as GtsSchema>::gts_schema_with_refs_allof()
     }};
+    ($base:ty, draft = $draft:literal) => {{
+        use $crate::GtsSchema;
+        const DRAFT: $crate::SchemaDraft = match $crate::SchemaDraft::from_name($draft) {
+            Some(draft) => draft,
+            None => panic!("gts_schema_for!: unsupported draft, expected \"07\" or \"2020-12\""),
+        };
+        $crate::convert_schema_draft(&<$base as GtsSchema>::gts_schema_with_refs_allof(), DRAFT)
+    }};
+}
+
+/// Generate a self-contained GTS schema for a type: like [`gts_schema_for!`],
+/// but every `gts://` reference to an ancestor in the `$id` chain is inlined
+/// into `$defs` (see [`bundle_gts_schema`]), so the result can be validated
+/// offline without a registry or resolver.
+///
+/// # Example
+///
+/// ```ignore
+/// use gts::gts_schema_for_bundled;
+///
+/// let schema = gts_schema_for_bundled!(AuditPayloadV1<()>);
+/// // {
+/// //   "$id": "gts://...AuditPayloadV1...",
+/// //   "allOf": [
+/// //     { "$ref": "#/$defs/gts.x.core.events.type.v1~0" },
+/// //     { ... }
+/// //   ],
+/// //   "$defs": { "gts.x.core.events.type.v1~": { ... } }
+/// // }
+/// ```
+#[macro_export]
+macro_rules! gts_schema_for_bundled {
+    ($base:ty) => {{
+        use $crate::GtsSchema;
+        $crate::bundle_gts_schema(
+            &<$base as GtsSchema>::gts_schema_with_refs_allof(),
+            &<$base as GtsSchema>::gts_ancestor_schemas(),
+        )
+    }};
+}
+
+/// Inline external `gts://` `$ref`s into a `$defs` section of `root`.
+///
+/// `schemas` are the candidate targets, matched against references by their
+/// `$id`. Each referenced schema is stored once under `$defs`, keyed by its
+/// GTS type ID with `$id`/`$schema` stripped, and every `gts://<id>` (or
+/// `gts://<id>#/pointer`) reference to it is rewritten to the matching
+/// internal `#/$defs/...` pointer. Inlined schemas are scanned in turn, so
+/// whole chains are bundled; each ID is inlined at most once, which also
+/// terminates cyclic references. A reference to `root`'s own `$id` becomes
+/// `#`, and references with no matching schema are left untouched. `$defs`
+/// entries are emitted in sorted ID order so the output is diffable.
+#[must_use]
+pub fn bundle_gts_schema(root: &Value, schemas: &[Value]) -> Value {
+    let available: std::collections::HashMap<&str, &Value> = schemas
+        .iter()
+        .filter_map(|schema| {
+            let id = schema
+                .get("$id")?
+                .as_str()?
+                .strip_prefix(GTS_ID_URI_PREFIX)?;
+            Some((id, schema))
+        })
+        .collect();
+    let root_id = root
+        .get("$id")
+        .and_then(Value::as_str)
+        .and_then(|id| id.strip_prefix(GTS_ID_URI_PREFIX));
+
+    let mut bundled = root.clone();
+    let mut defs = std::collections::BTreeMap::new();
+    let mut pending = Vec::new();
+    rewrite_gts_refs(&mut bundled, root_id, &available, &mut pending);
+    while let Some(id) = pending.pop() {
+        if defs.contains_key(&id) {
+            continue;
+        }
+        let Some(schema) = available.get(id.as_str()) else {
+            continue;
+        };
+        let mut def = (*schema).clone();
+        if let Some(obj) = def.as_object_mut() {
+            obj.remove("$id");
+            obj.remove("$schema");
+        }
+        rewrite_gts_refs(&mut def, root_id, &available, &mut pending);
+        defs.insert(id, def);
+    }
+
+    if !defs.is_empty()
+        && let Some(obj) = bundled.as_object_mut()
+    {
+        let entry = obj
+            .entry("$defs")
+            .or_insert_with(|| Value::Object(serde_json::Map::new()));
+        if let Some(existing) = entry.as_object_mut() {
+            for (id, def) in defs {
+                existing.insert(id, def);
+            }
+        }
+    }
+    bundled
+}
+
+/// Replace external `gts://` `$ref`s in `root` by the schemas they name.
+///
+/// Unlike [`bundle_gts_schema`], which moves the targets into `$defs`, every
+/// subschema that is exactly `{ "$ref": "gts://<id>" }` with `<id>` among
+/// `schemas` is replaced by that schema, with `$id`/`$schema` stripped and
+/// its own references inlined in turn. References with a `#` fragment or
+/// sibling keywords, references to unknown IDs and references that would
+/// recurse into a schema being inlined are left untouched.
+#[must_use]
+pub fn inline_gts_schema(root: &Value, schemas: &[Value]) -> Value {
+    let available: std::collections::HashMap<&str, &Value> = schemas
+        .iter()
+        .filter_map(|schema| {
+            let id = schema
+                .get("$id")?
+                .as_str()?
+                .strip_prefix(GTS_ID_URI_PREFIX)?;
+            Some((id, schema))
+        })
+        .collect();
+    let mut inlined = root.clone();
+    let mut stack: Vec<&str> = root
+        .get("$id")
+        .and_then(Value::as_str)
+        .and_then(|id| id.strip_prefix(GTS_ID_URI_PREFIX))
+        .into_iter()
+        .collect();
+    inline_gts_refs(&mut inlined, &available, &mut stack);
+    inlined
+}
+
+/// Inline the `gts://` `$ref`s [`inline_gts_schema`] can satisfy; `stack`
+/// holds the IDs being inlined, to stop on cycles.
+fn inline_gts_refs<'a>(
+    value: &mut Value,
+    available: &std::collections::HashMap<&'a str, &'a Value>,
+    stack: &mut Vec<&'a str>,
+) {
+    match value {
+        Value::Object(map) => {
+            let target = match map.get("$ref") {
+                Some(Value::String(ref_uri)) if map.len() == 1 => ref_uri
+                    .strip_prefix(GTS_ID_URI_PREFIX)
+                    .and_then(|id| available.get_key_value(id))
+                    .filter(|(id, _)| !stack.contains(id)),
+                _ => None,
+            };
+            if let Some((&id, &schema)) = target {
+                let mut def = schema.clone();
+                if let Some(obj) = def.as_object_mut() {
+                    obj.remove("$id");
+                    obj.remove("$schema");
+                }
+                stack.push(id);
+                inline_gts_refs(&mut def, available, stack);
+                stack.pop();
+                *value = def;
+                return;
+            }
+            for (key, v) in map.iter_mut() {
+                // Data-valued keywords hold instance data, not subschemas.
+                if matches!(key.as_str(), "const" | "default" | "examples" | "enum") {
+                    continue;
+                }
+                inline_gts_refs(v, available, stack);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                inline_gts_refs(item, available, stack);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Replace the `#/...` pointer `$ref`s in `fragment` by the subschemas they
+/// point at in `root`, the document `fragment` was taken from.
+///
+/// `schemars` factors nested types (enums, plain structs) into the root's
+/// `$defs`; a property schema lifted out of that root keeps refs that no
+/// longer resolve. Inlining them makes the fragment self-contained. Pointers
+/// that do not resolve against `root` are left untouched.
+#[must_use]
+pub fn inline_local_refs(fragment: &Value, root: &Value) -> Value {
+    crate::schema_traits::inline_local_pointers(fragment, root)
+}
+
+/// Rewrite `gts://` `$ref`s that [`bundle_gts_schema`] can satisfy into
+/// internal pointers, queueing each newly referenced ID in `pending`.
+fn rewrite_gts_refs(
+    value: &mut Value,
+    root_id: Option<&str>,
+    available: &std::collections::HashMap<&str, &Value>,
+    pending: &mut Vec<String>,
+) {
+    match value {
+        Value::Object(map) => {
+            if let Some(Value::String(ref_uri)) = map.get_mut("$ref")
+                && let Some(rest) = ref_uri.strip_prefix(GTS_ID_URI_PREFIX)
+            {
+                let (id, fragment) = rest.split_once('#').unwrap_or((rest, ""));
+                if root_id == Some(id) {
+                    *ref_uri = format!("#{fragment}");
+                } else if available.contains_key(id) {
+                    let escaped = id.replace('~', "~0").replace('/', "~1");
+                    let rewritten = format!("#/$defs/{escaped}{fragment}");
+                    pending.push(id.to_owned());
+                    *ref_uri = rewritten;
+                }
+            }
+            for (key, v) in map.iter_mut() {
+                // Data-valued keywords hold instance data, not subschemas.
+                if matches!(
+                    key.as_str(),
+                    "$ref" | "const" | "default" | "examples" | "enum"
+                ) {
+                    continue;
+                }
+                rewrite_gts_refs(v, root_id, available, pending);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                rewrite_gts_refs(item, root_id, available, pending);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Top-level schema keys written first, in this order, by
+/// [`canonical_schema_to_string`]; the rest follow alphabetically.
+const CANONICAL_KEY_ORDER: [&str; 8] = [
+    "$schema",
+    "$id",
+    "title",
+    "description",
+    "type",
+    "allOf",
+    "properties",
+    "required",
+];
+
+/// Put a schema into canonical form for stable, diffable output: every
+/// `required` array, at any depth, is sorted.
+///
+/// Data-valued keywords (`const`, `default`, `examples`, `enum`) hold
+/// instance data and are left untouched. Key order cannot be fixed in a
+/// [`Value`], whose map order depends on `serde_json`'s `preserve_order`
+/// feature; [`canonical_schema_to_string`] applies it when writing.
+pub fn canonicalize_schema(schema: &mut Value) {
+    match schema {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                match key.as_str() {
+                    "const" | "default" | "examples" | "enum" => {}
+                    "required"
+                        if value
+                            .as_array()
+                            .is_some_and(|names| names.iter().all(Value::is_string)) =>
+                    {
+                        if let Value::Array(names) = value {
+                            names.sort_by(|a, b| a.as_str().cmp(&b.as_str()));
+                        }
+                    }
+                    // Also reached for a property *named* `required`.
+                    _ => canonicalize_schema(value),
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(canonicalize_schema),
+        _ => {}
+    }
+}
+
+/// `schema` in canonical form ([`canonicalize_schema`]) as compact JSON, with
+/// the top-level keys in a fixed order (`$schema`, `$id`, `title`,
+/// `description`, `type`, `allOf`, `properties`, `required`, then the rest
+/// alphabetically) and the keys of every nested object sorted.
+///
+/// The order is applied while serializing, so the output is the same
+/// whether or not `serde_json` preserves insertion order.
+#[must_use]
+pub fn canonical_schema_to_string(schema: &Value) -> String {
+    let mut schema = schema.clone();
+    canonicalize_schema(&mut schema);
+    // Serializing a `Value` cannot fail.
+    serde_json::to_string(&CanonicalKeys::top_level(&schema)).unwrap_or_default()
+}
+
+/// [`canonical_schema_to_string`], pretty-printed.
+#[must_use]
+pub fn canonical_schema_to_string_pretty(schema: &Value) -> String {
+    let mut schema = schema.clone();
+    canonicalize_schema(&mut schema);
+    // Serializing a `Value` cannot fail.
+    serde_json::to_string_pretty(&CanonicalKeys::top_level(&schema)).unwrap_or_default()
+}
+
+/// Serializes a [`Value`] with its object keys in canonical order.
+struct CanonicalKeys<'a> {
+    value: &'a Value,
+    top_level: bool,
+}
+
+impl<'a> CanonicalKeys<'a> {
+    fn top_level(value: &'a Value) -> Self {
+        Self {
+            value,
+            top_level: true,
+        }
+    }
+
+    fn nested(value: &'a Value) -> Self {
+        Self {
+            value,
+            top_level: false,
+        }
+    }
+}
+
+impl serde::Serialize for CanonicalKeys<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::{SerializeMap, SerializeSeq};
+
+        match self.value {
+            Value::Object(map) => {
+                // Nested objects rank every key equally, so they sort by name.
+                let rank = |key: &str| {
+                    CANONICAL_KEY_ORDER
+                        .iter()
+                        .position(|k| self.top_level && *k == key)
+                        .unwrap_or(CANONICAL_KEY_ORDER.len())
+                };
+                let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+                entries.sort_by_key(|(key, _)| (rank(key), *key));
+                let mut out = serializer.serialize_map(Some(entries.len()))?;
+                for (key, value) in entries {
+                    out.serialize_entry(key, &Self::nested(value))?;
+                }
+                out.end()
+            }
+            Value::Array(items) => {
+                let mut out = serializer.serialize_seq(Some(items.len()))?;
+                for item in items {
+                    out.serialize_element(&Self::nested(item))?;
+                }
+                out.end()
+            }
+            scalar => scalar.serialize(serializer),
+        }
+    }
 }
 
 /// Strip schema metadata fields ($id, $schema, title, description) for cleaner nested schemas.
@@ -552,6 +1011,22 @@ mod tests {
         assert_eq!(<()>::GENERIC_FIELD, None);
     }
 
+    #[test]
+    fn test_default_version_accessors_parse_type_id() {
+        struct Manual;
+        impl GtsSchema for Manual {
+            const TYPE_ID: &'static str = "gts.x.core.events.type.v1~x.app.items.thing.v2.3~";
+            fn gts_schema_with_refs() -> Value {
+                json!({})
+            }
+        }
+
+        assert_eq!(Manual::gts_schema_version(), 2);
+        assert_eq!(Manual::gts_schema_minor_version(), Some(3));
+        assert_eq!(<()>::gts_schema_version(), 0);
+        assert_eq!(<()>::gts_schema_minor_version(), None);
+    }
+
     #[test]
     fn test_wrap_in_nesting_path_empty_path() {
         let properties = json!({"field1": {"type": "string"}});
@@ -632,6 +1107,80 @@ mod tests {
         assert_eq!(props.get("field1").unwrap(), &json!({"type": "string"}));
     }
 
+    #[test]
+    fn test_canonicalize_schema_sorts_required() {
+        let mut schema = json!({
+            "required": ["b", "a"],
+            "properties": {
+                "required": {"type": "object", "required": ["z", "y"]},
+                "kind": {"enum": ["b", "a"], "default": ["b", "a"]}
+            },
+            "allOf": [{"required": ["d", "c"]}]
+        });
+        canonicalize_schema(&mut schema);
+        assert_eq!(
+            schema,
+            json!({
+                "required": ["a", "b"],
+                "properties": {
+                    "required": {"type": "object", "required": ["y", "z"]},
+                    "kind": {"enum": ["b", "a"], "default": ["b", "a"]}
+                },
+                "allOf": [{"required": ["c", "d"]}]
+            })
+        );
+    }
+
+    #[test]
+    fn test_canonical_schema_to_string_orders_keys() {
+        // Inserted out of order, so the output does not depend on whether
+        // `serde_json` preserves insertion order.
+        let mut schema = serde_json::Map::new();
+        for (key, value) in [
+            ("x-gts-traits", json!({"retention": "P30D", "topic": "t"})),
+            ("required", json!(["b", "a"])),
+            (
+                "properties",
+                json!({"b": {"type": "string"}, "a": {"type": "integer"}}),
+            ),
+            (
+                "allOf",
+                json!([{"$ref": "gts://gts.x.core.events.type.v1~"}]),
+            ),
+            ("additionalProperties", json!(false)),
+            ("type", json!("object")),
+            ("description", json!("An event")),
+            ("title", json!("Event")),
+            (
+                "$id",
+                json!("gts://gts.x.core.events.type.v1~x.core.audit.event.v1~"),
+            ),
+            ("$schema", json!(JSON_SCHEMA_DRAFT_07)),
+        ] {
+            schema.insert(key.to_owned(), value);
+        }
+        let schema = Value::Object(schema);
+
+        assert_eq!(
+            canonical_schema_to_string(&schema),
+            concat!(
+                r#"{"$schema":"http://json-schema.org/draft-07/schema#","#,
+                r#""$id":"gts://gts.x.core.events.type.v1~x.core.audit.event.v1~","#,
+                r#""title":"Event","description":"An event","type":"object","#,
+                r#""allOf":[{"$ref":"gts://gts.x.core.events.type.v1~"}],"#,
+                r#""properties":{"a":{"type":"integer"},"b":{"type":"string"}},"#,
+                r#""required":["a","b"],"additionalProperties":false,"#,
+                r#""x-gts-traits":{"retention":"P30D","topic":"t"}}"#
+            )
+        );
+        let pretty = canonical_schema_to_string_pretty(&schema);
+        assert!(pretty.starts_with("{\n  \"$schema\": "), "{pretty}");
+        assert_eq!(
+            serde_json::from_str::<Value>(&pretty).unwrap(),
+            serde_json::from_str::<Value>(&canonical_schema_to_string(&schema)).unwrap()
+        );
+    }
+
     #[test]
     fn test_strip_schema_metadata_removes_all_metadata() {
         // Test removal of all metadata fields including $id, $schema, title, description
@@ -767,4 +1316,144 @@ mod tests {
         assert_eq!(props_obj.get("properties").unwrap(), &properties);
         assert_eq!(props_obj.get("required").unwrap(), &json!(required));
     }
+
+    #[test]
+    fn test_bundle_gts_schema_inlines_chain_into_defs() {
+        let grandparent = serde_json::json!({
+            "$id": "gts://gts.x.core.events.type.v1~",
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "type": "object"
+        });
+        let parent = serde_json::json!({
+            "$id": "gts://gts.x.core.events.type.v1~x.core.audit.event.v1~",
+            "allOf": [{ "$ref": "gts://gts.x.core.events.type.v1~" }]
+        });
+        let root = serde_json::json!({
+            "$id": "gts://gts.x.core.events.type.v1~x.core.audit.event.v1~x.app.order.v1~",
+            "allOf": [
+                { "$ref": "gts://gts.x.core.events.type.v1~x.core.audit.event.v1~" },
+                { "properties": { "kind": { "const": { "$ref": "gts://gts.x.core.events.type.v1~" } } } }
+            ]
+        });
+
+        let bundled = bundle_gts_schema(&root, &[parent, grandparent]);
+
+        assert_eq!(
+            bundled["allOf"][0]["$ref"],
+            "#/$defs/gts.x.core.events.type.v1~0x.core.audit.event.v1~0"
+        );
+        // Data-valued keywords are left alone.
+        assert_eq!(
+            bundled["allOf"][1]["properties"]["kind"]["const"]["$ref"],
+            "gts://gts.x.core.events.type.v1~"
+        );
+        let defs = bundled["$defs"].as_object().unwrap();
+        let keys: Vec<&String> = defs.keys().collect();
+        assert_eq!(
+            keys,
+            [
+                "gts.x.core.events.type.v1~",
+                "gts.x.core.events.type.v1~x.core.audit.event.v1~"
+            ]
+        );
+        let parent_def = &defs["gts.x.core.events.type.v1~x.core.audit.event.v1~"];
+        assert!(parent_def.get("$id").is_none());
+        assert_eq!(
+            parent_def["allOf"][0]["$ref"],
+            "#/$defs/gts.x.core.events.type.v1~0"
+        );
+        assert!(defs["gts.x.core.events.type.v1~"].get("$schema").is_none());
+    }
+
+    #[test]
+    fn test_inline_gts_schema_replaces_chain_refs() {
+        let grandparent = serde_json::json!({
+            "$id": "gts://gts.x.core.events.type.v1~",
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "properties": { "id": { "type": "string" } }
+        });
+        let parent = serde_json::json!({
+            "$id": "gts://gts.x.core.events.type.v1~x.core.audit.event.v1~",
+            "allOf": [{ "$ref": "gts://gts.x.core.events.type.v1~" }]
+        });
+        let root = serde_json::json!({
+            "$id": "gts://gts.x.core.events.type.v1~x.core.audit.event.v1~x.app.order.v1~",
+            "allOf": [
+                { "$ref": "gts://gts.x.core.events.type.v1~x.core.audit.event.v1~" },
+                { "properties": { "kind": { "const": { "$ref": "gts://gts.x.core.events.type.v1~" } } } }
+            ]
+        });
+
+        let inlined = inline_gts_schema(&root, &[parent, grandparent]);
+
+        assert_eq!(
+            inlined["allOf"][0],
+            serde_json::json!({
+                "allOf": [{ "properties": { "id": { "type": "string" } } }]
+            })
+        );
+        // Data-valued keywords are left alone.
+        assert_eq!(
+            inlined["allOf"][1]["properties"]["kind"]["const"]["$ref"],
+            "gts://gts.x.core.events.type.v1~"
+        );
+        assert!(inlined.get("$defs").is_none());
+    }
+
+    #[test]
+    fn test_inline_gts_schema_leaves_cycles_and_unknown_refs() {
+        let a = serde_json::json!({
+            "$id": "gts://gts.x.test.cycle.a.v1~",
+            "properties": { "b": { "$ref": "gts://gts.x.test.cycle.b.v1~" } }
+        });
+        let b = serde_json::json!({
+            "$id": "gts://gts.x.test.cycle.b.v1~",
+            "properties": {
+                "a": { "$ref": "gts://gts.x.test.cycle.a.v1~" },
+                "c": { "$ref": "gts://gts.x.test.cycle.c.v1~" },
+                "d": { "$ref": "gts://gts.x.test.cycle.a.v1~#/properties/b" }
+            }
+        });
+
+        let inlined = inline_gts_schema(&a, &[a.clone(), b]);
+
+        let b_inlined = &inlined["properties"]["b"]["properties"];
+        assert_eq!(b_inlined["a"]["$ref"], "gts://gts.x.test.cycle.a.v1~");
+        assert_eq!(b_inlined["c"]["$ref"], "gts://gts.x.test.cycle.c.v1~");
+        assert_eq!(
+            b_inlined["d"]["$ref"],
+            "gts://gts.x.test.cycle.a.v1~#/properties/b"
+        );
+    }
+
+    #[test]
+    fn test_bundle_gts_schema_handles_cycles_and_unknown_refs() {
+        let a = serde_json::json!({
+            "$id": "gts://gts.x.test.cycle.a.v1~",
+            "properties": { "b": { "$ref": "gts://gts.x.test.cycle.b.v1~" } }
+        });
+        let b = serde_json::json!({
+            "$id": "gts://gts.x.test.cycle.b.v1~",
+            "properties": {
+                "a": { "$ref": "gts://gts.x.test.cycle.a.v1~#/properties/b" },
+                "c": { "$ref": "gts://gts.x.test.cycle.c.v1~" }
+            }
+        });
+
+        let bundled = bundle_gts_schema(&a, &[a.clone(), b]);
+
+        let b_def = &bundled["$defs"]["gts.x.test.cycle.b.v1~"];
+        assert_eq!(b_def["properties"]["a"]["$ref"], "#/properties/b");
+        assert_eq!(
+            b_def["properties"]["c"]["$ref"],
+            "gts://gts.x.test.cycle.c.v1~"
+        );
+        assert_eq!(bundled["$defs"].as_object().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_bundle_gts_schema_without_refs_is_unchanged() {
+        let root = serde_json::json!({ "$id": "gts://gts.x.test.plain.v1~", "type": "object" });
+        assert_eq!(bundle_gts_schema(&root, &[]), root);
+    }
 }
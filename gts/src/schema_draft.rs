@@ -0,0 +1,265 @@
+//! JSON Schema dialect selection for generated GTS schemas.
+//!
+//! Generated schemas default to draft-07 ([`crate::JSON_SCHEMA_DRAFT_07`]);
+//! [`SchemaDraft::Draft2020_12`] targets registries that validate against
+//! draft 2020-12. [`convert_schema_draft`] rewrites the keywords whose
+//! spelling differs between the two dialects:
+//!
+//! | draft-07 | draft 2020-12 |
+//! |---|---|
+//! | `definitions` (and `#/definitions/...` refs) | `$defs` (and `#/$defs/...` refs) |
+//! | `items: [..]` + `additionalItems` | `prefixItems: [..]` + `items` |
+//!
+//! `exclusiveMinimum`/`exclusiveMaximum` are numeric in both dialects (the
+//! boolean form predates draft-06), so they are left as they are.
+
+use serde_json::{Map, Value};
+
+use crate::schema::JSON_SCHEMA_DRAFT_07;
+
+/// The JSON Schema **draft 2020-12** dialect URI.
+pub const JSON_SCHEMA_DRAFT_2020_12: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// JSON Schema dialect of a generated schema.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum SchemaDraft {
+    /// Draft-07 (the GTS default).
+    #[default]
+    Draft07,
+    /// Draft 2020-12.
+    Draft2020_12,
+}
+
+impl SchemaDraft {
+    /// The `$schema` URI of this dialect.
+    #[must_use]
+    pub const fn uri(self) -> &'static str {
+        match self {
+            SchemaDraft::Draft07 => JSON_SCHEMA_DRAFT_07,
+            SchemaDraft::Draft2020_12 => JSON_SCHEMA_DRAFT_2020_12,
+        }
+    }
+
+    /// Parse the short name used by the `draft = "..."` macro options:
+    /// `"07"` or `"2020-12"`. `const` so an unknown name passed to
+    /// `gts_schema_for!` fails at compile time.
+    #[must_use]
+    pub const fn from_name(name: &str) -> Option<Self> {
+        if bytes_eq(name.as_bytes(), b"07") {
+            Some(SchemaDraft::Draft07)
+        } else if bytes_eq(name.as_bytes(), b"2020-12") {
+            Some(SchemaDraft::Draft2020_12)
+        } else {
+            None
+        }
+    }
+}
+
+/// The dialect named by `GTS_SCHEMA_DRAFT`, as `#[struct_to_gts_schema]`
+/// reads it with `option_env!` in the crate being compiled; `None` when the
+/// variable is unset.
+///
+/// # Panics
+///
+/// On a name other than `"07"` or `"2020-12"`. The generated code calls this
+/// in a `const`, so the panic is a compile error.
+#[doc(hidden)]
+#[must_use]
+pub const fn draft_from_env(name: Option<&str>) -> Option<SchemaDraft> {
+    match name {
+        None => None,
+        Some(name) => match SchemaDraft::from_name(name) {
+            Some(draft) => Some(draft),
+            None => panic!("unsupported GTS_SCHEMA_DRAFT, expected \"07\" or \"2020-12\""),
+        },
+    }
+}
+
+const fn bytes_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Rewrite `schema` into the keyword dialect of `draft`, setting the root
+/// `$schema` (when present) to the dialect URI.
+#[must_use]
+pub fn convert_schema_draft(schema: &Value, draft: SchemaDraft) -> Value {
+    let mut converted = schema.clone();
+    convert_subschema(&mut converted, draft);
+    if let Some(obj) = converted.as_object_mut()
+        && obj.contains_key("$schema")
+    {
+        obj.insert("$schema".to_owned(), Value::String(draft.uri().to_owned()));
+    }
+    converted
+}
+
+fn convert_subschema(value: &mut Value, draft: SchemaDraft) {
+    let Value::Object(map) = value else {
+        return;
+    };
+    match draft {
+        SchemaDraft::Draft07 => to_draft_07(map),
+        SchemaDraft::Draft2020_12 => to_draft_2020_12(map),
+    }
+    if let Some(Value::String(ref_uri)) = map.get_mut("$ref") {
+        let (from, to) = match draft {
+            SchemaDraft::Draft07 => ("#/$defs", "#/definitions"),
+            SchemaDraft::Draft2020_12 => ("#/definitions", "#/$defs"),
+        };
+        if let Some(rest) = ref_uri.strip_prefix(from)
+            && (rest.is_empty() || rest.starts_with('/'))
+        {
+            *ref_uri = format!("{to}{rest}");
+        }
+    }
+
+    for (key, child) in map.iter_mut() {
+        match key.as_str() {
+            // Data-valued keywords hold instance data, not subschemas.
+            "const" | "default" | "examples" | "enum" | "x-gts-traits" => {}
+            // Name -> subschema maps: the keys are names, not keywords.
+            "properties" | "patternProperties" | "$defs" | "definitions" | "dependentSchemas" => {
+                if let Value::Object(named) = child {
+                    for subschema in named.values_mut() {
+                        convert_subschema(subschema, draft);
+                    }
+                }
+            }
+            _ => match child {
+                Value::Object(_) => convert_subschema(child, draft),
+                Value::Array(items) => {
+                    for item in items {
+                        convert_subschema(item, draft);
+                    }
+                }
+                _ => {}
+            },
+        }
+    }
+}
+
+fn to_draft_07(map: &mut Map<String, Value>) {
+    if let Some(defs) = map.remove("$defs") {
+        map.insert("definitions".to_owned(), defs);
+    }
+    if let Some(prefix) = map.remove("prefixItems") {
+        if let Some(rest) = map.remove("items") {
+            map.insert("additionalItems".to_owned(), rest);
+        }
+        map.insert("items".to_owned(), prefix);
+    }
+}
+
+fn to_draft_2020_12(map: &mut Map<String, Value>) {
+    if let Some(defs) = map.remove("definitions") {
+        map.insert("$defs".to_owned(), defs);
+    }
+    if matches!(map.get("items"), Some(Value::Array(_))) {
+        if let Some(prefix) = map.remove("items") {
+            map.insert("prefixItems".to_owned(), prefix);
+        }
+        if let Some(rest) = map.remove("additionalItems") {
+            map.insert("items".to_owned(), rest);
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn draft_07_schema() -> Value {
+        json!({
+            "$schema": JSON_SCHEMA_DRAFT_07,
+            "type": "object",
+            "properties": {
+                "pair": {
+                    "type": "array",
+                    "items": [{ "type": "integer" }, { "$ref": "#/definitions/Name" }],
+                    "additionalItems": false
+                },
+                "items": { "type": "string", "default": { "items": [] } },
+                "list": { "type": "array", "items": { "type": "integer" } }
+            },
+            "definitions": { "Name": { "type": "string" } }
+        })
+    }
+
+    fn draft_2020_12_schema() -> Value {
+        json!({
+            "$schema": JSON_SCHEMA_DRAFT_2020_12,
+            "type": "object",
+            "properties": {
+                "pair": {
+                    "type": "array",
+                    "prefixItems": [{ "type": "integer" }, { "$ref": "#/$defs/Name" }],
+                    "items": false
+                },
+                "items": { "type": "string", "default": { "items": [] } },
+                "list": { "type": "array", "items": { "type": "integer" } }
+            },
+            "$defs": { "Name": { "type": "string" } }
+        })
+    }
+
+    #[test]
+    fn test_draft_from_env() {
+        const DRAFT: Option<SchemaDraft> = draft_from_env(Some("2020-12"));
+        assert_eq!(DRAFT, Some(SchemaDraft::Draft2020_12));
+        assert_eq!(draft_from_env(Some("07")), Some(SchemaDraft::Draft07));
+        assert_eq!(draft_from_env(None), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "unsupported GTS_SCHEMA_DRAFT")]
+    fn test_draft_from_env_rejects_unknown_name() {
+        let _ = draft_from_env(Some("2019-09"));
+    }
+
+    #[test]
+    fn test_convert_07_to_2020_12() {
+        let converted = convert_schema_draft(&draft_07_schema(), SchemaDraft::Draft2020_12);
+        assert_eq!(converted, draft_2020_12_schema());
+    }
+
+    #[test]
+    fn test_convert_2020_12_to_07() {
+        let converted = convert_schema_draft(&draft_2020_12_schema(), SchemaDraft::Draft07);
+        assert_eq!(converted, draft_07_schema());
+    }
+
+    #[test]
+    fn test_convert_is_idempotent() {
+        let once = convert_schema_draft(&draft_07_schema(), SchemaDraft::Draft07);
+        assert_eq!(once, draft_07_schema());
+    }
+
+    #[test]
+    fn test_convert_without_schema_keyword_leaves_it_absent() {
+        let converted =
+            convert_schema_draft(&json!({ "type": "string" }), SchemaDraft::Draft2020_12);
+        assert!(converted.get("$schema").is_none());
+    }
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(SchemaDraft::from_name("07"), Some(SchemaDraft::Draft07));
+        assert_eq!(
+            SchemaDraft::from_name("2020-12"),
+            Some(SchemaDraft::Draft2020_12)
+        );
+        assert_eq!(SchemaDraft::from_name("2019-09"), None);
+    }
+}
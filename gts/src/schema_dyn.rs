@@ -0,0 +1,137 @@
+//! Object-safe access to [`GtsSchema`] types.
+//!
+//! `GtsSchema` is made of associated consts and static methods, so it cannot
+//! be used as a trait object. [`GtsSchemaDyn`] exposes the same information
+//! through `&self`, which lets registration code hold schema types of
+//! different Rust types side by side:
+//!
+//! ```ignore
+//! use gts::GtsSchemaDyn;
+//!
+//! // Types, with no value at hand.
+//! let types: Vec<Box<dyn GtsSchemaDyn>> = vec![
+//!     gts::erase::<BaseEventV1<()>>(),
+//!     gts::erase::<TopicV1>(),
+//! ];
+//! for schema in &types {
+//!     ops.add_schema(schema.schema_id().to_owned(), &schema.schema_with_refs());
+//! }
+//!
+//! // Values, which also serialize as instances.
+//! let instances: Vec<Box<dyn GtsSchemaDyn>> = vec![Box::new(event), Box::new(topic)];
+//! for instance in &instances {
+//!     let json = instance.instance_json()?.expect("a value");
+//!     ops.add_entity(&json, true);
+//! }
+//! ```
+//!
+//! The types registered by `#[struct_to_gts_schema]` are also available this
+//! way, through [`SchemaIndexEntry::schema`](crate::SchemaIndexEntry::schema)
+//! and [`registered_schema`](crate::registered_schema).
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::schema::GtsSchema;
+
+/// Object-safe companion of [`GtsSchema`], implemented for every value of a
+/// `GtsSchema + Serialize` type and for the types themselves via [`erase`].
+pub trait GtsSchemaDyn {
+    /// The GTS type ID ([`GtsSchema::TYPE_ID`]).
+    fn schema_id(&self) -> &'static str;
+
+    /// The JSON schema with `$ref` references intact
+    /// ([`GtsSchema::gts_schema_with_refs`]).
+    fn schema_with_refs(&self) -> Value;
+
+    /// The value serialized as a JSON instance, or `None` for a type erased
+    /// with [`erase`], which has no value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value cannot be serialized to JSON, e.g. a
+    /// map with non-string keys.
+    fn instance_json(&self) -> serde_json::Result<Option<Value>>;
+}
+
+impl<T: GtsSchema + Serialize> GtsSchemaDyn for T {
+    fn schema_id(&self) -> &'static str {
+        T::TYPE_ID
+    }
+
+    fn schema_with_refs(&self) -> Value {
+        T::gts_schema_with_refs()
+    }
+
+    fn instance_json(&self) -> serde_json::Result<Option<Value>> {
+        serde_json::to_value(self).map(Some)
+    }
+}
+
+impl fmt::Debug for dyn GtsSchemaDyn + '_ {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("GtsSchemaDyn")
+            .field(&self.schema_id())
+            .finish()
+    }
+}
+
+impl fmt::Debug for dyn GtsSchemaDyn + Sync + '_ {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self as &dyn GtsSchemaDyn, f)
+    }
+}
+
+/// The type `T` as a [`GtsSchemaDyn`] without a value, so
+/// [`instance_json`](GtsSchemaDyn::instance_json) returns `None`.
+///
+/// Generic types are erased with their parameters, e.g.
+/// `erase::<BaseEventV1<()>>()`.
+#[must_use]
+pub fn erase<T: GtsSchema + 'static>() -> Box<dyn GtsSchemaDyn> {
+    Box::new(ErasedSchema::<T>(PhantomData))
+}
+
+/// A [`GtsSchema`] type without a value. Built by [`erase`], and by
+/// `#[struct_to_gts_schema]` through [`ErasedSchema::DYN`].
+#[doc(hidden)]
+pub struct ErasedSchema<T: ?Sized>(PhantomData<fn() -> T>);
+
+impl<T: GtsSchema + 'static> ErasedSchema<T> {
+    /// `T` as a static [`GtsSchemaDyn`], for the link-time registry.
+    pub const DYN: &'static (dyn GtsSchemaDyn + Sync) = &Self(PhantomData);
+}
+
+impl<T: GtsSchema> GtsSchemaDyn for ErasedSchema<T> {
+    fn schema_id(&self) -> &'static str {
+        T::TYPE_ID
+    }
+
+    fn schema_with_refs(&self) -> Value {
+        T::gts_schema_with_refs()
+    }
+
+    fn instance_json(&self) -> serde_json::Result<Option<Value>> {
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value_and_erased_type_differ_only_in_instance() {
+        let value = serde_json::json!({"id": 1});
+        let schemas: Vec<Box<dyn GtsSchemaDyn>> = vec![Box::new(value.clone()), erase::<Value>()];
+        assert_eq!(schemas[0].schema_id(), schemas[1].schema_id());
+        assert_eq!(schemas[0].schema_with_refs(), schemas[1].schema_with_refs());
+        assert_eq!(schemas[0].instance_json().unwrap(), Some(value));
+        assert_eq!(schemas[1].instance_json().unwrap(), None);
+        assert_eq!(format!("{:?}", schemas[1]), "GtsSchemaDyn(\"\")");
+    }
+}
@@ -0,0 +1,165 @@
+//! Generic-field extension contract (`x-gts-generic-field`).
+//!
+//! A generic base type has one extensible slot (e.g. `payload`) that derived
+//! types plug into. The base advertises the slot's property name as
+//! `x-gts-generic-field`. For a chain, the slot's full path from the document
+//! root is the ancestors' generic fields in order: `payload`, then `data`
+//! inside it, and so on. A derived schema's own properties (top-level
+//! `properties` and its `allOf` branches) may then only add constraints under
+//! that path.
+//!
+//! One exception: a top-level property whose schema is exactly
+//! `{"const": "<derived type id>"}` is allowed. That is how a derived type
+//! pins the base's GTS Type field (`inject_type_field`) to its own ID.
+
+use serde_json::{Map, Value};
+
+pub const X_GTS_GENERIC_FIELD: &str = "x-gts-generic-field";
+
+/// The generic field name a schema advertises, if any.
+///
+/// # Errors
+/// Returns an error if `x-gts-generic-field` is not a non-empty string.
+pub fn generic_field(schema: &Value) -> Result<Option<&str>, String> {
+    match schema.get(X_GTS_GENERIC_FIELD) {
+        None => Ok(None),
+        Some(Value::String(name)) if !name.is_empty() => Ok(Some(name)),
+        Some(_) => Err(format!("{X_GTS_GENERIC_FIELD} must be a property name")),
+    }
+}
+
+/// The first own property of `derived` that lies outside `generic_path`, as a
+/// dot-separated path (e.g. `payload.extra`), or `None` if the schema conforms.
+///
+/// `derived_id` is the derived schema's type ID, used to recognise a pinned
+/// type field.
+#[must_use]
+pub fn find_nonconformant_property(
+    derived: &Value,
+    derived_id: &str,
+    generic_path: &[String],
+) -> Option<String> {
+    let branches = derived
+        .get("allOf")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .chain(std::iter::once(derived));
+    branches
+        .filter_map(|branch| branch.get("properties").and_then(Value::as_object))
+        .find_map(|properties| {
+            let mut prefix = Vec::new();
+            check_properties(properties, derived_id, generic_path, &mut prefix)
+        })
+}
+
+fn check_properties<'a>(
+    properties: &'a Map<String, Value>,
+    derived_id: &str,
+    generic_path: &[String],
+    prefix: &mut Vec<&'a str>,
+) -> Option<String> {
+    let (field, rest) = generic_path.split_first()?;
+    for (name, schema) in properties {
+        if name == field {
+            if let Some(nested) = schema.get("properties").and_then(Value::as_object) {
+                prefix.push(name);
+                let found = check_properties(nested, derived_id, rest, prefix);
+                prefix.pop();
+                if found.is_some() {
+                    return found;
+                }
+            }
+        } else if !(prefix.is_empty() && pins_type_id(schema, derived_id)) {
+            let mut path = prefix.join(".");
+            if !path.is_empty() {
+                path.push('.');
+            }
+            path.push_str(name);
+            return Some(path);
+        }
+    }
+    None
+}
+
+/// `true` for a property schema that is exactly `{"const": derived_id}`.
+fn pins_type_id(schema: &Value, derived_id: &str) -> bool {
+    schema
+        .as_object()
+        .is_some_and(|map| map.len() == 1 && map.get("const") == Some(&Value::from(derived_id)))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const DERIVED: &str = "gts.x.core.events.type.v1~x.core.audit.event.v1~";
+
+    fn path(names: &[&str]) -> Vec<String> {
+        names.iter().map(|&name| name.to_owned()).collect()
+    }
+
+    #[test]
+    fn test_generic_field() {
+        assert_eq!(generic_field(&json!({})).unwrap(), None);
+        assert_eq!(
+            generic_field(&json!({ X_GTS_GENERIC_FIELD: "payload" })).unwrap(),
+            Some("payload")
+        );
+        for bad in [json!(""), json!(["payload"]), json!(true)] {
+            assert!(generic_field(&json!({ X_GTS_GENERIC_FIELD: bad })).is_err());
+        }
+    }
+
+    #[test]
+    fn test_conforming_extension() {
+        let derived = json!({
+            "allOf": [
+                { "$ref": "gts://gts.x.core.events.type.v1~" },
+                { "properties": {
+                    "type": { "const": DERIVED },
+                    "payload": { "properties": { "data": {
+                        "properties": { "user_id": { "type": "string" } }
+                    } } }
+                } }
+            ]
+        });
+        assert_eq!(
+            find_nonconformant_property(&derived, DERIVED, &path(&["payload", "data"])),
+            None
+        );
+    }
+
+    #[test]
+    fn test_property_outside_generic_field() {
+        let derived = json!({
+            "allOf": [
+                { "$ref": "gts://gts.x.core.events.type.v1~" },
+                { "properties": { "payload": { "properties": { "extra": {} } } } }
+            ]
+        });
+        assert_eq!(
+            find_nonconformant_property(&derived, DERIVED, &path(&["payload", "data"])),
+            Some("payload.extra".to_owned())
+        );
+
+        let top_level = json!({ "properties": { "tenant_id": { "format": "uuid" } } });
+        assert_eq!(
+            find_nonconformant_property(&top_level, DERIVED, &path(&["payload"])),
+            Some("tenant_id".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_only_own_type_id_pin_is_exempt() {
+        let derived = json!({
+            "properties": { "type": { "const": "gts.x.core.events.type.v1~" } }
+        });
+        assert_eq!(
+            find_nonconformant_property(&derived, DERIVED, &path(&["payload"])),
+            Some("type".to_owned())
+        );
+    }
+}
@@ -34,7 +34,7 @@ pub enum ExtractRefsError {
 }
 
 /// How a single `$ref` value is interpreted under GTS rules.
-enum RefKind<'a> {
+pub(crate) enum RefKind<'a> {
     /// Internal JSON Pointer (`#`, `#/...`); not an external dependency.
     Local,
     /// External GTS type dependency: the canonical id (scheme + fragment
@@ -49,7 +49,7 @@ enum RefKind<'a> {
 ///
 /// External references MUST use the `gts://` scheme; a bare id (no scheme) is
 /// rejected, matching what the store is able to register and retrieve.
-fn classify_ref(ref_uri: &str) -> Result<RefKind<'_>, InvalidRefReason> {
+pub(crate) fn classify_ref(ref_uri: &str) -> Result<RefKind<'_>, InvalidRefReason> {
     // Local JSON Pointers (`#`, `#/...`) are always valid and not external edges.
     if ref_uri.starts_with('#') {
         return Ok(RefKind::Local);
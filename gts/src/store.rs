@@ -3,7 +3,8 @@ use serde_json::Value;
 use std::collections::HashMap;
 use thiserror::Error;
 
-use crate::entities::GtsEntity;
+use crate::bundle::{BundleError, BundleReport};
+use crate::entities::{GtsEntity, ValidationError};
 use crate::gts::{GtsId, GtsIdError, GtsIdPattern};
 use crate::schema_cast::GtsEntityCastResult;
 
@@ -25,6 +26,14 @@ pub enum StoreError {
     CircularRef,
     #[error("Unresolved $ref(s): {}", .0.join(", "))]
     UnresolvedRefs(Vec<String>),
+    #[error(
+        "Schema '{type_id}' extends base '{base_id}' outside its generic field: property '{path}'"
+    )]
+    NonConformantExtension {
+        type_id: String,
+        base_id: String,
+        path: String,
+    },
 }
 
 pub trait GtsReader: Send {
@@ -117,6 +126,19 @@ impl GtsStore {
         store
     }
 
+    /// Check that the schema bundle in `dir` is self-contained: every
+    /// `gts://` `$ref` resolves within it, every `$id` is unique, the `$ref`
+    /// graph is acyclic and all schemas declare the same `$schema`. Reads
+    /// every `*.json` file whose `$id` is a `gts://` URI, without loading
+    /// them into a store.
+    ///
+    /// # Errors
+    /// Returns [`BundleError`] if the directory cannot be walked or a JSON
+    /// file cannot be read or parsed.
+    pub fn verify_bundle(dir: &std::path::Path) -> Result<BundleReport, BundleError> {
+        crate::bundle::verify_dir(dir)
+    }
+
     fn populate_from_reader(&mut self) {
         if let Some(ref mut reader) = self.reader {
             for entity in reader.iter() {
@@ -388,7 +410,9 @@ impl GtsStore {
     /// The heavy lifting is delegated to [`crate::schema_compat`].
     ///
     /// # Errors
-    /// Returns `StoreError::ValidationError` if any derived schema loosens base constraints.
+    /// Returns `StoreError::ValidationError` if any derived schema loosens base
+    /// constraints, or `StoreError::NonConformantExtension` if it constrains a
+    /// property outside the base's `x-gts-generic-field`.
     pub(crate) fn validate_schema_chain(&mut self, gts_id: &str) -> Result<(), StoreError> {
         let gid = GtsId::try_new(gts_id)
             .map_err(|e| StoreError::ValidationError(format!("Invalid GTS ID: {e}")))?;
@@ -400,6 +424,9 @@ impl GtsStore {
 
         // Build pairs of (base_id, derived_id) for each adjacent level
         let chain_ids = gid.chain_ids();
+        // Path of the base's generic field from the document root, while every
+        // ancestor so far advertises one.
+        let mut generic_path = Some(Vec::new());
         for i in 0..chain_ids.len() - 1 {
             let base_id = &chain_ids[i];
             let derived_id = &chain_ids[i + 1];
@@ -434,6 +461,28 @@ impl GtsStore {
                 ))
             })?;
 
+            // A base that advertises its generic field only lets derived
+            // schemas add constraints under it.
+            let generic_field = crate::schema_generic::generic_field(&base_content)
+                .map_err(|e| StoreError::ValidationError(format!("Schema '{base_id}': {e}")))?;
+            generic_path = generic_path.zip(generic_field).map(|(mut path, field)| {
+                path.push(field.to_owned());
+                path
+            });
+            if let Some(generic_path) = &generic_path
+                && let Some(path) = crate::schema_generic::find_nonconformant_property(
+                    &derived_content,
+                    derived_id,
+                    generic_path,
+                )
+            {
+                return Err(StoreError::NonConformantExtension {
+                    type_id: derived_id.clone(),
+                    base_id: base_id.clone(),
+                    path,
+                });
+            }
+
             let base_resolved = self
                 .resolve_schema_refs(&base_content)
                 .map_err(|e| StoreError::ValidationError(format!("Schema '{base_id}' has {e}")))?;
@@ -660,9 +709,109 @@ impl GtsStore {
     /// `StoreError::SchemaNotFound` if the type is not registered.
     pub fn validate_payload(&mut self, type_id: &str, payload: &Value) -> Result<(), StoreError> {
         let content = self.get_schema_content(type_id)?;
+        let (validator, resolved_schema) = self.payload_validator(type_id, &content)?;
 
+        let errors: Vec<String> = validator
+            .iter_errors(payload)
+            .map(|e| e.to_string())
+            .collect();
+        if !errors.is_empty() {
+            return Err(StoreError::ValidationError(format!(
+                "Validation failed: {}",
+                errors.join(", ")
+            )));
+        }
+
+        let xref = crate::x_gts_ref::XGtsRefValidator::new();
+        let xref_errors = xref.validate_instance(payload, &resolved_schema, "");
+        Self::check_x_gts_ref_errors(&xref_errors)?;
+
+        Ok(())
+    }
+
+    /// Every violation of `payload` against `type_id`'s schema, one per JSON
+    /// Schema or `x-gts-ref` error and located by the JSON Pointer in
+    /// `instance_path`; empty when the payload is valid.
+    ///
+    /// Like [`Self::validate_payload`], but the violations are returned
+    /// individually, and only entities already in the store are used (no
+    /// reader fallback), so the store can be shared.
+    ///
+    /// # Errors
+    /// `StoreError::SchemaNotFound` if the type is not in the store;
+    /// `StoreError::ValidationError` if it is abstract or its schema does not
+    /// compile; `StoreError::InvalidTypeId` / `InvalidEntity` as for
+    /// [`Self::validate_payload`].
+    pub fn payload_violations(
+        &self,
+        type_id: &str,
+        payload: &Value,
+    ) -> Result<Vec<ValidationError>, StoreError> {
+        crate::GtsTypeId::try_new(type_id).map_err(StoreError::InvalidTypeId)?;
+        let content = match self.by_id.get(type_id) {
+            Some(entity) if entity.is_schema => &entity.content,
+            Some(_) => {
+                return Err(StoreError::InvalidEntity(format!(
+                    "Entity '{type_id}' is not a schema"
+                )));
+            }
+            None => return Err(StoreError::SchemaNotFound(type_id.to_owned())),
+        };
+        let (validator, resolved_schema) = self.payload_validator(type_id, content)?;
+
+        let mut violations: Vec<ValidationError> = validator
+            .iter_errors(payload)
+            .map(|e| {
+                let schema_path = e.schema_path().as_str().to_owned();
+                let keyword = schema_path
+                    .rsplit('/')
+                    .next()
+                    .unwrap_or_default()
+                    .to_owned();
+                ValidationError {
+                    instance_path: e.instance_path().as_str().to_owned(),
+                    schema_path,
+                    keyword,
+                    message: e.to_string(),
+                    params: HashMap::new(),
+                    data: None,
+                }
+            })
+            .collect();
+        let xref = crate::x_gts_ref::XGtsRefValidator::new();
+        violations.extend(
+            xref.validate_instance(payload, &resolved_schema, "")
+                .into_iter()
+                .map(|e| ValidationError {
+                    // x-gts-ref paths are dotted (`payload.type`).
+                    instance_path: e
+                        .field_path
+                        .split('.')
+                        .filter(|segment| !segment.is_empty())
+                        .fold(String::new(), |mut pointer, segment| {
+                            pointer.push('/');
+                            pointer.push_str(segment);
+                            pointer
+                        }),
+                    schema_path: String::new(),
+                    keyword: "x-gts-ref".to_owned(),
+                    message: e.reason,
+                    params: HashMap::new(),
+                    data: None,
+                }),
+        );
+        Ok(violations)
+    }
+
+    /// The compiled validator for payloads of `type_id`, whose schema is
+    /// `content`, and the resolved schema it was compiled from.
+    fn payload_validator(
+        &self,
+        type_id: &str,
+        content: &Value,
+    ) -> Result<(jsonschema::Validator, Value), StoreError> {
         // Abstract types cannot have direct instances (OP#6).
-        if Self::content_is_abstract(&content) {
+        if Self::content_is_abstract(content) {
             return Err(StoreError::ValidationError(format!(
                 "type '{type_id}' is abstract and cannot have direct instances"
             )));
@@ -672,7 +821,7 @@ impl GtsStore {
         // schema-level metadata (§9.7) and never appear in instances, so the
         // effective-traits build is deliberately skipped here.
         let resolved_schema = self
-            .resolve_schema_refs(&content)
+            .resolve_schema_refs(content)
             .map_err(|e| StoreError::ValidationError(format!("Schema '{type_id}' has {e}")))?;
 
         // Strip x-gts-ref before compiling; resolve_schema_refs has already
@@ -683,23 +832,7 @@ impl GtsStore {
             .map_err(|e| {
                 StoreError::ValidationError(format!("Invalid schema for '{type_id}': {e}"))
             })?;
-
-        let errors: Vec<String> = validator
-            .iter_errors(payload)
-            .map(|e| e.to_string())
-            .collect();
-        if !errors.is_empty() {
-            return Err(StoreError::ValidationError(format!(
-                "Validation failed: {}",
-                errors.join(", ")
-            )));
-        }
-
-        let xref = crate::x_gts_ref::XGtsRefValidator::new();
-        let xref_errors = xref.validate_instance(payload, &resolved_schema, "");
-        Self::check_x_gts_ref_errors(&xref_errors)?;
-
-        Ok(())
+        Ok((validator, resolved_schema))
     }
 
     /// Validates an instance against its schema.
@@ -4558,6 +4558,52 @@ fn test_validate_payload_ok_and_reject() {
     );
 }
 
+#[test]
+fn test_payload_violations_locates_each_error() {
+    let mut store = GtsStore::new();
+    store
+        .register_schema(
+            "gts.x.vp.tr.viol.v1~",
+            &json!({
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "type": "object",
+                "required": ["id", "items"],
+                "properties": {
+                    "id": {"type": "string"},
+                    "ref": {"type": "string", "x-gts-ref": "gts.x.vp.tr.viol.v1~"},
+                    "items": {"type": "array", "items": {"type": "integer"}}
+                }
+            }),
+        )
+        .unwrap();
+
+    let violations = store
+        .payload_violations("gts.x.vp.tr.viol.v1~", &json!({"id": "x", "items": [1]}))
+        .unwrap();
+    assert!(violations.is_empty(), "{violations:?}");
+
+    let violations = store
+        .payload_violations(
+            "gts.x.vp.tr.viol.v1~",
+            &json!({"id": 1, "ref": "gts.x.other.ns.type.v1~", "items": [1, "two"]}),
+        )
+        .unwrap();
+    let mut located: Vec<(&str, &str)> = violations
+        .iter()
+        .map(|v| (v.instance_path.as_str(), v.keyword.as_str()))
+        .collect();
+    located.sort_unstable();
+    assert_eq!(
+        located,
+        [("/id", "type"), ("/items/1", "type"), ("/ref", "x-gts-ref")]
+    );
+
+    assert!(matches!(
+        store.payload_violations("gts.x.vp.tr.missing.v1~", &json!({})),
+        Err(StoreError::SchemaNotFound(_))
+    ));
+}
+
 #[test]
 fn test_validate_payload_rejects_abstract_type() {
     let mut store = GtsStore::new();
@@ -5910,3 +5956,65 @@ fn test_validate_instance_reports_unresolvable_ref() {
             .contains("Unresolved $ref(s): gts://gts.vendor.package.namespace.nonexistent.v1.0~")
     );
 }
+
+#[test]
+fn test_validate_schema_rejects_extension_outside_generic_field() {
+    let base_id = "gts.x.test.generic.event.v1~";
+    let mut store = GtsStore::new();
+    store
+        .register_schema(
+            base_id,
+            &json!({
+                "$id": format!("gts://{base_id}"),
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "type": "object",
+                "x-gts-generic-field": "payload",
+                "properties": {
+                    "source": {"type": "string"},
+                    "payload": {"type": "object"}
+                }
+            }),
+        )
+        .expect("register base");
+
+    let conforming_id = "gts.x.test.generic.event.v1~x.test._.created.v1~";
+    let violating_id = "gts.x.test.generic.event.v1~x.test._.tagged.v1~";
+    for (id, overlay) in [
+        (
+            conforming_id,
+            json!({"payload": {"properties": {"name": {"type": "string"}}}}),
+        ),
+        (violating_id, json!({"source": {"maxLength": 16}})),
+    ] {
+        store
+            .register_schema(
+                id,
+                &json!({
+                    "$id": format!("gts://{id}"),
+                    "$schema": "http://json-schema.org/draft-07/schema#",
+                    "type": "object",
+                    "allOf": [
+                        {"$ref": format!("gts://{base_id}")},
+                        {"type": "object", "properties": overlay}
+                    ]
+                }),
+            )
+            .expect("register derived");
+    }
+
+    store
+        .validate_schema(conforming_id)
+        .expect("extension under the generic field is allowed");
+    match store.validate_schema(violating_id) {
+        Err(StoreError::NonConformantExtension {
+            type_id,
+            base_id: base,
+            path,
+        }) => {
+            assert_eq!(type_id, violating_id);
+            assert_eq!(base, base_id);
+            assert_eq!(path, "source");
+        }
+        other => panic!("expected NonConformantExtension, got {other:?}"),
+    }
+}
@@ -0,0 +1,152 @@
+//! Link-time registry of `#[struct_to_gts_schema]` types.
+//!
+//! Every annotated type registers a [`SchemaIndexEntry`], so tooling can list
+//! all schemas a binary knows about with [`schema_index`] or
+//! [`generate_schema_index_json`], and look one up as a
+//! [`GtsSchemaDyn`] with [`registered_schema`].
+//!
+//! Types annotated with `check_unique = true` also register their type ID here.
+//! A proc macro only sees one type at a time, so duplicates across a crate
+//! (or its dependencies) are detected by calling [`assert_unique_type_ids`]
+//! from a test:
+//!
+//! ```ignore
+//! #[test]
+//! fn gts_type_ids_are_unique() {
+//!     gts::assert_unique_type_ids();
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde_json::{Value, json};
+
+use crate::schema_dyn::GtsSchemaDyn;
+
+#[doc(hidden)]
+pub use inventory;
+
+/// A type ID registered by `#[struct_to_gts_schema(check_unique = true)]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GtsTypeRegistration {
+    /// The GTS type ID.
+    pub type_id: &'static str,
+    /// The path of the annotated Rust type.
+    pub rust_type: &'static str,
+}
+
+inventory::collect!(GtsTypeRegistration);
+
+/// A schema registered by `#[struct_to_gts_schema]`.
+#[derive(Debug, Clone, Copy)]
+pub struct SchemaIndexEntry {
+    /// The GTS type ID.
+    pub schema_id: &'static str,
+    /// The schema's `description`.
+    pub description: &'static str,
+    /// The parent type ID for derived schemas.
+    pub base_schema_id: Option<&'static str>,
+    /// Builds the schema as compact JSON (`gts_schema_with_refs_as_string`).
+    /// Schemas are generated at run time, so the entry holds the generator.
+    pub schema_json: fn() -> String,
+    /// The type as a [`GtsSchemaDyn`], without a value.
+    pub schema: &'static (dyn GtsSchemaDyn + Sync),
+}
+
+inventory::collect!(SchemaIndexEntry);
+
+/// All registered schemas, sorted by type ID.
+#[must_use]
+pub fn schema_index() -> Vec<SchemaIndexEntry> {
+    let mut entries: Vec<SchemaIndexEntry> = inventory::iter::<SchemaIndexEntry>
+        .into_iter()
+        .copied()
+        .collect();
+    entries.sort_by_key(|e| e.schema_id);
+    entries
+}
+
+/// The registered schema with this type ID, if any. Generic types are
+/// registered as `Type<()>`.
+#[must_use]
+pub fn registered_schema(type_id: &str) -> Option<&'static dyn GtsSchemaDyn> {
+    inventory::iter::<SchemaIndexEntry>
+        .into_iter()
+        .find(|entry| entry.schema_id == type_id)
+        .map(|entry| -> &'static dyn GtsSchemaDyn { entry.schema })
+}
+
+/// The [`schema_index`] as a pretty-printed JSON array of
+/// `{"schema_id", "description", "base_schema_id", "schema"}` objects.
+#[must_use]
+pub fn generate_schema_index_json() -> String {
+    let entries: Vec<Value> = schema_index()
+        .into_iter()
+        .map(|entry| {
+            let schema: Value = serde_json::from_str(&(entry.schema_json)()).unwrap_or(Value::Null);
+            json!({
+                "schema_id": entry.schema_id,
+                "description": entry.description,
+                "base_schema_id": entry.base_schema_id,
+                "schema": schema,
+            })
+        })
+        .collect();
+    // Serializing a `Value` cannot fail.
+    serde_json::to_string_pretty(&entries).unwrap_or_default()
+}
+
+/// A type ID claimed by more than one Rust type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateTypeId {
+    /// The type ID registered more than once.
+    pub type_id: &'static str,
+    /// The Rust types sharing [`type_id`](Self::type_id), sorted.
+    pub rust_types: Vec<&'static str>,
+}
+
+/// All registered type IDs, sorted by ID and then Rust type.
+#[must_use]
+pub fn registered_type_ids() -> Vec<GtsTypeRegistration> {
+    let mut registrations: Vec<GtsTypeRegistration> = inventory::iter::<GtsTypeRegistration>
+        .into_iter()
+        .copied()
+        .collect();
+    registrations.sort_by_key(|r| (r.type_id, r.rust_type));
+    registrations
+}
+
+/// Registered type IDs claimed by more than one Rust type, sorted by ID.
+#[must_use]
+pub fn duplicate_type_ids() -> Vec<DuplicateTypeId> {
+    let mut by_id: BTreeMap<&'static str, Vec<&'static str>> = BTreeMap::new();
+    for registration in registered_type_ids() {
+        by_id
+            .entry(registration.type_id)
+            .or_default()
+            .push(registration.rust_type);
+    }
+    by_id
+        .into_iter()
+        .filter(|(_, rust_types)| rust_types.len() > 1)
+        .map(|(type_id, rust_types)| DuplicateTypeId {
+            type_id,
+            rust_types,
+        })
+        .collect()
+}
+
+/// Panics if two registered Rust types share a GTS type ID, naming them.
+///
+/// # Panics
+/// Panics listing every duplicated type ID and the types that declare it.
+pub fn assert_unique_type_ids() {
+    let duplicates = duplicate_type_ids();
+    if !duplicates.is_empty() {
+        let details: Vec<String> = duplicates
+            .iter()
+            .map(|d| format!("'{}' is used by {}", d.type_id, d.rust_types.join(" and ")))
+            .collect();
+        panic!("duplicate GTS type IDs: {}", details.join("; "));
+    }
+}
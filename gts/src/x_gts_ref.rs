@@ -92,7 +92,7 @@
 use serde_json::Value;
 use std::fmt;
 
-use crate::gts::{GTS_ID_PREFIX, GTS_ID_URI_PREFIX, GtsId, GtsIdPattern};
+use crate::gts::{GTS_ID_PREFIX, GTS_ID_URI_PREFIX, GtsId, GtsIdPattern, validate_ref_pattern};
 
 /// Error type for x-gts-ref validation failures
 #[derive(Debug, Clone)]
@@ -402,7 +402,7 @@ impl XGtsRefValidator {
         // are validated by the canonical pattern parser, which rejects malformed
         // patterns such as `gts.x.*.events.*` (mid-string / multiple wildcards).
         if ref_pattern.starts_with(GTS_ID_PREFIX) {
-            return GtsIdPattern::try_new(ref_pattern).err().map(|e| {
+            return validate_ref_pattern(ref_pattern).err().map(|e| {
                 XGtsRefValidationError::new(
                     field_path.to_owned(),
                     ref_pattern.to_owned(),
@@ -421,7 +421,7 @@ impl XGtsRefValidator {
                     // validate it through the canonical pattern parser so a
                     // resolved wildcard (e.g. `gts.x.core.*`) is accepted here
                     // just as a literal one is.
-                    if let Err(e) = GtsIdPattern::try_new(&resolved) {
+                    if let Err(e) = validate_ref_pattern(&resolved) {
                         return Some(XGtsRefValidationError::new(
                             field_path.to_owned(),
                             ref_pattern.to_owned(),